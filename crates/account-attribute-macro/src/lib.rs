@@ -3,7 +3,11 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, Attribute, ItemStruct, Result};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, parse_quote, Attribute, Expr, Fields, GenericParam, Ident, Item, ItemEnum,
+    ItemStruct, LitInt, Result, Token,
+};
 
 fn strip_account_attr(attrs: &[Attribute]) -> Vec<Attribute> {
     attrs
@@ -19,26 +23,124 @@ fn strip_account_attr(attrs: &[Attribute]) -> Vec<Attribute> {
 /// #[derive(Discriminator, Len, ZcDeserialize, ZcDeserializeMut, ZcInitialize, Copy, Clone)]
 /// #[repr(C)]
 /// ```
+///
+/// plus a `RentPayer` impl if the struct has a `rent_payer: Address` field (and a
+/// `MaybeRentPayer` impl in any case), and a `ReentrancyGuard` impl if it has an
+/// `in_flight: PodBool` field.
+///
+/// `#[account(discriminator = "account:Vault")]` keeps the discriminator of an existing account
+/// type, e.g. one created by an Anchor program, see `#[derive(Discriminator)]`.
+///
+/// `#[account(disc_len = 4)]` shortens the discriminator to 1 or 4 bytes to save account space.
+/// The struct starts right after it, so its alignment can't exceed the discriminator length:
+/// use `PodU64` and friends rather than `u64` fields.
+///
+/// `#[account(owner = partner::ID)]` implements `OwnerProgram` with the given program, in place
+/// of `#[derive(OwnerProgram)]` which uses `crate::ID`, e.g. for accounts of another program read
+/// by this one.
+///
+/// `#[account(immutable)]` skips the `DeserializeMut` and `ZcDeserializeMut` derives, so the
+/// account can't be written through `Mut<ZcAccount<..>>` by mistake.
+///
+/// `#[account(zero_copy)]` is the default and only layout, `#[account(borsh)]` is rejected:
+/// accounts are read in place through `ZcAccount`, without a borsh counterpart.
+///
+/// Const generic structs, `struct Ring<const N: usize> { entries: [PodU64; N] }`, implement
+/// `Pod` and `Len` by hand since bytemuck can't derive them, the padding check runs for each `N`
+/// used. All the instances share the discriminator of the name.
+///
+/// Enums are stored as a `<Name>Account` struct: a one byte tag, the index of the variant, then
+/// the fields of the variant laid out as the generated `#[repr(C)]` `<Name><Variant>` struct,
+/// zero filled up to the largest variant. `try_get` reads the enum back, `set` replaces it and
+/// a new account holds the first variant, zeroed. The account type implements `OwnerProgram`,
+/// with `crate::ID` unless `owner` is given.
+///
+/// ```ignore
+/// #[account]
+/// #[derive(Clone, Copy)]
+/// pub enum Auction {
+///     Open { best_bid: PodU64 },
+///     Settled { winner: Address },
+///     Cancelled,
+/// }
+///
+/// let auction = ctx.auction.try_deserialize()?.try_get()?; // ZcAccount<'ix, AuctionAccount>
+/// ```
 #[proc_macro_attribute]
 pub fn account(attr: TokenStream, item: TokenStream) -> TokenStream {
-    if !proc_macro2::TokenStream::from(attr.clone()).is_empty() {
-        return syn::Error::new_spanned(
-            proc_macro2::TokenStream::from(attr),
-            "#[account] does not take arguments",
-        )
-        .to_compile_error()
-        .into();
-    }
+    let args = parse_macro_input!(attr as AccountArgs);
+    let input = parse_macro_input!(item as Item);
 
-    let input = parse_macro_input!(item as ItemStruct);
+    let expanded = match input {
+        Item::Struct(input) => expand_account(args, input),
+        Item::Enum(input) => expand_account_enum(args, input),
+        input => Err(syn::Error::new_spanned(
+            input,
+            "#[account] expects a struct or an enum",
+        )),
+    };
 
-    match expand_account(input) {
+    match expanded {
         Ok(ts) => ts.into(),
         Err(e) => e.to_compile_error().into(),
     }
 }
 
-fn expand_account(input: ItemStruct) -> Result<proc_macro2::TokenStream> {
+struct AccountArgs {
+    discriminator: Option<Expr>,
+    disc_len: Option<LitInt>,
+    owner: Option<Expr>,
+    immutable: bool,
+}
+
+impl Parse for AccountArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = Self {
+            discriminator: None,
+            disc_len: None,
+            owner: None,
+            immutable: false,
+        };
+
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+
+            if key == "discriminator" {
+                input.parse::<Token![=]>()?;
+                args.discriminator = Some(input.parse()?);
+            } else if key == "disc_len" {
+                input.parse::<Token![=]>()?;
+                args.disc_len = Some(input.parse()?);
+            } else if key == "owner" {
+                input.parse::<Token![=]>()?;
+                args.owner = Some(input.parse()?);
+            } else if key == "immutable" {
+                args.immutable = true;
+            } else if key == "zero_copy" {
+                // the default
+            } else if key == "borsh" {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "#[account] structs are zero-copy, borsh accounts are not supported",
+                ));
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "expected `discriminator = ..`, `disc_len = N`, `owner = ..`, `immutable` or \
+                     `zero_copy`",
+                ));
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+fn expand_account(args: AccountArgs, input: ItemStruct) -> Result<proc_macro2::TokenStream> {
     let ItemStruct {
         attrs,
         vis,
@@ -56,26 +158,393 @@ fn expand_account(input: ItemStruct) -> Result<proc_macro2::TokenStream> {
         ));
     }
 
+    if let Some(param) = generics
+        .params
+        .iter()
+        .find(|param| !matches!(param, GenericParam::Const(_)))
+    {
+        return Err(syn::Error::new_spanned(
+            param,
+            "#[account] only supports const generics",
+        ));
+    }
+
     let preserved_struct_attrs = strip_account_attr(&attrs);
-    let (impl_generics, _ty_generics, where_clause) = generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let is_generic = !generics.params.is_empty();
+
+    let has_rent_payer = fields
+        .iter()
+        .any(|field| field.ident.as_ref().is_some_and(|i| i == "rent_payer"));
+
+    let rent_payer_impl = match has_rent_payer {
+        true => quote! {
+            impl #impl_generics RentPayer for #ident #ty_generics #where_clause {
+                #[inline(always)]
+                fn rent_payer(&self) -> &Address {
+                    &self.rent_payer
+                }
+
+                #[inline(always)]
+                fn set_rent_payer(&mut self, rent_payer: Address) {
+                    self.rent_payer = rent_payer;
+                }
+            }
+
+            impl #impl_generics MaybeRentPayer for #ident #ty_generics #where_clause {
+                #[inline(always)]
+                fn maybe_rent_payer(&self) -> Option<&Address> {
+                    Some(&self.rent_payer)
+                }
+
+                #[inline(always)]
+                fn maybe_set_rent_payer(&mut self, rent_payer: Address) {
+                    self.rent_payer = rent_payer;
+                }
+            }
+        },
+        false => quote! {
+            impl #impl_generics MaybeRentPayer for #ident #ty_generics #where_clause {}
+        },
+    };
+
+    let has_in_flight = fields
+        .iter()
+        .any(|field| field.ident.as_ref().is_some_and(|i| i == "in_flight"));
+
+    let reentrancy_guard_impl = has_in_flight.then(|| {
+        quote! {
+            impl #impl_generics ReentrancyGuard for #ident #ty_generics #where_clause {
+                #[inline(always)]
+                fn in_flight(&self) -> bool {
+                    self.in_flight.get()
+                }
+
+                #[inline(always)]
+                fn set_in_flight(&mut self, in_flight: bool) {
+                    self.in_flight.set(in_flight);
+                }
+            }
+        }
+    });
+
+    let owner_program_impl = args.owner.as_ref().map(|owner| {
+        quote! {
+            impl #impl_generics OwnerProgram for #ident #ty_generics #where_clause {
+                const OWNER: Address = #owner;
+            }
+        }
+    });
+
+    let mut_derives = (!args.immutable).then(|| quote! { DeserializeMut, ZcDeserializeMut, });
+
+    let discriminator = match (&args.discriminator, &args.disc_len) {
+        (None, None) => None,
+        (Some(discriminator), None) => Some(quote! { #[discriminator(#discriminator)] }),
+        (None, Some(len)) => Some(quote! { #[discriminator(len = #len)] }),
+        (Some(discriminator), Some(len)) => {
+            Some(quote! { #[discriminator(#discriminator, len = #len)] })
+        }
+    };
+
+    // generic structs check their layout per instance, from `Len`
+    let self_ty = match is_generic {
+        true => quote! { Self },
+        false => quote! { #ident },
+    };
+
+    let discriminator_checks = discriminator.is_some().then(|| {
+        quote! {
+            let len = <#self_ty as Discriminator>::DISCRIMINATOR.len();
+            assert!(
+                len == 1 || len == 4 || len == 8,
+                "#[account]: account discriminators are 1, 4 or 8 bytes",
+            );
+            assert!(
+                len == 8 || core::mem::align_of::<#self_ty>() <= len,
+                "#[account]: the struct alignment exceeds the discriminator length",
+            );
+        }
+    });
+
+    let (pod_derives, discriminator_len_check, generic_impls) = match is_generic {
+        false => (
+            Some(quote! { ::bytemuck::Pod, ::bytemuck::Zeroable, Len, }),
+            discriminator_checks.map(|checks| quote! { const _: () = { #checks }; }),
+            None,
+        ),
+        true => (
+            None,
+            None,
+            Some(generic_pod_impls(
+                &ident,
+                &generics,
+                &fields,
+                discriminator_checks,
+            )),
+        ),
+    };
 
     Ok(quote! {
         #(#preserved_struct_attrs)*
+        #[derive(
+            #pod_derives
+            Discriminator,
+            Deserialize,
+            Zc,
+            ZcDeserialize,
+            #mut_derives
+            ZcInitialize,
+            Copy,
+            Clone,
+        )]
+        #discriminator
+        #[repr(C)]
+        #vis struct #ident #impl_generics #fields #where_clause
+
+        #discriminator_len_check
+        #generic_impls
+        #owner_program_impl
+        #rent_payer_impl
+        #reentrancy_guard_impl
+    })
+}
+
+/// `Pod`, `Zeroable` and `Len` of a const generic struct, which bytemuck can't derive: the
+/// padding check needs the size of every field, only known once `N` is.
+fn generic_pod_impls(
+    ident: &Ident,
+    generics: &syn::Generics,
+    fields: &Fields,
+    discriminator_checks: Option<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let field_tys: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+
+    let mut pod_generics = generics.clone();
+    let pod_where_clause = pod_generics.make_where_clause();
+    for ty in &field_tys {
+        pod_where_clause
+            .predicates
+            .push(parse_quote!(#ty: ::bytemuck::Pod));
+    }
+    let (_, _, pod_where_clause) = pod_generics.split_for_impl();
+
+    let padding_error = format!(
+        "#[account] `{ident}`: the struct has padding, reorder the fields or add explicit padding"
+    );
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            const __ACCOUNT_LAYOUT: () = {
+                assert!(
+                    core::mem::size_of::<Self>() == 0 #( + core::mem::size_of::<#field_tys>() )*,
+                    #padding_error,
+                );
+                #discriminator_checks
+            };
+        }
+
+        impl #impl_generics Len for #ident #ty_generics #where_clause {
+            const DISCRIMINATED_LEN: usize = {
+                let () = Self::__ACCOUNT_LAYOUT;
+                <Self as Discriminator>::DISCRIMINATOR.len() + core::mem::size_of::<Self>()
+            };
+        }
+
+        unsafe impl #impl_generics ::bytemuck::Zeroable for #ident #ty_generics #pod_where_clause {}
+        unsafe impl #impl_generics ::bytemuck::Pod for #ident #ty_generics #pod_where_clause {}
+    }
+}
+
+/// `#[account] enum Name`, stored as the `NameAccount` struct, see [`account`]
+fn expand_account_enum(args: AccountArgs, input: ItemEnum) -> Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let account = quote::format_ident!("{name}Account");
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "#[account] enums can't be generic",
+        ));
+    }
+    if input.variants.is_empty() || input.variants.len() > 256 {
+        return Err(syn::Error::new_spanned(
+            name,
+            "#[account] enums take 1 to 256 variants",
+        ));
+    }
+    if let Some(variant) = input.variants.iter().find(|v| v.discriminant.is_some()) {
+        return Err(syn::Error::new_spanned(
+            variant,
+            "#[account] enums are tagged with the variant index, remove the discriminant",
+        ));
+    }
+
+    let mut payloads = Vec::new();
+    let mut payload_sizes = Vec::new();
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+
+    for (tag, variant) in input.variants.iter().enumerate() {
+        let tag = tag as u8;
+        let variant_ident = &variant.ident;
+        let payload = quote::format_ident!("{name}{variant_ident}");
+        let doc = format!(" Fields of [`{name}::{variant_ident}`] in a [`{account}`]");
+
+        let (bindings, construct, declare) = match &variant.fields {
+            Fields::Unit => {
+                reads.push(quote! { #tag => #name::#variant_ident, });
+                writes.push(quote! { #name::#variant_ident => self.tag = #tag, });
+                continue;
+            }
+            Fields::Named(fields) => {
+                let idents: Vec<_> = fields.named.iter().map(|f| &f.ident).collect();
+                let tys = fields.named.iter().map(|f| &f.ty);
+                (
+                    quote! { { #(#idents),* } },
+                    quote! { { #(#idents: payload.#idents),* } },
+                    quote! { { #(pub #idents: #tys),* } },
+                )
+            }
+            Fields::Unnamed(fields) => {
+                let idents: Vec<_> = (0..fields.unnamed.len())
+                    .map(|i| quote::format_ident!("field_{i}"))
+                    .collect();
+                let indices = (0..fields.unnamed.len()).map(syn::Index::from);
+                let tys = fields.unnamed.iter().map(|f| &f.ty);
+                (
+                    quote! { ( #(#idents),* ) },
+                    quote! { ( #(payload.#indices),* ) },
+                    quote! { ( #(pub #tys),* ); },
+                )
+            }
+        };
+
+        payloads.push(quote! {
+            #[doc = #doc]
+            #[derive(::bytemuck::Pod, ::bytemuck::Zeroable, Copy, Clone)]
+            #[repr(C)]
+            #vis struct #payload #declare
+        });
+        payload_sizes.push(quote! { core::mem::size_of::<#payload>() });
+        reads.push(quote! {
+            #tag => {
+                let payload: #payload = ::bytemuck::pod_read_unaligned(
+                    &self.payload[..core::mem::size_of::<#payload>()],
+                );
+                #name::#variant_ident #construct
+            }
+        });
+        writes.push(quote! {
+            #name::#variant_ident #bindings => {
+                self.tag = #tag;
+                self.payload[..core::mem::size_of::<#payload>()]
+                    .copy_from_slice(::bytemuck::bytes_of(&#payload #bindings));
+            }
+        });
+    }
+
+    let mut_derives = (!args.immutable).then(|| quote! { DeserializeMut, ZcDeserializeMut, });
+    let discriminator = match (&args.discriminator, &args.disc_len) {
+        (None, None) => None,
+        (Some(discriminator), None) => Some(quote! { #[discriminator(#discriminator)] }),
+        (None, Some(len)) => Some(quote! { #[discriminator(len = #len)] }),
+        (Some(discriminator), Some(len)) => {
+            Some(quote! { #[discriminator(#discriminator, len = #len)] })
+        }
+    };
+    let owner = match &args.owner {
+        Some(owner) => quote! { #owner },
+        None => quote! { crate::ID },
+    };
+    let account_doc = format!(" Account storing a [`{name}`], see `#[account]` for the layout");
+    let tag_error = format!("{account}: invalid variant tag");
+
+    let mut enum_attrs = input.attrs.clone();
+    enum_attrs.retain(|attr| !attr.path().is_ident("account"));
+    let variants = &input.variants;
+
+    Ok(quote! {
+        #(#enum_attrs)*
+        #vis enum #name {
+            #variants
+        }
+
+        #(#payloads)*
+
+        #[doc = #account_doc]
         #[derive(
             ::bytemuck::Pod,
             ::bytemuck::Zeroable,
             Discriminator,
             Len,
             Deserialize,
-            DeserializeMut,
             Zc,
             ZcDeserialize,
-            ZcDeserializeMut,
+            #mut_derives
             ZcInitialize,
             Copy,
             Clone,
         )]
+        #discriminator
         #[repr(C)]
-        #vis struct #ident #impl_generics #fields #where_clause
+        #vis struct #account {
+            tag: u8,
+            payload: [u8; {
+                let mut len = 0;
+                #(
+                    if #payload_sizes > len {
+                        len = #payload_sizes;
+                    }
+                )*
+                len
+            }],
+        }
+
+        impl #account {
+            /// Bytes after the tag, the size of the largest variant
+            pub const PAYLOAD_LEN: usize = core::mem::size_of::<Self>() - 1;
+
+            /// Index of the stored variant
+            #[inline(always)]
+            pub fn tag(&self) -> u8 {
+                self.tag
+            }
+
+            /// Reads the stored variant, fails if the tag is out of range
+            #[inline(always)]
+            pub fn try_get(&self) -> Result<#name> {
+                Ok(match self.tag {
+                    #(#reads)*
+                    _ => {
+                        error_msg!(#tag_error, ProgramError::InvalidAccountData);
+                    }
+                })
+            }
+
+            /// Stores `value`, zeroing what the previous variant left past it
+            #[inline(always)]
+            pub fn set(&mut self, value: #name) {
+                self.payload = [0; Self::PAYLOAD_LEN];
+                match value {
+                    #(#writes)*
+                }
+            }
+        }
+
+        impl From<#name> for #account {
+            #[inline(always)]
+            fn from(value: #name) -> Self {
+                let mut account = <Self as ::bytemuck::Zeroable>::zeroed();
+                account.set(value);
+                account
+            }
+        }
+
+        impl OwnerProgram for #account {
+            const OWNER: Address = #owner;
+        }
     })
 }
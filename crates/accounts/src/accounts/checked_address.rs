@@ -39,6 +39,8 @@ impl<'ix, T> CheckedAddress<'ix, T>
 where
     T: RawZcDeserializeUnchecked,
 {
+    /// # Safety
+    /// See [`RawZcDeserializeUnchecked::try_deserialize_raw_unchecked`]
     #[inline(always)]
     pub unsafe fn try_deserialize_unchecked(&self) -> Result<&'ix T> {
         T::try_deserialize_raw_unchecked(self.account_view)
@@ -69,6 +71,9 @@ impl<'ix, T> CheckedAddress<'ix, T>
 where 
     T: RawZcDeserializeUncheckedMut,
 {
+    /// # Safety
+    /// See [`RawZcDeserializeUncheckedMut::try_deserialize_raw_unchecked_mut`]
+    #[allow(clippy::mut_from_ref)]
     #[inline(always)]
     pub unsafe fn try_deserialize_raw_unchecked_mut(&self) -> Result<&'ix mut T> {
         T::try_deserialize_raw_unchecked_mut(self.account_view)
@@ -0,0 +1,86 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{FromAccountView, NoMeta, ToAccountView, WritableAllowed};
+use core::ops::Deref;
+use hayabusa_common::{AccountView, RefMut};
+use hayabusa_errors::Result;
+use hayabusa_ser::{try_deserialize_initializing_zc_mut, try_finalize_zc, ZcInitialize};
+
+/// A zero-copy account created with [`ZcInitialize::try_begin_initialize`] that has not been
+/// finalized yet.
+///
+/// Only accounts in the initializing state are accepted, and regular deserialization (e.g. through
+/// [`crate::ZcAccount`]) rejects them until [`Initializing::try_finalize`] is called.
+pub struct Initializing<'ix, T>
+where
+    T: ZcInitialize,
+{
+    pub account_view: &'ix AccountView,
+    _phantom: core::marker::PhantomData<T>,
+}
+
+impl<'ix, T> Initializing<'ix, T>
+where
+    T: ZcInitialize,
+{
+    #[inline(always)]
+    pub fn try_deserialize_mut(&self) -> Result<RefMut<'ix, T>> {
+        try_deserialize_initializing_zc_mut::<T>(self.account_view)
+    }
+
+    /// Writes the regular discriminator, after which the account can be used like any other
+    /// account of type `T`.
+    #[inline(always)]
+    pub fn try_finalize(&self) -> Result<()> {
+        try_finalize_zc::<T>(self.account_view)
+    }
+}
+
+impl<'ix, T> FromAccountView<'ix> for Initializing<'ix, T>
+where
+    T: ZcInitialize,
+{
+    type Meta<'a>
+        = NoMeta
+    where
+        'ix: 'a;
+
+    #[inline(always)]
+    fn try_from_account_view<'a>(account_view: &'ix AccountView, _: Self::Meta<'a>) -> Result<Self>
+    where
+        'ix: 'a,
+    {
+        // validates owner, length and the initializing discriminator
+        drop(try_deserialize_initializing_zc_mut::<T>(account_view)?);
+
+        Ok(Initializing {
+            account_view,
+            _phantom: core::marker::PhantomData,
+        })
+    }
+}
+
+impl<T> ToAccountView for Initializing<'_, T>
+where
+    T: ZcInitialize,
+{
+    #[inline(always)]
+    fn to_account_view(&self) -> &AccountView {
+        self.account_view
+    }
+}
+
+impl<T> WritableAllowed for Initializing<'_, T> where T: ZcInitialize {}
+
+impl<T> Deref for Initializing<'_, T>
+where
+    T: ZcInitialize,
+{
+    type Target = AccountView;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.account_view
+    }
+}
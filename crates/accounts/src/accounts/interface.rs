@@ -1,12 +1,14 @@
 // Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{FromAccountView, NoMeta, ProgramIds, ToAccountView};
+use crate::{FromAccountView, NoMeta, ProgramIds, ToAccountView, PROGRAM_OWNERS};
 use core::ops::Deref;
-use hayabusa_common::AccountView;
+use hayabusa_common::{address_eq, AccountView, Address};
 use hayabusa_errors::{ErrorCode, ProgramError, Result};
 use hayabusa_utility::{error_msg, hint::unlikely};
 
+/// Program account that may be any of the programs in [`ProgramIds::IDS`], e.g.
+/// `Interface<'ix, TokenInterface>` accepts either the token or the token-2022 program.
 pub struct Interface<'ix, T>
 where
     T: ProgramIds,
@@ -36,6 +38,14 @@ where
             );
         }
 
+        // SAFETY: the owner is only read
+        if unlikely(!PROGRAM_OWNERS.contains(unsafe { account_view.owner() })) {
+            error_msg!(
+                "Interface::try_from_account_view: program account not owned by a loader",
+                ProgramError::InvalidAccountOwner,
+            );
+        }
+
         if unlikely(!T::IDS.contains(account_view.address())) {
             error_msg!(
                 "Interface::try_from_account_view: program ID mismatch",
@@ -50,6 +60,17 @@ where
     }
 }
 
+impl<T> Interface<'_, T>
+where
+    T: ProgramIds,
+{
+    /// Whether the passed program is `program_id`, for handlers that branch on the program
+    #[inline(always)]
+    pub fn is(&self, program_id: &Address) -> bool {
+        address_eq(self.account_view.address(), program_id)
+    }
+}
+
 impl<T> ToAccountView for Interface<'_, T>
 where
     T: ProgramIds,
@@ -68,6 +89,6 @@ where
 
     #[inline(always)]
     fn deref(&self) -> &Self::Target {
-        &self.account_view
+        self.account_view
     }
 }
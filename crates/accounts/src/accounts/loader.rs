@@ -0,0 +1,92 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{FromAccountView, NoMeta, ToAccountView, WritableAllowed};
+use core::ops::Deref;
+use hayabusa_common::{AccountView, Ref, RefMut};
+use hayabusa_errors::Result;
+use hayabusa_ser::{Deserialize, Zc, ZcDeserialize, ZcDeserializeMut};
+
+/// Zero-copy account checked while parsing but borrowed only in [`Loader::load`] and
+/// [`Loader::load_mut`].
+///
+/// Parsing checks owner, length and discriminator and releases the borrow right away, so an
+/// account passed in two roles (e.g. source and destination) parses fine and only the handler
+/// decides which borrows overlap. `load` and `load_mut` run the same checks again, the account
+/// may have been closed or reassigned by a CPI in between.
+pub struct Loader<'ix, T>
+where
+    T: Zc + Deserialize,
+{
+    pub account_view: &'ix AccountView,
+    _phantom: core::marker::PhantomData<T>,
+}
+
+impl<'ix, T> FromAccountView<'ix> for Loader<'ix, T>
+where
+    T: ZcDeserialize,
+{
+    type Meta<'a>
+        = NoMeta
+    where
+        'ix: 'a;
+
+    #[inline(always)]
+    fn try_from_account_view<'a>(account_view: &'ix AccountView, _: Self::Meta<'a>) -> Result<Self>
+    where
+        'ix: 'a,
+    {
+        T::try_deserialize(account_view)?;
+
+        Ok(Self {
+            account_view,
+            _phantom: core::marker::PhantomData,
+        })
+    }
+}
+
+impl<T> Loader<'_, T>
+where
+    T: ZcDeserialize,
+{
+    /// Borrows the account, fails if it is mutably borrowed
+    #[inline(always)]
+    pub fn load(&self) -> Result<Ref<'_, T>> {
+        T::try_deserialize(self.account_view)
+    }
+}
+
+impl<T> Loader<'_, T>
+where
+    T: ZcDeserialize + ZcDeserializeMut,
+{
+    /// Borrows the account mutably, fails if it is borrowed
+    #[inline(always)]
+    pub fn load_mut(&self) -> Result<RefMut<'_, T>> {
+        T::try_deserialize_mut(self.account_view)
+    }
+}
+
+impl<T> ToAccountView for Loader<'_, T>
+where
+    T: Zc + Deserialize,
+{
+    #[inline(always)]
+    fn to_account_view(&self) -> &AccountView {
+        self.account_view
+    }
+}
+
+impl<T> WritableAllowed for Loader<'_, T> where T: Zc + Deserialize {}
+
+impl<T> Deref for Loader<'_, T>
+where
+    T: Zc + Deserialize,
+{
+    type Target = AccountView;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.account_view
+    }
+}
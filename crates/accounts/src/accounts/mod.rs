@@ -2,10 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod checked_address;
+pub mod initializing;
 pub mod interface;
+pub mod loader;
 pub mod mutable;
+pub mod pda_account;
 pub mod program;
+pub mod program_ref;
+pub mod rent_exempt;
 pub mod signer;
+pub mod signer_address;
+pub mod sysvar_account;
 pub mod system_account;
 pub mod unchecked_account;
+pub mod versioned_zc_account;
+pub mod wallet;
 pub mod zc_account;
+pub mod zc_account_with_tail;
@@ -1,13 +1,27 @@
 // Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{FromAccountView, WritableAllowed};
+use crate::{FromAccountView, InitAccount, ToAccountView, WritableAllowed};
 use core::ops::{Deref, DerefMut};
-use hayabusa_common::AccountView;
+use hayabusa_common::{AccountView, Address};
 use hayabusa_errors::{ErrorCode, ProgramError, Result};
-use hayabusa_utility::{error_msg, hint::unlikely};
+use hayabusa_ser::{try_credit_lamports, try_debit_lamports, InitAccounts};
+use hayabusa_utility::{error_msg, hint::unlikely, Lamports};
+use solana_instruction_view::cpi::Signer;
 
-pub struct Mut<T>(pub T);
+/// Writable account, parsing fails with [`ErrorCode::AccountNotWritable`] when the account was
+/// not passed as writable, before the runtime would reject the write at the end of the
+/// instruction.
+///
+/// With the `state-diff` feature it also carries a [`StateSnapshot`](crate::state_diff::StateSnapshot)
+/// and logs the bytes that changed when dropped.
+pub struct Mut<T>(
+    pub T,
+    // only dropped, which logs the diff
+    #[cfg(feature = "state-diff")]
+    #[allow(dead_code)]
+    crate::state_diff::StateSnapshot,
+);
 
 impl<'ix, T> FromAccountView<'ix> for Mut<T>
 where
@@ -33,7 +47,45 @@ where
             );
         }
 
-        Ok(Mut(T::try_from_account_view(account_view, meta)?))
+        Ok(Mut(
+            T::try_from_account_view(account_view, meta)?,
+            #[cfg(feature = "state-diff")]
+            crate::state_diff::StateSnapshot::new(account_view),
+        ))
+    }
+}
+
+impl<T> Mut<T>
+where
+    T: ToAccountView,
+{
+    /// See [`try_credit_lamports`]
+    #[inline(always)]
+    pub fn try_credit_lamports(&self, amount: Lamports) -> Result<Lamports> {
+        try_credit_lamports(self.0.to_account_view(), amount)
+    }
+
+    /// See [`try_debit_lamports`], `program_id` is usually `&crate::ID`
+    #[inline(always)]
+    pub fn try_debit_lamports(&self, program_id: &Address, amount: Lamports) -> Result<Lamports> {
+        try_debit_lamports(self.0.to_account_view(), program_id, amount)
+    }
+}
+
+impl<T> InitAccount for Mut<T>
+where
+    T: InitAccount,
+{
+    const DEFAULT_SPACE: usize = T::DEFAULT_SPACE;
+
+    #[inline(always)]
+    fn try_init_account(
+        account_view: &AccountView,
+        init_accounts: InitAccounts<'_, '_>,
+        space: usize,
+        signers: Option<&[Signer]>,
+    ) -> Result<()> {
+        T::try_init_account(account_view, init_accounts, space, signers)
     }
 }
 
@@ -0,0 +1,153 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{AsZcAccount, FromAccountView, NoMeta, ToAccountView, WritableAllowed, ZcAccount};
+use core::ops::Deref;
+use hayabusa_common::{address_eq, AccountView};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_ser::{Deserialize, Zc, ZcDeserialize};
+use hayabusa_syscalls::try_create_program_address;
+use hayabusa_utility::{error_msg, hint::unlikely, PdaState};
+use solana_instruction_view::cpi::{Seed, Signer};
+
+/// Most seeds of a PDA, bump included
+const MAX_SEEDS: usize = 16;
+
+/// PDA account whose seeds and bump are stored in its own state, see [`PdaState`].
+///
+/// Parsing deserializes the state and checks that the address is the PDA of
+/// `T::OWNER` for the seeds and stored bump, a single `create_program_address` instead of
+/// searching for the bump. [`PdaAccount::try_with_signer`] then signs CPIs with the same seeds.
+pub struct PdaAccount<'ix, T>
+where
+    T: Zc + Deserialize,
+{
+    pub account: ZcAccount<'ix, T>,
+    bump: u8,
+}
+
+impl<'ix, T> FromAccountView<'ix> for PdaAccount<'ix, T>
+where
+    T: ZcDeserialize + PdaState,
+{
+    type Meta<'a>
+        = NoMeta
+    where
+        'ix: 'a;
+
+    #[inline(always)]
+    fn try_from_account_view<'a>(account_view: &'ix AccountView, _: Self::Meta<'a>) -> Result<Self>
+    where
+        'ix: 'a,
+    {
+        let account = ZcAccount::<T>::try_from_account_view(account_view, NoMeta)?;
+
+        let bump = {
+            let state = account.try_deserialize()?;
+            let bump = [state.bump()];
+
+            let pda = state.with_seeds(|seeds| {
+                with_bump(seeds, &bump, |seeds| try_create_program_address(seeds, &T::OWNER))
+            })?;
+
+            if unlikely(!address_eq(account_view.address(), &pda)) {
+                error_msg!(
+                    "PdaAccount::try_from_account_view: address is not the PDA of the stored seeds",
+                    ErrorCode::InvalidSeeds,
+                );
+            }
+
+            bump[0]
+        };
+
+        Ok(Self { account, bump })
+    }
+}
+
+impl<T> PdaAccount<'_, T>
+where
+    T: ZcDeserialize + PdaState,
+{
+    /// Stored bump, checked while parsing
+    #[inline(always)]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Calls `f` with the signer of the PDA, e.g. for `CpiCtx::try_new_with_signer`.
+    ///
+    /// The seeds are read from a copy of the state, no borrow of the account is held during
+    /// `f`.
+    #[inline(always)]
+    pub fn try_with_signer<R, F>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&[Signer]) -> Result<R>,
+    {
+        let state = *self.account.try_deserialize()?;
+        let bump = [self.bump];
+
+        state.with_seeds(|seeds| {
+            with_bump(seeds, &bump, |seeds| {
+                let signer_seeds: [Seed; MAX_SEEDS] =
+                    core::array::from_fn(|i| Seed::from(seeds.get(i).copied().unwrap_or(&[])));
+                let signers = [Signer::from(&signer_seeds[..seeds.len()])];
+
+                f(&signers)
+            })
+        })
+    }
+}
+
+/// Calls `f` with `seeds` followed by `bump`
+#[inline(always)]
+fn with_bump<R>(
+    seeds: &[&[u8]],
+    bump: &[u8; 1],
+    f: impl FnOnce(&[&[u8]]) -> Result<R>,
+) -> Result<R> {
+    if unlikely(seeds.len() >= MAX_SEEDS) {
+        error_msg!("PdaAccount: too many seeds", ErrorCode::TooManySeeds);
+    }
+
+    let mut all: [&[u8]; MAX_SEEDS] = [&[]; MAX_SEEDS];
+    all[..seeds.len()].copy_from_slice(seeds);
+    all[seeds.len()] = bump;
+
+    f(&all[..=seeds.len()])
+}
+
+impl<T> ToAccountView for PdaAccount<'_, T>
+where
+    T: Zc + Deserialize,
+{
+    #[inline(always)]
+    fn to_account_view(&self) -> &AccountView {
+        self.account.account_view
+    }
+}
+
+impl<T> WritableAllowed for PdaAccount<'_, T> where T: Zc + Deserialize {}
+
+impl<T> AsZcAccount for PdaAccount<'_, T>
+where
+    T: Zc + Deserialize,
+{
+    type State = T;
+
+    #[inline(always)]
+    fn as_zc_account(&self) -> &ZcAccount<'_, T> {
+        &self.account
+    }
+}
+
+impl<'ix, T> Deref for PdaAccount<'ix, T>
+where
+    T: Zc + Deserialize,
+{
+    type Target = ZcAccount<'ix, T>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.account
+    }
+}
@@ -3,10 +3,26 @@
 
 use crate::{FromAccountView, NoMeta, ProgramId, ToAccountView};
 use core::ops::Deref;
-use hayabusa_common::{address_eq, AccountView, Address};
+use hayabusa_common::{address, address_eq, AccountView, Address};
 use hayabusa_errors::{ErrorCode, ProgramError, Result};
 use hayabusa_utility::{error_msg, hint::unlikely};
 
+pub const BPF_LOADER_DEPRECATED_ID: Address = address!("BPFLoader1111111111111111111111111111111111");
+pub const BPF_LOADER_ID: Address = address!("BPFLoader2111111111111111111111111111111111");
+pub const BPF_LOADER_UPGRADEABLE_ID: Address = address!("BPFLoaderUpgradeab1e11111111111111111111111");
+pub const LOADER_V4_ID: Address = address!("LoaderV411111111111111111111111111111111111");
+/// Owner of the builtin programs, e.g. the system program
+pub const NATIVE_LOADER_ID: Address = address!("NativeLoader1111111111111111111111111111111");
+
+/// Owners a program account can have
+pub const PROGRAM_OWNERS: &[Address] = &[
+    BPF_LOADER_UPGRADEABLE_ID,
+    BPF_LOADER_ID,
+    LOADER_V4_ID,
+    NATIVE_LOADER_ID,
+    BPF_LOADER_DEPRECATED_ID,
+];
+
 pub struct Program<'ix, T>
 where
     T: ProgramId,
@@ -36,6 +52,14 @@ where
             );
         }
 
+        // SAFETY: the owner is only read
+        if unlikely(!PROGRAM_OWNERS.contains(unsafe { account_view.owner() })) {
+            error_msg!(
+                "Program::try_from_account_view: program account not owned by a loader",
+                ProgramError::InvalidAccountOwner,
+            );
+        }
+
         if unlikely(!address_eq(account_view.address(), &T::ID)) {
             error_msg!(
                 "Program::try_from_account_view: program ID mismatch",
@@ -65,7 +89,7 @@ impl<T: ProgramId> Deref for Program<'_, T> {
 
     #[inline(always)]
     fn deref(&self) -> &Self::Target {
-        &self.account_view
+        self.account_view
     }
 }
 
@@ -0,0 +1,146 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BPF_LOADER_UPGRADEABLE_ID, LOADER_V4_ID, PROGRAM_OWNERS};
+use bytemuck::{Pod, Zeroable};
+use hayabusa_common::{address_eq, AccountView, Address};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+/// `UpgradeableLoaderState::Program` tag, followed by the program data address
+const UPGRADEABLE_PROGRAM_TAG: u32 = 2;
+/// `UpgradeableLoaderState::ProgramData` tag, followed by the slot and the upgrade authority
+const UPGRADEABLE_PROGRAM_DATA_TAG: u32 = 3;
+/// Offset of the `Option<Address>` upgrade authority in the program data account
+const UPGRADE_AUTHORITY_OFFSET: usize = 12;
+
+/// Offset of the status in the loader-v4 program header
+const LOADER_V4_STATUS_OFFSET: usize = 40;
+const LOADER_V4_STATUS_FINALIZED: u64 = 2;
+
+/// Reference to another program, stored in config accounts.
+///
+/// [`ProgramRef::try_verify`] checks the program account passed to an instruction against it,
+/// optionally requiring the program to be frozen so its code can't change under the protocol.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ProgramRef {
+    pub program_id: Address,
+    require_frozen: u8,
+    _padding: [u8; 7],
+}
+
+impl ProgramRef {
+    #[inline(always)]
+    pub const fn new(program_id: Address, require_frozen: bool) -> Self {
+        Self {
+            program_id,
+            require_frozen: require_frozen as u8,
+            _padding: [0; 7],
+        }
+    }
+
+    #[inline(always)]
+    pub fn requires_frozen(&self) -> bool {
+        self.require_frozen != 0
+    }
+
+    /// Checks that `program` is the referenced, executable, loader owned program.
+    ///
+    /// Programs of the upgradeable loader also need their `program_data` account when the
+    /// reference requires a frozen program, it is ignored otherwise.
+    pub fn try_verify(&self, program: &AccountView, program_data: Option<&AccountView>) -> Result<()> {
+        if unlikely(!address_eq(program.address(), &self.program_id)) {
+            error_msg!(
+                "ProgramRef::try_verify: program ID mismatch",
+                ErrorCode::InvalidProgram,
+            );
+        }
+
+        if unlikely(!program.executable()) {
+            error_msg!(
+                "ProgramRef::try_verify: program account is not executable",
+                ErrorCode::ProgramAccountNotExecutable,
+            );
+        }
+
+        // SAFETY: the owner is only read
+        if unlikely(!PROGRAM_OWNERS.contains(unsafe { program.owner() })) {
+            error_msg!(
+                "ProgramRef::try_verify: program account not owned by a loader",
+                ProgramError::InvalidAccountOwner,
+            );
+        }
+
+        if self.requires_frozen() && unlikely(!is_program_frozen(program, program_data)?) {
+            error_msg!(
+                "ProgramRef::try_verify: program is upgradeable",
+                ErrorCode::ProgramNotFrozen,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether the code of an executable `program` can no longer change.
+///
+/// Upgradeable loader programs are frozen once their upgrade authority is removed, which is
+/// stored in `program_data`, loader-v4 programs once they are finalized. Programs of the other
+/// loaders can't be upgraded.
+pub fn is_program_frozen(program: &AccountView, program_data: Option<&AccountView>) -> Result<bool> {
+    // SAFETY: the owner is only read
+    let owner = unsafe { program.owner() };
+
+    if address_eq(owner, &BPF_LOADER_UPGRADEABLE_ID) {
+        let Some(program_data) = program_data else {
+            error_msg!(
+                "is_program_frozen: upgradeable program requires its program data account",
+                ProgramError::NotEnoughAccountKeys,
+            );
+        };
+
+        let data = program.try_borrow()?;
+
+        if unlikely(
+            data.len() < 36
+                || data[..4] != UPGRADEABLE_PROGRAM_TAG.to_le_bytes()
+                || data[4..36] != *program_data.address().as_ref(),
+        ) {
+            error_msg!(
+                "is_program_frozen: program data account mismatch",
+                ProgramError::InvalidAccountData,
+            );
+        }
+
+        let program_data_bytes = program_data.try_borrow()?;
+
+        if unlikely(
+            !program_data.owned_by(&BPF_LOADER_UPGRADEABLE_ID)
+                || program_data_bytes.len() <= UPGRADE_AUTHORITY_OFFSET
+                || program_data_bytes[..4] != UPGRADEABLE_PROGRAM_DATA_TAG.to_le_bytes(),
+        ) {
+            error_msg!(
+                "is_program_frozen: invalid program data account",
+                ProgramError::InvalidAccountData,
+            );
+        }
+
+        return Ok(program_data_bytes[UPGRADE_AUTHORITY_OFFSET] == 0);
+    }
+
+    if address_eq(owner, &LOADER_V4_ID) {
+        let data = program.try_borrow()?;
+
+        let Some(status) = data.get(LOADER_V4_STATUS_OFFSET..LOADER_V4_STATUS_OFFSET + 8) else {
+            error_msg!(
+                "is_program_frozen: invalid loader-v4 program account",
+                ProgramError::InvalidAccountData,
+            );
+        };
+
+        return Ok(status == LOADER_V4_STATUS_FINALIZED.to_le_bytes());
+    }
+
+    Ok(true)
+}
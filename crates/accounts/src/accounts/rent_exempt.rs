@@ -0,0 +1,96 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{AsZcAccount, FromAccountView, InitAccount, WritableAllowed, ZcAccount};
+use core::ops::{Deref, DerefMut};
+use hayabusa_common::AccountView;
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_ser::InitAccounts;
+use hayabusa_sysvars::{rent::Rent, Sysvar};
+use hayabusa_utility::{error_msg, hint::unlikely};
+use solana_instruction_view::cpi::Signer;
+
+/// Account holding at least the rent exempt minimum for its current data length, parsing fails
+/// with [`ErrorCode::AccountNotRentExempt`] otherwise.
+///
+/// The check runs after `T` is parsed and reads the `Rent` sysvar, so it costs a syscall per
+/// account. Combine with [`Mut`](crate::Mut) as `Mut<RentExempt<T>>`.
+pub struct RentExempt<T>(pub T);
+
+impl<'ix, T> FromAccountView<'ix> for RentExempt<T>
+where
+    T: FromAccountView<'ix>,
+{
+    type Meta<'a>
+        = T::Meta<'a>
+    where
+        'ix: 'a;
+
+    #[inline(always)]
+    fn try_from_account_view<'a>(
+        account_view: &'ix AccountView,
+        meta: Self::Meta<'a>,
+    ) -> Result<Self>
+    where
+        'ix: 'a,
+    {
+        let inner = T::try_from_account_view(account_view, meta)?;
+
+        let minimum_balance = Rent::get()?.try_minimum_balance(account_view.data_len())?;
+        if unlikely(account_view.lamports() < minimum_balance) {
+            error_msg!(
+                "RentExempt::try_from_account_view: account not rent exempt",
+                ErrorCode::AccountNotRentExempt,
+            );
+        }
+
+        Ok(RentExempt(inner))
+    }
+}
+
+impl<T> WritableAllowed for RentExempt<T> where T: WritableAllowed {}
+
+impl<T> AsZcAccount for RentExempt<T>
+where
+    T: AsZcAccount,
+{
+    type State = T::State;
+
+    #[inline(always)]
+    fn as_zc_account(&self) -> &ZcAccount<'_, Self::State> {
+        self.0.as_zc_account()
+    }
+}
+
+impl<T> InitAccount for RentExempt<T>
+where
+    T: InitAccount,
+{
+    const DEFAULT_SPACE: usize = T::DEFAULT_SPACE;
+
+    #[inline(always)]
+    fn try_init_account(
+        account_view: &AccountView,
+        init_accounts: InitAccounts<'_, '_>,
+        space: usize,
+        signers: Option<&[Signer]>,
+    ) -> Result<()> {
+        T::try_init_account(account_view, init_accounts, space, signers)
+    }
+}
+
+impl<T> Deref for RentExempt<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for RentExempt<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
@@ -54,6 +54,6 @@ impl Deref for Signer<'_> {
 
     #[inline(always)]
     fn deref(&self) -> &Self::Target {
-        &self.account_view
+        self.account_view
     }
 }
@@ -0,0 +1,90 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{FromAccountView, KnownAddress, NoMeta, ToAccountView, WritableAllowed};
+use core::ops::Deref;
+use hayabusa_common::{address_eq, AccountView, Address};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+/// Signer that must be `T::ADDRESS`, e.g. `SignerAddress<'ix, Admin>`.
+///
+/// Both checks fail with the same [`ErrorCode::UnauthorizedSigner`], a wrong key and a missing
+/// signature are the same mistake for a privileged instruction.
+///
+/// ```ignore
+/// pub struct Admin;
+///
+/// impl KnownAddress for Admin {
+///     const ADDRESS: Address = address!("...");
+/// }
+/// ```
+pub struct SignerAddress<'ix, T>
+where
+    T: KnownAddress,
+{
+    pub account_view: &'ix AccountView,
+    _phantom: core::marker::PhantomData<T>,
+}
+
+impl<'ix, T> SignerAddress<'ix, T>
+where
+    T: KnownAddress,
+{
+    #[inline(always)]
+    pub fn address(&self) -> &'ix Address {
+        self.account_view.address()
+    }
+}
+
+impl<'ix, T> FromAccountView<'ix> for SignerAddress<'ix, T>
+where
+    T: KnownAddress,
+{
+    type Meta<'a>
+        = NoMeta
+    where
+        'ix: 'a;
+
+    #[inline(always)]
+    fn try_from_account_view<'a>(account_view: &'ix AccountView, _: Self::Meta<'a>) -> Result<Self>
+    where
+        'ix: 'a,
+    {
+        if unlikely(!account_view.is_signer() || !address_eq(account_view.address(), &T::ADDRESS)) {
+            error_msg!(
+                "SignerAddress::try_from_account_view: account is not the expected signer",
+                ErrorCode::UnauthorizedSigner,
+            );
+        }
+
+        Ok(Self {
+            account_view,
+            _phantom: core::marker::PhantomData,
+        })
+    }
+}
+
+impl<T> ToAccountView for SignerAddress<'_, T>
+where
+    T: KnownAddress,
+{
+    #[inline(always)]
+    fn to_account_view(&self) -> &AccountView {
+        self.account_view
+    }
+}
+
+impl<T> WritableAllowed for SignerAddress<'_, T> where T: KnownAddress {}
+
+impl<T> Deref for SignerAddress<'_, T>
+where
+    T: KnownAddress,
+{
+    type Target = AccountView;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.account_view
+    }
+}
@@ -3,10 +3,17 @@
 
 use crate::{FromAccountView, NoMeta, ToAccountView, WritableAllowed};
 use core::ops::Deref;
-use hayabusa_common::AccountView;
+use hayabusa_common::{AccountView, Address};
+use hayabusa_cpi::CpiCtx;
 use hayabusa_errors::{ErrorCode, ProgramError, Result};
-use hayabusa_utility::{error_msg, hint::unlikely};
+use hayabusa_system_program::instructions::{
+    assign, create_account_with_lamports, Assign, CreateAccount,
+};
+use hayabusa_utility::{error_msg, hint::unlikely, Lamports};
+use solana_instruction_view::cpi::Signer;
 
+/// Account owned by the system program without data, a wallet or an address that wasn't created
+/// yet.
 pub struct SystemAccount<'ix> {
     pub account_view: &'ix AccountView,
 }
@@ -29,10 +36,64 @@ impl<'ix> FromAccountView<'ix> for SystemAccount<'ix> {
             );
         }
 
+        if unlikely(!account_view.is_data_empty()) {
+            error_msg!(
+                "SystemAccount::try_from_account_view: account has data",
+                ErrorCode::InvalidAccount,
+            );
+        }
+
         Ok(SystemAccount { account_view })
     }
 }
 
+impl<'ix> SystemAccount<'ix> {
+    /// Creates the account with `lamports` paid by `payer`, still owned by the system program and
+    /// without data. Fails if the account already holds lamports.
+    ///
+    /// The account must sign, pass its seeds in `signers` for a PDA.
+    #[inline(always)]
+    pub fn try_create_from(
+        &self,
+        payer: &'ix AccountView,
+        system_program: &'ix AccountView,
+        lamports: Lamports,
+        signers: Option<&[Signer]>,
+    ) -> Result<()> {
+        let cpi_ctx = CpiCtx::try_new(
+            system_program,
+            CreateAccount {
+                from: payer,
+                to: self.account_view,
+            },
+            signers,
+        )?;
+
+        create_account_with_lamports(cpi_ctx, &hayabusa_system_program::ID, 0, lamports)
+    }
+
+    /// Assigns the account to `owner`, which can then allocate and write its data.
+    ///
+    /// The account must sign, pass its seeds in `signers` for a PDA.
+    #[inline(always)]
+    pub fn try_assign_to(
+        &self,
+        system_program: &'ix AccountView,
+        owner: &Address,
+        signers: Option<&[Signer]>,
+    ) -> Result<()> {
+        let cpi_ctx = CpiCtx::try_new(
+            system_program,
+            Assign {
+                account: self.account_view,
+            },
+            signers,
+        )?;
+
+        assign(cpi_ctx, owner)
+    }
+}
+
 impl ToAccountView for SystemAccount<'_> {
     #[inline(always)]
     fn to_account_view(&self) -> &AccountView {
@@ -47,6 +108,6 @@ impl Deref for SystemAccount<'_> {
 
     #[inline(always)]
     fn deref(&self) -> &Self::Target {
-        &self.account_view
+        self.account_view
     }
 }
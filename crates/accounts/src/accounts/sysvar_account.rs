@@ -0,0 +1,81 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{FromAccountView, NoMeta, ToAccountView};
+use core::ops::Deref;
+use hayabusa_common::{address_eq, AccountView};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_sysvars::SysvarAccountData;
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+/// A sysvar passed as an account, e.g. `SysvarAccount<'ix, Clock>`.
+///
+/// Prefer `Sysvar::get` for sysvars that support it, it doesn't take an account slot.
+pub struct SysvarAccount<'ix, T>
+where
+    T: SysvarAccountData,
+{
+    pub account_view: &'ix AccountView,
+    _phantom: core::marker::PhantomData<T>,
+}
+
+impl<'ix, T> FromAccountView<'ix> for SysvarAccount<'ix, T>
+where
+    T: SysvarAccountData,
+{
+    type Meta<'a>
+        = NoMeta
+    where
+        'ix: 'a;
+
+    #[inline(always)]
+    fn try_from_account_view<'a>(account_view: &'ix AccountView, _: Self::Meta<'a>) -> Result<Self>
+    where
+        'ix: 'a,
+    {
+        if unlikely(!address_eq(account_view.address(), &T::ID)) {
+            error_msg!(
+                "SysvarAccount::try_from_account_view: sysvar address mismatch",
+                ErrorCode::InvalidAccount,
+            );
+        }
+
+        Ok(SysvarAccount {
+            account_view,
+            _phantom: core::marker::PhantomData,
+        })
+    }
+}
+
+impl<'ix, T> SysvarAccount<'ix, T>
+where
+    T: SysvarAccountData,
+{
+    /// Parses the sysvar account data
+    #[inline(always)]
+    pub fn try_get(&self) -> Result<T::Data<'ix>> {
+        T::from_sysvar_account(self.account_view)
+    }
+}
+
+impl<T> ToAccountView for SysvarAccount<'_, T>
+where
+    T: SysvarAccountData,
+{
+    #[inline(always)]
+    fn to_account_view(&self) -> &AccountView {
+        self.account_view
+    }
+}
+
+impl<T> Deref for SysvarAccount<'_, T>
+where
+    T: SysvarAccountData,
+{
+    type Target = AccountView;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.account_view
+    }
+}
@@ -1,14 +1,70 @@
 // Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{FromAccountView, NoMeta, ToAccountView, WritableAllowed};
+use crate::{
+    FromAccountView, Mut, NoMeta, Program, ProgramId, Signer, SystemAccount, ToAccountView,
+    WritableAllowed, ZcAccount,
+};
 use hayabusa_common::AccountView;
 use hayabusa_errors::Result;
+use hayabusa_ser::{Deserialize, Zc};
 
+/// Account without checks.
+///
+/// The `try_into_*` methods upgrade it with the checks of the `FromAccountView` of the target
+/// type, for handlers that only know the role of an account at runtime, e.g. an authority that is
+/// either a wallet or a PDA.
 pub struct UncheckedAccount<'ix> {
     pub account_view: &'ix AccountView,
 }
 
+impl<'ix> UncheckedAccount<'ix> {
+    #[inline(always)]
+    pub fn try_into_checked<'a, T>(&self, meta: T::Meta<'a>) -> Result<T>
+    where
+        T: FromAccountView<'ix>,
+        'ix: 'a,
+    {
+        T::try_from_account_view(self.account_view, meta)
+    }
+
+    #[inline(always)]
+    pub fn try_into_signer(&self) -> Result<Signer<'ix>> {
+        self.try_into_checked(NoMeta)
+    }
+
+    #[inline(always)]
+    pub fn try_into_system_account(&self) -> Result<SystemAccount<'ix>> {
+        self.try_into_checked(NoMeta)
+    }
+
+    #[inline(always)]
+    pub fn try_into_program<T>(&self) -> Result<Program<'ix, T>>
+    where
+        T: ProgramId,
+    {
+        self.try_into_checked(NoMeta)
+    }
+
+    /// The data is checked when deserialized, like a `ZcAccount` field.
+    #[inline(always)]
+    pub fn try_into_zc_account<T>(&self) -> Result<ZcAccount<'ix, T>>
+    where
+        T: Zc + Deserialize,
+    {
+        self.try_into_checked(NoMeta)
+    }
+
+    /// Writable `T`, e.g. `try_into_mut::<ZcAccount<Vault>>()`.
+    #[inline(always)]
+    pub fn try_into_mut<T>(&self) -> Result<Mut<T>>
+    where
+        T: FromAccountView<'ix, Meta<'ix> = NoMeta> + WritableAllowed,
+    {
+        self.try_into_checked(NoMeta)
+    }
+}
+
 impl<'ix> FromAccountView<'ix> for UncheckedAccount<'ix> {
     type Meta<'a>
         = NoMeta
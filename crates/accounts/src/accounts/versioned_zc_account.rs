@@ -0,0 +1,111 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{FromAccountView, NoMeta, ToAccountView, WritableAllowed};
+use core::ops::Deref;
+use hayabusa_common::{AccountView, Ref, RefMut};
+use hayabusa_errors::Result;
+use hayabusa_ser::{
+    try_deserialize_zc, try_deserialize_zc_mut, try_migrate_zc, try_version_zc, Migrate, Version,
+};
+
+/// Zero-copy account stored either as `T` or as the layout it replaces, `T::Previous`.
+///
+/// The layout is checked when the account is parsed, [`VersionedZcAccount::try_migrate`] upgrades
+/// old accounts in place the first time they are written to.
+pub struct VersionedZcAccount<'ix, T>
+where
+    T: Migrate,
+{
+    pub account_view: &'ix AccountView,
+    /// Layout the account had when it was parsed
+    pub version: Version,
+    _phantom: core::marker::PhantomData<T>,
+}
+
+impl<'ix, T> VersionedZcAccount<'ix, T>
+where
+    T: Migrate,
+{
+    #[inline(always)]
+    pub fn is_current(&self) -> bool {
+        self.version == Version::Current
+    }
+
+    /// Fails if the account was not migrated yet.
+    #[inline(always)]
+    pub fn try_deserialize(&self) -> Result<Ref<'_, T>> {
+        try_deserialize_zc::<T>(self.account_view)
+    }
+
+    /// Fails if the account was not migrated yet.
+    #[inline(always)]
+    pub fn try_deserialize_mut(&self) -> Result<RefMut<'_, T>> {
+        try_deserialize_zc_mut::<T>(self.account_view)
+    }
+
+    /// Fails if the account was already migrated.
+    #[inline(always)]
+    pub fn try_deserialize_previous(&self) -> Result<Ref<'_, T::Previous>>
+    where
+        T::Previous: hayabusa_utility::OwnerProgram,
+    {
+        try_deserialize_zc::<T::Previous>(self.account_view)
+    }
+
+    /// Upgrades the account if needed and returns its current state, see [`try_migrate_zc`]
+    #[inline(always)]
+    pub fn try_migrate(
+        &self,
+        payer: &AccountView,
+        system_program: &AccountView,
+    ) -> Result<RefMut<'ix, T>> {
+        try_migrate_zc::<T>(self.account_view, payer, system_program)
+    }
+}
+
+impl<'ix, T> FromAccountView<'ix> for VersionedZcAccount<'ix, T>
+where
+    T: Migrate,
+{
+    type Meta<'a>
+        = NoMeta
+    where
+        'ix: 'a;
+
+    #[inline(always)]
+    fn try_from_account_view<'a>(account_view: &'ix AccountView, _: Self::Meta<'a>) -> Result<Self>
+    where
+        'ix: 'a,
+    {
+        Ok(VersionedZcAccount {
+            account_view,
+            version: try_version_zc::<T>(account_view)?,
+            _phantom: core::marker::PhantomData,
+        })
+    }
+}
+
+impl<T> ToAccountView for VersionedZcAccount<'_, T>
+where
+    T: Migrate,
+{
+    #[inline(always)]
+    fn to_account_view(&self) -> &AccountView {
+        self.account_view
+    }
+}
+
+impl<T> WritableAllowed for VersionedZcAccount<'_, T> where T: Migrate {}
+
+impl<T> Deref for VersionedZcAccount<'_, T>
+where
+    T: Migrate,
+{
+    type Target = AccountView;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.account_view
+    }
+}
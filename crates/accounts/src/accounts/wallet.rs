@@ -0,0 +1,64 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{FromAccountView, NoMeta, ToAccountView, WritableAllowed};
+use core::ops::Deref;
+use hayabusa_common::{AccountView, Address};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_syscalls::is_on_curve;
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+/// Account whose address is on the ed25519 curve, so it has a private key and is not a PDA.
+///
+/// For accounts that must be able to sign later without the program, e.g. the new authority
+/// of a transfer or the recipient of funds that are meant to leave program control.
+pub struct Wallet<'ix> {
+    pub account_view: &'ix AccountView,
+}
+
+impl<'ix> Wallet<'ix> {
+    #[inline(always)]
+    pub fn address(&self) -> &'ix Address {
+        self.account_view.address()
+    }
+}
+
+impl<'ix> FromAccountView<'ix> for Wallet<'ix> {
+    type Meta<'a>
+        = NoMeta
+    where
+        'ix: 'a;
+
+    #[inline(always)]
+    fn try_from_account_view<'a>(account_view: &'ix AccountView, _: Self::Meta<'a>) -> Result<Self>
+    where
+        'ix: 'a,
+    {
+        if unlikely(!is_on_curve(account_view.address())) {
+            error_msg!(
+                "Wallet::try_from_account_view: address is off curve, a PDA can't be a wallet",
+                ErrorCode::AddressNotOnCurve,
+            );
+        }
+
+        Ok(Self { account_view })
+    }
+}
+
+impl ToAccountView for Wallet<'_> {
+    #[inline(always)]
+    fn to_account_view(&self) -> &AccountView {
+        self.account_view
+    }
+}
+
+impl WritableAllowed for Wallet<'_> {}
+
+impl Deref for Wallet<'_> {
+    type Target = AccountView;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.account_view
+    }
+}
@@ -1,14 +1,18 @@
 // Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{FromAccountView, NoMeta, ToAccountView, WritableAllowed};
+use crate::{
+    CloseAccount, FromAccountView, InitAccount, Mut, NoMeta, ToAccountView, WritableAllowed,
+};
 use core::ops::Deref;
-use hayabusa_common::{AccountView, Ref, RefMut};
-use hayabusa_errors::Result;
+use hayabusa_common::{address_eq, AccountView, Ref, RefMut};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
 use hayabusa_ser::{
     Deserialize, InitAccounts, RawZcDeserialize, RawZcDeserializeMut, RawZcDeserializeUnchecked,
-    RawZcDeserializeUncheckedMut, Zc, ZcDeserialize, ZcDeserializeMut, ZcInitialize,
+    RawZcDeserializeUncheckedMut, tombstone_account, try_close_zc, try_close_zc_to_rent_payer, try_create_zc, try_tombstone_zc, Zc, ZcDeserialize,
+    ZcDeserializeMut, ZcInitialize,
 };
+use hayabusa_utility::{error_msg, hint::unlikely, MaybeRentPayer, ReentrancyGuard, RentPayer};
 use solana_instruction_view::cpi::Signer;
 
 // ideally would put more concrete trait bound but ZcDeserialize and RawZcDeserialize are sometimes mutually exclusive
@@ -26,33 +30,211 @@ where
     T: ZcDeserialize,
 {
     #[inline(always)]
-    pub fn try_deserialize(&self) -> Result<Ref<T>> {
+    pub fn try_deserialize(&self) -> Result<Ref<'_, T>> {
         T::try_deserialize(self.account_view)
     }
 }
 
-#[allow(dead_code)]
-impl<T> ZcAccount<'_, T>
+/// Account types wrapping a [`ZcAccount`], e.g. [`crate::PdaAccount`] or
+/// [`crate::RentExempt`].
+///
+/// The methods that write the account (`try_deserialize_mut`, `try_initialize`, `try_close`,
+/// ...) are only implemented on `Mut<A>`, so using a field that wasn't declared writable is a
+/// compile error rather than a failed borrow or a transaction rejected by the runtime.
+pub trait AsZcAccount {
+    type State: Zc + Deserialize;
+
+    fn as_zc_account(&self) -> &ZcAccount<'_, Self::State>;
+}
+
+impl<T> AsZcAccount for ZcAccount<'_, T>
+where
+    T: Zc + Deserialize,
+{
+    type State = T;
+
+    #[inline(always)]
+    fn as_zc_account(&self) -> &ZcAccount<'_, T> {
+        self
+    }
+}
+
+impl<A> Mut<A>
+where
+    A: AsZcAccount,
+{
+    #[inline(always)]
+    fn zc_account_view(&self) -> &AccountView {
+        self.0.as_zc_account().account_view
+    }
+}
+
+impl<A, T> Mut<A>
 where
+    A: AsZcAccount<State = T>,
     T: ZcDeserialize + ZcDeserializeMut,
 {
     #[inline(always)]
-    pub fn try_deserialize_mut(&self) -> Result<RefMut<T>> {
-        T::try_deserialize_mut(self.account_view)
+    pub fn try_deserialize_mut(&self) -> Result<RefMut<'_, T>> {
+        T::try_deserialize_mut(self.zc_account_view())
     }
 }
 
-impl<'ix, T> ZcAccount<'ix, T>
+impl<A, T> Mut<A>
 where
+    A: AsZcAccount<State = T>,
     T: ZcDeserialize + ZcInitialize,
 {
     #[inline(always)]
-    pub fn try_initialize(
-        &self,
-        init_accounts: InitAccounts<'ix, '_>,
+    pub fn try_initialize<'a>(
+        &'a self,
+        init_accounts: InitAccounts<'a, '_>,
+        signers: Option<&[Signer]>,
+    ) -> Result<RefMut<'a, T>> {
+        T::try_initialize(self.zc_account_view(), init_accounts, signers)
+    }
+
+    /// See [`ZcInitialize::try_initialize_if_needed`]
+    #[inline(always)]
+    pub fn try_initialize_if_needed<'a>(
+        &'a self,
+        init_accounts: InitAccounts<'a, '_>,
+        signers: Option<&[Signer]>,
+    ) -> Result<RefMut<'a, T>> {
+        T::try_initialize_if_needed(self.zc_account_view(), init_accounts, signers)
+    }
+
+    /// See [`ZcInitialize::try_initialize_with_rent_payer`]
+    #[inline(always)]
+    pub fn try_initialize_with_rent_payer<'a>(
+        &'a self,
+        init_accounts: InitAccounts<'a, '_>,
+        signers: Option<&[Signer]>,
+    ) -> Result<RefMut<'a, T>>
+    where
+        T: RentPayer,
+    {
+        T::try_initialize_with_rent_payer(self.zc_account_view(), init_accounts, signers)
+    }
+
+    /// See [`ZcInitialize::try_begin_initialize`], the account is then passed as
+    /// [`crate::Initializing`] to the instructions that complete it
+    #[inline(always)]
+    pub fn try_begin_initialize<'a>(
+        &'a self,
+        init_accounts: InitAccounts<'a, '_>,
+        signers: Option<&[Signer]>,
+    ) -> Result<RefMut<'a, T>> {
+        T::try_begin_initialize(self.zc_account_view(), init_accounts, signers)
+    }
+
+    /// See [`ZcInitialize::try_initialize_with`]
+    #[inline(always)]
+    pub fn try_initialize_with<'a, F>(
+        &'a self,
+        init_accounts: InitAccounts<'a, '_>,
+        signers: Option<&[Signer]>,
+        init: F,
+    ) -> Result<RefMut<'a, T>>
+    where
+        F: FnOnce(&mut T),
+    {
+        T::try_initialize_with(self.zc_account_view(), init_accounts, signers, init)
+    }
+
+    /// See [`ZcInitialize::try_initialize_with_state`]
+    #[inline(always)]
+    pub fn try_initialize_with_state<'a>(
+        &'a self,
+        init_accounts: InitAccounts<'a, '_>,
         signers: Option<&[Signer]>,
-    ) -> Result<RefMut<'ix, T>> {
-        T::try_initialize(self.account_view, init_accounts, signers)
+        state: T,
+    ) -> Result<RefMut<'a, T>> {
+        T::try_initialize_with_state(self.zc_account_view(), init_accounts, signers, state)
+    }
+}
+
+impl<A, T> Mut<A>
+where
+    A: AsZcAccount<State = T>,
+    T: ZcDeserialize,
+{
+    /// Closes the account and moves its lamports to `destination`
+    #[inline(always)]
+    pub fn try_close(&self, destination: &AccountView) -> Result<()> {
+        try_close_zc::<T>(self.zc_account_view(), destination)
+    }
+
+    /// Closes the account and refunds its lamports to the recorded rent payer
+    #[inline(always)]
+    pub fn try_close_to_rent_payer(&self, rent_payer: &AccountView) -> Result<()>
+    where
+        T: RentPayer,
+    {
+        try_close_zc_to_rent_payer::<T>(self.zc_account_view(), rent_payer)
+    }
+
+    /// Closes the account, moves its lamports to `destination` and leaves a `CLOSED` tombstone
+    /// so the account can't be reused within the same transaction
+    #[inline(always)]
+    pub fn try_close_with_tombstone(&self, destination: &AccountView) -> Result<()> {
+        try_tombstone_zc::<T>(self.zc_account_view(), destination)
+    }
+}
+
+impl<T> ZcAccount<'_, T>
+where
+    T: ZcDeserialize + ZcDeserializeMut + ReentrancyGuard,
+{
+    /// Fails with [`ErrorCode::ReentrancyDetected`] while the account is inside
+    /// `Mut::try_guarded`.
+    #[inline(always)]
+    pub fn try_check_not_in_flight(&self) -> Result<()> {
+        if unlikely(self.try_deserialize()?.in_flight()) {
+            error_msg!(
+                "ZcAccount::try_check_not_in_flight: account is in flight",
+                ErrorCode::ReentrancyDetected,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl<A, T> Mut<A>
+where
+    A: AsZcAccount<State = T>,
+    T: ZcDeserialize + ZcDeserializeMut + ReentrancyGuard,
+{
+    /// Runs `cpi` with the `in_flight` flag of the account set, and clears it once `cpi`
+    /// succeeded.
+    ///
+    /// The flag is written to the account data before the CPI, so an instruction of this program
+    /// reached from the callee (directly or through hooks and adapters) sees it and is rejected by
+    /// the `not_in_flight` constraint. No borrow of the account is held during `cpi`, state
+    /// should be updated before or after it.
+    #[inline(always)]
+    pub fn try_guarded<R, F>(&self, cpi: F) -> Result<R>
+    where
+        F: FnOnce() -> Result<R>,
+    {
+        {
+            let mut state = self.try_deserialize_mut()?;
+
+            if unlikely(state.in_flight()) {
+                error_msg!(
+                    "ZcAccount::try_guarded: account is in flight",
+                    ErrorCode::ReentrancyDetected,
+                );
+            }
+
+            state.set_in_flight(true);
+        }
+
+        let result = cpi()?;
+        self.try_deserialize_mut()?.set_in_flight(false);
+
+        Ok(result)
     }
 }
 
@@ -61,18 +243,19 @@ where
     T: RawZcDeserialize,
 {
     #[inline(always)]
-    pub fn try_deserialize_raw(&self) -> Result<Ref<T>> {
+    pub fn try_deserialize_raw(&self) -> Result<Ref<'_, T>> {
         T::try_deserialize_raw(self.account_view)
     }
 }
 
-impl<T> ZcAccount<'_, T>
+impl<A, T> Mut<A>
 where
+    A: AsZcAccount<State = T>,
     T: RawZcDeserialize + RawZcDeserializeMut,
 {
     #[inline(always)]
-    pub fn try_deserialize_raw_mut(&self) -> Result<RefMut<T>> {
-        T::try_deserialize_raw_mut(self.account_view)
+    pub fn try_deserialize_raw_mut(&self) -> Result<RefMut<'_, T>> {
+        T::try_deserialize_raw_mut(self.zc_account_view())
     }
 }
 
@@ -80,19 +263,25 @@ impl<T> ZcAccount<'_, T>
 where
     T: RawZcDeserializeUnchecked,
 {
+    /// # Safety
+    /// See [`RawZcDeserializeUnchecked::try_deserialize_raw_unchecked`]
     #[inline(always)]
     pub unsafe fn try_deserialize_raw_unchecked(&self) -> Result<&T> {
         T::try_deserialize_raw_unchecked(self.account_view)
     }
 }
 
-impl<T> ZcAccount<'_, T>
+impl<A, T> Mut<A>
 where
+    A: AsZcAccount<State = T>,
     T: RawZcDeserializeUnchecked + RawZcDeserializeUncheckedMut,
 {
+    /// # Safety
+    /// See [`RawZcDeserializeUncheckedMut::try_deserialize_raw_unchecked_mut`]
+    #[allow(clippy::mut_from_ref)]
     #[inline(always)]
     pub unsafe fn try_deserialize_raw_unchecked_mut(&self) -> Result<&mut T> {
-        T::try_deserialize_raw_unchecked_mut(self.account_view)
+        T::try_deserialize_raw_unchecked_mut(self.zc_account_view())
     }
 }
 
@@ -117,6 +306,66 @@ where
     }
 }
 
+/// Accounts recording a [`RentPayer`] get `init_accounts.payer_account` as theirs
+impl<T> InitAccount for ZcAccount<'_, T>
+where
+    T: Zc + Deserialize + ZcInitialize + MaybeRentPayer,
+{
+    const DEFAULT_SPACE: usize = T::DISCRIMINATED_LEN;
+
+    #[inline(always)]
+    fn try_init_account(
+        account_view: &AccountView,
+        init_accounts: InitAccounts<'_, '_>,
+        space: usize,
+        signers: Option<&[Signer]>,
+    ) -> Result<()> {
+        // the accessors only read accounts of exactly this length, only tail accounts grow past it
+        if unlikely(space != T::DISCRIMINATED_LEN) {
+            error_msg!(
+                "ZcAccount::try_init_account: space must be the discriminated length of the account",
+                ProgramError::InvalidArgument,
+            );
+        }
+
+        let rent_payer = *init_accounts.payer_account.address();
+
+        try_create_zc::<T>(account_view, init_accounts, space, signers)?;
+
+        let mut data = account_view.try_borrow_mut()?;
+        bytemuck::from_bytes_mut::<T>(&mut data[T::DISCRIMINATOR.len()..])
+            .maybe_set_rent_payer(rent_payer);
+
+        Ok(())
+    }
+}
+
+/// Accounts recording a [`RentPayer`] can only be closed into it
+impl<A, T> CloseAccount for Mut<A>
+where
+    A: AsZcAccount<State = T>,
+    T: ZcDeserialize + MaybeRentPayer,
+{
+    #[inline(always)]
+    fn try_close_account(account_view: &AccountView, destination: &AccountView) -> Result<()> {
+        // validates owner, length and discriminator
+        let account = T::try_deserialize(account_view)?;
+
+        if let Some(rent_payer) = account.maybe_rent_payer() {
+            if unlikely(!address_eq(destination.address(), rent_payer)) {
+                error_msg!(
+                    "ZcAccount::try_close_account: close receiver is not the rent payer",
+                    ErrorCode::InvalidAccount,
+                );
+            }
+        }
+
+        drop(account);
+
+        tombstone_account(account_view, destination)
+    }
+}
+
 impl<T> ToAccountView for ZcAccount<'_, T>
 where
     T: Zc + Deserialize,
@@ -137,6 +386,6 @@ where
 
     #[inline(always)]
     fn deref(&self) -> &Self::Target {
-        &self.account_view
+        self.account_view
     }
 }
@@ -0,0 +1,153 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{FromAccountView, InitAccount, NoMeta, ToAccountView, WritableAllowed};
+use bytemuck::Pod;
+use core::ops::Deref;
+use hayabusa_common::{AccountView, Ref, RefMut};
+use hayabusa_errors::Result;
+use hayabusa_ser::{
+    try_create_zc, try_deserialize_zc_header, try_deserialize_zc_header_mut,
+    try_deserialize_zc_tail, try_deserialize_zc_tail_mut, try_initialize_zc_with_tail,
+    try_split_zc_tail_mut, try_tail_len, InitAccounts, ZcInitialize,
+};
+use solana_instruction_view::cpi::Signer;
+
+/// Zero-copy account made of an `H` header (an `#[account]` type) followed by as many `T` as the
+/// account data holds, e.g. an orderbook header and its orders.
+///
+/// Every accessor checks the owner and discriminator of `H` and that the data length is the
+/// header plus a whole number of `T`. Grow the tail with [`hayabusa_ser::try_realloc_account`].
+pub struct ZcAccountWithTail<'ix, H, T>
+where
+    H: ZcInitialize,
+    T: Pod,
+{
+    pub account_view: &'ix AccountView,
+    _phantom: core::marker::PhantomData<(H, T)>,
+}
+
+impl<'ix, H, T> ZcAccountWithTail<'ix, H, T>
+where
+    H: ZcInitialize,
+    T: Pod,
+{
+    #[inline(always)]
+    pub fn header(&self) -> Result<Ref<'_, H>> {
+        try_deserialize_zc_header::<H, T>(self.account_view)
+    }
+
+    #[inline(always)]
+    pub fn header_mut(&self) -> Result<RefMut<'_, H>> {
+        try_deserialize_zc_header_mut::<H, T>(self.account_view)
+    }
+
+    #[inline(always)]
+    pub fn tail(&self) -> Result<Ref<'_, [T]>> {
+        try_deserialize_zc_tail::<H, T>(self.account_view)
+    }
+
+    #[inline(always)]
+    pub fn tail_mut(&self) -> Result<RefMut<'_, [T]>> {
+        try_deserialize_zc_tail_mut::<H, T>(self.account_view)
+    }
+
+    /// Number of `T` the account data holds
+    #[inline(always)]
+    pub fn tail_len(&self) -> Result<usize> {
+        try_tail_len::<H, T>(self.account_view.data_len())
+    }
+
+    /// See [`try_split_zc_tail_mut`]
+    #[inline(always)]
+    pub fn try_split_mut<R, F>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut H, &mut [T]) -> R,
+    {
+        try_split_zc_tail_mut::<H, T, R, F>(self.account_view, f)
+    }
+
+    /// Creates the account with room for `capacity` elements, see
+    /// [`try_initialize_zc_with_tail`]
+    #[inline(always)]
+    pub fn try_initialize(
+        &self,
+        init_accounts: InitAccounts<'ix, '_>,
+        signers: Option<&[Signer]>,
+        capacity: usize,
+    ) -> Result<()> {
+        try_initialize_zc_with_tail::<H, T>(self.account_view, init_accounts, signers, capacity)
+    }
+}
+
+impl<'ix, H, T> FromAccountView<'ix> for ZcAccountWithTail<'ix, H, T>
+where
+    H: ZcInitialize,
+    T: Pod,
+{
+    type Meta<'a>
+        = NoMeta
+    where
+        'ix: 'a;
+
+    #[inline(always)]
+    fn try_from_account_view<'a>(account_view: &'ix AccountView, _: Self::Meta<'a>) -> Result<Self>
+    where
+        'ix: 'a,
+    {
+        Ok(ZcAccountWithTail {
+            account_view,
+            _phantom: core::marker::PhantomData,
+        })
+    }
+}
+
+/// `space` of the `init` constraint is the full account size, header included
+impl<H, T> InitAccount for ZcAccountWithTail<'_, H, T>
+where
+    H: ZcInitialize,
+    T: Pod,
+{
+    const DEFAULT_SPACE: usize = H::DISCRIMINATED_LEN;
+
+    #[inline(always)]
+    fn try_init_account(
+        account_view: &AccountView,
+        init_accounts: InitAccounts<'_, '_>,
+        space: usize,
+        signers: Option<&[Signer]>,
+    ) -> Result<()> {
+        try_create_zc::<H>(account_view, init_accounts, space, signers)
+    }
+}
+
+impl<H, T> ToAccountView for ZcAccountWithTail<'_, H, T>
+where
+    H: ZcInitialize,
+    T: Pod,
+{
+    #[inline(always)]
+    fn to_account_view(&self) -> &AccountView {
+        self.account_view
+    }
+}
+
+impl<H, T> WritableAllowed for ZcAccountWithTail<'_, H, T>
+where
+    H: ZcInitialize,
+    T: Pod,
+{
+}
+
+impl<H, T> Deref for ZcAccountWithTail<'_, H, T>
+where
+    H: ZcInitialize,
+    T: Pod,
+{
+    type Target = AccountView;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.account_view
+    }
+}
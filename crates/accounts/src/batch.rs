@@ -0,0 +1,107 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use hayabusa_common::AccountView;
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_ser::{ZcDeserialize, ZcDeserializeMut};
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+/// Runs the owner, length and discriminator checks of `T` over every account, e.g. the remaining
+/// accounts of a crank instruction.
+///
+/// Same checks as `T::try_deserialize`, without creating a `Ref` per account: the borrow state
+/// is only checked so the discriminator can be read in place. Fails on the first invalid
+/// account, its index is logged.
+#[inline(always)]
+pub fn validate_all<T>(account_views: &[AccountView]) -> Result<()>
+where
+    T: ZcDeserialize,
+{
+    for (i, account_view) in account_views.iter().enumerate() {
+        account_view.check_borrow()?;
+        validate::<T>(account_view, i)?;
+    }
+
+    Ok(())
+}
+
+/// [`validate_all`] for accounts that will be written, each must also be writable and not
+/// borrowed.
+#[inline(always)]
+pub fn validate_all_mut<T>(account_views: &[AccountView]) -> Result<()>
+where
+    T: ZcDeserialize + ZcDeserializeMut,
+{
+    for (i, account_view) in account_views.iter().enumerate() {
+        if unlikely(!account_view.is_writable()) {
+            error_msg!(
+                "validate_all_mut: account {} not writable",
+                ErrorCode::AccountNotWritable,
+                i,
+            );
+        }
+
+        account_view.check_borrow_mut()?;
+        validate::<T>(account_view, i)?;
+    }
+
+    Ok(())
+}
+
+/// Validates every account with [`validate_all_mut`] first, then calls `f` with the index and
+/// state of each, so nothing is written unless the whole batch is valid.
+#[inline(always)]
+pub fn try_for_each_mut<T, F>(account_views: &[AccountView], mut f: F) -> Result<()>
+where
+    T: ZcDeserialize + ZcDeserializeMut,
+    F: FnMut(usize, &mut T) -> Result<()>,
+{
+    validate_all_mut::<T>(account_views)?;
+
+    for (i, account_view) in account_views.iter().enumerate() {
+        let mut data = account_view.try_borrow_mut()?;
+        f(
+            i,
+            bytemuck::from_bytes_mut(&mut data[T::DISCRIMINATOR.len()..T::DISCRIMINATED_LEN]),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[inline(always)]
+fn validate<T>(account_view: &AccountView, index: usize) -> Result<()>
+where
+    T: ZcDeserialize,
+{
+    if unlikely(!account_view.owned_by(&T::OWNER)) {
+        error_msg!(
+            "validate_all: account {} has the wrong owner",
+            ProgramError::InvalidAccountOwner,
+            index,
+        );
+    }
+
+    if unlikely(account_view.data_len() != T::DISCRIMINATED_LEN) {
+        error_msg!(
+            "validate_all: account {} has the wrong data length",
+            ProgramError::InvalidAccountData,
+            index,
+        );
+    }
+
+    // SAFETY: the length covers the discriminator and the caller checked the account isn't
+    // mutably borrowed
+    let disc =
+        unsafe { core::slice::from_raw_parts(account_view.data_ptr(), T::DISCRIMINATOR.len()) };
+
+    if unlikely(disc != T::DISCRIMINATOR) {
+        error_msg!(
+            "validate_all: account {} has an invalid discriminator",
+            ProgramError::InvalidAccountData,
+            index,
+        );
+    }
+
+    Ok(())
+}
@@ -4,13 +4,21 @@
 #![no_std]
 
 mod accounts;
+mod batch;
+#[cfg(feature = "state-diff")]
+pub mod state_diff;
 pub use accounts::{
-    interface::*, mutable::*, program::*, signer::*, system_account::*, unchecked_account::*,
-    zc_account::*, checked_address::*,
+    initializing::*, interface::*, loader::*, mutable::*, pda_account::*, program::*, program_ref::*, rent_exempt::*, signer::*, signer_address::*, sysvar_account::*, system_account::*, unchecked_account::*,
+    versioned_zc_account::*, wallet::*,
+    zc_account::*, zc_account_with_tail::*, checked_address::*,
 };
+pub use batch::*;
 
 use hayabusa_common::{AccountView, Address};
+#[cfg(feature = "audit-writable")]
+use hayabusa_errors::{ErrorCode, ProgramError};
 use hayabusa_errors::Result;
+use hayabusa_ser::InitAccounts;
 
 pub trait FromAccountView<'ix>: Sized {
     type Meta<'a>
@@ -38,8 +46,63 @@ where
     fn initialize_account(&self, account_data: &[u8]) -> Result<()>;
 }
 
+/// Account types that can be created by the `init` meta constraint of `FromAccountViews`
+pub trait InitAccount {
+    /// Space allocated when the constraint doesn't specify one, discriminator included
+    const DEFAULT_SPACE: usize;
+
+    fn try_init_account(
+        account_view: &AccountView,
+        init_accounts: InitAccounts<'_, '_>,
+        space: usize,
+        signers: Option<&[solana_instruction_view::cpi::Signer]>,
+    ) -> Result<()>;
+}
+
+/// Account types that can be closed by the `close` meta constraint of `FromAccountViews`
+///
+/// Closing drains the lamports to `destination` and leaves a tombstone behind, so the account
+/// can't be reused later in the same transaction. Zero-copy accounts are only closed through
+/// `Mut`, like every other write: `Mut<ZcAccount<T>>` implements it, `ZcAccount<T>` doesn't.
+pub trait CloseAccount {
+    fn try_close_account(account_view: &AccountView, destination: &AccountView) -> Result<()>;
+}
+
+/// Account types that can be wrapped in [`Mut`], `FromAccountViews` rejects other `Mut` fields
+/// at compile time.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be declared writable",
+    label = "not `WritableAllowed`",
+    note = "remove the `Mut` wrapper, the account type never writes the account"
+)]
 pub trait WritableAllowed {}
 
+/// Compile-time check emitted by `FromAccountViews` for every `Mut<T>` field.
+#[doc(hidden)]
+#[inline(always)]
+pub const fn assert_writable_allowed<T: WritableAllowed>() {}
+
+/// Emitted by `FromAccountViews` for every field not declared `Mut`.
+///
+/// With the `audit-writable` feature an account passed as writable fails with
+/// `ErrorCode::UnexpectedWritableAccount`, surfacing clients that request more write locks
+/// than the program declares. Only meant for test builds: in a transaction where another
+/// instruction writes the same account the flag is legitimately set. A no-op otherwise.
+#[doc(hidden)]
+#[inline(always)]
+pub fn audit_read_only(_account_view: &AccountView, _field: &str) -> Result<()> {
+    #[cfg(feature = "audit-writable")]
+    if hayabusa_utility::hint::unlikely(_account_view.is_writable()) {
+        hayabusa_utility::error_msg!(
+            "audit: {} is writable but not declared Mut",
+            ErrorCode::UnexpectedWritableAccount,
+            _field,
+        );
+    }
+
+    Ok(())
+}
+
 pub trait ProgramId {
     const ID: Address;
 }
@@ -47,3 +110,8 @@ pub trait ProgramId {
 pub trait ProgramIds {
     const IDS: &'static [Address];
 }
+
+/// Fixed key an account must have, e.g. an admin or upgrade authority, see [`SignerAddress`]
+pub trait KnownAddress {
+    const ADDRESS: Address;
+}
@@ -0,0 +1,100 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! `state-diff` feature: every [`Mut`](crate::Mut) account logs the byte ranges of its data that
+//! changed while it was alive, i.e. during the handler it was parsed for.
+//!
+//! ```text
+//! state diff: <address>
+//! state diff: bytes 8..16
+//! state diff: len 48 -> 80
+//! ```
+//!
+//! Offsets include the 8 byte discriminator. Only the first [`STATE_DIFF_MAX_LEN`] bytes are
+//! compared, a debugging aid, not something to ship: every `Mut` field carries a copy of them.
+
+#![allow(unexpected_cfgs)] // silence warning about target_os = "solana"
+
+use hayabusa_common::AccountView;
+
+/// Bytes of account data compared by the diff
+pub const STATE_DIFF_MAX_LEN: usize = 512;
+
+/// Copy of the account data taken when a `Mut` account is parsed, diffed and logged on drop.
+pub struct StateSnapshot {
+    account_view: AccountView,
+    len: usize,
+    bytes: [u8; STATE_DIFF_MAX_LEN],
+}
+
+impl StateSnapshot {
+    // `AccountView` is `Copy` with its `copy` feature
+    #[allow(clippy::clone_on_copy)]
+    #[inline(always)]
+    pub(crate) fn new(account_view: &AccountView) -> Self {
+        let mut bytes = [0u8; STATE_DIFF_MAX_LEN];
+        let len = match account_view.try_borrow() {
+            Ok(data) => {
+                let n = data.len().min(STATE_DIFF_MAX_LEN);
+                bytes[..n].copy_from_slice(&data[..n]);
+                data.len()
+            }
+            // mutably borrowed while parsing, nothing to diff against
+            Err(_) => usize::MAX,
+        };
+
+        Self {
+            account_view: account_view.clone(),
+            len,
+            bytes,
+        }
+    }
+}
+
+impl Drop for StateSnapshot {
+    fn drop(&mut self) {
+        if self.len == usize::MAX {
+            return;
+        }
+        let Ok(data) = self.account_view.try_borrow() else {
+            return;
+        };
+
+        let old = &self.bytes[..self.len.min(STATE_DIFF_MAX_LEN)];
+        let new = &data[..data.len().min(STATE_DIFF_MAX_LEN)];
+        if self.len == data.len() && old == new {
+            return;
+        }
+
+        #[cfg(target_os = "solana")]
+        // SAFETY: the address is 32 readable bytes
+        unsafe {
+            hayabusa_syscalls::sol_log_pubkey(self.account_view.address().as_ref().as_ptr())
+        };
+        #[cfg(not(target_os = "solana"))]
+        pinocchio_log::log!("state diff: <address>");
+
+        let mut offset = 0;
+        while let Some((start, end)) = next_diff(old, new, offset) {
+            pinocchio_log::log!("state diff: bytes {}..{}", start, end);
+            offset = end;
+        }
+
+        if self.len != data.len() {
+            pinocchio_log::log!("state diff: len {} -> {}", self.len, data.len());
+        }
+    }
+}
+
+/// First range of differing bytes at or after `offset`, bytes past the end of the shorter slice
+/// count as different.
+#[inline(always)]
+fn next_diff(old: &[u8], new: &[u8], offset: usize) -> Option<(usize, usize)> {
+    let len = old.len().max(new.len());
+    let differs = |i: usize| old.get(i) != new.get(i);
+
+    let start = (offset..len).find(|&i| differs(i))?;
+    let end = (start..len).find(|&i| !differs(i)).unwrap_or(len);
+
+    Some((start, end))
+}
@@ -0,0 +1,291 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Running totals with lazy, slot-based accrual, the shared piece of staking and lending math.
+//!
+//! An [`Accumulator`] lives in the pool account and is brought up to date with
+//! [`Accumulator::try_accrue`] before every change of its total. Emissions are distributed
+//! through a reward index, every position keeps a [`Snapshot`] of the index it last settled at:
+//!
+//! ```ignore
+//! let emission = LinearEmission { per_slot: 1_000 };
+//! pool.rewards.try_accrue(&emission, slot!(), AccrualTarget::Index)?;
+//!
+//! let pending = position.snapshot.try_settle(&pool.rewards, position.staked)?;
+//! pool.rewards.try_add(amount)?;
+//! position.staked += amount;
+//! ```
+//!
+//! Interest on the other hand capitalizes into the total ([`AccrualTarget::Total`]).
+
+#![no_std]
+
+use bytemuck::{Pod, Zeroable};
+use hayabusa_errors::Result;
+use hayabusa_utility::{error_msg, hint::unlikely};
+use solana_program_error::ProgramError;
+
+/// Fixed point scale of [`Accumulator::index`], i.e. `1e12` is one unit accrued per unit of total.
+pub const INDEX_SCALE: u128 = 1_000_000_000_000;
+
+/// Fixed point scale of [`SimpleInterest::rate_per_slot`].
+pub const RATE_SCALE: u128 = 1_000_000_000_000;
+
+/// Computes what accrued on `total` over `elapsed_slots`, called lazily by
+/// [`Accumulator::try_accrue`].
+pub trait Accrual {
+    fn accrued(&self, total: u64, elapsed_slots: u64) -> Result<u64>;
+}
+
+/// A fixed amount per slot, independent of the total, e.g. staking emissions.
+#[derive(Clone, Copy, Debug)]
+pub struct LinearEmission {
+    pub per_slot: u64,
+}
+
+impl Accrual for LinearEmission {
+    #[inline(always)]
+    fn accrued(&self, _total: u64, elapsed_slots: u64) -> Result<u64> {
+        match self.per_slot.checked_mul(elapsed_slots) {
+            Some(accrued) => Ok(accrued),
+            None => {
+                error_msg!(
+                    "LinearEmission::accrued: overflow",
+                    ProgramError::ArithmeticOverflow,
+                );
+            }
+        }
+    }
+}
+
+/// Simple (per accrual) interest on the total, `rate_per_slot` is scaled by [`RATE_SCALE`].
+///
+/// Accruing often compounds it, the same way lending protocols compound on every interaction.
+#[derive(Clone, Copy, Debug)]
+pub struct SimpleInterest {
+    pub rate_per_slot: u64,
+}
+
+impl Accrual for SimpleInterest {
+    #[inline(always)]
+    fn accrued(&self, total: u64, elapsed_slots: u64) -> Result<u64> {
+        let accrued = (total as u128)
+            .checked_mul(self.rate_per_slot as u128)
+            .and_then(|v| v.checked_mul(elapsed_slots as u128))
+            .map(|v| v / RATE_SCALE);
+
+        match accrued {
+            Some(accrued) if accrued <= u64::MAX as u128 => Ok(accrued as u64),
+            _ => {
+                error_msg!(
+                    "SimpleInterest::accrued: overflow",
+                    ProgramError::ArithmeticOverflow,
+                );
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccrualTarget {
+    /// Add the accrued amount to the total, e.g. interest on borrows.
+    Total,
+    /// Distribute the accrued amount over the total through the reward index.
+    Index,
+}
+
+/// Running total, the slot it was last accrued at and a reward index.
+///
+/// Only contains 8 byte aligned fields, it can be embedded in any zero-copy account.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pod, Zeroable)]
+pub struct Accumulator {
+    pub total: u64,
+    pub last_update_slot: u64,
+    /// Everything accrued so far, in either target.
+    pub accrued: u64,
+    // u128 as two words, keeps the alignment at 8
+    index: [u64; 2],
+}
+
+impl Accumulator {
+    /// Cumulative accrual per unit of total, scaled by [`INDEX_SCALE`].
+    #[inline(always)]
+    pub fn index(&self) -> u128 {
+        (self.index[0] as u128) | ((self.index[1] as u128) << 64)
+    }
+
+    #[inline(always)]
+    fn set_index(&mut self, index: u128) {
+        self.index = [index as u64, (index >> 64) as u64];
+    }
+
+    #[inline]
+    pub fn try_add(&mut self, amount: u64) -> Result<u64> {
+        let Some(total) = self.total.checked_add(amount) else {
+            error_msg!(
+                "Accumulator::try_add: overflow",
+                ProgramError::ArithmeticOverflow,
+            );
+        };
+
+        self.total = total;
+        Ok(total)
+    }
+
+    #[inline]
+    pub fn try_sub(&mut self, amount: u64) -> Result<u64> {
+        let Some(total) = self.total.checked_sub(amount) else {
+            error_msg!(
+                "Accumulator::try_sub: underflow",
+                ProgramError::ArithmeticOverflow,
+            );
+        };
+
+        self.total = total;
+        Ok(total)
+    }
+
+    /// Accrues everything since [`Accumulator::last_update_slot`] into `target` and moves the
+    /// update slot to `current_slot`, returning the accrued amount.
+    ///
+    /// Emissions accrued while the total is zero are dropped, nobody was there to earn them.
+    pub fn try_accrue<A: Accrual>(
+        &mut self,
+        accrual: &A,
+        current_slot: u64,
+        target: AccrualTarget,
+    ) -> Result<u64> {
+        if unlikely(current_slot < self.last_update_slot) {
+            error_msg!(
+                "Accumulator::try_accrue: slot went backwards",
+                ProgramError::InvalidArgument,
+            );
+        }
+
+        let elapsed_slots = current_slot - self.last_update_slot;
+        self.last_update_slot = current_slot;
+
+        if elapsed_slots == 0 || self.total == 0 {
+            return Ok(0);
+        }
+
+        let accrued = accrual.accrued(self.total, elapsed_slots)?;
+
+        match target {
+            AccrualTarget::Total => {
+                self.try_add(accrued)?;
+            }
+            AccrualTarget::Index => {
+                let index = (accrued as u128)
+                    .checked_mul(INDEX_SCALE)
+                    .map(|v| v / self.total as u128)
+                    .and_then(|v| v.checked_add(self.index()));
+
+                let Some(index) = index else {
+                    error_msg!(
+                        "Accumulator::try_accrue: index overflow",
+                        ProgramError::ArithmeticOverflow,
+                    );
+                };
+
+                self.set_index(index);
+            }
+        }
+
+        self.accrued = self.accrued.saturating_add(accrued);
+
+        Ok(accrued)
+    }
+}
+
+/// Reward index a position last settled at.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pod, Zeroable)]
+pub struct Snapshot {
+    index: [u64; 2],
+}
+
+impl Snapshot {
+    #[inline(always)]
+    pub fn index(&self) -> u128 {
+        (self.index[0] as u128) | ((self.index[1] as u128) << 64)
+    }
+
+    /// Pending rewards of `balance` since the last settlement, without settling.
+    #[inline]
+    pub fn try_pending(&self, accumulator: &Accumulator, balance: u64) -> Result<u64> {
+        let pending = accumulator
+            .index()
+            .checked_sub(self.index())
+            .and_then(|delta| delta.checked_mul(balance as u128))
+            .map(|v| v / INDEX_SCALE);
+
+        match pending {
+            Some(pending) if pending <= u64::MAX as u128 => Ok(pending as u64),
+            _ => {
+                error_msg!(
+                    "Snapshot::try_pending: overflow",
+                    ProgramError::ArithmeticOverflow,
+                );
+            }
+        }
+    }
+
+    /// Returns the pending rewards of `balance` and moves the snapshot to the current index.
+    ///
+    /// Must be called with the balance held since the last settlement, i.e. before it changes.
+    #[inline]
+    pub fn try_settle(&mut self, accumulator: &Accumulator, balance: u64) -> Result<u64> {
+        let pending = self.try_pending(accumulator, balance)?;
+        self.index = accumulator.index;
+        Ok(pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emission_index() {
+        let mut pool = Accumulator::default();
+        let mut alice = Snapshot::default();
+        let mut bob = Snapshot::default();
+        let emission = LinearEmission { per_slot: 100 };
+
+        // alice stakes 500 at slot 0
+        pool.try_accrue(&emission, 0, AccrualTarget::Index).unwrap();
+        alice.try_settle(&pool, 0).unwrap();
+        pool.try_add(500).unwrap();
+
+        // bob stakes 500 at slot 10, alice earned everything so far
+        pool.try_accrue(&emission, 10, AccrualTarget::Index).unwrap();
+        bob.try_settle(&pool, 0).unwrap();
+        pool.try_add(500).unwrap();
+
+        pool.try_accrue(&emission, 20, AccrualTarget::Index).unwrap();
+        assert_eq!(alice.try_settle(&pool, 500).unwrap(), 1_000 + 500);
+        assert_eq!(bob.try_settle(&pool, 500).unwrap(), 500);
+        assert_eq!(pool.accrued, 2_000);
+
+        assert!(pool.try_sub(1_001).is_err());
+        assert!(pool.try_accrue(&emission, 19, AccrualTarget::Index).is_err());
+    }
+
+    #[test]
+    fn test_interest() {
+        let mut borrows = Accumulator {
+            total: 1_000_000,
+            ..Default::default()
+        };
+        // 0.1% per slot
+        let interest = SimpleInterest {
+            rate_per_slot: (RATE_SCALE / 1_000) as u64,
+        };
+
+        let accrued = borrows.try_accrue(&interest, 10, AccrualTarget::Total).unwrap();
+        assert_eq!(accrued, 10_000);
+        assert_eq!(borrows.total, 1_010_000);
+    }
+}
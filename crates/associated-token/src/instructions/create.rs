@@ -0,0 +1,79 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use hayabusa_cpi::{CheckProgramId, CpiCtx};
+use hayabusa_errors::Result;
+use solana_account_view::AccountView;
+use solana_address::Address;
+use solana_instruction_view::{
+    cpi::{invoke, invoke_signed},
+    InstructionAccount, InstructionView,
+};
+
+pub struct Create<'ix> {
+    /// Funding account
+    pub payer: &'ix AccountView,
+    /// Associated token account to create
+    pub account: &'ix AccountView,
+    /// Wallet owning the new account
+    pub wallet: &'ix AccountView,
+    /// Mint of the new account
+    pub mint: &'ix AccountView,
+    /// System program
+    pub system_program: &'ix AccountView,
+    /// SPL Token or Token-2022 program
+    pub token_program: &'ix AccountView,
+}
+
+impl CheckProgramId for Create<'_> {
+    const ID: Address = crate::ID;
+}
+
+/// Creates the associated token account, fails if it already exists.
+#[inline]
+pub fn create<'ix>(cpi_ctx: CpiCtx<'ix, '_, '_, '_, Create<'ix>>) -> Result<()> {
+    invoke_create(cpi_ctx, 0)
+}
+
+/// Creates the associated token account, succeeds if it already exists with the same mint and
+/// owner.
+#[inline]
+pub fn create_idempotent<'ix>(cpi_ctx: CpiCtx<'ix, '_, '_, '_, Create<'ix>>) -> Result<()> {
+    invoke_create(cpi_ctx, 1)
+}
+
+#[inline(always)]
+fn invoke_create<'ix>(cpi_ctx: CpiCtx<'ix, '_, '_, '_, Create<'ix>>, discriminator: u8) -> Result<()> {
+    let account_views = [
+        cpi_ctx.payer,
+        cpi_ctx.account,
+        cpi_ctx.wallet,
+        cpi_ctx.mint,
+        cpi_ctx.system_program,
+        cpi_ctx.token_program,
+    ];
+    let instruction_accounts = [
+        InstructionAccount::writable_signer(cpi_ctx.payer.address()),
+        InstructionAccount::writable(cpi_ctx.account.address()),
+        InstructionAccount::readonly(cpi_ctx.wallet.address()),
+        InstructionAccount::readonly(cpi_ctx.mint.address()),
+        InstructionAccount::readonly(cpi_ctx.system_program.address()),
+        InstructionAccount::readonly(cpi_ctx.token_program.address()),
+    ];
+
+    // ix data
+    // - [0]: discriminator
+    let ix_data = [discriminator];
+
+    let instruction = InstructionView {
+        program_id: &crate::ID,
+        accounts: &instruction_accounts,
+        data: &ix_data,
+    };
+
+    if let Some(signers) = cpi_ctx.signers {
+        invoke_signed(&instruction, &account_views, signers)
+    } else {
+        invoke(&instruction, &account_views)
+    }
+}
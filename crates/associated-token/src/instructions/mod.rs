@@ -0,0 +1,6 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod create;
+
+pub use create::*;
@@ -0,0 +1,82 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_std]
+
+pub mod instructions;
+
+solana_address::declare_id!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+use hayabusa_accounts::ProgramId;
+use hayabusa_errors::{ErrorCode, Result};
+use hayabusa_syscalls::try_find_program_address;
+use hayabusa_utility::{error_msg, hint::unlikely};
+use solana_account_view::AccountView;
+use solana_address::{address, address_eq, Address};
+use solana_program_error::ProgramError;
+
+/// SPL Token program, the default token program of associated token accounts.
+pub const TOKEN_PROGRAM_ID: Address = address!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// SPL Token-2022 program.
+pub const TOKEN_2022_PROGRAM_ID: Address = address!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+pub struct AssociatedToken;
+
+impl ProgramId for AssociatedToken {
+    const ID: Address = ID;
+}
+
+/// Address and bump of the associated token account of `wallet` for `mint`, owned by
+/// `token_program` (SPL Token or Token-2022).
+#[inline]
+pub fn get_associated_token_address(
+    wallet: &Address,
+    mint: &Address,
+    token_program: &Address,
+) -> Result<(Address, u8)> {
+    try_find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ID,
+    )
+}
+
+/// Checks that `account_view` is the associated token account of `wallet` for `mint`.
+///
+/// Unless `uninitialized` is set, the account must also be owned by `token_program`.
+#[inline]
+pub fn check_associated_token_account(
+    account_view: &AccountView,
+    wallet: &Address,
+    mint: &Address,
+    token_program: &Address,
+    uninitialized: bool,
+) -> Result<()> {
+    if unlikely(
+        !address_eq(token_program, &TOKEN_PROGRAM_ID)
+            && !address_eq(token_program, &TOKEN_2022_PROGRAM_ID),
+    ) {
+        error_msg!(
+            "check_associated_token_account: unknown token program",
+            ProgramError::IncorrectProgramId,
+        );
+    }
+
+    let (expected, _) = get_associated_token_address(wallet, mint, token_program)?;
+
+    if unlikely(!address_eq(account_view.address(), &expected)) {
+        error_msg!(
+            "check_associated_token_account: address mismatch",
+            ErrorCode::InvalidAccount,
+        );
+    }
+
+    if unlikely(!uninitialized && !account_view.owned_by(token_program)) {
+        error_msg!(
+            "check_associated_token_account: invalid owner",
+            ProgramError::InvalidAccountOwner,
+        );
+    }
+
+    Ok(())
+}
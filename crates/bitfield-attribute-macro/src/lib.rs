@@ -0,0 +1,298 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Fields, Ident, Item, ItemEnum, ItemStruct, LitInt, Type};
+
+/// Packs small fields into a single `u64` word.
+///
+/// On a struct, the fields are replaced by an 8 byte little endian word with an alignment of 1,
+/// so the struct can be used inside `#[account]` structs. Each field takes `#[bits(N)]` bits, or
+/// `BitfieldValue::BITS` of its type, in declaration order starting from the lowest bit.
+///
+/// ```ignore
+/// #[bitfield]
+/// pub enum Tier { Bronze, Silver, Gold }
+///
+/// #[bitfield]
+/// pub struct Flags {
+///     pub paused: bool,
+///     pub tier: Tier,
+///     #[bits(6)]
+///     pub level: u8,
+/// }
+/// ```
+///
+/// Generates per field:
+///   - `bool` and integers: `x()`, and `set_x(v)`, or `try_set_x(v)` when `#[bits(N)]` is narrower
+///     than the integer
+///   - other types (enums): `try_x()`, failing on an unknown value, and `set_x(v)`
+///
+/// On a fieldless enum, implements `BitfieldValue` with the bits needed for the largest
+/// discriminant.
+#[proc_macro_attribute]
+pub fn bitfield(_attr: TokenStream, input: TokenStream) -> TokenStream {
+    let expanded = match parse_macro_input!(input as Item) {
+        Item::Struct(s) => expand_struct(s),
+        Item::Enum(e) => expand_enum(e),
+        item => Err(syn::Error::new_spanned(
+            item,
+            "#[bitfield] requires a struct or a fieldless enum",
+        )),
+    };
+
+    expanded.unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+enum Kind {
+    Bool,
+    Int,
+    Value,
+}
+
+fn field_kind(ty: &Type) -> Kind {
+    let Type::Path(path) = ty else {
+        return Kind::Value;
+    };
+
+    match path.path.get_ident().map(|i| i.to_string()).as_deref() {
+        Some("bool") => Kind::Bool,
+        Some("u8" | "u16" | "u32" | "u64") => Kind::Int,
+        _ => Kind::Value,
+    }
+}
+
+fn expand_struct(s: ItemStruct) -> syn::Result<TokenStream2> {
+    let name = &s.ident;
+    let vis = &s.vis;
+    let attrs = &s.attrs;
+
+    let Fields::Named(fields) = &s.fields else {
+        return Err(syn::Error::new_spanned(
+            &s,
+            "#[bitfield] requires named fields",
+        ));
+    };
+
+    let mut offset = quote! { 0u32 };
+    let mut consts = Vec::new();
+    let mut asserts = Vec::new();
+    let mut accessors = Vec::new();
+
+    for f in &fields.named {
+        let ident = f.ident.as_ref().unwrap();
+        let ty = &f.ty;
+        let field_vis = &f.vis;
+        let docs = f.attrs.iter().filter(|a| a.path().is_ident("doc"));
+
+        let mut explicit_bits = None;
+        for attr in f.attrs.iter().filter(|a| a.path().is_ident("bits")) {
+            explicit_bits = Some(attr.parse_args::<LitInt>()?.base10_parse::<u32>()?);
+        }
+
+        let upper = ident.to_string().to_uppercase();
+        let offset_ident = Ident::new(&format!("{}_OFFSET", upper), Span::call_site());
+        let bits_ident = Ident::new(&format!("{}_BITS", upper), Span::call_site());
+
+        let width = match explicit_bits {
+            Some(bits) => {
+                asserts.push(quote! {
+                    assert!(
+                        #bits > 0 && #bits <= 64,
+                        concat!("#[bitfield]: invalid width for `", stringify!(#ident), "`")
+                    );
+                });
+                quote! { #bits }
+            }
+            None => quote! { <#ty as BitfieldValue>::BITS },
+        };
+
+        consts.push(quote! {
+            pub const #offset_ident: u32 = #offset;
+            pub const #bits_ident: u32 = #width;
+        });
+        offset = quote! { Self::#offset_ident + Self::#bits_ident };
+
+        let read = quote! { bitfield::read_bits(&self.bits, Self::#offset_ident, Self::#bits_ident) };
+        let setter = format_ident!("set_{}", ident);
+
+        match field_kind(ty) {
+            Kind::Bool => accessors.push(quote! {
+                #(#docs)*
+                #[inline(always)]
+                #field_vis fn #ident(&self) -> bool {
+                    #read != 0
+                }
+
+                #[inline(always)]
+                #field_vis fn #setter(&mut self, value: bool) {
+                    bitfield::write_bits(&mut self.bits, Self::#offset_ident, Self::#bits_ident, value as u64);
+                }
+            }),
+            Kind::Int if explicit_bits.is_some() => {
+                let try_setter = format_ident!("try_set_{}", ident);
+
+                asserts.push(quote! {
+                    assert!(
+                        #name::#bits_ident <= <#ty as BitfieldValue>::BITS,
+                        concat!("#[bitfield]: `", stringify!(#ident), "` is wider than its type")
+                    );
+                });
+                accessors.push(quote! {
+                    #(#docs)*
+                    #[inline(always)]
+                    #field_vis fn #ident(&self) -> #ty {
+                        #read as #ty
+                    }
+
+                    /// Fails if `value` doesn't fit in the field.
+                    #[inline(always)]
+                    #field_vis fn #try_setter(&mut self, value: #ty) -> Result<()> {
+                        bitfield::try_write_bits(&mut self.bits, Self::#offset_ident, Self::#bits_ident, value as u64)
+                    }
+                });
+            }
+            Kind::Int => accessors.push(quote! {
+                #(#docs)*
+                #[inline(always)]
+                #field_vis fn #ident(&self) -> #ty {
+                    #read as #ty
+                }
+
+                #[inline(always)]
+                #field_vis fn #setter(&mut self, value: #ty) {
+                    bitfield::write_bits(&mut self.bits, Self::#offset_ident, Self::#bits_ident, value as u64);
+                }
+            }),
+            Kind::Value => {
+                let getter = format_ident!("try_{}", ident);
+
+                asserts.push(quote! {
+                    assert!(
+                        #name::#bits_ident >= <#ty as BitfieldValue>::BITS,
+                        concat!("#[bitfield]: `", stringify!(#ident), "` is too narrow for its type")
+                    );
+                });
+                accessors.push(quote! {
+                    #(#docs)*
+                    /// Fails if the stored bits are not a valid value.
+                    #[inline(always)]
+                    #field_vis fn #getter(&self) -> Result<#ty> {
+                        <#ty as BitfieldValue>::try_from_bits(#read)
+                    }
+
+                    #[inline(always)]
+                    #field_vis fn #setter(&mut self, value: #ty) {
+                        bitfield::write_bits(
+                            &mut self.bits,
+                            Self::#offset_ident,
+                            Self::#bits_ident,
+                            BitfieldValue::into_bits(value),
+                        );
+                    }
+                });
+            }
+        }
+    }
+
+    Ok(quote! {
+        #(#attrs)*
+        #[repr(transparent)]
+        #[derive(Clone, Copy, Default, PartialEq, Eq)]
+        #vis struct #name {
+            bits: [u8; 8],
+        }
+
+        // SAFETY: transparent over a byte array
+        unsafe impl ::bytemuck::Zeroable for #name {}
+        unsafe impl ::bytemuck::Pod for #name {}
+
+        impl #name {
+            #(#consts)*
+
+            /// Bits used by the fields, at most 64.
+            pub const USED_BITS: u32 = #offset;
+
+            #[inline(always)]
+            pub const fn from_word(word: u64) -> Self {
+                Self { bits: word.to_le_bytes() }
+            }
+
+            #[inline(always)]
+            pub const fn word(&self) -> u64 {
+                u64::from_le_bytes(self.bits)
+            }
+
+            #(#accessors)*
+        }
+
+        const _: () = {
+            #(#asserts)*
+            assert!(
+                #name::USED_BITS <= 64,
+                concat!("#[bitfield]: `", stringify!(#name), "` needs more than 64 bits")
+            );
+        };
+    })
+}
+
+fn expand_enum(e: ItemEnum) -> syn::Result<TokenStream2> {
+    let name = &e.ident;
+
+    if e.variants.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &e,
+            "#[bitfield] requires at least one enum variant",
+        ));
+    }
+
+    if let Some(v) = e.variants.iter().find(|v| !v.fields.is_empty()) {
+        return Err(syn::Error::new_spanned(
+            v,
+            "#[bitfield] requires a fieldless enum",
+        ));
+    }
+
+    let variants: Vec<_> = e.variants.iter().map(|v| &v.ident).collect();
+
+    Ok(quote! {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        #e
+
+        impl BitfieldValue for #name {
+            const BITS: u32 = {
+                let values = [#(#name::#variants as u64),*];
+                let mut max = 0;
+                let mut i = 0;
+                while i < values.len() {
+                    if values[i] > max {
+                        max = values[i];
+                    }
+                    i += 1;
+                }
+                bitfield::bits_for(max)
+            };
+
+            #[inline(always)]
+            fn into_bits(self) -> u64 {
+                self as u64
+            }
+
+            #[inline(always)]
+            fn try_from_bits(bits: u64) -> Result<Self> {
+                #(
+                    if bits == #name::#variants as u64 {
+                        return Ok(#name::#variants);
+                    }
+                )*
+
+                error_msg!(
+                    "BitfieldValue::try_from_bits: unknown variant",
+                    ProgramError::InvalidAccountData,
+                );
+            }
+        }
+    })
+}
@@ -0,0 +1,166 @@
+//! `hayabusa expand`: golden file of the macro expansion of a program.
+//!
+//! The program crate is expanded with rustc's `-Zunpretty=expanded` (`RUSTC_BOOTSTRAP` lets a
+//! stable toolchain do it), formatted with prettyplease and written to a checked-in file.
+//! `--check` compares against that file instead, so a framework upgrade that changes the code
+//! generated by `#[instruction]`, `#[account]`, `#[derive(FromAccountViews)]` and friends shows
+//! up as a failing check and a reviewable diff rather than a silent behavior change.
+//!
+//! The expansion also depends on the toolchain, regenerate the file when upgrading rustc.
+
+use anyhow::{bail, Context, Result};
+use std::{fs, path::Path, process::Command};
+
+/// Lines of context printed around the first difference
+const CONTEXT_LINES: usize = 5;
+
+pub fn cmd_expand(package: Option<&str>, golden: &Path, check: bool) -> Result<()> {
+    let expanded = expand(Path::new("."), package)?;
+
+    if !check {
+        crate::write_file(golden, &expanded)?;
+        println!("Wrote the expansion to {}", golden.display());
+        return Ok(());
+    }
+
+    let previous = fs::read_to_string(golden).with_context(|| {
+        format!(
+            "Failed to read {}, run `hayabusa expand` to create it",
+            golden.display()
+        )
+    })?;
+
+    if let Some(line) = first_difference(&previous, &expanded) {
+        print_difference(&previous, &expanded, line);
+        bail!(
+            "the expansion differs from {} at line {}, review the change and run `hayabusa expand` to accept it",
+            golden.display(),
+            line + 1
+        );
+    }
+
+    println!("Expansion matches {}", golden.display());
+
+    Ok(())
+}
+
+/// Expansion of the program crate in `dir`, or of `package` in its workspace
+fn expand(dir: &Path, package: Option<&str>) -> Result<String> {
+    // a target dir of its own, the bootstrap flag would otherwise invalidate regular builds
+    let target_dir = dir.join("target/expand");
+    fs::create_dir_all(&target_dir)
+        .with_context(|| format!("Failed to create {}", target_dir.display()))?;
+    let target_dir = target_dir.canonicalize()?;
+
+    // a new output path every run, so cargo never considers the crate fresh and skips rustc
+    let out = target_dir.join(format!("expanded-{}.rs", std::process::id()));
+
+    let mut command = Command::new("cargo");
+    command
+        .current_dir(dir)
+        .env("RUSTC_BOOTSTRAP", "1")
+        .args(["rustc", "--lib", "--profile=check", "--target-dir"])
+        .arg(&target_dir);
+    if let Some(package) = package {
+        command.args(["--package", package]);
+    }
+    command.args(["--", "-Zunpretty=expanded", "-o"]).arg(&out);
+
+    let status = command.status().context("Failed to spawn cargo rustc")?;
+    if !status.success() {
+        bail!("cargo rustc failed");
+    }
+
+    let expanded =
+        fs::read_to_string(&out).with_context(|| format!("Failed to read {}", out.display()))?;
+    let _ = fs::remove_file(&out);
+
+    // rustc's pretty printer keeps the blank lines left by the macros, reformatted for stable
+    // and readable diffs
+    let file = syn::parse_file(&expanded).context("Failed to parse the expansion")?;
+
+    Ok(prettyplease::unparse(&file))
+}
+
+/// Index of the first line that differs, `None` if the texts are equal
+fn first_difference(previous: &str, expanded: &str) -> Option<usize> {
+    if previous == expanded {
+        return None;
+    }
+
+    let mut previous_lines = previous.lines();
+    let mut expanded_lines = expanded.lines();
+
+    (0..).find(|_| previous_lines.next() != expanded_lines.next())
+}
+
+fn print_difference(previous: &str, expanded: &str, line: usize) {
+    let start = line.saturating_sub(CONTEXT_LINES);
+
+    for (label, text) in [("golden", previous), ("expanded", expanded)] {
+        eprintln!("--- {label}");
+        for (i, content) in text
+            .lines()
+            .enumerate()
+            .skip(start)
+            .take(2 * CONTEXT_LINES + 1)
+        {
+            let marker = if i == line { '>' } else { ' ' };
+            eprintln!("{marker}{:>6} | {content}", i + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Crate without dependencies, so the test runs offline
+    const FIXTURE_MANIFEST: &str = r#"[package]
+name = "expand-fixture"
+version = "0.1.0"
+edition = "2021"
+
+[workspace]
+"#;
+
+    const FIXTURE_LIB: &str = r#"
+macro_rules! counter {
+    ($name:ident) => {
+        pub struct $name {
+            pub count: u64,
+        }
+    };
+}
+
+counter!(Counter);
+
+#[derive(Clone, Copy)]
+pub struct Bumps;
+"#;
+
+    #[test]
+    fn test_expand_fixture() {
+        let dir = std::env::temp_dir().join(format!("hayabusa-expand-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("Cargo.toml"), FIXTURE_MANIFEST).unwrap();
+        fs::write(dir.join("src/lib.rs"), FIXTURE_LIB).unwrap();
+
+        let expanded = expand(&dir, None).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        // macros are expanded and the output is formatted
+        assert!(!expanded.contains("counter!"));
+        assert!(expanded.contains("pub struct Counter {\n    pub count: u64,\n}"));
+        assert!(expanded.contains("impl ::core::clone::Clone for Bumps"));
+        assert_eq!(expanded, prettyplease::unparse(&syn::parse_file(&expanded).unwrap()));
+    }
+
+    #[test]
+    fn test_first_difference() {
+        assert_eq!(first_difference("a\nb\n", "a\nb\n"), None);
+        assert_eq!(first_difference("a\nb\n", "a\nc\n"), Some(1));
+        // a line added at the end
+        assert_eq!(first_difference("a\n", "a\nb\n"), Some(1));
+    }
+}
@@ -0,0 +1,206 @@
+//! `hayabusa locks`: the accounts each instruction writes, written into the program IDL.
+//!
+//! Handlers are the functions taking a `Ctx<'ix, Accounts<'ix>>`, named like the instruction,
+//! and an account is written when its field in the `#[derive(FromAccountViews)]` struct is
+//! wrapped in `Mut`, the same rule as `FromAccountViews::WRITABLE_ACCOUNTS`. Two instructions
+//! whose write locks don't overlap the accounts of the other can be scheduled in parallel.
+
+use crate::types::collect_sources;
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Value};
+use std::{collections::BTreeMap, fs, path::Path};
+use syn::{
+    punctuated::Punctuated, FnArg, GenericArgument, Item, ItemStruct, PathArguments, Token, Type,
+};
+
+/// Top level IDL key of the write locks section, an object mapping instruction names to the
+/// names of the accounts they write, in account order.
+const IDL_WRITE_LOCKS: &str = "writeLocks";
+
+pub fn cmd_locks(src: &Path, idl_path: &Path) -> Result<()> {
+    let mut files = Vec::new();
+    collect_sources(src, &mut files)?;
+
+    let mut accounts = BTreeMap::new();
+    let mut handlers = BTreeMap::new();
+
+    for file in &files {
+        let source =
+            fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))?;
+        let ast =
+            syn::parse_file(&source).with_context(|| format!("Failed to parse {}", file.display()))?;
+
+        collect_items(&ast.items, &mut accounts, &mut handlers);
+    }
+
+    let source = fs::read_to_string(idl_path)
+        .with_context(|| format!("Failed to read {}", idl_path.display()))?;
+    let mut idl: Value = serde_json::from_str(&source)
+        .with_context(|| format!("Failed to parse {}", idl_path.display()))?;
+
+    let instructions: Vec<String> = idl
+        .get("instructions")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|ix| Some(ix.get("name")?.as_str()?.to_string()))
+        .collect();
+
+    let mut locks = Map::new();
+    for name in instructions {
+        let Some(writable) = handlers.get(&name).and_then(|ctx| accounts.get(ctx)) else {
+            eprintln!("warning: no handler found for instruction {name}");
+            continue;
+        };
+
+        locks.insert(name, Value::from(writable.clone()));
+    }
+
+    let Some(idl_object) = idl.as_object_mut() else {
+        bail!("{} is not a json object", idl_path.display());
+    };
+    let count = locks.len();
+    idl_object.insert(IDL_WRITE_LOCKS.to_string(), locks.into());
+
+    crate::write_file(idl_path, &(serde_json::to_string_pretty(&idl)? + "\n"))?;
+
+    println!("Wrote the write locks of {count} instructions to {}", idl_path.display());
+
+    Ok(())
+}
+
+/// Collects the writable fields of every `FromAccountViews` struct, and the accounts struct of
+/// every handler.
+fn collect_items(
+    items: &[Item],
+    accounts: &mut BTreeMap<String, Vec<String>>,
+    handlers: &mut BTreeMap<String, String>,
+) {
+    for item in items {
+        match item {
+            Item::Struct(item) if derives_from_account_views(item) => {
+                let writable = item
+                    .fields
+                    .iter()
+                    .filter(|field| is_mut(&field.ty))
+                    .filter_map(|field| field.ident.as_ref())
+                    .map(|ident| syn::ext::IdentExt::unraw(ident).to_string())
+                    .collect();
+
+                accounts.insert(item.ident.to_string(), writable);
+            }
+            Item::Fn(item) => {
+                if let Some(ctx) = item.sig.inputs.first().and_then(ctx_accounts) {
+                    handlers.insert(item.sig.ident.to_string(), ctx);
+                }
+            }
+            Item::Mod(module) => {
+                if let Some((_, items)) = &module.content {
+                    collect_items(items, accounts, handlers);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn derives_from_account_views(item: &ItemStruct) -> bool {
+    item.attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .any(|path| path.segments.last().is_some_and(|s| s.ident == "FromAccountViews"))
+}
+
+fn is_mut(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+
+    path.path.segments.last().is_some_and(|segment| segment.ident == "Mut")
+}
+
+/// `Accounts` for an argument of type `Ctx<'ix, Accounts<'ix>>`
+fn ctx_accounts(arg: &FnArg) -> Option<String> {
+    let FnArg::Typed(arg) = arg else {
+        return None;
+    };
+    let Type::Path(path) = arg.ty.as_ref() else {
+        return None;
+    };
+
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Ctx" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+
+    arguments.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(Type::Path(accounts)) => {
+            Some(accounts.path.segments.last()?.ident.to_string())
+        }
+        _ => None,
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = r#"
+#[derive(FromAccountViews)]
+pub struct Increment<'ix> {
+    pub user: Signer<'ix>,
+    pub counter: Mut<ZcAccount<'ix, Counter>>,
+    pub r#type: Mut<UncheckedAccount<'ix>>,
+}
+
+mod instructions {
+    #[derive(FromAccountViews)]
+    pub struct Read<'ix> {
+        pub counter: ZcAccount<'ix, Counter>,
+    }
+
+    fn read<'ix>(ctx: Ctx<'ix, Read<'ix>>) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[instruction]
+fn increment<'ix>(ctx: Ctx<'ix, Increment<'ix>>, amount: u64) -> Result<()> {
+    Ok(())
+}
+"#;
+
+    #[test]
+    fn test_write_locks() {
+        let dir = std::env::temp_dir().join(format!("hayabusa-locks-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), PROGRAM).unwrap();
+
+        let idl_path = dir.join("idl.json");
+        let instructions = serde_json::json!({
+            "instructions": [{ "name": "increment" }, { "name": "read" }, { "name": "missing" }]
+        });
+        fs::write(&idl_path, instructions.to_string()).unwrap();
+
+        cmd_locks(&dir.join("src"), &idl_path).unwrap();
+        let idl: Value = serde_json::from_str(&fs::read_to_string(&idl_path).unwrap()).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        // raw identifiers unescaped, instructions without a handler left out
+        assert_eq!(
+            idl[IDL_WRITE_LOCKS],
+            serde_json::json!({ "increment": ["counter", "type"], "read": [] })
+        );
+        assert_eq!(idl["instructions"].as_array().unwrap().len(), 3);
+    }
+}
@@ -1,3 +1,8 @@
+mod expand;
+mod locks;
+mod migrate;
+mod types;
+
 use anyhow::{anyhow, bail, Context, Result};
 use clap::{Parser, Subcommand};
 use std::{
@@ -51,6 +56,57 @@ enum Commands {
         #[arg(long)]
         workspace: Option<PathBuf>,
     },
+
+    /// Generate TypeScript typings (types.d.ts) and layout descriptors (layouts.json) for the
+    /// #[account] and #[event] structs of a program
+    Types {
+        /// Program source file or directory (default: ./src)
+        #[arg(long)]
+        src: Option<PathBuf>,
+
+        /// Output directory (default: ./target/types)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Write the accounts each instruction writes (its `Mut` fields) into the writeLocks
+    /// section of the program IDL, for transaction schedulers
+    Locks {
+        /// Program source file or directory (default: ./src)
+        #[arg(long)]
+        src: Option<PathBuf>,
+
+        /// IDL json to update, its instructions are matched to the handlers by name
+        #[arg(long)]
+        idl: PathBuf,
+    },
+
+    /// Write the macro expansion of a program to a golden file, or check it against the file
+    Expand {
+        /// Program package to expand (default: the package in the current directory)
+        #[arg(long)]
+        package: Option<String>,
+
+        /// Golden file (default: ./expanded.rs)
+        #[arg(long)]
+        out: Option<PathBuf>,
+
+        /// Fail if the expansion differs from the golden file instead of writing it
+        #[arg(long, default_value_t = false)]
+        check: bool,
+    },
+
+    /// Rewrite the common constructs of an Anchor program (accounts structs, handlers, errors,
+    /// require!, ..) into their hayabusa equivalents, as a starting point for a port
+    Migrate {
+        /// Anchor program source file or directory (default: ./src)
+        #[arg(long)]
+        src: Option<PathBuf>,
+
+        /// Output directory (default: ./target/migrated)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
 }
 
 fn main() -> Result<()> {
@@ -62,6 +118,28 @@ fn main() -> Result<()> {
             cmd_build(program.as_deref(), workspace.as_deref())
         }
         Commands::Test { workspace } => cmd_test(workspace.as_deref()),
+        Commands::Types { src, out } => types::cmd_types(
+            src.as_deref().unwrap_or_else(|| Path::new("src")),
+            out.as_deref().unwrap_or_else(|| Path::new("target/types")),
+        ),
+        Commands::Locks { src, idl } => locks::cmd_locks(
+            src.as_deref().unwrap_or_else(|| Path::new("src")),
+            &idl,
+        ),
+        Commands::Expand {
+            package,
+            out,
+            check,
+        } => expand::cmd_expand(
+            package.as_deref(),
+            out.as_deref().unwrap_or_else(|| Path::new("expanded.rs")),
+            check,
+        ),
+        Commands::Migrate { src, out } => migrate::cmd_migrate(
+            src.as_deref().unwrap_or_else(|| Path::new("src")),
+            out.as_deref()
+                .unwrap_or_else(|| Path::new("target/migrated")),
+        ),
     }
 }
 
@@ -332,8 +410,10 @@ pub struct UpdateCounter<'a> {
 
 // Intentionally kept manual, you get to see what the FromAccountViews proc macro is doing
 impl<'a> FromAccountViews<'a> for UpdateCounter<'a> {
+    type Bumps = NoBumps;
+
     #[inline(always)]
-    fn try_from_account_views(account_views: &mut AccountIter<'a>) -> Result<Self> {
+    fn try_from_account_views(account_views: &mut AccountIter<'a>, _: &mut NoBumps) -> Result<Self> {
         let user = Signer::try_from_account_view(account_views.next()?)?;
         let counter = Mut::try_from_account_view(account_views.next()?)?;
 
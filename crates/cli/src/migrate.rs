@@ -0,0 +1,647 @@
+//! `hayabusa migrate`: rewrites the common constructs of an Anchor program into their hayabusa
+//! equivalents, as a starting point for a port rather than a finished one.
+//!
+//! - `#[program] mod` handlers move to the crate root as `#[instruction]`s taking a
+//!   `Ctx<'ix, Accounts<'ix>>`, keeping the Anchor discriminator (`global:<name>`) so existing
+//!   clients still reach them, and a `dispatch!` entrypoint routing all of them is added.
+//! - `#[derive(Accounts)]` becomes `#[derive(FromAccountViews)]`: `Account` and `AccountLoader`
+//!   become `ZcAccount`, `AccountInfo` becomes `UncheckedAccount`, `mut` wraps the field in `Mut`
+//!   and the `#[account(..)]` constraints hayabusa shares with Anchor move to `#[meta(..)]`,
+//!   `has_one` as a `constraint`.
+//! - `#[account]` structs keep their `account:<Name>` discriminator and derive `OwnerProgram`,
+//!   `#[error_code]` becomes `#[error]` with the messages as doc comments, and `#[event]`s with
+//!   `String` or `Vec` fields become `#[event(dynamic)]`. `emit!` is the same macro.
+//! - `err!`, `error!` and the `require*!` macros, `ctx.accounts.x`, `.key()`, `Pubkey` and the
+//!   `'info` lifetime are rewritten in place.
+//!
+//! Hayabusa accounts are zero-copy: the fields of a borsh account don't always have a `Pod`
+//! equivalent with the same layout. Whatever the rewrite can't carry over is dropped or left as
+//! is and reported, file by file. Files are written through `prettyplease`, so comments other
+//! than doc comments are lost, diff the output against the sources rather than replacing them.
+
+use crate::types::collect_sources;
+use anyhow::{Context, Result};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use std::{fs, path::Path};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    punctuated::Punctuated,
+    visit_mut::{self, VisitMut},
+    Attribute, Expr, FnArg, GenericArgument, Ident, Item, ItemFn, ItemStruct, Lifetime,
+    PathArguments, Stmt, Token, Type,
+};
+
+pub fn cmd_migrate(src: &Path, out: &Path) -> Result<()> {
+    let mut files = Vec::new();
+    collect_sources(src, &mut files)?;
+
+    let mut noted = 0;
+    for file in &files {
+        let source = fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let mut ast = syn::parse_file(&source)
+            .with_context(|| format!("Failed to parse {}", file.display()))?;
+
+        let mut migrator = Migrator::default();
+        migrator.migrate_file(&mut ast);
+
+        let relative = match file.strip_prefix(src) {
+            Ok(relative) if !relative.as_os_str().is_empty() => relative,
+            _ => Path::new(file.file_name().unwrap_or_default()),
+        };
+        crate::write_file(&out.join(relative), &prettyplease::unparse(&ast))?;
+
+        for note in &migrator.notes {
+            println!("{}: {note}", file.display());
+        }
+        noted += migrator.notes.len();
+    }
+
+    println!(
+        "Migrated {} files into {}, {noted} notes to review",
+        files.len(),
+        out.display()
+    );
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct Migrator {
+    notes: Vec<String>,
+}
+
+impl Migrator {
+    fn migrate_file(&mut self, file: &mut syn::File) {
+        let mut items = Vec::new();
+        let mut instructions = Vec::new();
+
+        for item in std::mem::take(&mut file.items) {
+            match item {
+                Item::Mod(module) if has_attr(&module.attrs, "program") => {
+                    for item in module.content.map(|(_, items)| items).unwrap_or_default() {
+                        match item {
+                            Item::Fn(mut handler)
+                                if !matches!(handler.vis, syn::Visibility::Inherited) =>
+                            {
+                                instructions.push(instruction_name(&handler.sig.ident));
+                                self.migrate_handler(&mut handler);
+                                items.push(Item::Fn(handler));
+                            }
+                            // the handlers now live next to the items they used
+                            Item::Use(item)
+                                if item.to_token_stream().to_string() == "use super :: * ;" => {}
+                            item => items.push(item),
+                        }
+                    }
+                }
+                item => items.push(item),
+            }
+        }
+
+        if !instructions.is_empty() {
+            if !file.attrs.iter().any(|attr| attr.path().is_ident("no_std")) {
+                file.attrs.insert(0, parse_quote!(#![no_std]));
+            }
+            items.push(parse_quote! {
+                #[cfg(not(feature = "no-entrypoint"))]
+                mod entrypoint {
+                    use super::*;
+
+                    program_entrypoint!(program_entrypoint);
+                    no_allocator!();
+                    nostd_panic_handler!();
+
+                    pub fn program_entrypoint(
+                        program_id: &Address,
+                        accounts: &[AccountView],
+                        instruction_data: &[u8],
+                    ) -> Result<()> {
+                        dispatch!(program_id, instruction_data, accounts, #(#instructions),*);
+                    }
+                }
+            });
+        }
+
+        file.items = items;
+        self.visit_file_mut(file);
+    }
+
+    /// `pub fn f(ctx: Context<F>, ..)` -> `#[instruction] pub fn f<'ix>(ctx: Ctx<'ix, F<'ix>>, ..)`
+    fn migrate_handler(&mut self, handler: &mut ItemFn) {
+        let name = &handler.sig.ident;
+        let discriminator = format!("global:{name}");
+
+        let mut borsh = false;
+        for arg in handler.sig.inputs.iter_mut() {
+            let FnArg::Typed(arg) = arg else {
+                continue;
+            };
+
+            match context_accounts(&arg.ty) {
+                Some(mut accounts) => {
+                    if let Type::Path(path) = &mut accounts {
+                        let last = path.path.segments.last_mut().unwrap();
+                        if last.arguments.is_empty() {
+                            last.arguments = PathArguments::AngleBracketed(parse_quote!(<'ix>));
+                        }
+                    }
+                    *arg.ty = parse_quote!(Ctx<'ix, #accounts>);
+                }
+                None => borsh |= is_any_of(&arg.ty, &["String", "Vec", "Option"]),
+            }
+        }
+
+        if !handler
+            .sig
+            .generics
+            .lifetimes()
+            .any(|lt| lt.lifetime.ident == "info" || lt.lifetime.ident == "ix")
+        {
+            handler.sig.generics.params.insert(0, parse_quote!('ix));
+        }
+
+        let attr: Attribute = match borsh {
+            true => {
+                self.note(format!(
+                    "`{name}` takes dynamic arguments, decoded with borsh: enable hayabusa's \
+                     `borsh` feature, replace `no_allocator!()` with an allocator and bound them \
+                     with `#[max_len(N)]`"
+                ));
+                parse_quote!(#[instruction(borsh, discriminator = #discriminator)])
+            }
+            false => parse_quote!(#[instruction(discriminator = #discriminator)]),
+        };
+        handler.attrs.insert(0, attr);
+    }
+
+    /// `#[derive(Accounts)]` struct
+    fn migrate_accounts(&mut self, item: &mut ItemStruct) {
+        let name = item.ident.clone();
+
+        item.attrs.retain(|attr| {
+            if attr.path().is_ident("instruction") {
+                self.notes.push(format!(
+                    "`{name}`: the instruction arguments aren't available to the account \
+                     constraints, `#[instruction(..)]` was dropped"
+                ));
+                return false;
+            }
+            true
+        });
+
+        for field in item.fields.iter_mut() {
+            let Some(ident) = field.ident.clone() else {
+                continue;
+            };
+
+            let mut writable = false;
+            let mut meta = Vec::new();
+            let mut attrs = Vec::new();
+
+            for attr in std::mem::take(&mut field.attrs) {
+                if !attr.path().is_ident("account") {
+                    attrs.push(attr);
+                    continue;
+                }
+
+                let args = match attr
+                    .parse_args_with(Punctuated::<AccountArg, Token![,]>::parse_terminated)
+                {
+                    Ok(args) => args,
+                    Err(_) => {
+                        self.note(format!(
+                            "`{name}.{ident}`: unparsed `#[account(..)]` dropped"
+                        ));
+                        continue;
+                    }
+                };
+
+                for mut arg in args {
+                    if let Some(value) = &mut arg.value {
+                        self.visit_expr_mut(value);
+                    }
+                    let key = arg.path.to_token_stream().to_string().replace(' ', "");
+                    match key.as_str() {
+                        "mut" => writable = true,
+                        "init" => {
+                            writable = true;
+                            meta.push(arg.to_token_stream());
+                        }
+                        "has_one" => {
+                            let target = &arg.value;
+                            let error = arg.error.as_ref().map(|error| quote! { @ #error });
+                            meta.push(quote! {
+                                constraint = address::address_eq(
+                                    &#ident.try_deserialize()?.#target,
+                                    #target.address()
+                                ) #error
+                            });
+                        }
+                        "payer"
+                        | "space"
+                        | "seeds"
+                        | "bump"
+                        | "constraint"
+                        | "close"
+                        | "address"
+                        | "owner"
+                        | "realloc"
+                        | "realloc::payer"
+                        | "token::mint"
+                        | "token::authority"
+                        | "associated_token::mint"
+                        | "associated_token::authority"
+                        | "associated_token::token_program" => {
+                            meta.push(arg.to_token_stream());
+                        }
+                        "realloc::zero" if arg.value.to_token_stream().to_string() == "false" => {
+                            self.note(format!(
+                                "`{name}.{ident}`: realloc always zeroes the new bytes, `realloc::zero = false` was dropped"
+                            ))
+                        }
+                        "realloc::zero" => meta.push(arg.to_token_stream()),
+                        _ => self.note(format!(
+                            "`{name}.{ident}`: `{key}` has no hayabusa equivalent and was dropped"
+                        )),
+                    }
+                }
+            }
+
+            if !meta.is_empty() {
+                attrs.push(parse_quote!(#[meta(#(#meta),*)]));
+            }
+            field.attrs = attrs;
+
+            match account_type(&field.ty) {
+                Some(ty) if writable => field.ty = parse_quote!(Mut<#ty>),
+                Some(ty) => field.ty = ty,
+                None => {
+                    let ty = &field.ty;
+                    self.note(format!(
+                        "`{name}.{ident}`: no hayabusa account type for `{}`",
+                        ty.to_token_stream()
+                    ));
+                }
+            }
+        }
+    }
+
+    fn note(&mut self, note: String) {
+        self.notes.push(note);
+    }
+}
+
+impl VisitMut for Migrator {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "info" {
+            lifetime.ident = format_ident!("ix");
+        }
+    }
+
+    fn visit_item_use_mut(&mut self, item: &mut syn::ItemUse) {
+        match item.tree.to_token_stream().to_string().as_str() {
+            "anchor_lang :: prelude :: *" => item.tree = parse_quote!(hayabusa::prelude::*),
+            tree if tree.starts_with("anchor_") => {
+                self.note(format!("`use {}` left as is", tree.replace(' ', "")));
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_item_struct_mut(&mut self, item: &mut ItemStruct) {
+        if item.attrs.iter().any(|attr| derives(attr, "Accounts")) {
+            rewrite_derives(
+                &mut item.attrs,
+                "Accounts",
+                Some(parse_quote!(FromAccountViews)),
+            );
+            self.migrate_accounts(item);
+        } else if let Some(account) = item
+            .attrs
+            .iter_mut()
+            .find(|attr| attr.path().is_ident("account"))
+        {
+            let discriminator = format!("account:{}", item.ident);
+            *account = parse_quote!(#[account(discriminator = #discriminator)]);
+            item.attrs.push(parse_quote!(#[derive(OwnerProgram)]));
+            self.note(format!(
+                "`{}` is now a zero-copy account: its fields must be `Pod`, with the borsh layout \
+                 of the existing accounts",
+                item.ident
+            ));
+        } else if let Some(event) = item
+            .attrs
+            .iter_mut()
+            .find(|attr| attr.path().is_ident("event"))
+        {
+            if item
+                .fields
+                .iter()
+                .any(|field| is_any_of(&field.ty, &["String", "Vec"]))
+            {
+                *event = parse_quote!(#[event(dynamic)]);
+            }
+        }
+
+        // the borsh derives of accounts and events
+        rewrite_derives(&mut item.attrs, "AnchorSerialize", None);
+        rewrite_derives(&mut item.attrs, "AnchorDeserialize", None);
+
+        visit_mut::visit_item_struct_mut(self, item);
+    }
+
+    fn visit_item_enum_mut(&mut self, item: &mut syn::ItemEnum) {
+        if let Some(attr) = item
+            .attrs
+            .iter_mut()
+            .find(|attr| attr.path().is_ident("error_code"))
+        {
+            *attr = parse_quote!(#[error]);
+
+            for variant in item.variants.iter_mut() {
+                for attr in variant.attrs.iter_mut() {
+                    if attr.path().is_ident("msg") {
+                        if let Ok(msg) = attr.parse_args::<syn::LitStr>() {
+                            let doc = format!(" {}", msg.value());
+                            *attr = parse_quote!(#[doc = #doc]);
+                        }
+                    }
+                }
+            }
+        }
+
+        visit_mut::visit_item_enum_mut(self, item);
+    }
+
+    fn visit_path_mut(&mut self, path: &mut syn::Path) {
+        if let Some(segment) = path.segments.first_mut() {
+            if segment.ident == "Pubkey" {
+                segment.ident = format_ident!("Address");
+            }
+        }
+
+        visit_mut::visit_path_mut(self, path);
+    }
+
+    /// Macro arguments are plain tokens to syn, they're rewritten like the rest when they parse as
+    /// expressions, e.g. the event of `emit!`
+    fn visit_macro_mut(&mut self, mac: &mut syn::Macro) {
+        if mac.path.is_ident("msg") {
+            mac.path = parse_quote!(log);
+        }
+
+        if let Ok(mut args) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+            for arg in args.iter_mut() {
+                self.visit_expr_mut(arg);
+            }
+            mac.tokens = args.to_token_stream();
+        }
+    }
+
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        if let Stmt::Macro(mac) = stmt {
+            if let Some(check) = self.require(&mac.mac) {
+                *stmt = check;
+            }
+        }
+
+        visit_mut::visit_stmt_mut(self, stmt);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            // `ctx.accounts.vault` -> `ctx.vault`
+            Expr::Field(field) => {
+                if let Expr::Field(base) = &*field.base {
+                    if matches!(&base.member, syn::Member::Named(member) if member == "accounts") {
+                        field.base = base.base.clone();
+                    }
+                }
+            }
+            Expr::MethodCall(call) if call.method == "key" && call.args.is_empty() => {
+                call.method = format_ident!("address");
+            }
+            Expr::Macro(mac) if mac.mac.path.is_ident("err") => {
+                if let Ok(code) = mac.mac.parse_body::<Expr>() {
+                    *expr = parse_quote!(Err(program_error!(#code)));
+                }
+            }
+            Expr::Macro(mac) if mac.mac.path.is_ident("error") => {
+                mac.mac.path = parse_quote!(program_error);
+            }
+            Expr::Macro(mac) if mac.mac.path.is_ident("emit_cpi") => {
+                self.note(
+                    "`emit_cpi!` takes an `EventCpi`, `emit_cpi!(event_cpi, event)`".to_string(),
+                );
+            }
+            Expr::Macro(mac) => {
+                if let Some(check) = self.require(&mac.mac) {
+                    *expr = parse_quote!({ #check });
+                }
+            }
+            _ => {}
+        }
+
+        visit_mut::visit_expr_mut(self, expr);
+    }
+}
+
+impl Migrator {
+    /// `require!(cond, Error)` and friends -> `if !(cond) { error!(Error); }`
+    fn require(&mut self, mac: &syn::Macro) -> Option<Stmt> {
+        let name = mac.path.get_ident()?.to_string();
+        let args = mac
+            .parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+            .ok()?;
+        let args: Vec<_> = args.into_iter().collect();
+
+        let (failed, code): (Expr, _) = match (name.as_str(), args.as_slice()) {
+            ("require", [cond, code]) => (parse_quote!(!(#cond)), code),
+            ("require_eq" | "require_keys_eq", [a, b, code]) => (parse_quote!(#a != #b), code),
+            ("require_neq" | "require_keys_neq", [a, b, code]) => (parse_quote!(#a == #b), code),
+            ("require_gt", [a, b, code]) => (parse_quote!(#a <= #b), code),
+            ("require_gte", [a, b, code]) => (parse_quote!(#a < #b), code),
+            (name, _) if name.starts_with("require") => {
+                self.note(format!("`{name}!` without an error code left as is"));
+                return None;
+            }
+            _ => return None,
+        };
+
+        Some(parse_quote! {
+            if #failed {
+                error!(#code);
+            }
+        })
+    }
+}
+
+/// Argument of an Anchor `#[account(..)]`, `mut`, `seeds = [..]`, `constraint = x @ Error`, ..
+struct AccountArg {
+    path: syn::Path,
+    value: Option<Expr>,
+    error: Option<Expr>,
+}
+
+impl Parse for AccountArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = match input.peek(Token![mut]) {
+            true => {
+                let token = input.parse::<Token![mut]>()?;
+                syn::Path::from(Ident::new("mut", token.span))
+            }
+            false => syn::Path::parse_mod_style(input)?,
+        };
+
+        let value = match input.peek(Token![=]) {
+            true => {
+                input.parse::<Token![=]>()?;
+                Some(input.parse()?)
+            }
+            false => None,
+        };
+
+        let error = match value.is_some() && input.peek(Token![@]) {
+            true => {
+                input.parse::<Token![@]>()?;
+                Some(input.parse()?)
+            }
+            false => None,
+        };
+
+        Ok(Self { path, value, error })
+    }
+}
+
+impl ToTokens for AccountArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.path.to_tokens(tokens);
+        if let Some(value) = &self.value {
+            tokens.extend(quote! { = #value });
+        }
+        if let Some(error) = &self.error {
+            tokens.extend(quote! { @ #error });
+        }
+    }
+}
+
+/// hayabusa account type of an Anchor one, `None` if there is none. Lifetimes are renamed
+/// afterwards.
+fn account_type(ty: &Type) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    let inner = generic_type(&segment.arguments);
+
+    Some(match segment.ident.to_string().as_str() {
+        "Box" => return account_type(inner?),
+        "Account" | "AccountLoader" => {
+            let inner = inner?;
+            parse_quote!(ZcAccount<'ix, #inner>)
+        }
+        "AccountInfo" | "UncheckedAccount" => parse_quote!(UncheckedAccount<'ix>),
+        "Signer" => parse_quote!(Signer<'ix>),
+        "SystemAccount" => parse_quote!(SystemAccount<'ix>),
+        "Program" => {
+            let inner = inner?;
+            parse_quote!(Program<'ix, #inner>)
+        }
+        "Sysvar" => {
+            let inner = inner?;
+            parse_quote!(SysvarAccount<'ix, #inner>)
+        }
+        _ => return None,
+    })
+}
+
+/// Accounts struct of a `Context<Accounts>` or `Context<'_, '_, '_, 'info, Accounts<'info>>`
+fn context_accounts(ty: &Type) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Context" {
+        return None;
+    }
+    generic_type(&segment.arguments).cloned()
+}
+
+/// Last type argument of `Type<'a, T>`
+fn generic_type(arguments: &PathArguments) -> Option<&Type> {
+    let PathArguments::AngleBracketed(args) = arguments else {
+        return None;
+    };
+    args.args.iter().rev().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+/// Whether `ty` is one of the `names` types, e.g. `String` or `Vec<T>`
+fn is_any_of(ty: &Type, names: &[&str]) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| names.iter().any(|name| segment.ident == name)),
+        _ => false,
+    }
+}
+
+fn has_attr(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident(name))
+}
+
+/// `#[derive(.., name, ..)]`
+fn derives(attr: &Attribute, name: &str) -> bool {
+    if !attr.path().is_ident("derive") {
+        return false;
+    }
+
+    attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+        .is_ok_and(|paths| paths.iter().any(|path| path.is_ident(name)))
+}
+
+/// Replaces `name` in the `#[derive(..)]` lists with `with`, or removes it, along with the lists
+/// left empty
+fn rewrite_derives(attrs: &mut Vec<Attribute>, name: &str, with: Option<syn::Path>) {
+    attrs.retain_mut(|attr| {
+        if !derives(attr, name) {
+            return true;
+        }
+
+        let Ok(paths) = attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+        else {
+            return true;
+        };
+        let paths: Vec<_> = paths
+            .into_iter()
+            .filter_map(|path| match path.is_ident(name) {
+                true => with.clone(),
+                false => Some(path),
+            })
+            .collect();
+
+        *attr = parse_quote!(#[derive(#(#paths),*)]);
+        !paths.is_empty()
+    });
+}
+
+/// `update_counter` -> `UpdateCounterInstruction`, the struct generated by `#[instruction]`
+fn instruction_name(handler: &Ident) -> Ident {
+    let mut name = String::new();
+    for part in handler.to_string().split('_') {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            name.extend(first.to_uppercase());
+            name.push_str(chars.as_str());
+        }
+    }
+
+    format_ident!("{name}Instruction")
+}
@@ -0,0 +1,607 @@
+//! `hayabusa types`: TypeScript typings and layout descriptors for `#[account]` and `#[event]`
+//! structs, read straight from the program sources.
+//!
+//! Accounts are zero-copy (`#[repr(C)]` + `Pod`, so no implicit padding) and events are written
+//! field by field in little endian, both layouts are therefore the fields back to back after the
+//! discriminator, 8 bytes unless shortened with `disc_len`. For the fixed size types allowed in
+//! events this is also their borsh encoding.
+//!
+//! Event fields marked `#[event_field(hex | base58)]`, logged human-readable by `emit`, are typed
+//! as strings and described as `hex(..)` or `base58(..)` of their layout type.
+
+use anyhow::{bail, Context, Result};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+use syn::{Expr, Fields, GenericArgument, Item, ItemStruct, Lit, PathArguments, Type};
+
+const DISCRIMINATOR_LEN: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Account,
+    Event,
+    /// Plain struct used as a field of an account or event
+    Type,
+}
+
+#[derive(Clone, Debug)]
+enum FieldType {
+    Int { name: String, size: usize },
+    Bool,
+    Address,
+    Bytes(usize),
+    Padding(usize),
+    Str(usize),
+    /// `PodOption<T, PAD>`, flag byte and padding before the value
+    Option(Box<FieldType>, usize),
+    /// `PodOptionKey`, the default address stands for `None`
+    OptionKey,
+    Array(Box<FieldType>, usize),
+    Struct(String),
+    /// `#[event_field(hex | base58)]`, same bytes rendered as a string in the logs
+    Rendered(Box<FieldType>, Render),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Render {
+    Hex,
+    Base58,
+}
+
+struct Field {
+    name: String,
+    ty: FieldType,
+}
+
+struct Definition {
+    name: String,
+    kind: Kind,
+    fields: Vec<Field>,
+    /// Set by `#[account(discriminator = .., disc_len = ..)]`, the hash of the name otherwise
+    discriminator: Option<Vec<u8>>,
+}
+
+struct Layout {
+    size: usize,
+    fields: Vec<(String, FieldType, usize, usize)>,
+}
+
+pub fn cmd_types(src: &Path, out: &Path) -> Result<()> {
+    let mut files = Vec::new();
+    collect_sources(src, &mut files)?;
+
+    let mut definitions = builtin_definitions();
+
+    for file in &files {
+        let source =
+            fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))?;
+        let ast =
+            syn::parse_file(&source).with_context(|| format!("Failed to parse {}", file.display()))?;
+
+        collect_definitions(&ast.items, &mut definitions);
+    }
+
+    let referenced = referenced_types(&definitions);
+    let mut declarations = String::new();
+    let mut layouts = Vec::new();
+
+    for definition in definitions.values() {
+        if definition.kind == Kind::Type && !referenced.contains(&definition.name) {
+            continue;
+        }
+
+        match layout(definition, &definitions) {
+            Ok(layout) => {
+                write_declaration(&mut declarations, definition, &layout);
+                layouts.push(layout_json(definition, &layout));
+            }
+            Err(err) => eprintln!("warning: skipping {}: {err}", definition.name),
+        }
+    }
+
+    fs::create_dir_all(out).with_context(|| format!("Failed to create {}", out.display()))?;
+
+    let typings = format!(
+        "// Generated by `hayabusa types`, do not edit.\n\n\
+         /** Base58 encoded address */\n\
+         export type Address = string;\n{declarations}"
+    );
+    let layouts = format!("[\n{}\n]\n", layouts.join(",\n"));
+
+    crate::write_file(&out.join("types.d.ts"), &typings)?;
+    crate::write_file(&out.join("layouts.json"), &layouts)?;
+
+    println!(
+        "Wrote {} and {}",
+        out.join("types.d.ts").display(),
+        out.join("layouts.json").display()
+    );
+
+    Ok(())
+}
+
+/// Pod structs exported by hayabusa that programs embed in their accounts.
+fn builtin_definitions() -> BTreeMap<String, Definition> {
+    let program_ref = Definition {
+        name: "ProgramRef".to_string(),
+        kind: Kind::Type,
+        fields: vec![
+            Field {
+                name: "program_id".to_string(),
+                ty: FieldType::Address,
+            },
+            Field {
+                name: "require_frozen".to_string(),
+                ty: FieldType::Int {
+                    name: "u8".to_string(),
+                    size: 1,
+                },
+            },
+            Field {
+                name: "_padding".to_string(),
+                ty: FieldType::Padding(7),
+            },
+        ],
+        discriminator: None,
+    };
+
+    BTreeMap::from([(program_ref.name.clone(), program_ref)])
+}
+
+pub(crate) fn collect_sources(path: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if path.is_file() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.path());
+
+    for entry in entries {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_sources(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn collect_definitions(items: &[Item], definitions: &mut BTreeMap<String, Definition>) {
+    for item in items {
+        match item {
+            Item::Struct(item) => {
+                if let Some(definition) = definition(item) {
+                    definitions.insert(definition.name.clone(), definition);
+                }
+            }
+            Item::Mod(module) => {
+                if let Some((_, items)) = &module.content {
+                    collect_definitions(items, definitions);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn definition(item: &ItemStruct) -> Option<Definition> {
+    let kind = if item.attrs.iter().any(|attr| attr.path().is_ident("account")) {
+        Kind::Account
+    } else if item.attrs.iter().any(|attr| attr.path().is_ident("event")) {
+        Kind::Event
+    } else {
+        Kind::Type
+    };
+
+    // fields are packed into a single little endian word
+    if item.attrs.iter().any(|attr| attr.path().is_ident("bitfield")) {
+        return Some(Definition {
+            name: item.ident.to_string(),
+            kind,
+            fields: vec![Field {
+                name: "bits".to_string(),
+                ty: FieldType::Int {
+                    name: "u64".to_string(),
+                    size: 8,
+                },
+            }],
+            discriminator: None,
+        });
+    }
+
+    let Fields::Named(named) = &item.fields else {
+        return None;
+    };
+
+    if !item.generics.params.is_empty() {
+        return None;
+    }
+
+    let mut fields = Vec::new();
+    for field in &named.named {
+        let ty = field_type(&field.ty)?;
+        fields.push(Field {
+            name: field.ident.as_ref()?.to_string(),
+            ty: match field_render(field) {
+                Some(render) => FieldType::Rendered(Box::new(ty), render),
+                None => ty,
+            },
+        });
+    }
+
+    Some(Definition {
+        name: item.ident.to_string(),
+        kind,
+        fields,
+        discriminator: discriminator_override(item),
+    })
+}
+
+/// Bytes of `#[account(discriminator = "name" | [..], disc_len = N)]`, see
+/// `#[derive(Discriminator)]`
+fn discriminator_override(item: &ItemStruct) -> Option<Vec<u8>> {
+    let mut value = None;
+    let mut len = DISCRIMINATOR_LEN;
+
+    item.attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("account"))?
+        .parse_nested_meta(|meta| {
+            if meta.path.is_ident("discriminator") {
+                value = Some(meta.value()?.parse::<Expr>()?);
+            } else if meta.path.is_ident("disc_len") {
+                len = meta.value()?.parse::<syn::LitInt>()?.base10_parse()?;
+            } else if meta.input.peek(syn::Token![=]) {
+                // `owner = ..`
+                meta.value()?.parse::<Expr>()?;
+            }
+            Ok(())
+        })
+        .ok()?;
+
+    let Some(value) = value else {
+        if len == DISCRIMINATOR_LEN {
+            return None;
+        }
+        return Some(discriminator(&item.ident.to_string()).get(..len)?.to_vec());
+    };
+
+    match value {
+        Expr::Lit(lit) => match lit.lit {
+            Lit::Str(name) => Some(discriminator(&name.value()).get(..len)?.to_vec()),
+            Lit::ByteStr(bytes) => Some(bytes.value()),
+            _ => None,
+        },
+        Expr::Array(array) => array
+            .elems
+            .iter()
+            .map(|elem| match elem {
+                Expr::Lit(syn::ExprLit {
+                    lit: Lit::Int(int), ..
+                }) => int.base10_parse().ok(),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+/// `#[event_field(hex | base58)]`
+fn field_render(field: &syn::Field) -> Option<Render> {
+    let mut render = None;
+
+    field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("event_field"))?
+        .parse_nested_meta(|meta| {
+            if meta.path.is_ident("hex") {
+                render = Some(Render::Hex);
+            } else if meta.path.is_ident("base58") {
+                render = Some(Render::Base58);
+            }
+            Ok(())
+        })
+        .ok()?;
+
+    render
+}
+
+fn field_type(ty: &Type) -> Option<FieldType> {
+    match ty {
+        Type::Array(array) => {
+            let len = array_len(&array.len)?;
+            match field_type(&array.elem)? {
+                FieldType::Int { name, .. } if name == "u8" => Some(FieldType::Bytes(len)),
+                elem => Some(FieldType::Array(Box::new(elem), len)),
+            }
+        }
+        Type::Path(path) => {
+            let segment = path.path.segments.last()?;
+            let name = segment.ident.to_string();
+
+            let size = match name.as_str() {
+                "u8" | "i8" => 1,
+                "u16" | "i16" => 2,
+                "u32" | "i32" => 4,
+                "u64" | "i64" => 8,
+                "u128" | "i128" => 16,
+                "bool" | "PodBool" => return Some(FieldType::Bool),
+                "Address" | "Pubkey" => return Some(FieldType::Address),
+                "Padding" => return Some(FieldType::Padding(const_arg(&segment.arguments)?)),
+                "PodStr" => return Some(FieldType::Str(const_arg(&segment.arguments)?)),
+                "PodOption" => return pod_option(&segment.arguments),
+                "PodU16" | "PodU32" | "PodU64" | "PodU128" | "PodI64" | "PodI128" => {
+                    return field_type(&syn::parse_str(&name[3..].to_lowercase()).ok()?);
+                }
+                "PodOptionKey" => return Some(FieldType::OptionKey),
+                // the discriminant
+                "PodU8Enum" => return field_type(&syn::parse_quote!(u8)),
+                _ => return Some(FieldType::Struct(name)),
+            };
+
+            Some(FieldType::Int { name, size })
+        }
+        _ => None,
+    }
+}
+
+/// `PodOption<T>` or `PodOption<T, PAD>`
+fn pod_option(arguments: &PathArguments) -> Option<FieldType> {
+    let PathArguments::AngleBracketed(args) = arguments else {
+        return None;
+    };
+    let mut args = args.args.iter();
+    let GenericArgument::Type(ty) = args.next()? else {
+        return None;
+    };
+    let pad = match args.next() {
+        Some(GenericArgument::Const(pad)) => array_len(pad)?,
+        Some(_) => return None,
+        None => 0,
+    };
+    Some(FieldType::Option(Box::new(field_type(ty)?), pad))
+}
+
+/// `N` of `Type<N>`
+fn const_arg(arguments: &PathArguments) -> Option<usize> {
+    let PathArguments::AngleBracketed(args) = arguments else {
+        return None;
+    };
+    let GenericArgument::Const(len) = args.args.first()? else {
+        return None;
+    };
+    array_len(len)
+}
+
+fn array_len(expr: &Expr) -> Option<usize> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => int.base10_parse().ok(),
+            _ => None,
+        },
+        Expr::Block(block) => match block.block.stmts.as_slice() {
+            [syn::Stmt::Expr(expr, None)] => array_len(expr),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Names of the plain structs used, directly or not, by an account or an event.
+fn referenced_types(definitions: &BTreeMap<String, Definition>) -> BTreeSet<String> {
+    fn collect(ty: &FieldType, pending: &mut Vec<String>) {
+        match ty {
+            FieldType::Struct(name) => pending.push(name.clone()),
+            FieldType::Array(elem, _)
+            | FieldType::Option(elem, _)
+            | FieldType::Rendered(elem, _) => collect(elem, pending),
+            _ => {}
+        }
+    }
+
+    let mut pending = Vec::new();
+    for definition in definitions.values() {
+        if definition.kind != Kind::Type {
+            for field in &definition.fields {
+                collect(&field.ty, &mut pending);
+            }
+        }
+    }
+
+    let mut referenced = BTreeSet::new();
+    while let Some(name) = pending.pop() {
+        let Some(definition) = definitions.get(&name) else {
+            continue;
+        };
+        if definition.kind == Kind::Type && referenced.insert(name) {
+            for field in &definition.fields {
+                collect(&field.ty, &mut pending);
+            }
+        }
+    }
+
+    referenced
+}
+
+fn type_size(
+    ty: &FieldType,
+    kind: Kind,
+    definitions: &BTreeMap<String, Definition>,
+) -> Result<usize> {
+    Ok(match ty {
+        FieldType::Int { size, .. } => *size,
+        FieldType::Bool => 1,
+        FieldType::Address => 32,
+        FieldType::Bytes(len) => *len,
+        // reserved space is not part of an event
+        FieldType::Padding(len) => match kind {
+            Kind::Event => 0,
+            _ => *len,
+        },
+        // u32 length prefix
+        FieldType::Str(len) => 4 + len,
+        FieldType::Option(elem, pad) => 1 + pad + type_size(elem, kind, definitions)?,
+        FieldType::OptionKey => 32,
+        FieldType::Array(elem, len) => type_size(elem, kind, definitions)? * len,
+        FieldType::Rendered(elem, _) => type_size(elem, kind, definitions)?,
+        FieldType::Struct(name) => {
+            let Some(definition) = definitions.get(name) else {
+                bail!("unknown type `{name}`");
+            };
+            if definition.kind != Kind::Type {
+                bail!("`{name}` is not a plain struct");
+            }
+            layout_fields(definition, Kind::Type, 0, definitions)?.size
+        }
+    })
+}
+
+fn layout_fields(
+    definition: &Definition,
+    kind: Kind,
+    start: usize,
+    definitions: &BTreeMap<String, Definition>,
+) -> Result<Layout> {
+    let mut offset = start;
+    let mut fields = Vec::new();
+
+    for field in &definition.fields {
+        let size = type_size(&field.ty, kind, definitions)?;
+        fields.push((field.name.clone(), field.ty.clone(), offset, size));
+        offset += size;
+    }
+
+    Ok(Layout {
+        size: offset,
+        fields,
+    })
+}
+
+fn layout(definition: &Definition, definitions: &BTreeMap<String, Definition>) -> Result<Layout> {
+    let start = match (&definition.kind, &definition.discriminator) {
+        (Kind::Type, _) => 0,
+        (_, Some(discriminator)) => discriminator.len(),
+        (_, None) => DISCRIMINATOR_LEN,
+    };
+
+    layout_fields(definition, definition.kind, start, definitions)
+}
+
+fn discriminator(name: &str) -> [u8; DISCRIMINATOR_LEN] {
+    let hash = Sha256::digest(name.as_bytes());
+    let mut discriminator = [0u8; DISCRIMINATOR_LEN];
+    discriminator.copy_from_slice(&hash[..DISCRIMINATOR_LEN]);
+    discriminator
+}
+
+fn ts_type(ty: &FieldType) -> String {
+    match ty {
+        FieldType::Int { size, .. } if *size >= 8 => "bigint".to_string(),
+        FieldType::Int { .. } => "number".to_string(),
+        FieldType::Bool => "boolean".to_string(),
+        FieldType::Address => "Address".to_string(),
+        FieldType::Bytes(_) | FieldType::Padding(_) => "Uint8Array".to_string(),
+        FieldType::Str(_) => "string".to_string(),
+        FieldType::Option(elem, _) => format!("{} | null", ts_type(elem)),
+        FieldType::OptionKey => "Address | null".to_string(),
+        FieldType::Array(elem, _) => format!("{}[]", ts_type(elem)),
+        FieldType::Struct(name) => name.clone(),
+        FieldType::Rendered(elem, Render::Base58) if matches!(**elem, FieldType::Address) => {
+            "Address".to_string()
+        }
+        FieldType::Rendered(..) => "string".to_string(),
+    }
+}
+
+fn layout_type(ty: &FieldType) -> String {
+    match ty {
+        FieldType::Int { name, .. } => name.clone(),
+        FieldType::Bool => "bool".to_string(),
+        FieldType::Address => "address".to_string(),
+        FieldType::Bytes(len) => format!("[u8; {len}]"),
+        FieldType::Padding(len) => format!("padding({len})"),
+        FieldType::Str(len) => format!("str({len})"),
+        FieldType::Option(elem, pad) => format!("option({}, pad = {pad})", layout_type(elem)),
+        FieldType::OptionKey => "option_key".to_string(),
+        FieldType::Array(elem, len) => format!("[{}; {len}]", layout_type(elem)),
+        FieldType::Struct(name) => name.clone(),
+        FieldType::Rendered(elem, Render::Hex) => format!("hex({})", layout_type(elem)),
+        FieldType::Rendered(elem, Render::Base58) => format!("base58({})", layout_type(elem)),
+    }
+}
+
+fn write_declaration(out: &mut String, definition: &Definition, layout: &Layout) {
+    let doc = match definition.kind {
+        Kind::Account => format!(
+            "Zero-copy account, {} bytes including the discriminator",
+            layout.size
+        ),
+        Kind::Event => format!("Event, {} bytes including the discriminator", layout.size),
+        Kind::Type => format!("Zero-copy struct, {} bytes", layout.size),
+    };
+
+    let _ = write!(out, "\n/** {doc} */\nexport interface {} {{\n", definition.name);
+    for (name, ty, _, _) in &layout.fields {
+        if matches!(ty, FieldType::Padding(_)) {
+            continue;
+        }
+        let _ = writeln!(out, "  {name}: {};", ts_type(ty));
+    }
+    out.push_str("}\n");
+}
+
+fn layout_json(definition: &Definition, layout: &Layout) -> String {
+    let (kind, encoding) = match definition.kind {
+        Kind::Account => ("account", "zero-copy"),
+        Kind::Event => ("event", "borsh"),
+        Kind::Type => ("type", "zero-copy"),
+    };
+
+    let mut json = format!(
+        "  {{\n    \"name\": \"{}\",\n    \"kind\": \"{kind}\",\n    \"encoding\": \"{encoding}\",\n",
+        definition.name
+    );
+
+    if definition.kind != Kind::Type {
+        let discriminator = definition
+            .discriminator
+            .clone()
+            .unwrap_or_else(|| discriminator(&definition.name).to_vec())
+            .iter()
+            .map(u8::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(json, "    \"discriminator\": [{discriminator}],");
+    }
+
+    let fields = layout
+        .fields
+        .iter()
+        .map(|(name, ty, offset, size)| {
+            format!(
+                "      {{ \"name\": \"{name}\", \"type\": \"{}\", \"offset\": {offset}, \"size\": {size} }}",
+                layout_type(ty)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+
+    let _ = write!(
+        json,
+        "    \"size\": {},\n    \"fields\": [\n{fields}\n    ]\n  }}",
+        layout.size
+    );
+
+    json
+}
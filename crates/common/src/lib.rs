@@ -3,5 +3,11 @@
 
 #![no_std]
 
+#[cfg(feature = "mock")]
+extern crate alloc;
+
+#[cfg(feature = "mock")]
+pub mod mock;
+
 pub use solana_account_view::{self as account_view, AccountView, Ref, RefMut};
 pub use solana_address::{address, address_eq, declare_id, Address, ADDRESS_BYTES};
@@ -0,0 +1,74 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Accounts laid out as the runtime serializes them, for off-chain tests of account parsers.
+
+use crate::{
+    account_view::{RuntimeAccount, NOT_BORROWED},
+    AccountView, Address,
+};
+use alloc::{vec, vec::Vec};
+use core::mem::size_of;
+
+/// Account header followed by its data, 8-byte aligned like the runtime input.
+pub struct MockAccount {
+    buf: Vec<u64>,
+}
+
+impl MockAccount {
+    /// Writable, non-signer account of `owner` holding `data`
+    pub fn new(address: Address, owner: Address, data: &[u8]) -> Self {
+        let header = size_of::<RuntimeAccount>();
+        let mut buf = vec![0u64; (header + data.len()).div_ceil(8)];
+        let raw = buf.as_mut_ptr() as *mut u8;
+
+        // SAFETY: the buffer holds the header and the data and is aligned for the header
+        unsafe {
+            (raw as *mut RuntimeAccount).write(RuntimeAccount {
+                borrow_state: NOT_BORROWED,
+                is_signer: 0,
+                is_writable: 1,
+                executable: 0,
+                resize_delta: 0,
+                address,
+                owner,
+                lamports: 0,
+                data_len: data.len() as u64,
+            });
+            core::ptr::copy_nonoverlapping(data.as_ptr(), raw.add(header), data.len());
+        }
+
+        Self { buf }
+    }
+
+    pub fn signer(mut self) -> Self {
+        self.header().is_signer = 1;
+        self
+    }
+
+    pub fn read_only(mut self) -> Self {
+        self.header().is_writable = 0;
+        self
+    }
+
+    pub fn executable(mut self) -> Self {
+        self.header().executable = 1;
+        self
+    }
+
+    pub fn with_lamports(mut self, lamports: u64) -> Self {
+        self.header().lamports = lamports;
+        self
+    }
+
+    fn header(&mut self) -> &mut RuntimeAccount {
+        // SAFETY: `new` wrote the header at the start of the buffer
+        unsafe { &mut *(self.buf.as_mut_ptr() as *mut RuntimeAccount) }
+    }
+
+    /// View of the account, valid until `self` is dropped
+    pub fn view(&mut self) -> AccountView {
+        // SAFETY: the buffer starts with an initialized header followed by the data
+        unsafe { AccountView::new_unchecked(self.buf.as_mut_ptr() as *mut RuntimeAccount) }
+    }
+}
@@ -3,27 +3,97 @@
 
 #![no_std]
 
-use hayabusa_common::AccountView;
+use core::cmp::Ordering;
+use hayabusa_common::{address_eq, AccountView, Address};
 use hayabusa_errors::{ErrorCode, ProgramError, Result};
 use hayabusa_utility::{error_msg, hint::unlikely};
 
+mod manifest;
+
+pub use manifest::{
+    encode_manifest, manifest_len, ManifestInstruction, MANIFEST_CHUNK_LEN, MANIFEST_MAGIC,
+};
+
 pub trait FromAccountViews<'ix>
 where
     Self: Sized,
 {
-    fn try_from_account_views(account_views: &mut AccountIter<'ix>) -> Result<Self>;
+    /// Canonical bumps of the PDA accounts validated while parsing `Self`,
+    /// use [`NoBumps`] when there are none
+    type Bumps: Default;
+
+    /// Names of the accounts written by the instruction, in account order. Derived from the
+    /// `Mut` fields, `hayabusa locks` exports the same list as the write locks in the IDL
+    const WRITABLE_ACCOUNTS: &'static [&'static str] = &[];
+
+    /// One entry per account, in account order, for IDL generators and client builders
+    const ACCOUNTS: &'static [AccountSpec] = &[];
+
+    fn try_from_account_views(
+        account_views: &mut AccountIter<'ix>,
+        bumps: &mut Self::Bumps,
+    ) -> Result<Self>;
+
+    /// Runs once the instruction handler succeeded, on the same account views `Self` was
+    /// parsed from, e.g. to close the accounts marked with the `close` meta constraint.
+    ///
+    /// The first account of `Self` is at index 0: for a struct parsed after
+    /// [`AccountIter::skip`] or nested in another one, pass [`AccountIter::consumed_since`]
+    /// the checkpoint taken right before parsing it.
+    #[inline(always)]
+    fn exit(_account_views: &'ix [AccountView]) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Account of an instruction as declared in its `FromAccountViews` struct
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccountSpec {
+    /// Field name
+    pub name: &'static str,
+    /// Doc comment of the field, lines joined with `\n`
+    pub docs: &'static str,
+    /// The field is wrapped in `Mut`
+    pub writable: bool,
+    /// The field is a `Signer`
+    pub signer: bool,
+    /// Arguments of the `#[meta(...)]` attributes as written, e.g. `seeds = [b"counter"]` and
+    /// `bump`
+    pub constraints: &'static [&'static str],
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct NoBumps;
+
+/// Accounts of an instruction that takes none, every account passed ends up in
+/// `remaining_accounts`
+pub struct NoAccounts;
+
+impl<'ix> FromAccountViews<'ix> for NoAccounts {
+    type Bumps = NoBumps;
+
+    #[inline(always)]
+    fn try_from_account_views(_: &mut AccountIter<'ix>, _: &mut NoBumps) -> Result<Self> {
+        Ok(NoAccounts)
+    }
 }
 
 /// ## Context
 ///
 /// A context consists of a set of typed/named accounts `T`
-/// with constraints applied and a remaining accounts slice
+/// with constraints applied, the bumps of the PDA accounts
+/// in `T` and a remaining accounts slice
+///
+/// Contexts run by `dispatch!` also carry the program id and the raw instruction data, see
+/// [`Ctx::program_id`] and [`Ctx::instruction_data`]
 pub struct Ctx<'ix, T>
 where
     T: FromAccountViews<'ix>,
 {
     pub accounts: T,
+    pub bumps: T::Bumps,
     pub remaining_accounts: &'ix [AccountView],
+    raw_ix: Option<(&'ix Address, &'ix [u8])>,
 }
 
 impl<'ix, T> Ctx<'ix, T>
@@ -34,14 +104,105 @@ where
     pub fn construct(account_views: &'ix [AccountView]) -> Result<Self> {
         let mut iter = AccountIter::new(account_views);
 
-        let accounts = T::try_from_account_views(&mut iter)?;
+        let mut bumps = T::Bumps::default();
+        let accounts = T::try_from_account_views(&mut iter, &mut bumps)?;
 
         Ok(Ctx {
             accounts,
-            remaining_accounts: &account_views.get(iter.index..).unwrap_or(&[]),
+            bumps,
+            remaining_accounts: account_views.get(iter.index..).unwrap_or(&[]),
+            raw_ix: None,
         })
     }
 
+    /// Attaches the program id and the raw instruction data, discriminator included
+    #[inline(always)]
+    pub fn with_instruction(mut self, program_id: &'ix Address, instruction_data: &'ix [u8]) -> Self {
+        self.raw_ix = Some((program_id, instruction_data));
+        self
+    }
+
+    /// Id of the program, `None` unless attached with [`Ctx::with_instruction`]
+    #[inline(always)]
+    pub fn program_id(&self) -> Option<&'ix Address> {
+        self.raw_ix.map(|(program_id, _)| program_id)
+    }
+
+    /// Raw instruction data, discriminator included, e.g. to hash the payload of a permit
+    /// signature. `None` unless attached with [`Ctx::with_instruction`]
+    #[inline(always)]
+    pub fn instruction_data(&self) -> Option<&'ix [u8]> {
+        self.raw_ix.map(|(_, instruction_data)| instruction_data)
+    }
+
+    /// Same as [`Ctx::construct`], but fails if accounts are left over once `T` is parsed,
+    /// for instructions that must not accept stray accounts.
+    #[inline(always)]
+    pub fn construct_exact(account_views: &'ix [AccountView]) -> Result<Self> {
+        let ctx = Self::construct(account_views)?;
+
+        if unlikely(!ctx.remaining_accounts.is_empty()) {
+            error_msg!(
+                "Ctx::construct_exact: {} unexpected remaining accounts",
+                ErrorCode::InvalidAccount,
+                ctx.remaining_accounts.len(),
+            );
+        }
+
+        Ok(ctx)
+    }
+
+    /// Same as [`Ctx::construct`], but fails with [`ErrorCode::DuplicateAccount`] if two accounts
+    /// of `T` share an address, e.g. the same vault passed as source and destination.
+    ///
+    /// Remaining accounts are not checked.
+    #[inline(always)]
+    pub fn construct_strict(account_views: &'ix [AccountView]) -> Result<Self> {
+        let ctx = Self::construct(account_views)?;
+
+        let named = account_views.len() - ctx.remaining_accounts.len();
+        try_check_no_dup(&account_views[..named], 0..named)?;
+
+        Ok(ctx)
+    }
+
+    /// Constructs the context, runs `handler` with it and then [`FromAccountViews::exit`]
+    #[inline(always)]
+    pub fn run<E, F>(account_views: &'ix [AccountView], handler: F) -> Result<()>
+    where
+        F: FnOnce(Self) -> core::result::Result<(), E>,
+        E: Into<ProgramError>,
+    {
+        let ctx = Self::construct(account_views)?;
+
+        let named = account_views.len() - ctx.remaining_accounts.len();
+
+        handler(ctx).map_err(Into::into)?;
+
+        T::exit(&account_views[..named])
+    }
+
+    /// Same as [`Ctx::run`], with the program id and the raw instruction data attached
+    #[inline(always)]
+    pub fn run_with_instruction<E, F>(
+        program_id: &'ix Address,
+        account_views: &'ix [AccountView],
+        instruction_data: &'ix [u8],
+        handler: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(Self) -> core::result::Result<(), E>,
+        E: Into<ProgramError>,
+    {
+        let ctx = Self::construct(account_views)?.with_instruction(program_id, instruction_data);
+
+        let named = account_views.len() - ctx.remaining_accounts.len();
+
+        handler(ctx).map_err(Into::into)?;
+
+        T::exit(&account_views[..named])
+    }
+
     #[inline(always)]
     pub fn remaining_accounts(&self) -> AccountIter<'ix> {
         AccountIter::new(self.remaining_accounts)
@@ -70,6 +231,79 @@ where
     }
 }
 
+/// `a` and `b` are the same account.
+///
+/// The runtime passes an account listed twice in an instruction as two views of the same
+/// account data, which is compared first, before falling back to the addresses.
+#[inline(always)]
+pub fn same_account(a: &AccountView, b: &AccountView) -> bool {
+    core::ptr::eq(a.data_ptr(), b.data_ptr()) || address_eq(a.address(), b.address())
+}
+
+/// Orders accounts by address, byte by byte.
+#[inline(always)]
+pub fn cmp_accounts(a: &AccountView, b: &AccountView) -> Ordering {
+    a.address().cmp(b.address())
+}
+
+/// Fails with [`ErrorCode::DuplicateAccount`] if an account at one of `indices` shares its address
+/// with any other account of `account_views`.
+#[inline]
+pub fn try_check_no_dup(
+    account_views: &[AccountView],
+    indices: impl IntoIterator<Item = usize>,
+) -> Result<()> {
+    for index in indices {
+        let account = &account_views[index];
+
+        for (other, account_view) in account_views.iter().enumerate() {
+            if unlikely(other != index && same_account(account, account_view)) {
+                error_msg!(
+                    "try_check_no_dup: duplicate account at index {}",
+                    ErrorCode::DuplicateAccount,
+                    index,
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Fails unless `account_views` are in canonical order, strictly ascending by address.
+///
+/// Lets a variable list of accounts, e.g. the remaining accounts of a multisig, be checked for
+/// duplicates in a single pass: a duplicate fails with [`ErrorCode::DuplicateAccount`], any other
+/// out of order account with [`ErrorCode::InvalidAccount`].
+#[inline]
+pub fn try_check_canonical_order(account_views: &[AccountView]) -> Result<()> {
+    for (index, pair) in account_views.windows(2).enumerate() {
+        match cmp_accounts(&pair[0], &pair[1]) {
+            Ordering::Less => {}
+            Ordering::Equal => {
+                error_msg!(
+                    "try_check_canonical_order: duplicate account at index {}",
+                    ErrorCode::DuplicateAccount,
+                    index + 1,
+                );
+            }
+            Ordering::Greater => {
+                error_msg!(
+                    "try_check_canonical_order: account out of order at index {}",
+                    ErrorCode::InvalidAccount,
+                    index + 1,
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Position in an [`AccountIter`], see [`AccountIter::checkpoint`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checkpoint(usize);
+
 #[derive(Clone)]
 pub struct AccountIter<'ix> {
     pub(crate) slice: &'ix [AccountView],
@@ -87,6 +321,53 @@ impl<'ix> AccountIter<'ix> {
         self.index
     }
 
+    /// Number of account views left
+    #[inline(always)]
+    pub fn remaining_len(&self) -> usize {
+        self.slice.len() - self.index
+    }
+
+    /// Next account view, without consuming it
+    #[inline(always)]
+    pub fn peek(&self) -> Result<&'ix AccountView> {
+        match self.slice.get(self.index) {
+            Some(account_view) => Ok(account_view),
+            None => {
+                error_msg!(
+                    "AccountIter::peek: no accounts remaining.",
+                    ErrorCode::InvalidAccount,
+                );
+            }
+        }
+    }
+
+    /// Consumes `n` account views
+    #[inline(always)]
+    pub fn skip(&mut self, n: usize) -> Result<()> {
+        if unlikely(n > self.remaining_len()) {
+            error_msg!(
+                "AccountIter::skip: not enough accounts remaining.",
+                ErrorCode::InvalidAccount,
+            );
+        }
+
+        self.index += n;
+
+        Ok(())
+    }
+
+    /// Current position, to go back to with [`AccountIter::rewind`], e.g. to retry parsing
+    /// with another account variant
+    #[inline(always)]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.index)
+    }
+
+    #[inline(always)]
+    pub fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.index = checkpoint.0;
+    }
+
     #[allow(clippy::should_implement_trait)]
     #[inline(always)]
     pub fn next(&mut self) -> Result<&'ix AccountView> {
@@ -103,6 +384,19 @@ impl<'ix> AccountIter<'ix> {
         Ok(account_view)
     }
 
+    /// Account views returned by [`AccountIter::next`] so far
+    #[inline(always)]
+    pub fn consumed(&self) -> &'ix [AccountView] {
+        &self.slice[..self.index]
+    }
+
+    /// Account views consumed since `checkpoint`, e.g. the views a nested `FromAccountViews`
+    /// struct was parsed from
+    #[inline(always)]
+    pub fn consumed_since(&self, checkpoint: Checkpoint) -> &'ix [AccountView] {
+        &self.slice[checkpoint.0..self.index]
+    }
+
     #[inline(always)]
     pub fn into_subslice(&self) -> &[AccountView] {
         &self.slice[self.index..]
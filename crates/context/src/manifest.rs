@@ -0,0 +1,198 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::AccountSpec;
+
+/// Most return data a program can set, the manifest is read in chunks of this size
+pub const MANIFEST_CHUNK_LEN: usize = 1024;
+
+/// Instruction listed in the manifest generated by [`program_manifest!`](crate::program_manifest)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ManifestInstruction {
+    pub discriminator: &'static [u8],
+    /// Handler name, e.g. `update_counter`
+    pub name: &'static str,
+    pub accounts: &'static [AccountSpec],
+}
+
+/// First bytes of the manifest, off-chain tools find it in the program data by scanning for them
+pub const MANIFEST_MAGIC: [u8; 8] = *b"HYBSMNFT";
+
+/// Length of the manifest encoded by [`encode_manifest`].
+pub const fn manifest_len(
+    name: &str,
+    version: &str,
+    instructions: &[ManifestInstruction],
+) -> usize {
+    // magic, body length, name, version, instruction count
+    let mut len = MANIFEST_MAGIC.len() + 4 + 4 + name.len() + 4 + version.len() + 4;
+
+    let mut i = 0;
+    while i < instructions.len() {
+        let ix = &instructions[i];
+        len += 4 + ix.discriminator.len() + 4 + ix.name.len() + 4;
+
+        let mut j = 0;
+        while j < ix.accounts.len() {
+            // name, writable, signer
+            len += 4 + ix.accounts[j].name.len() + 2;
+            j += 1;
+        }
+        i += 1;
+    }
+
+    len
+}
+
+/// Encodes the program manifest, `N` is [`manifest_len`] of the same arguments.
+///
+/// [`MANIFEST_MAGIC`] and the `u32` length of the rest, followed by the borsh encoding of
+///
+/// ```text
+/// struct Manifest { name: String, version: String, instructions: Vec<Instruction> }
+/// struct Instruction { discriminator: Vec<u8>, name: String, accounts: Vec<Account> }
+/// struct Account { name: String, writable: bool, signer: bool }
+/// ```
+///
+/// Panics, at compile time when used in a const, if `N` is not the encoded length.
+pub const fn encode_manifest<const N: usize>(
+    name: &str,
+    version: &str,
+    instructions: &[ManifestInstruction],
+) -> [u8; N] {
+    assert!(
+        N == manifest_len(name, version, instructions),
+        "manifest length mismatch"
+    );
+
+    let mut writer = Writer {
+        out: [0; N],
+        len: 0,
+    };
+
+    writer.bytes(&MANIFEST_MAGIC);
+    writer.u32((N - MANIFEST_MAGIC.len() - 4) as u32);
+    writer.vec(name.as_bytes());
+    writer.vec(version.as_bytes());
+    writer.u32(instructions.len() as u32);
+
+    let mut i = 0;
+    while i < instructions.len() {
+        let ix = &instructions[i];
+        writer.vec(ix.discriminator);
+        writer.vec(ix.name.as_bytes());
+        writer.u32(ix.accounts.len() as u32);
+
+        let mut j = 0;
+        while j < ix.accounts.len() {
+            let account = &ix.accounts[j];
+            writer.vec(account.name.as_bytes());
+            writer.bytes(&[account.writable as u8, account.signer as u8]);
+            j += 1;
+        }
+        i += 1;
+    }
+
+    writer.out
+}
+
+struct Writer<const N: usize> {
+    out: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Writer<N> {
+    const fn bytes(&mut self, bytes: &[u8]) {
+        let mut i = 0;
+        while i < bytes.len() {
+            self.out[self.len] = bytes[i];
+            self.len += 1;
+            i += 1;
+        }
+    }
+
+    const fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    /// Borsh `Vec<u8>` and `String`, `u32` length then the bytes
+    const fn vec(&mut self, bytes: &[u8]) {
+        self.u32(bytes.len() as u32);
+        self.bytes(bytes);
+    }
+}
+
+/// Embeds a program manifest in the binary and generates the `ProgramManifestIx` view
+/// instruction returning it through return data, to be added to `dispatch!` as
+/// `ProgramManifestIx => program_manifest(offset)`.
+///
+/// The manifest lists the name, discriminator and accounts (with their writable and signer
+/// flags) of the given `#[instruction]` types, so explorers can label the accounts of any
+/// transaction to the program without its IDL, see [`encode_manifest`] for the layout. Return
+/// data is limited to [`MANIFEST_CHUNK_LEN`] bytes, the instruction takes the `u32` offset to read
+/// from (no data reads from the start) and a chunk shorter than that is the last one.
+///
+/// Without `name` and `version` the crate name and version are used:
+///
+/// ```ignore
+/// program_manifest!(DepositInstruction, WithdrawInstruction);
+/// program_manifest!(name = "vault", version = "1.2.0", DepositInstruction, WithdrawInstruction);
+/// ```
+#[macro_export]
+macro_rules! program_manifest {
+    ($($IxTy:ty),+ $(,)?) => {
+        $crate::program_manifest!(
+            name = env!("CARGO_PKG_NAME"),
+            version = env!("CARGO_PKG_VERSION"),
+            $($IxTy),+
+        );
+    };
+    (name = $name:expr, version = $version:expr, $($IxTy:ty),+ $(,)?) => {
+        const PROGRAM_MANIFEST_INSTRUCTIONS: &[$crate::ManifestInstruction] = &[$(
+            $crate::ManifestInstruction {
+                discriminator: <$IxTy>::DISCRIMINATOR,
+                name: <$IxTy>::NAME,
+                accounts: <$IxTy>::ACCOUNTS,
+            },
+        )+];
+        const PROGRAM_MANIFEST_LEN: usize =
+            $crate::manifest_len($name, $version, PROGRAM_MANIFEST_INSTRUCTIONS);
+
+        #[used]
+        #[link_section = ".rodata.program_manifest"]
+        pub static PROGRAM_MANIFEST: [u8; PROGRAM_MANIFEST_LEN] =
+            $crate::encode_manifest($name, $version, PROGRAM_MANIFEST_INSTRUCTIONS);
+
+        #[derive(Clone, Copy, Discriminator)]
+        #[repr(C)]
+        pub struct ProgramManifestIx {
+            offset: u32,
+        }
+
+        impl<'ix> DecodeIx<'ix> for ProgramManifestIx {
+            const MAX_DATA_LEN: usize = <Self as Discriminator>::DISCRIMINATOR.len() + 4;
+
+            #[inline(always)]
+            fn decode(bytes: &'ix [u8]) -> Result<Self> {
+                let offset = match bytes {
+                    [] => 0,
+                    [a, b, c, d] => u32::from_le_bytes([*a, *b, *c, *d]),
+                    _ => return Err(ProgramError::InvalidInstructionData),
+                };
+
+                Ok(Self { offset })
+            }
+        }
+
+        pub fn program_manifest<'ix>(_: Ctx<'ix, NoAccounts>, offset: u32) -> Result<()> {
+            let start = core::cmp::min(offset as usize, PROGRAM_MANIFEST.len());
+            let end = core::cmp::min(
+                start + $crate::MANIFEST_CHUNK_LEN,
+                PROGRAM_MANIFEST.len(),
+            );
+
+            syscalls::set_return_data(&PROGRAM_MANIFEST[start..end]);
+            Ok(())
+        }
+    };
+}
@@ -6,8 +6,11 @@
 use hayabusa_errors::Result;
 use hayabusa_utility::{error_msg, hint::unlikely};
 use solana_account_view::AccountView;
-use solana_address::Address;
-use solana_instruction_view::cpi::Signer;
+use solana_address::{address_eq, Address};
+use solana_instruction_view::{
+    cpi::{invoke, invoke_signed, Signer},
+    InstructionAccount, InstructionView,
+};
 use solana_program_error::ProgramError;
 
 pub trait CheckProgramId {
@@ -73,6 +76,29 @@ impl<'ix, 'a, 'b, 'c, T: CheckProgramId> CpiCtx<'ix, 'a, 'b, 'c, T> {
             signers: Some(signers),
         })
     }
+
+    /// Invokes `T::ID` with `data`, signed with `signers` when set.
+    ///
+    /// For CPI helpers that build their own instruction data, like the ones generated by
+    /// `#[instruction(cpi)]`.
+    #[inline(always)]
+    pub fn try_invoke<const N: usize>(
+        &self,
+        instruction_accounts: &[InstructionAccount; N],
+        account_views: &[&AccountView; N],
+        data: &[u8],
+    ) -> Result<()> {
+        let instruction = InstructionView {
+            program_id: &T::ID,
+            accounts: instruction_accounts,
+            data,
+        };
+
+        match self.signers {
+            Some(signers) => invoke_signed(&instruction, account_views, signers),
+            None => invoke(&instruction, account_views),
+        }
+    }
 }
 
 impl<T: CheckProgramId> core::ops::Deref for CpiCtx<'_, '_, '_, '_, T> {
@@ -83,3 +109,50 @@ impl<T: CheckProgramId> core::ops::Deref for CpiCtx<'_, '_, '_, '_, T> {
         &self.accounts
     }
 }
+
+/// Removes repeated accounts (same address) from a CPI account list in place and returns the
+/// new length, `metas[..len]` and `views[..len]` are then passed to the invoke.
+///
+/// The first occurrence of an address is kept with the union of the privileges of all its
+/// occurrences, so an account requested read-only by one builder and writable by another ends
+/// up writable once. Only for callees that look accounts up by address, e.g. a list of extra
+/// accounts, since positions after the first duplicate shift.
+///
+/// Fails if `metas` and `views` differ in length or don't pair up address by address.
+#[inline(always)]
+pub fn dedup_cpi_accounts(
+    metas: &mut [InstructionAccount],
+    views: &mut [&AccountView],
+) -> Result<usize> {
+    if unlikely(metas.len() != views.len()) {
+        error_msg!(
+            "dedup_cpi_accounts: as many account views as instruction accounts required",
+            ProgramError::NotEnoughAccountKeys,
+        );
+    }
+
+    let mut len = 0;
+    for i in 0..metas.len() {
+        if unlikely(!address_eq(views[i].address(), metas[i].address)) {
+            error_msg!(
+                "dedup_cpi_accounts: account view doesn't match its instruction account",
+                ProgramError::InvalidArgument,
+            );
+        }
+
+        match (0..len).find(|&j| address_eq(metas[j].address, metas[i].address)) {
+            Some(j) => {
+                metas[j].is_writable |= metas[i].is_writable;
+                metas[j].is_signer |= metas[i].is_signer;
+            }
+            None => {
+                // everything in `len..i` was merged into an earlier entry
+                metas.swap(len, i);
+                views.swap(len, i);
+                len += 1;
+            }
+        }
+    }
+
+    Ok(len)
+}
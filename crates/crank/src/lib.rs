@@ -0,0 +1,276 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pending work keyed by the slot it becomes due in.
+//!
+//! [`CrankQueue`] is a binary min-heap laid out in account data, programs push work with a
+//! deadline slot and keepers pop whatever is due, as much as their compute budget allows:
+//!
+//! ```ignore
+//! let mut data = ctx.queue.try_borrow_mut()?;
+//! let mut queue = CrankQueue::<Order>::from_bytes_mut(&mut data[8..])?;
+//!
+//! queue.pop_due_within_budget(Clock::get()?.slot, 20_000, |slot, order| execute(slot, order))?;
+//! ```
+//!
+//! Items due in the same slot are popped in no particular order.
+
+#![no_std]
+
+use bytemuck::{Pod, Zeroable};
+use hayabusa_errors::{ErrorCode, Result};
+use hayabusa_syscalls::remaining_compute_units;
+use hayabusa_utility::{error_msg, hint::unlikely};
+use solana_program_error::ProgramError;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct CrankQueueHeader {
+    len: u32,
+    capacity: u32,
+}
+
+/// Slot-keyed min-heap over `header | slots: [u64; capacity] | items: [T; capacity]`.
+///
+/// The byte slice must be 8 byte aligned, which holds for account data past the discriminator.
+pub struct CrankQueue<'a, T: Pod> {
+    header: &'a mut CrankQueueHeader,
+    slots: &'a mut [u64],
+    items: &'a mut [T],
+}
+
+impl<'a, T: Pod> CrankQueue<'a, T> {
+    /// Bytes needed for a queue of `capacity` items, excluding the discriminator.
+    pub const fn space(capacity: usize) -> usize {
+        let items_offset = Self::items_offset(capacity);
+        items_offset + capacity * core::mem::size_of::<T>()
+    }
+
+    const fn items_offset(capacity: usize) -> usize {
+        let offset = core::mem::size_of::<CrankQueueHeader>() + capacity * 8;
+        let align = core::mem::align_of::<T>();
+        offset.div_ceil(align) * align
+    }
+
+    /// Writes an empty queue spanning all of `data`.
+    #[inline]
+    pub fn initialize(data: &'a mut [u8]) -> Result<Self> {
+        let header_len = core::mem::size_of::<CrankQueueHeader>();
+
+        if unlikely(data.len() < header_len) {
+            error_msg!(
+                "CrankQueue::initialize: data too small",
+                ProgramError::AccountDataTooSmall,
+            );
+        }
+
+        // largest capacity that fits
+        let mut capacity = (data.len() - header_len) / (8 + core::mem::size_of::<T>());
+        while capacity > 0 && Self::space(capacity) > data.len() {
+            capacity -= 1;
+        }
+
+        data.fill(0);
+        let header = bytemuck::from_bytes_mut::<CrankQueueHeader>(&mut data[..header_len]);
+        header.capacity = capacity as u32;
+
+        Self::from_bytes_mut(data)
+    }
+
+    /// Queue previously written by [`CrankQueue::initialize`].
+    #[inline]
+    pub fn from_bytes_mut(data: &'a mut [u8]) -> Result<Self> {
+        let header_len = core::mem::size_of::<CrankQueueHeader>();
+        let data_len = data.len();
+
+        if unlikely(data_len < header_len) {
+            error_msg!(
+                "CrankQueue::from_bytes_mut: data too small",
+                ProgramError::AccountDataTooSmall,
+            );
+        }
+
+        let (header, rest) = data.split_at_mut(header_len);
+        let header: &mut CrankQueueHeader = bytemuck::try_from_bytes_mut(header)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let capacity = header.capacity as usize;
+
+        if unlikely(header.len > header.capacity || Self::space(capacity) > data_len) {
+            error_msg!(
+                "CrankQueue::from_bytes_mut: invalid header",
+                ProgramError::InvalidAccountData,
+            );
+        }
+
+        let (slots, rest) = rest.split_at_mut(capacity * 8);
+        let padding = Self::items_offset(capacity) - header_len - capacity * 8;
+        let items = &mut rest[padding..padding + capacity * core::mem::size_of::<T>()];
+
+        Ok(Self {
+            header,
+            slots: bytemuck::try_cast_slice_mut(slots)
+                .map_err(|_| ProgramError::InvalidAccountData)?,
+            items: bytemuck::try_cast_slice_mut(items)
+                .map_err(|_| ProgramError::InvalidAccountData)?,
+        })
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.header.len as usize
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.header.len == 0
+    }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.header.capacity as usize
+    }
+
+    /// Slot and item due first.
+    #[inline(always)]
+    pub fn peek(&self) -> Option<(u64, &T)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some((self.slots[0], &self.items[0]))
+    }
+
+    /// Schedules `item` for `slot`.
+    #[inline]
+    pub fn push(&mut self, slot: u64, item: T) -> Result<()> {
+        let mut index = self.len();
+
+        if unlikely(index >= self.capacity()) {
+            error_msg!("CrankQueue::push: queue is full", ErrorCode::BufferFull);
+        }
+
+        self.slots[index] = slot;
+        self.items[index] = item;
+        self.header.len += 1;
+
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if self.slots[parent] <= self.slots[index] {
+                break;
+            }
+            self.swap(parent, index);
+            index = parent;
+        }
+
+        Ok(())
+    }
+
+    /// Removes and returns the first item if it is due at `current_slot`.
+    #[inline]
+    pub fn pop_due(&mut self, current_slot: u64) -> Option<(u64, T)> {
+        match self.peek() {
+            Some((slot, _)) if slot <= current_slot => {}
+            _ => return None,
+        }
+
+        let last = self.len() - 1;
+        let popped = (self.slots[0], self.items[0]);
+
+        self.swap(0, last);
+        self.header.len -= 1;
+        self.sift_down(0);
+
+        Some(popped)
+    }
+
+    /// Pops and processes due items until none is left or fewer than `reserve_compute_units`
+    /// remain, so the keeper's transaction always has budget left to finish. Returns the number
+    /// of processed items.
+    ///
+    /// An error from `process` aborts the transaction, the popped item is restored with it.
+    #[inline]
+    pub fn pop_due_within_budget<F>(
+        &mut self,
+        current_slot: u64,
+        reserve_compute_units: u64,
+        mut process: F,
+    ) -> Result<usize>
+    where
+        F: FnMut(u64, T) -> Result<()>,
+    {
+        let mut processed = 0;
+
+        while remaining_compute_units() > reserve_compute_units {
+            let Some((slot, item)) = self.pop_due(current_slot) else {
+                break;
+            };
+
+            process(slot, item)?;
+            processed += 1;
+        }
+
+        Ok(processed)
+    }
+
+    #[inline(always)]
+    fn swap(&mut self, a: usize, b: usize) {
+        self.slots.swap(a, b);
+        self.items.swap(a, b);
+    }
+
+    #[inline]
+    fn sift_down(&mut self, mut index: usize) {
+        let len = self.len();
+
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+            let mut smallest = index;
+
+            if left < len && self.slots[left] < self.slots[smallest] {
+                smallest = left;
+            }
+            if right < len && self.slots[right] < self.slots[smallest] {
+                smallest = right;
+            }
+            if smallest == index {
+                return;
+            }
+
+            self.swap(index, smallest);
+            index = smallest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pop_due_in_slot_order() {
+        let mut buf = [0u64; 32];
+        let data: &mut [u8] = bytemuck::cast_slice_mut(&mut buf);
+
+        let mut queue = CrankQueue::<u32>::initialize(data).unwrap();
+        assert_eq!(queue.capacity(), 20);
+
+        for (slot, item) in [(30, 3), (10, 1), (50, 5), (20, 2), (40, 4)] {
+            queue.push(slot, item).unwrap();
+        }
+
+        assert_eq!(queue.pop_due(5), None);
+        assert_eq!(queue.pop_due(25), Some((10, 1)));
+        assert_eq!(queue.pop_due(25), Some((20, 2)));
+        assert_eq!(queue.pop_due(25), None);
+        assert_eq!(queue.len(), 3);
+
+        let data: &mut [u8] = bytemuck::cast_slice_mut(&mut buf);
+        let mut queue = CrankQueue::<u32>::from_bytes_mut(data).unwrap();
+
+        assert_eq!(queue.pop_due(u64::MAX), Some((30, 3)));
+        assert_eq!(queue.pop_due(u64::MAX), Some((40, 4)));
+        assert_eq!(queue.pop_due(u64::MAX), Some((50, 5)));
+        assert!(queue.is_empty());
+    }
+}
@@ -3,8 +3,43 @@
 
 #![no_std]
 
+pub mod max_len;
+pub mod varint;
+pub mod zero_copy;
+
+pub use max_len::BorshMaxLen;
+pub use varint::Varint;
+pub use zero_copy::{
+    slice_arg_len, try_read_pod_arg, try_read_slice_arg, write_pod_arg, write_slice_arg,
+};
+
+use hayabusa_common::{AccountView, Address};
 use hayabusa_errors::Result;
 
 pub trait DecodeIx<'ix>: Sized {
+    /// Longest instruction data accepted, discriminator included, `usize::MAX` if unbounded.
+    /// `dispatch!` rejects longer data before decoding.
+    const MAX_DATA_LEN: usize = usize::MAX;
+
     fn decode(bytes: &'ix [u8]) -> Result<Self>;
 }
+
+/// Decodes the instruction data, constructs the context and calls the handler,
+/// generated by `#[instruction]` and called by `dispatch!`
+pub trait ProcessIx {
+    /// `instruction_data` is the raw instruction data, discriminator included, it is attached
+    /// to the context along with `program_id`
+    #[inline(always)]
+    fn process(program_id: &Address, accounts: &[AccountView], instruction_data: &[u8]) -> Result<()> {
+        Self::process_at(program_id, accounts, instruction_data, 0)
+    }
+
+    /// Same as [`ProcessIx::process`], with the discriminator of the instruction at `offset`, e.g.
+    /// after the group discriminator of `dispatch_nested!`. The context still gets the whole data.
+    fn process_at(
+        program_id: &Address,
+        accounts: &[AccountView],
+        instruction_data: &[u8],
+        offset: usize,
+    ) -> Result<()>;
+}
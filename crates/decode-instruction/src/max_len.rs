@@ -0,0 +1,49 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Longest borsh encoding of instruction arguments, see `#[instruction(borsh)]`.
+//!
+//! Only fixed size types and options of them have one. Vectors and strings are unbounded, their
+//! argument takes the bound explicitly with `#[max_len(N)]`.
+
+/// Longest borsh encoding of the type.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no bounded borsh encoding",
+    note = "bound the argument with `#[max_len(N)]`, N being its longest encoding in bytes"
+)]
+pub trait BorshMaxLen {
+    const BORSH_MAX_LEN: usize;
+}
+
+macro_rules! impl_borsh_max_len {
+    ($($t:ty),+) => {
+        $(
+            impl BorshMaxLen for $t {
+                const BORSH_MAX_LEN: usize = core::mem::size_of::<$t>();
+            }
+        )+
+    };
+}
+
+impl_borsh_max_len!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, bool);
+
+impl<T: BorshMaxLen, const N: usize> BorshMaxLen for [T; N] {
+    const BORSH_MAX_LEN: usize = T::BORSH_MAX_LEN * N;
+}
+
+/// Tag byte then the value
+impl<T: BorshMaxLen> BorshMaxLen for Option<T> {
+    const BORSH_MAX_LEN: usize = 1 + T::BORSH_MAX_LEN;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borsh_max_len() {
+        assert_eq!(<Option<u64>>::BORSH_MAX_LEN, 9);
+        assert_eq!(<[Option<u16>; 4]>::BORSH_MAX_LEN, 12);
+        assert_eq!(bool::BORSH_MAX_LEN, 1);
+    }
+}
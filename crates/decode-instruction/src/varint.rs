@@ -0,0 +1,212 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Varint packed instruction arguments, see `#[instruction(varint)]`.
+//!
+//! Unsigned integers are LEB128 encoded, signed integers are zigzag encoded first so small
+//! negative values stay short. `u8`, byte arrays and addresses are written as is. Encodings are
+//! canonical: overlong or overflowing varints are rejected, so every value has a single encoding.
+
+use hayabusa_common::Address;
+use hayabusa_errors::{ProgramError, Result};
+
+pub trait Varint: Sized {
+    /// Longest encoding of the type.
+    const MAX_LEN: usize;
+
+    /// Reads a value from the front of `bytes` and advances past it.
+    fn try_read_varint(bytes: &mut &[u8]) -> Result<Self>;
+
+    /// Writes the value to the front of `out`, returning the number of bytes written or `None` if
+    /// `out` is too short.
+    fn write_varint(&self, out: &mut [u8]) -> Option<usize>;
+}
+
+macro_rules! impl_varint_unsigned {
+    ($t:ty) => {
+        impl Varint for $t {
+            const MAX_LEN: usize = (<$t>::BITS as usize).div_ceil(7);
+
+            #[inline(always)]
+            fn try_read_varint(bytes: &mut &[u8]) -> Result<Self> {
+                let mut value: $t = 0;
+
+                for i in 0..Self::MAX_LEN {
+                    let Some(&byte) = bytes.get(i) else {
+                        return Err(ProgramError::InvalidInstructionData);
+                    };
+
+                    let shift = 7 * i as u32;
+                    let bits = (byte & 0x7f) as $t;
+
+                    // the last byte can only carry the bits left in the type
+                    if shift + 7 > <$t>::BITS && bits >> (<$t>::BITS - shift) != 0 {
+                        return Err(ProgramError::InvalidInstructionData);
+                    }
+
+                    value |= bits << shift;
+
+                    if byte & 0x80 == 0 {
+                        // a zero final byte after the first one is an overlong encoding
+                        if i > 0 && byte == 0 {
+                            return Err(ProgramError::InvalidInstructionData);
+                        }
+
+                        *bytes = &bytes[i + 1..];
+                        return Ok(value);
+                    }
+                }
+
+                Err(ProgramError::InvalidInstructionData)
+            }
+
+            #[inline(always)]
+            fn write_varint(&self, out: &mut [u8]) -> Option<usize> {
+                let mut value = *self;
+                let mut len = 0;
+
+                loop {
+                    let byte = (value & 0x7f) as u8;
+                    value >>= 7;
+
+                    let slot = out.get_mut(len)?;
+                    len += 1;
+
+                    if value == 0 {
+                        *slot = byte;
+                        return Some(len);
+                    }
+
+                    *slot = byte | 0x80;
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_varint_signed {
+    ($t:ty, $u:ty) => {
+        impl Varint for $t {
+            const MAX_LEN: usize = <$u as Varint>::MAX_LEN;
+
+            #[inline(always)]
+            fn try_read_varint(bytes: &mut &[u8]) -> Result<Self> {
+                let zigzag = <$u>::try_read_varint(bytes)?;
+                Ok((zigzag >> 1) as $t ^ -((zigzag & 1) as $t))
+            }
+
+            #[inline(always)]
+            fn write_varint(&self, out: &mut [u8]) -> Option<usize> {
+                let zigzag = ((*self << 1) ^ (*self >> (<$t>::BITS - 1))) as $u;
+                zigzag.write_varint(out)
+            }
+        }
+    };
+}
+
+impl_varint_unsigned!(u16);
+impl_varint_unsigned!(u32);
+impl_varint_unsigned!(u64);
+impl_varint_unsigned!(u128);
+
+impl_varint_signed!(i16, u16);
+impl_varint_signed!(i32, u32);
+impl_varint_signed!(i64, u64);
+impl_varint_signed!(i128, u128);
+
+/// Fixed size values, copied as is.
+impl<const N: usize> Varint for [u8; N] {
+    const MAX_LEN: usize = N;
+
+    #[inline(always)]
+    fn try_read_varint(bytes: &mut &[u8]) -> Result<Self> {
+        let Some((value, rest)) = bytes.split_first_chunk::<N>() else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        *bytes = rest;
+        Ok(*value)
+    }
+
+    #[inline(always)]
+    fn write_varint(&self, out: &mut [u8]) -> Option<usize> {
+        out.get_mut(..N)?.copy_from_slice(self);
+        Some(N)
+    }
+}
+
+impl Varint for u8 {
+    const MAX_LEN: usize = 1;
+
+    #[inline(always)]
+    fn try_read_varint(bytes: &mut &[u8]) -> Result<Self> {
+        <[u8; 1]>::try_read_varint(bytes).map(|[value]| value)
+    }
+
+    #[inline(always)]
+    fn write_varint(&self, out: &mut [u8]) -> Option<usize> {
+        [*self].write_varint(out)
+    }
+}
+
+impl Varint for i8 {
+    const MAX_LEN: usize = 1;
+
+    #[inline(always)]
+    fn try_read_varint(bytes: &mut &[u8]) -> Result<Self> {
+        u8::try_read_varint(bytes).map(|value| value as i8)
+    }
+
+    #[inline(always)]
+    fn write_varint(&self, out: &mut [u8]) -> Option<usize> {
+        (*self as u8).write_varint(out)
+    }
+}
+
+impl Varint for Address {
+    const MAX_LEN: usize = 32;
+
+    #[inline(always)]
+    fn try_read_varint(bytes: &mut &[u8]) -> Result<Self> {
+        <[u8; 32]>::try_read_varint(bytes).map(Address::new_from_array)
+    }
+
+    #[inline(always)]
+    fn write_varint(&self, out: &mut [u8]) -> Option<usize> {
+        out.get_mut(..32)?.copy_from_slice(self.as_ref());
+        Some(32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip<T: Varint + PartialEq + core::fmt::Debug>(value: T, len: usize) {
+        let mut buf = [0u8; 32];
+        assert_eq!(value.write_varint(&mut buf), Some(len));
+
+        let mut bytes = &buf[..len];
+        assert_eq!(T::try_read_varint(&mut bytes).unwrap(), value);
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        roundtrip(0u64, 1);
+        roundtrip(127u64, 1);
+        roundtrip(128u64, 2);
+        roundtrip(u64::MAX, 10);
+        roundtrip(u16::MAX, 3);
+        roundtrip(-1i64, 1);
+        roundtrip(-65i32, 2);
+        roundtrip(i128::MIN, 19);
+        roundtrip(200u8, 1);
+        roundtrip([7u8; 3], 3);
+
+        // overlong, overflowing and truncated encodings
+        assert!(u64::try_read_varint(&mut &[0x80, 0x00][..]).is_err());
+        assert!(u16::try_read_varint(&mut &[0xff, 0xff, 0x04][..]).is_err());
+        assert!(u32::try_read_varint(&mut &[0x80][..]).is_err());
+    }
+}
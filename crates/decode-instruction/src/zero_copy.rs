@@ -0,0 +1,122 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Zero-copy instruction arguments, see `#[instruction(zero_copy)]`.
+//!
+//! The fixed-size arguments come first, packed like a pod instruction, then every slice argument
+//! as a `u32` element count followed by its elements. Slices are borrowed from the instruction
+//! data, so their elements must have an alignment of 1 (`u8`, byte arrays, `PodU64`, ...).
+
+use bytemuck::Pod;
+use core::mem::{align_of, size_of};
+use hayabusa_errors::{ProgramError, Result};
+
+/// Reads a fixed-size argument from the front of `bytes` and advances past it.
+#[inline(always)]
+pub fn try_read_pod_arg<T: Pod>(bytes: &mut &[u8]) -> Result<T> {
+    let Some((value, rest)) = bytes.split_at_checked(size_of::<T>()) else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    *bytes = rest;
+    Ok(bytemuck::pod_read_unaligned(value))
+}
+
+/// Borrows a length-prefixed slice argument from the front of `bytes` and advances past it.
+#[inline(always)]
+pub fn try_read_slice_arg<'ix, T: Pod>(bytes: &mut &'ix [u8]) -> Result<&'ix [T]> {
+    const {
+        assert!(
+            align_of::<T>() == 1,
+            "zero-copy slice elements must have an alignment of 1"
+        )
+    };
+
+    let Some((count, rest)) = bytes.split_first_chunk::<4>() else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+    let Some(len) = (u32::from_le_bytes(*count) as usize).checked_mul(size_of::<T>()) else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+    let Some((slice, rest)) = rest.split_at_checked(len) else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    *bytes = rest;
+    // alignment 1 and a whole number of elements, the cast can't fail
+    Ok(bytemuck::cast_slice(slice))
+}
+
+/// Bytes a slice argument of `len` elements takes, length prefix included.
+#[inline(always)]
+pub const fn slice_arg_len<T>(len: usize) -> usize {
+    4 + len * size_of::<T>()
+}
+
+/// Writes a fixed-size argument at `out[*len..]` and advances `len`, `None` if `out` is too
+/// short.
+#[inline(always)]
+pub fn write_pod_arg<T: Pod>(value: &T, out: &mut [u8], len: &mut usize) -> Option<()> {
+    let bytes = bytemuck::bytes_of(value);
+    out.get_mut(*len..*len + bytes.len())?
+        .copy_from_slice(bytes);
+    *len += bytes.len();
+    Some(())
+}
+
+/// Writes a slice argument, length prefix first, at `out[*len..]` and advances `len`, `None` if
+/// `out` is too short or the slice has more than `u32::MAX` elements.
+#[inline(always)]
+pub fn write_slice_arg<T: Pod>(value: &[T], out: &mut [u8], len: &mut usize) -> Option<()> {
+    let count = u32::try_from(value.len()).ok()?;
+    let bytes: &[u8] = bytemuck::cast_slice(value);
+
+    let out = out.get_mut(*len..*len + 4 + bytes.len())?;
+    out[..4].copy_from_slice(&count.to_le_bytes());
+    out[4..].copy_from_slice(bytes);
+    *len += out.len();
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let proof = [[1u8; 32], [2u8; 32]];
+        let mut buf = [0u8; 128];
+        let mut len = 0;
+        write_pod_arg(&7u64, &mut buf, &mut len).unwrap();
+        write_slice_arg(b"abc".as_slice(), &mut buf, &mut len).unwrap();
+        write_slice_arg(proof.as_slice(), &mut buf, &mut len).unwrap();
+        assert_eq!(
+            len,
+            8 + slice_arg_len::<u8>(3) + slice_arg_len::<[u8; 32]>(2)
+        );
+
+        let mut bytes = &buf[..len];
+        assert_eq!(try_read_pod_arg::<u64>(&mut bytes).unwrap(), 7);
+        assert_eq!(try_read_slice_arg::<u8>(&mut bytes).unwrap(), b"abc");
+        assert_eq!(try_read_slice_arg::<[u8; 32]>(&mut bytes).unwrap(), &proof);
+        assert!(bytes.is_empty());
+
+        // the proof doesn't fit after the header and the bytes
+        let mut buf = [0u8; 64];
+        let mut len = 15;
+        assert!(write_slice_arg(proof.as_slice(), &mut buf, &mut len).is_none());
+        assert_eq!(len, 15);
+    }
+
+    #[test]
+    fn test_truncated() {
+        let mut bytes: &[u8] = &[3, 0, 0, 0, 1, 2];
+        assert!(try_read_slice_arg::<u8>(&mut bytes).is_err());
+
+        let mut bytes: &[u8] = &[0xff, 0xff, 0xff, 0xff];
+        assert!(try_read_slice_arg::<[u8; 32]>(&mut bytes).is_err());
+
+        let mut bytes: &[u8] = &[1, 2, 3];
+        assert!(try_read_pod_arg::<u32>(&mut bytes).is_err());
+    }
+}
@@ -4,19 +4,42 @@
 use proc_macro::TokenStream;
 use quote::quote;
 use sha2::{Digest, Sha256};
-use syn::{parse_macro_input, DeriveInput};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    spanned::Spanned,
+    DeriveInput, Expr, Ident, Lit, LitInt, Result, Token,
+};
 
-#[proc_macro_derive(Discriminator)]
+/// Length of the sha256 based discriminators, unless set with `len = N`
+const DISCRIMINATOR_LEN: usize = 8;
+
+/// `DISCRIMINATOR` is the first 8 bytes of the sha256 of the type name.
+///
+/// `#[discriminator("name")]` hashes `name` instead, e.g. `"account:Vault"` for the discriminator
+/// of an Anchor account, and `#[discriminator([1])]` uses the given bytes as is, e.g. the one
+/// byte tag of a native program instruction, up to 8 bytes.
+///
+/// `#[discriminator(len = 1)]` keeps only the first 1, 4 or 8 bytes of the hash, for programs
+/// that trade the collision resistance of 8 bytes for smaller instruction data or accounts, also
+/// combined with a name: `#[discriminator("account:Vault", len = 4)]`.
+#[proc_macro_derive(Discriminator, attributes(discriminator))]
 pub fn derive_discriminator(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
 
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    // Generate the discriminator using the hasher
-    let name_str = name.to_string();
-    let hasher = DiscriminatorHasher::new(&name_str);
-    let discriminator = hasher.hash_and_extract_discriminator();
+    let discriminator = match discriminator_override(&input) {
+        Ok(Some(discriminator)) => discriminator,
+        Ok(None) => {
+            // Generate the discriminator using the hasher
+            let name_str = name.to_string();
+            let hasher = DiscriminatorHasher::new(&name_str);
+            hasher.hash_and_extract_discriminator().to_vec()
+        }
+        Err(e) => return e.to_compile_error().into(),
+    };
 
     let expanded = quote! {
         impl #impl_generics Discriminator for #name #ty_generics #where_clause {
@@ -27,6 +50,129 @@ pub fn derive_discriminator(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+/// Arguments of `#[discriminator(..)]`: a name to hash or the bytes, and/or `len = N`
+struct DiscriminatorArgs {
+    value: Option<Expr>,
+    len: Option<LitInt>,
+}
+
+impl Parse for DiscriminatorArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut value = None;
+        let mut len = None;
+
+        while !input.is_empty() {
+            if input.peek(Ident) && input.peek2(Token![=]) {
+                let key: Ident = input.parse()?;
+                if key != "len" {
+                    return Err(syn::Error::new(key.span(), "expected `len = N`"));
+                }
+                input.parse::<Token![=]>()?;
+                len = Some(input.parse()?);
+            } else {
+                value = Some(input.parse()?);
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(Self { value, len })
+    }
+}
+
+/// Bytes of a `#[discriminator(..)]` attribute, if any
+fn discriminator_override(input: &DeriveInput) -> Result<Option<Vec<u8>>> {
+    let Some(attr) = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("discriminator"))
+    else {
+        return Ok(None);
+    };
+
+    let args: DiscriminatorArgs = attr.parse_args()?;
+
+    let len = match &args.len {
+        Some(len) => {
+            let value = len.base10_parse::<usize>()?;
+            if !matches!(value, 1 | 4 | 8) {
+                return Err(syn::Error::new(
+                    len.span(),
+                    "the discriminator length must be 1, 4 or 8",
+                ));
+            }
+            value
+        }
+        None => DISCRIMINATOR_LEN,
+    };
+
+    let hashed = |name: &str| DiscriminatorHasher::new(name).hash_and_extract_discriminator();
+
+    let Some(expr) = &args.value else {
+        return Ok(Some(hashed(&input.ident.to_string())[..len].to_vec()));
+    };
+
+    let bytes = match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Str(name) => hashed(&name.value())[..len].to_vec(),
+            Lit::ByteStr(bytes) => bytes.value(),
+            _ => return Err(invalid(expr)),
+        },
+        Expr::Array(array) => array
+            .elems
+            .iter()
+            .map(|elem| match elem {
+                Expr::Lit(syn::ExprLit {
+                    lit: Lit::Int(int), ..
+                }) => int.base10_parse::<u8>(),
+                _ => Err(invalid(elem)),
+            })
+            .collect::<Result<_>>()?,
+        _ => return Err(invalid(expr)),
+    };
+
+    if bytes.is_empty() {
+        return Err(syn::Error::new(
+            expr.span(),
+            "the discriminator can't be empty",
+        ));
+    }
+
+    // account discriminators are read back as the first 8 bytes of the data
+    if bytes.len() > DISCRIMINATOR_LEN {
+        return Err(syn::Error::new(
+            expr.span(),
+            "the discriminator can't be longer than 8 bytes",
+        ));
+    }
+
+    if let Some(len) = &args.len {
+        if !matches!(
+            expr,
+            Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(_),
+                ..
+            })
+        ) {
+            return Err(syn::Error::new(
+                len.span(),
+                "`len` only applies to hashed discriminators, the bytes set their own length",
+            ));
+        }
+    }
+
+    Ok(Some(bytes))
+}
+
+fn invalid(expr: &impl Spanned) -> syn::Error {
+    syn::Error::new(
+        expr.span(),
+        "expected a name to hash, e.g. \"account:Vault\", or the bytes, e.g. [1]",
+    )
+}
+
 struct DiscriminatorHasher<'a> {
     pub identifier: &'a str,
 }
@@ -36,14 +182,14 @@ impl<'a> DiscriminatorHasher<'a> {
         Self { identifier }
     }
 
-    pub fn hash_and_extract_discriminator(&self) -> [u8; 8] {
+    pub fn hash_and_extract_discriminator(&self) -> [u8; DISCRIMINATOR_LEN] {
         let mut hasher = Sha256::new();
         hasher.update(self.identifier);
 
         let hash = hasher.finalize();
-        let mut discriminator = [0u8; 8];
+        let mut discriminator = [0u8; DISCRIMINATOR_LEN];
 
-        discriminator.copy_from_slice(&hash[..8]);
+        discriminator.copy_from_slice(&hash[..DISCRIMINATOR_LEN]);
         discriminator
     }
 }
@@ -11,6 +11,31 @@ pub trait Discriminator {
     const DISCRIMINATOR: &'static [u8];
 }
 
+/// Discriminator of an account that was created through a two-phase init and not finalized yet.
+///
+/// It is the bitwise complement of [`Discriminator::DISCRIMINATOR`], so a half-initialized
+/// account never passes the regular discriminator check.
+/// Only the first `DISCRIMINATOR.len()` bytes (at most 8) are used, the rest is zero.
+pub trait InitializingDiscriminator: Discriminator {
+    const INITIALIZING_DISCRIMINATOR: [u8; 8] = initializing_discriminator(Self::DISCRIMINATOR);
+}
+
+impl<T> InitializingDiscriminator for T where T: Discriminator {}
+
+const fn initializing_discriminator(discriminator: &[u8]) -> [u8; 8] {
+    let mut initializing = [0u8; 8];
+    let mut i = 0;
+
+    while i < discriminator.len() {
+        initializing[i] = !discriminator[i];
+        i += 1;
+    }
+
+    initializing
+}
+
+/// The first 8 bytes of the account data, which start with the discriminator whatever its length.
+///
 /// # Safety
 /// This function assumes account data is at least 8 bytes long
 #[inline(always)]
@@ -4,6 +4,7 @@
 //! Attribution: https://github.com/anza-xyz/pinocchio/blob/main/sdk/src/entrypoint/mod.rs
 
 #![no_std]
+#![allow(unexpected_cfgs)] // silence warning about target_os = "solana"
 
 use core::{
     alloc::{GlobalAlloc, Layout},
@@ -13,7 +14,8 @@ use core::{
     slice::from_raw_parts,
 };
 use solana_account_view::{AccountView, RuntimeAccount, MAX_PERMITTED_DATA_INCREASE};
-use solana_address::Address;
+use hayabusa_utility::hint::unlikely;
+use solana_address::{address_eq, Address};
 use solana_program_error::ProgramError;
 
 #[cfg(feature = "alloc")]
@@ -166,16 +168,49 @@ macro_rules! entrypoint {
 /// expected by instructions of the program. This is useful to reduce the stack size requirement for
 /// the entrypoint, as the default is set to [`MAX_TX_ACCOUNTS`]. If the program receives more
 /// accounts than the specified maximum, these accounts will be ignored.
+///
+/// The program id passed by the runtime is checked once against `crate::ID` (declared with
+/// `declare_id!`) before `process_instruction` is called, see [`process_checked_entrypoint`].
+///
+/// With `eager = K` instead of a maximum, only the first `K` accounts are parsed up front and the
+/// rest is left to the program, see [`process_hybrid_entrypoint`]:
+///
+/// ```ignore
+/// program_entrypoint!(process_instruction, eager = 4);
+///
+/// fn process_instruction(
+///     program_id: &Address,
+///     accounts: &[AccountView],   // At most the first 4 accounts
+///     tail: InstructionContext,   // Remaining accounts, read on demand
+///     instruction_data: &[u8],
+/// ) -> ProgramResult;
+/// ```
+#[allow(clippy::crate_in_macro_def)]
 #[macro_export]
 macro_rules! program_entrypoint {
     ( $process_instruction:expr ) => {
         $crate::program_entrypoint!($process_instruction, { $crate::MAX_TX_ACCOUNTS });
     };
+    ( $process_instruction:expr, eager = $eager:expr ) => {
+        /// Program entrypoint.
+        #[no_mangle]
+        pub unsafe extern "C" fn entrypoint(input: *mut u8) -> u64 {
+            $crate::process_hybrid_entrypoint::<$eager>(
+                input,
+                &crate::ID,
+                $process_instruction,
+            )
+        }
+    };
     ( $process_instruction:expr, $maximum:expr ) => {
         /// Program entrypoint.
         #[no_mangle]
         pub unsafe extern "C" fn entrypoint(input: *mut u8) -> u64 {
-            $crate::process_entrypoint::<$maximum>(input, $process_instruction)
+            $crate::process_checked_entrypoint::<$maximum>(
+                input,
+                &crate::ID,
+                $process_instruction,
+            )
         }
     };
 }
@@ -193,6 +228,104 @@ macro_rules! program_entrypoint {
 pub unsafe fn process_entrypoint<const MAX_ACCOUNTS: usize>(
     input: *mut u8,
     process_instruction: fn(&Address, &[AccountView], &[u8]) -> Result<()>,
+) -> u64 {
+    unsafe { run_entrypoint::<MAX_ACCOUNTS>(input, None, process_instruction) }
+}
+
+/// Entrypoint deserialization with a program id check.
+///
+/// Same as [`process_entrypoint`], but fails with [`ProgramError::IncorrectProgramId`] before
+/// calling `process_instruction` if the program id passed by the runtime differs from
+/// `expected_program_id`, i.e. the binary was deployed at another address. This is the only
+/// program id check performed, `dispatch!` relies on it.
+///
+/// With the `trust-program-id` feature the check is compiled out. Only enable it if the program
+/// is never deployed at an address other than the one it was built for, or if running under a
+/// different program id is harmless: every PDA, owner and CPI check the program makes against
+/// `crate::ID` silently assumes it is the executing program.
+///
+/// # Safety
+///
+/// See [`process_entrypoint`].
+#[inline(always)]
+pub unsafe fn process_checked_entrypoint<const MAX_ACCOUNTS: usize>(
+    input: *mut u8,
+    expected_program_id: &Address,
+    process_instruction: fn(&Address, &[AccountView], &[u8]) -> Result<()>,
+) -> u64 {
+    #[cfg(not(feature = "trust-program-id"))]
+    let expected_program_id = Some(expected_program_id);
+
+    #[cfg(feature = "trust-program-id")]
+    let expected_program_id = {
+        let _ = expected_program_id;
+        None
+    };
+
+    unsafe { run_entrypoint::<MAX_ACCOUNTS>(input, expected_program_id, process_instruction) }
+}
+
+/// Entrypoint deserialization for programs with a few hot accounts and a long, optional tail.
+///
+/// The first `EAGER` accounts are parsed like in [`process_checked_entrypoint`], the remaining
+/// ones are only skipped to reach the instruction data and handed to `process_instruction` as an
+/// [`InstructionContext`] positioned on the first of them, so the program only pays for the tail
+/// accounts it reads. A duplicated tail account refers to the index of its original in the
+/// instruction, which is an eager account when lower than `EAGER`.
+///
+/// The program id is checked as in [`process_checked_entrypoint`].
+///
+/// # Safety
+///
+/// See [`process_entrypoint`].
+#[inline(always)]
+pub unsafe fn process_hybrid_entrypoint<const EAGER: usize>(
+    input: *mut u8,
+    expected_program_id: &Address,
+    process_instruction: fn(&Address, &[AccountView], InstructionContext, &[u8]) -> Result<()>,
+) -> u64 {
+    const UNINIT: MaybeUninit<AccountView> = MaybeUninit::<AccountView>::uninit();
+    let mut accounts = [UNINIT; EAGER];
+
+    let (program_id, count, tail, instruction_data) =
+        unsafe { deserialize_hybrid::<EAGER>(input, &mut accounts) };
+
+    #[cfg(feature = "trace")]
+    pinocchio_log::log!(
+        "trace: enter accounts={} data_len={}",
+        count + tail.remaining() as usize,
+        instruction_data.len()
+    );
+
+    #[cfg(not(feature = "trust-program-id"))]
+    if unlikely(!address_eq(program_id, expected_program_id)) {
+        return trace_exit(ProgramError::IncorrectProgramId.into());
+    }
+
+    #[cfg(feature = "trust-program-id")]
+    let _ = expected_program_id;
+
+    let result = process_instruction(
+        program_id,
+        unsafe { from_raw_parts(accounts.as_ptr() as _, count) },
+        tail,
+        instruction_data,
+    );
+
+    #[cfg(feature = "heap-watermark")]
+    crate::log_heap_usage!();
+
+    trace_exit(match result {
+        Ok(()) => SUCCESS,
+        Err(error) => error.into(),
+    })
+}
+
+#[inline(always)]
+unsafe fn run_entrypoint<const MAX_ACCOUNTS: usize>(
+    input: *mut u8,
+    expected_program_id: Option<&Address>,
+    process_instruction: fn(&Address, &[AccountView], &[u8]) -> Result<()>,
 ) -> u64 {
     const UNINIT: MaybeUninit<AccountView> = MaybeUninit::<AccountView>::uninit();
     // Create an array of uninitialized account views.
@@ -201,16 +334,52 @@ pub unsafe fn process_entrypoint<const MAX_ACCOUNTS: usize>(
     let (program_id, count, instruction_data) =
         unsafe { deserialize::<MAX_ACCOUNTS>(input, &mut accounts) };
 
+    #[cfg(feature = "trace")]
+    pinocchio_log::log!(
+        "trace: enter accounts={} data_len={}",
+        count,
+        instruction_data.len()
+    );
+
+    if let Some(expected_program_id) = expected_program_id {
+        if unlikely(!address_eq(program_id, expected_program_id)) {
+            return trace_exit(ProgramError::IncorrectProgramId.into());
+        }
+    }
+
     // Call the program's entrypoint passing `count` account views; we know that
     // they are initialized so we cast the pointer to a slice of `[AccountView]`.
-    match process_instruction(
+    let result = process_instruction(
         program_id,
         unsafe { from_raw_parts(accounts.as_ptr() as _, count) },
         instruction_data,
-    ) {
+    );
+
+    #[cfg(feature = "heap-watermark")]
+    crate::log_heap_usage!();
+
+    trace_exit(match result {
         Ok(()) => SUCCESS,
         Err(error) => error.into(),
-    }
+    })
+}
+
+/// Execution trace emitted with the `trace` feature, one block per instruction:
+///
+/// ```text
+/// trace: enter accounts=<n> data_len=<n>
+/// trace: ix=<instruction type>    (logged by `dispatch!`)
+/// trace: exit result=<n>
+/// ```
+///
+/// `result` is the value returned to the runtime, `0` on success. Blocks nest when the program
+/// calls itself through CPI.
+#[inline(always)]
+fn trace_exit(result: u64) -> u64 {
+    #[cfg(feature = "trace")]
+    pinocchio_log::log!("trace: exit result={}", result);
+
+    result
 }
 
 /// Align a pointer to the BPF alignment of [`u128`].
@@ -327,9 +496,31 @@ unsafe fn clone_account_view(
 /// of the program execution since the returned values reference the `input`.
 #[inline(always)]
 pub unsafe fn deserialize<const MAX_ACCOUNTS: usize>(
-    mut input: *mut u8,
+    input: *mut u8,
     accounts: &mut [MaybeUninit<AccountView>; MAX_ACCOUNTS],
 ) -> (&'static Address, usize, &'static [u8]) {
+    let (mut input, processed, to_skip) = deserialize_accounts::<MAX_ACCOUNTS>(input, accounts);
+
+    // There might be accounts to skip only when `MAX_ACCOUNTS < MAX_TX_ACCOUNTS` so this allows
+    // the compiler to optimize the code and avoid the loop when `MAX_ACCOUNTS == MAX_TX_ACCOUNTS`.
+    if MAX_ACCOUNTS < MAX_TX_ACCOUNTS {
+        input = skip_accounts(input, to_skip);
+    }
+
+    let (program_id, instruction_data) = deserialize_instruction(input);
+
+    (program_id, processed, instruction_data)
+}
+
+/// Parses up to `MAX_ACCOUNTS` accounts into `accounts`.
+///
+/// Returns the input pointer past the parsed accounts, the number of accounts parsed and the
+/// number of accounts left in the input buffer.
+#[inline(always)]
+unsafe fn deserialize_accounts<const MAX_ACCOUNTS: usize>(
+    mut input: *mut u8,
+    accounts: &mut [MaybeUninit<AccountView>; MAX_ACCOUNTS],
+) -> (*mut u8, usize, usize) {
     // Ensure that MAX_ACCOUNTS is less than or equal to the maximum number of accounts
     // (MAX_TX_ACCOUNTS) that can be processed in a transaction.
     const {
@@ -341,6 +532,7 @@ pub unsafe fn deserialize<const MAX_ACCOUNTS: usize>(
 
     // Number of accounts to process.
     let mut processed = *(input as *const u64) as usize;
+    let mut to_skip = 0;
     // Skip the number of accounts (8 bytes).
     input = input.add(size_of::<u64>());
 
@@ -375,7 +567,7 @@ pub unsafe fn deserialize<const MAX_ACCOUNTS: usize>(
                 processed
             };
 
-            let mut to_skip = processed - to_process_plus_one;
+            to_skip = processed - to_process_plus_one;
             processed = to_process_plus_one;
 
             // This is an optimization to reduce the number of jumps required to process the
@@ -412,36 +604,67 @@ pub unsafe fn deserialize<const MAX_ACCOUNTS: usize>(
                     }
                 }
             }
+        }
+    }
 
-            // Process any remaining accounts to move the offset to the instruction data (there is a
-            // duplication of logic but we avoid testing whether we have space for the account or
-            // not).
-            //
-            // There might be accounts to skip only when `MAX_ACCOUNTS < MAX_TX_ACCOUNTS` so this
-            // allows the compiler to optimize the code and avoid the loop when `MAX_ACCOUNTS ==
-            // MAX_TX_ACCOUNTS`.
-            if MAX_ACCOUNTS < MAX_TX_ACCOUNTS {
-                while to_skip > 0 {
-                    // Marks the account as skipped.
-                    to_skip -= 1;
-
-                    // Read the next account.
-                    let account: *mut RuntimeAccount = input as *mut RuntimeAccount;
-                    // Adds an 8-bytes offset for:
-                    //   - rent epoch in case of a non-duplicated account
-                    //   - duplicated marker + 7 bytes of padding in case of a duplicated account
-                    input = input.add(size_of::<u64>());
-
-                    if (*account).borrow_state == NON_DUP_MARKER {
-                        input = input.add(STATIC_ACCOUNT_DATA);
-                        input = input.add((*account).data_len as usize);
-                        input = align_pointer!(input);
-                    }
-                }
-            }
+    (input, processed, to_skip)
+}
+
+/// Parse the first `EAGER` accounts from the runtime input buffer and locate the others.
+///
+/// Same as [`deserialize`], but the accounts past `EAGER` are returned as an
+/// [`InstructionContext`] instead of being ignored.
+///
+/// # Safety
+///
+/// See [`deserialize`].
+#[inline(always)]
+pub unsafe fn deserialize_hybrid<const EAGER: usize>(
+    input: *mut u8,
+    accounts: &mut [MaybeUninit<AccountView>; EAGER],
+) -> (&'static Address, usize, InstructionContext, &'static [u8]) {
+    const {
+        assert!(EAGER > 0, "at least one account must be parsed eagerly");
+    }
+
+    let (input, processed, to_skip) = deserialize_accounts::<EAGER>(input, accounts);
+
+    let tail = InstructionContext {
+        buffer: input,
+        remaining: to_skip as u64,
+    };
+    let (program_id, instruction_data) = deserialize_instruction(skip_accounts(input, to_skip));
+
+    (program_id, processed, tail, instruction_data)
+}
+
+/// Moves `input` past `count` serialized accounts without parsing them.
+#[inline(always)]
+unsafe fn skip_accounts(mut input: *mut u8, mut count: usize) -> *mut u8 {
+    while count > 0 {
+        // Marks the account as skipped.
+        count -= 1;
+
+        // Read the next account.
+        let account: *mut RuntimeAccount = input as *mut RuntimeAccount;
+        // Adds an 8-bytes offset for:
+        //   - rent epoch in case of a non-duplicated account
+        //   - duplicated marker + 7 bytes of padding in case of a duplicated account
+        input = input.add(size_of::<u64>());
+
+        if (*account).borrow_state == NON_DUP_MARKER {
+            input = input.add(STATIC_ACCOUNT_DATA);
+            input = input.add((*account).data_len as usize);
+            input = align_pointer!(input);
         }
     }
 
+    input
+}
+
+/// Reads the instruction data and program id following the accounts.
+#[inline(always)]
+unsafe fn deserialize_instruction(mut input: *mut u8) -> (&'static Address, &'static [u8]) {
     // instruction data
     let instruction_data_len = *(input as *const u64) as usize;
     input = input.add(size_of::<u64>());
@@ -452,7 +675,7 @@ pub unsafe fn deserialize<const MAX_ACCOUNTS: usize>(
     // program id
     let program_id: &Address = &*(input as *const Address);
 
-    (program_id, processed, instruction_data)
+    (program_id, instruction_data)
 }
 
 /// Default panic hook.
@@ -549,6 +772,43 @@ macro_rules! default_allocator {
     };
 }
 
+/// Returns the number of heap bytes allocated so far by the allocator set up with
+/// [`crate::default_allocator!`].
+///
+/// The bump allocator never frees memory, so this is also the peak heap usage of the current
+/// instruction, which is the number to size `request_heap_frame` against. Always returns `0` when
+/// not compiled for the `"solana"` target.
+#[cfg(feature = "alloc")]
+#[inline(always)]
+pub fn heap_usage() -> usize {
+    #[cfg(any(target_os = "solana", target_arch = "bpf"))]
+    {
+        // SAFETY: Same range as the one handed to the global allocator by `default_allocator!`,
+        // only the position word at the start of the region is read.
+        unsafe {
+            BumpAllocator::new_unchecked(HEAP_START_ADDRESS as usize, MAX_HEAP_LENGTH as usize)
+        }
+        .used()
+    }
+
+    #[cfg(not(any(target_os = "solana", target_arch = "bpf")))]
+    {
+        0
+    }
+}
+
+/// Logs the heap watermark of the default allocator, see [`heap_usage`].
+///
+/// The log line has the form `heap usage: <n> bytes`. With the `heap-watermark` feature enabled
+/// the entrypoint emits it after every instruction.
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! log_heap_usage {
+    () => {
+        pinocchio_log::log!("heap usage: {} bytes", $crate::heap_usage());
+    };
+}
+
 /// A global allocator that does not dynamically allocate memory.
 ///
 /// This macro sets up a global allocator that denies all dynamic allocations, while allowing static
@@ -679,6 +939,22 @@ mod alloc {
                 end: start + len,
             }
         }
+
+        /// Returns the number of bytes handed out so far, including alignment padding.
+        ///
+        /// Reads the heap position word at the start of the memory region, so it is only
+        /// meaningful for the allocator that owns that region.
+        #[inline(always)]
+        pub fn used(&self) -> usize {
+            // SAFETY: `new_unchecked` requires `start` to be valid for reads and aligned to `usize`.
+            let pos = unsafe { *(self.start as *const usize) };
+
+            if pos == 0 {
+                0
+            } else {
+                pos - (self.start + size_of::<usize>())
+            }
+        }
     }
 
     // Integer arithmetic in this global allocator implementation is safe when operating on the
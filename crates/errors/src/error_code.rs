@@ -18,6 +18,24 @@ pub enum ErrorCode {
     TooManySeeds,
     InvalidIndex,
     ProgramAccountNotExecutable,
+    ConstraintViolated = 115,
+    InvalidReveal = 116,
+    RevealNotReady = 117,
+    RevealExpired = 118,
+    UnknownEmitter = 119,
+    ProgramNotFrozen = 120,
+    DuplicateAccount = 121,
+    ProposalNotPassed = 122,
+    ReentrancyDetected = 123,
+    AccountNotRentExempt = 124,
+    AddressNotOnCurve = 125,
+    DurableNonceNotAllowed = 126,
+    UnauthorizedSigner = 127,
+    TooEarly = 128,
+    DeadlinePassed = 129,
+    UnexpectedWritableAccount = 130,
+    ArgumentOutOfRange = 131,
+    ArgumentZero = 132,
 }
 
 impl TryFrom<u32> for ErrorCode {
@@ -38,6 +56,24 @@ impl TryFrom<u32> for ErrorCode {
             112 => Ok(ErrorCode::TooManySeeds),
             113 => Ok(ErrorCode::InvalidIndex),
             114 => Ok(ErrorCode::ProgramAccountNotExecutable),
+            115 => Ok(ErrorCode::ConstraintViolated),
+            116 => Ok(ErrorCode::InvalidReveal),
+            117 => Ok(ErrorCode::RevealNotReady),
+            118 => Ok(ErrorCode::RevealExpired),
+            119 => Ok(ErrorCode::UnknownEmitter),
+            120 => Ok(ErrorCode::ProgramNotFrozen),
+            121 => Ok(ErrorCode::DuplicateAccount),
+            122 => Ok(ErrorCode::ProposalNotPassed),
+            123 => Ok(ErrorCode::ReentrancyDetected),
+            124 => Ok(ErrorCode::AccountNotRentExempt),
+            125 => Ok(ErrorCode::AddressNotOnCurve),
+            126 => Ok(ErrorCode::DurableNonceNotAllowed),
+            127 => Ok(ErrorCode::UnauthorizedSigner),
+            128 => Ok(ErrorCode::TooEarly),
+            129 => Ok(ErrorCode::DeadlinePassed),
+            130 => Ok(ErrorCode::UnexpectedWritableAccount),
+            131 => Ok(ErrorCode::ArgumentOutOfRange),
+            132 => Ok(ErrorCode::ArgumentZero),
             _ => Err(ProgramError::InvalidArgument),
         }
     }
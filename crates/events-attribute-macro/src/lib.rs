@@ -3,11 +3,50 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, ItemStruct};
+use syn::{parse_macro_input, Expr, ItemStruct};
 
+/// `#[event]` logs the event as one `EVENT: <hex>` line, twice the event size plus the prefix.
+/// The line length is checked at compile time against the transaction log budget,
+/// `MAX_EVENT_LOG_LEN`, past which the runtime truncates logs and the event is lost.
+/// `#[event(max_log_len = 1024)]` sets a lower cap, e.g. to leave room for the other logs of the
+/// instruction. The length is exposed as the `LOG_LEN` const of the event.
+///
+/// The event bytes start with the discriminator, whatever its length, and are built by
+/// `to_event_bytes`; `event_log` and `event_cpi_data` return exactly what `emit` logs and
+/// `emit_cpi` sends.
+///
+/// `#[event(dynamic)]`, with hayabusa's `alloc` feature, also accepts `Vec<u8>` and `String`
+/// fields, written with a `u32` length prefix (see `DynamicEventField`). The event is built on
+/// the heap and its length is only known at runtime, so it isn't checked against the log budget.
+///
+/// `#[event_field(hex)]` and `#[event_field(base58)]` on an address or byte array field make
+/// `emit` log it a second time, human-readable, on a `Name: field=<rendered> ..` line after the
+/// `EVENT: <hex>` one, which counts towards `LOG_LEN`. The event bytes, and so `emit_cpi` and
+/// `decode_event`, are unchanged. Fixed-size events only.
 #[proc_macro_attribute]
-pub fn event(_attr: TokenStream, input: TokenStream) -> TokenStream {
-    let s = parse_macro_input!(input as ItemStruct);
+pub fn event(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let mut max_log_len: Option<Expr> = None;
+    let mut dynamic = false;
+
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("max_log_len") {
+            max_log_len = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("dynamic") {
+            dynamic = true;
+            Ok(())
+        } else {
+            Err(meta.error("unsupported event argument, expected `max_log_len = N` or `dynamic`"))
+        }
+    });
+
+    parse_macro_input!(attr with parser);
+
+    let mut s = parse_macro_input!(input as ItemStruct);
+    let renders = match take_field_renders(&mut s) {
+        Ok(renders) => renders,
+        Err(err) => return err.to_compile_error().into(),
+    };
     let name = &s.ident;
 
     let fields = match &s.fields {
@@ -15,14 +54,38 @@ pub fn event(_attr: TokenStream, input: TokenStream) -> TokenStream {
         _ => panic!("#[event] requires named fields"),
     };
 
+    if dynamic {
+        if let Some(max_log_len) = max_log_len {
+            return syn::Error::new_spanned(
+                max_log_len,
+                "`max_log_len` is checked at compile time, dynamic events only know their length at runtime",
+            )
+            .to_compile_error()
+            .into();
+        }
+
+        if let Some((field, _)) = fields.iter().zip(&renders).find(|(_, r)| r.is_some()) {
+            return syn::Error::new_spanned(
+                field,
+                "`#[event_field]` is only supported on fixed-size events",
+            )
+            .to_compile_error()
+            .into();
+        }
+
+        return expand_dynamic_event(&s, fields).into();
+    }
+
     let field_sizes: Vec<_> =
         fields.iter().map(|f| {
             let ty = &f.ty;
             quote! { <#ty as EventField>::SIZE }
         }).collect();
 
-    // offsets
-    let mut offset = quote! { 8usize };
+    // offsets, the fields follow the discriminator whatever its length
+    let disc_len = quote! { <#name as Discriminator>::DISCRIMINATOR.len() };
+    let mut offset = disc_len.clone();
+    let mut reads = Vec::new();
     let writes = fields.iter().map(|f| {
         let ident = f.ident.as_ref().unwrap();
         let ty = &f.ty;
@@ -32,60 +95,251 @@ pub fn event(_attr: TokenStream, input: TokenStream) -> TokenStream {
 
         offset = end.clone();
 
+        reads.push(quote! {
+            #ident: <#ty as EventField>::read(&__data[#start .. #end]),
+        });
+
         quote! {
             self.#ident.write(&mut __buf[#start .. #end]);
         }
-    });
+    }).collect::<Vec<_>>();
 
     let total_size = quote! {
-        8usize #( + #field_sizes )*
+        #disc_len #( + #field_sizes )*
+    };
+
+    // `Name: a=<rendered> b=<rendered>`, logged after the `EVENT: <hex>` line
+    let mut rendered_lens = Vec::new();
+    let mut rendered_writes = Vec::new();
+    for (f, render) in fields.iter().zip(&renders) {
+        let Some(render) = render else {
+            continue;
+        };
+        let ident = f.ident.as_ref().unwrap();
+        let ty = &f.ty;
+
+        let label = match rendered_writes.is_empty() {
+            true => format!("{name}: {ident}="),
+            false => format!(" {ident}="),
+        };
+        let label = syn::LitByteStr::new(label.as_bytes(), proc_macro2::Span::call_site());
+        let (max_len, write) = match render {
+            Render::Hex => (quote! { hex_len }, quote! { write_hex }),
+            Render::Base58 => (quote! { base58_max_len }, quote! { write_base58 }),
+        };
+
+        rendered_lens.push(quote! { #label.len() + #max_len(<#ty as EventField>::SIZE) });
+        rendered_writes.push(quote! {
+            __out[__len..__len + #label.len()].copy_from_slice(#label);
+            __len += #label.len();
+            __len += #write(AsRef::<[u8]>::as_ref(&self.#ident), &mut __out[__len..]);
+        });
+    }
+
+    let (rendered_len, rendered_impl, rendered_emit) = match rendered_writes.is_empty() {
+        true => (quote! { 0 }, quote! {}, quote! {}),
+        false => (
+            quote! { #name::RENDERED_LOG_LEN },
+            quote! {
+                /// Longest line of `#[event_field]` fields logged by `emit` after the
+                /// `EVENT: <hex>` one
+                pub const RENDERED_LOG_LEN: usize = 0 #( + #rendered_lens )*;
+
+                /// Writes the line of `#[event_field]` fields at the start of `out`, which must
+                /// hold `RENDERED_LOG_LEN` bytes, and returns its length
+                pub fn write_rendered_fields(&self, __out: &mut [u8]) -> usize {
+                    let mut __len = 0;
+                    #(#rendered_writes)*
+                    __len
+                }
+            },
+            quote! {
+                let mut __rendered = [0u8; #name::RENDERED_LOG_LEN];
+                let __len = self.write_rendered_fields(&mut __rendered);
+                logger::log_message(&__rendered[..__len]);
+            },
+        ),
     };
 
+    let max_log_len = match max_log_len {
+        Some(max_log_len) => quote! { #max_log_len },
+        None => quote! { MAX_EVENT_LOG_LEN },
+    };
+    let log_len_error = format!(
+        "#[event] `{name}`: the log line exceeds the log budget, shrink the event or raise \
+         `max_log_len` (up to `MAX_EVENT_LOG_LEN`)"
+    );
+
     let expanded = quote! {
         #[derive(Discriminator)]
         #s
 
-        impl EventBuilder for #name {
-            fn emit(&self) {
-                const __TOTAL_SIZE: usize = #total_size;
-
-                /* ---- raw event buffer ---- */
-                let mut __buf: [u8; __TOTAL_SIZE] = [0u8; __TOTAL_SIZE];
+        impl #name {
+            /// Length of the event bytes, discriminator included
+            pub const EVENT_LEN: usize = #total_size;
 
-                // discriminator
-                __buf[..8].copy_from_slice(&Self::DISCRIMINATOR);
+            /// Bytes logged by `emit`, the `EVENT: <hex>` line and the `#[event_field]` one
+            pub const LOG_LEN: usize = 7 + 2 * #name::EVENT_LEN + #rendered_len;
 
-                // fields
+            /// Event bytes, discriminator first, as logged by `emit` and sent by `emit_cpi`
+            pub fn to_event_bytes(&self) -> [u8; #name::EVENT_LEN] {
+                let mut __buf = [0u8; #name::EVENT_LEN];
+                __buf[..#disc_len].copy_from_slice(Self::DISCRIMINATOR);
                 #(#writes)*
+                __buf
+            }
 
-                /* ---- hex encoding ---- */
-                const __HEX_LEN: usize = __TOTAL_SIZE * 2;
-                let mut __hex: [u8; __HEX_LEN] = [0u8; __HEX_LEN];
-
-                {
-                    const HEX: &[u8; 16] = b"0123456789abcdef";
-                    let mut i = 0;
-                    while i < __TOTAL_SIZE {
-                        let b = __buf[i];
-                        __hex[2*i]     = HEX[(b >> 4) as usize];
-                        __hex[2*i + 1] = HEX[(b & 0x0f) as usize];
-                        i += 1;
-                    }
-                }
+            /// The `EVENT: <hex>` line logged by `emit`
+            pub fn event_log(&self) -> logger::Logger<{ 7 + 2 * #name::EVENT_LEN }> {
+                const HEX: &[u8; 16] = b"0123456789abcdef";
 
-                const __PREFIX_LEN: usize = 7;
-                const __LOG_LEN: usize = __PREFIX_LEN + __HEX_LEN;
+                let __buf = self.to_event_bytes();
+                let mut __hex = [0u8; 2 * #name::EVENT_LEN];
+                for (i, b) in __buf.iter().enumerate() {
+                    __hex[2 * i] = HEX[(b >> 4) as usize];
+                    __hex[2 * i + 1] = HEX[(b & 0x0f) as usize];
+                }
 
-                let mut __logger = logger::Logger::<__LOG_LEN>::default();
+                let mut __logger = logger::Logger::default();
                 __logger.append("EVENT: ");
                 // SAFETY: hex output is always valid ASCII
-                __logger.append(unsafe {
-                    core::str::from_utf8_unchecked(&__hex)
-                });
-                __logger.log();
+                __logger.append(unsafe { core::str::from_utf8_unchecked(&__hex) });
+                __logger
+            }
+
+            /// Instruction data of the self-CPI sent by `emit_cpi`, [`EVENT_IX_TAG`] then the
+            /// event bytes
+            pub fn event_cpi_data(&self) -> [u8; EVENT_IX_TAG.len() + #name::EVENT_LEN] {
+                let mut __ix_data = [0u8; EVENT_IX_TAG.len() + #name::EVENT_LEN];
+                __ix_data[..EVENT_IX_TAG.len()].copy_from_slice(EVENT_IX_TAG);
+                __ix_data[EVENT_IX_TAG.len()..].copy_from_slice(&self.to_event_bytes());
+                __ix_data
+            }
+
+            #rendered_impl
+        }
+
+        const _: () = assert!(
+            #name::LOG_LEN <= #max_log_len && #name::LOG_LEN <= MAX_EVENT_LOG_LEN,
+            #log_len_error
+        );
+
+        impl EventBuilder for #name {
+            fn emit(&self) {
+                self.event_log().log();
+
+                #rendered_emit
+            }
+
+            fn emit_cpi(&self, event_cpi: &EventCpi<'_>) -> Result<()> {
+                event_cpi.invoke(&self.event_cpi_data())
+            }
+
+            fn decode_event(__data: &[u8]) -> Option<Self> {
+                if __data.len() != Self::EVENT_LEN || &__data[..#disc_len] != Self::DISCRIMINATOR {
+                    return None;
+                }
+
+                Some(Self {
+                    #(#reads)*
+                })
             }
         }
     };
 
     expanded.into()
 }
+
+enum Render {
+    Hex,
+    Base58,
+}
+
+/// Removes the `#[event_field(hex | base58)]` attributes of the fields and returns them in field
+/// order.
+fn take_field_renders(s: &mut ItemStruct) -> syn::Result<Vec<Option<Render>>> {
+    let mut renders = Vec::new();
+
+    for field in s.fields.iter_mut() {
+        let mut render = None;
+        let mut result = Ok(());
+
+        field.attrs.retain(|attr| {
+            if !attr.path().is_ident("event_field") {
+                return true;
+            }
+
+            let parsed = attr.parse_nested_meta(|meta| {
+                if render.is_some() {
+                    return Err(meta.error("`hex` and `base58` are mutually exclusive"));
+                }
+                if meta.path.is_ident("hex") {
+                    render = Some(Render::Hex);
+                } else if meta.path.is_ident("base58") {
+                    render = Some(Render::Base58);
+                } else {
+                    return Err(
+                        meta.error("unsupported event field argument, expected `hex` or `base58`")
+                    );
+                }
+                Ok(())
+            });
+            if let Err(err) = parsed {
+                result = Err(err);
+            }
+            false
+        });
+
+        result?;
+        renders.push(render);
+    }
+
+    Ok(renders)
+}
+
+/// `#[event(dynamic)]`, every field goes through `DynamicEventField`
+fn expand_dynamic_event(
+    s: &ItemStruct,
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+) -> proc_macro2::TokenStream {
+    let name = &s.ident;
+    let idents: Vec<_> = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+    let types: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+
+    let payload_len = quote! {
+        Self::DISCRIMINATOR.len() #( + <#types as DynamicEventField>::encoded_len(&self.#idents) )*
+    };
+
+    quote! {
+        #[derive(Discriminator)]
+        #s
+
+        impl EventBuilder for #name {
+            fn emit(&self) {
+                let mut __buf = DynamicEventBuf::with_capacity(#payload_len);
+                __buf.extend_from_slice(&Self::DISCRIMINATOR);
+                #( <#types as DynamicEventField>::write_dynamic(&self.#idents, &mut __buf); )*
+
+                log_dynamic_event(&__buf);
+            }
+
+            fn emit_cpi(&self, event_cpi: &EventCpi<'_>) -> Result<()> {
+                let mut __ix_data = dynamic_event_cpi_buf(#payload_len);
+                __ix_data.extend_from_slice(&Self::DISCRIMINATOR);
+                #( <#types as DynamicEventField>::write_dynamic(&self.#idents, &mut __ix_data); )*
+
+                event_cpi.invoke(&__ix_data)
+            }
+
+            fn decode_event(__data: &[u8]) -> Option<Self> {
+                let mut __rest = __data.strip_prefix(Self::DISCRIMINATOR)?;
+
+                let __event = Self {
+                    #( #idents: <#types as DynamicEventField>::read_dynamic(&mut __rest)?, )*
+                };
+
+                __rest.is_empty().then_some(__event)
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Events emitted as a CPI into the emitting program itself.
+//!
+//! Logs can be truncated by the runtime, instruction data can't: the event is sent as the data
+//! of an instruction the program invokes on itself, signed by its event authority PDA, and
+//! indexers read it back from the inner instructions of the transaction.
+//!
+//! The instruction is tagged with [`EVENT_IX_TAG`] (the same tag Anchor uses, so existing
+//! tooling understands it) and is accepted by `dispatch!` without running any handler.
+
+use hayabusa_common::{address_eq, AccountView, Address};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_syscalls::try_find_program_address;
+use hayabusa_utility::{error_msg, hint::unlikely};
+use solana_instruction_view::{
+    cpi::{invoke_signed, Seed, Signer},
+    InstructionAccount, InstructionView,
+};
+
+/// Tag of the self-CPI event instruction, followed by the event discriminator and fields.
+pub const EVENT_IX_TAG: &[u8] = &[0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d];
+
+/// Seed of the event authority PDA, the only signer accepted for the event instruction.
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Emitter of self-CPI events, usually built from a context with
+///
+/// ```ignore
+/// #[meta(seeds = [EVENT_AUTHORITY_SEED], bump)]
+/// pub event_authority: UncheckedAccount<'ix>,
+/// // the program itself, the runtime only allows CPIs into programs passed to the instruction
+/// pub program: UncheckedAccount<'ix>,
+/// ```
+///
+/// and `EventCpi::new(&crate::ID, ctx.event_authority.to_account_view(), ctx.bumps.event_authority)`.
+pub struct EventCpi<'ix> {
+    pub program_id: &'ix Address,
+    pub event_authority: &'ix AccountView,
+    pub bump: u8,
+}
+
+impl<'ix> EventCpi<'ix> {
+    #[inline(always)]
+    pub fn new(program_id: &'ix Address, event_authority: &'ix AccountView, bump: u8) -> Self {
+        Self {
+            program_id,
+            event_authority,
+            bump,
+        }
+    }
+
+    /// Invokes the program with `data`, which must start with [`EVENT_IX_TAG`]
+    #[inline]
+    pub fn invoke(&self, data: &[u8]) -> Result<()> {
+        let instruction_accounts = [InstructionAccount::readonly_signer(
+            self.event_authority.address(),
+        )];
+
+        let instruction = InstructionView {
+            program_id: self.program_id,
+            accounts: &instruction_accounts,
+            data,
+        };
+
+        let bump = [self.bump];
+        let seeds = [Seed::from(EVENT_AUTHORITY_SEED), Seed::from(&bump)];
+
+        invoke_signed(&instruction, &[self.event_authority], &[Signer::from(&seeds)])
+    }
+}
+
+/// Accepts the self-CPI event instruction, whose data is only meant for indexers.
+///
+/// The first account must be the event authority of `program_id` and a signer, i.e. the
+/// instruction was invoked by the program itself.
+#[inline(never)]
+pub fn try_process_event_cpi(program_id: &Address, account_views: &[AccountView]) -> Result<()> {
+    let Some(event_authority) = account_views.first() else {
+        error_msg!(
+            "try_process_event_cpi: missing event authority",
+            ProgramError::NotEnoughAccountKeys,
+        );
+    };
+
+    if unlikely(!event_authority.is_signer()) {
+        error_msg!(
+            "try_process_event_cpi: event authority is not a signer",
+            ErrorCode::AccountNotSigner,
+        );
+    }
+
+    let (expected, _) = try_find_program_address(&[EVENT_AUTHORITY_SEED], program_id)?;
+
+    if unlikely(!address_eq(event_authority.address(), &expected)) {
+        error_msg!(
+            "try_process_event_cpi: invalid event authority",
+            ErrorCode::InvalidAccount,
+        );
+    }
+
+    Ok(())
+}
+
+/// Event bytes (discriminator first) carried by a self-CPI event instruction, `None` for any
+/// other instruction data.
+#[inline]
+pub fn decode_cpi_event(ix_data: &[u8]) -> Option<&[u8]> {
+    ix_data.strip_prefix(EVENT_IX_TAG)
+}
@@ -0,0 +1,196 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Events with variable-length fields, `#[event(dynamic)]`.
+//!
+//! Fixed-size events are built on the stack, dynamic ones on the heap: every field is written
+//! with [`DynamicEventField`], which adds a `u32` length prefix to byte vectors and strings (the
+//! borsh encoding) and leaves the fixed-size fields as they are. The payload is logged and sent
+//! through `emit_cpi!` exactly like a fixed-size event, `decode_event` reads it back off-chain.
+
+use crate::{EventField, EVENT_IX_TAG};
+use alloc::{string::String, vec::Vec};
+use pinocchio_log::logger::log_message;
+
+/// Buffer the payload of a dynamic event is written to
+pub type DynamicEventBuf = Vec<u8>;
+
+/// Field of a `#[event(dynamic)]` struct.
+///
+/// Implemented by every [`EventField`], written as is, and by `Vec<u8>` and `String`, written
+/// with a `u32` length prefix. The methods are named apart from the [`EventField`] ones, both
+/// traits are in scope through the prelude.
+pub trait DynamicEventField: Sized {
+    /// Bytes appended by [`DynamicEventField::write_dynamic`]
+    fn encoded_len(&self) -> usize;
+
+    fn write_dynamic(&self, buf: &mut DynamicEventBuf);
+
+    /// Reads the field from the front of `buf` and advances past it, `None` if `buf` is too
+    /// short or the field is malformed
+    fn read_dynamic(buf: &mut &[u8]) -> Option<Self>;
+}
+
+impl<T: EventField> DynamicEventField for T {
+    #[inline(always)]
+    fn encoded_len(&self) -> usize {
+        T::SIZE
+    }
+
+    #[inline(always)]
+    fn write_dynamic(&self, buf: &mut DynamicEventBuf) {
+        let start = buf.len();
+        buf.resize(start + T::SIZE, 0);
+        EventField::write(self, &mut buf[start..]);
+    }
+
+    #[inline(always)]
+    fn read_dynamic(buf: &mut &[u8]) -> Option<Self> {
+        let (field, rest) = buf.split_at_checked(T::SIZE)?;
+        *buf = rest;
+        Some(EventField::read(field))
+    }
+}
+
+impl DynamicEventField for Vec<u8> {
+    #[inline(always)]
+    fn encoded_len(&self) -> usize {
+        4 + self.len()
+    }
+
+    #[inline(always)]
+    fn write_dynamic(&self, buf: &mut DynamicEventBuf) {
+        write_prefixed(self, buf);
+    }
+
+    #[inline(always)]
+    fn read_dynamic(buf: &mut &[u8]) -> Option<Self> {
+        read_prefixed(buf).map(<[u8]>::to_vec)
+    }
+}
+
+impl DynamicEventField for String {
+    #[inline(always)]
+    fn encoded_len(&self) -> usize {
+        4 + self.len()
+    }
+
+    #[inline(always)]
+    fn write_dynamic(&self, buf: &mut DynamicEventBuf) {
+        write_prefixed(self.as_bytes(), buf);
+    }
+
+    #[inline(always)]
+    fn read_dynamic(buf: &mut &[u8]) -> Option<Self> {
+        String::from_utf8(read_prefixed(buf)?.to_vec()).ok()
+    }
+}
+
+fn write_prefixed(bytes: &[u8], buf: &mut DynamicEventBuf) {
+    buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+fn read_prefixed<'a>(buf: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let (len, rest) = buf.split_first_chunk::<4>()?;
+    let (bytes, rest) = rest.split_at_checked(u32::from_le_bytes(*len) as usize)?;
+    *buf = rest;
+    Some(bytes)
+}
+
+/// Logs `EVENT: <hex>` for the payload of a dynamic event, discriminator first, the same line
+/// `emit!` writes for fixed-size events.
+///
+/// The length is only known at runtime: a line past `MAX_EVENT_LOG_LEN` is truncated, events
+/// that can grow that large are better sent with `emit_cpi!`.
+pub fn log_dynamic_event(payload: &[u8]) {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    const PREFIX: &[u8] = b"EVENT: ";
+
+    let mut line = Vec::with_capacity(PREFIX.len() + 2 * payload.len());
+    line.extend_from_slice(PREFIX);
+    for byte in payload {
+        line.push(HEX[(byte >> 4) as usize]);
+        line.push(HEX[(byte & 0x0f) as usize]);
+    }
+
+    log_message(&line);
+}
+
+/// Payload buffer of a dynamic event sent with `emit_cpi!`, starting with [`EVENT_IX_TAG`]
+pub fn dynamic_event_cpi_buf(payload_len: usize) -> DynamicEventBuf {
+    let mut buf = Vec::with_capacity(EVENT_IX_TAG.len() + payload_len);
+    buf.extend_from_slice(EVENT_IX_TAG);
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{indexer::EventRegistry, *};
+    use alloc::vec;
+    use hayabusa_discriminator::Discriminator;
+    use hayabusa_discriminator_derive::Discriminator;
+    use hayabusa_errors::Result;
+    use hayabusa_events_attribute_macro::event;
+
+    #[event(dynamic)]
+    #[discriminator([9])]
+    #[derive(Debug, PartialEq)]
+    struct Memo {
+        amount: u64,
+        payload: Vec<u8>,
+        memo: String,
+    }
+
+    fn payload() -> Vec<u8> {
+        let mut payload = vec![9];
+        payload.extend_from_slice(&5u64.to_le_bytes());
+        payload.extend_from_slice(&[2, 0, 0, 0, 0xab, 0xcd]);
+        payload.extend_from_slice(&[2, 0, 0, 0]);
+        payload.extend_from_slice(b"hi");
+        payload
+    }
+
+    fn memo() -> Memo {
+        Memo {
+            amount: 5,
+            payload: vec![0xab, 0xcd],
+            memo: String::from("hi"),
+        }
+    }
+
+    #[test]
+    fn test_decode_dynamic_event() {
+        assert_eq!(Memo::decode_event(&payload()), Some(memo()));
+
+        // truncated, trailing bytes, a length past the end, invalid UTF-8
+        let payload = payload();
+        assert_eq!(Memo::decode_event(&payload[..payload.len() - 1]), None);
+        assert_eq!(Memo::decode_event(&[&payload[..], &[0]].concat()), None);
+        let mut long = payload.clone();
+        long[9] = 3;
+        assert_eq!(Memo::decode_event(&long), None);
+        let mut invalid = payload.clone();
+        invalid[payload.len() - 1] = 0xff;
+        assert_eq!(Memo::decode_event(&invalid), None);
+    }
+
+    #[test]
+    fn test_registry_decodes_dynamic_log() {
+        let registry = EventRegistry::new().register(|memo: Memo| memo);
+
+        let hex: String = payload().iter().map(|byte| format!("{byte:02x}")).collect();
+        assert_eq!(
+            registry.decode_log(&format!("Program log: EVENT: {hex}")),
+            Some(memo())
+        );
+
+        let mut data = dynamic_event_cpi_buf(0);
+        data.extend_from_slice(&payload());
+        assert_eq!(Memo::decode_event(decode_cpi_event(&data).unwrap()), Some(memo()));
+
+        // off-chain the log is printed, it must not panic
+        memo().emit();
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Off-chain decoding of the events of a program, from its logs (`emit!`) or from the inner
+//! instructions of a transaction (`emit_cpi!`).
+//!
+//! ```ignore
+//! enum Event {
+//!     Deposited(Deposited),
+//!     Withdrawn(Withdrawn),
+//! }
+//!
+//! let registry = EventRegistry::new()
+//!     .register(Event::Deposited)
+//!     .register(Event::Withdrawn);
+//!
+//! let events = registry.decode_inner_instructions(&program_id, &account_keys, inner_instructions);
+//! ```
+
+use crate::{decode_cpi_event, EventBuilder};
+use hayabusa_common::Address;
+use std::{boxed::Box, vec::Vec};
+
+/// Inner instruction as recorded in the transaction status meta, the program is an index into
+/// the account keys of the transaction (loaded addresses included).
+#[derive(Clone, Copy, Debug)]
+pub struct InnerInstruction<'a> {
+    pub program_id_index: u8,
+    pub data: &'a [u8],
+}
+
+type Decoder<T> = Box<dyn Fn(&[u8]) -> Option<T>>;
+
+/// Event types of a program, decoded into a common `T`, usually an enum with a variant per event.
+pub struct EventRegistry<T> {
+    decoders: Vec<Decoder<T>>,
+}
+
+impl<T> Default for EventRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> EventRegistry<T> {
+    pub fn new() -> Self {
+        Self {
+            decoders: Vec::new(),
+        }
+    }
+
+    pub fn register<E>(mut self, map: impl Fn(E) -> T + 'static) -> Self
+    where
+        E: EventBuilder,
+    {
+        self.decoders
+            .push(Box::new(move |data| E::decode_event(data).map(&map)));
+        self
+    }
+
+    /// Event from its bytes, discriminator first
+    pub fn decode(&self, data: &[u8]) -> Option<T> {
+        self.decoders.iter().find_map(|decode| decode(data))
+    }
+
+    /// Event of a log line written by `emit!`, i.e. `Program log: EVENT: <hex>`
+    pub fn decode_log(&self, log: &str) -> Option<T> {
+        let hex = log.strip_prefix("Program log: ").unwrap_or(log);
+        let hex = hex.strip_prefix("EVENT: ")?;
+
+        self.decode(&decode_hex(hex)?)
+    }
+
+    /// Events sent by `program_id` to itself with `emit_cpi!`, in execution order.
+    ///
+    /// Instructions of other programs, or that aren't events, are skipped, as are events that
+    /// aren't registered.
+    pub fn decode_inner_instructions<'a>(
+        &self,
+        program_id: &Address,
+        account_keys: &[Address],
+        inner_instructions: impl IntoIterator<Item = InnerInstruction<'a>>,
+    ) -> Vec<T> {
+        inner_instructions
+            .into_iter()
+            .filter(|ix| account_keys.get(ix.program_id_index as usize) == Some(program_id))
+            .filter_map(|ix| self.decode(decode_cpi_event(ix.data)?))
+            .collect()
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use hayabusa_discriminator::Discriminator;
+    use hayabusa_discriminator_derive::Discriminator;
+    use hayabusa_errors::Result;
+    use hayabusa_events_attribute_macro::event;
+    use pinocchio_log::logger;
+
+    #[event]
+    struct Deposited {
+        amount: u64,
+    }
+
+    #[event]
+    struct Withdrawn {
+        amount: u64,
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Deposited(u64),
+        Withdrawn(u64),
+    }
+
+    fn registry() -> EventRegistry<Event> {
+        EventRegistry::new()
+            .register(|e: Deposited| Event::Deposited(e.amount))
+            .register(|e: Withdrawn| Event::Withdrawn(e.amount))
+    }
+
+    #[test]
+    fn test_decode_log() {
+        let registry = registry();
+        let log = Withdrawn { amount: 7 }.event_log();
+        let log = std::str::from_utf8(&log).unwrap();
+
+        assert_eq!(
+            registry.decode_log(&format!("Program log: {log}")),
+            Some(Event::Withdrawn(7))
+        );
+        assert_eq!(registry.decode_log(log), Some(Event::Withdrawn(7)));
+        assert_eq!(registry.decode_log("Program log: EVENT: 0"), None);
+        assert_eq!(registry.decode_log("Program log: hello"), None);
+    }
+
+    #[test]
+    fn test_decode_inner_instructions() {
+        let registry = registry();
+        let program_id = Address::new_from_array([1; 32]);
+        let other = Address::new_from_array([2; 32]);
+        let account_keys = [other, program_id];
+
+        let deposited = Deposited { amount: 3 }.event_cpi_data();
+        let withdrawn = Withdrawn { amount: 4 }.event_cpi_data();
+        let inner_instructions = [
+            InnerInstruction {
+                program_id_index: 1,
+                data: &deposited,
+            },
+            // same event data, sent by another program
+            InnerInstruction {
+                program_id_index: 0,
+                data: &deposited,
+            },
+            // not an event
+            InnerInstruction {
+                program_id_index: 1,
+                data: &deposited[EVENT_IX_TAG.len()..],
+            },
+            InnerInstruction {
+                program_id_index: 1,
+                data: &withdrawn,
+            },
+        ];
+
+        assert_eq!(
+            registry.decode_inner_instructions(&program_id, &account_keys, inner_instructions),
+            vec![Event::Deposited(3), Event::Withdrawn(4)]
+        );
+    }
+}
@@ -1,18 +1,52 @@
 // Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
 // SPDX-License-Identifier: Apache-2.0
 
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod cpi;
+#[cfg(feature = "alloc")]
+pub mod dynamic;
+#[cfg(feature = "std")]
+pub mod indexer;
+mod render;
+
+pub use cpi::*;
+#[cfg(feature = "alloc")]
+pub use dynamic::{dynamic_event_cpi_buf, log_dynamic_event, DynamicEventBuf, DynamicEventField};
+pub use render::{base58_max_len, hex_len, write_base58, write_hex};
 
 use hayabusa_common::Address;
+use hayabusa_errors::Result;
+use hayabusa_utility::{
+    Padding, PodBool, PodI128, PodI64, PodStr, PodU128, PodU16, PodU32, PodU64,
+};
+
+/// Bytes of log messages a transaction keeps, later messages are truncated. `#[event]` fails to
+/// compile when the log line of a single event is longer.
+pub const MAX_EVENT_LOG_LEN: usize = 10_000;
 
 pub trait EventField {
     const SIZE: usize;
 
     fn write(&self, buf: &mut [u8]);
+
+    /// Inverse of [`EventField::write`], `buf` is exactly [`EventField::SIZE`] bytes long
+    fn read(buf: &[u8]) -> Self;
 }
 
 pub trait EventBuilder {
     fn emit(&self);
+
+    /// Emits the event as the data of a self-CPI, see [`EventCpi`]
+    fn emit_cpi(&self, event_cpi: &EventCpi<'_>) -> Result<()>;
+
+    /// Event from its bytes (discriminator first), as logged by `emit` or sent by `emit_cpi`
+    fn decode_event(data: &[u8]) -> Option<Self>
+    where
+        Self: Sized;
 }
 
 /// Emit a hex-encoded event log
@@ -23,6 +57,14 @@ macro_rules! emit {
     };
 }
 
+/// Emit an event through a self-CPI, `emit_cpi!(event_cpi, Event { .. })?`
+#[macro_export]
+macro_rules! emit_cpi {
+    ($event_cpi:expr, $event:expr) => {
+        $event.emit_cpi(&$event_cpi)
+    };
+}
+
 #[macro_export]
 macro_rules! impl_event_field_int {
     ($t:ty) => {
@@ -33,6 +75,11 @@ macro_rules! impl_event_field_int {
             fn write(&self, buf: &mut [u8]) {
                 buf.copy_from_slice(&self.to_le_bytes());
             }
+
+            #[inline(always)]
+            fn read(buf: &[u8]) -> Self {
+                <$t>::from_le_bytes(buf.try_into().unwrap())
+            }
         }
     };
 }
@@ -43,6 +90,46 @@ impl_event_field_int!(u32);
 impl_event_field_int!(u64);
 impl_event_field_int!(u128);
 
+/// Pod integers are written like the integer they wrap
+macro_rules! impl_event_field_pod_int {
+    ($t:ty, $int:ty) => {
+        impl EventField for $t {
+            const SIZE: usize = core::mem::size_of::<$int>();
+
+            #[inline(always)]
+            fn write(&self, buf: &mut [u8]) {
+                buf.copy_from_slice(bytemuck::bytes_of(self));
+            }
+
+            #[inline(always)]
+            fn read(buf: &[u8]) -> Self {
+                <$t>::new(<$int>::from_le_bytes(buf.try_into().unwrap()))
+            }
+        }
+    };
+}
+
+impl_event_field_pod_int!(PodU16, u16);
+impl_event_field_pod_int!(PodU32, u32);
+impl_event_field_pod_int!(PodU64, u64);
+impl_event_field_pod_int!(PodU128, u128);
+impl_event_field_pod_int!(PodI64, i64);
+impl_event_field_pod_int!(PodI128, i128);
+
+impl EventField for PodBool {
+    const SIZE: usize = 1;
+
+    #[inline(always)]
+    fn write(&self, buf: &mut [u8]) {
+        buf[0] = self.get() as u8;
+    }
+
+    #[inline(always)]
+    fn read(buf: &[u8]) -> Self {
+        PodBool::new(buf[0] != 0)
+    }
+}
+
 impl EventField for Address {
     const SIZE: usize = 32;
 
@@ -50,6 +137,11 @@ impl EventField for Address {
     fn write(&self, buf: &mut [u8]) {
         buf.copy_from_slice(self.as_ref());
     }
+
+    #[inline(always)]
+    fn read(buf: &[u8]) -> Self {
+        Address::new_from_array(buf.try_into().unwrap())
+    }
 }
 
 impl<const N: usize> EventField for [u8; N] {
@@ -59,4 +151,105 @@ impl<const N: usize> EventField for [u8; N] {
     fn write(&self, buf: &mut [u8]) {
         buf.copy_from_slice(self);
     }
-}
\ No newline at end of file
+
+    #[inline(always)]
+    fn read(buf: &[u8]) -> Self {
+        buf.try_into().unwrap()
+    }
+}
+/// Reserved space is not part of the event
+impl<const N: usize> EventField for Padding<N> {
+    const SIZE: usize = 0;
+
+    #[inline(always)]
+    fn write(&self, _buf: &mut [u8]) {}
+
+    #[inline(always)]
+    fn read(_buf: &[u8]) -> Self {
+        Padding::ZERO
+    }
+}
+
+/// Written as is, length prefix and zeroed tail included
+impl<const N: usize> EventField for PodStr<N> {
+    const SIZE: usize = 4 + N;
+
+    #[inline(always)]
+    fn write(&self, buf: &mut [u8]) {
+        buf.copy_from_slice(bytemuck::bytes_of(self));
+    }
+
+    #[inline(always)]
+    fn read(buf: &[u8]) -> Self {
+        bytemuck::pod_read_unaligned(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hayabusa_common::{
+        account_view::{RuntimeAccount, NOT_BORROWED},
+        AccountView,
+    };
+    use hayabusa_discriminator::Discriminator;
+    use hayabusa_discriminator_derive::Discriminator;
+    use hayabusa_events_attribute_macro::event;
+    use pinocchio_log::logger;
+
+    #[event]
+    #[discriminator([7])]
+    struct Tagged {
+        amount: u64,
+        flag: PodBool,
+    }
+
+    #[event]
+    #[discriminator("event:Hashed", len = 4)]
+    struct Hashed {
+        amount: u32,
+    }
+
+    #[test]
+    fn test_short_discriminators() {
+        let event = Tagged {
+            amount: 3,
+            flag: PodBool::new(true),
+        };
+        assert_eq!(Tagged::EVENT_LEN, 1 + 8 + 1);
+        assert_eq!(event.to_event_bytes(), [7, 3, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(&*event.event_log(), b"EVENT: 07030000000000000001");
+        assert_eq!(
+            event.event_cpi_data()[EVENT_IX_TAG.len()..],
+            event.to_event_bytes()
+        );
+
+        let decoded = Tagged::decode_event(&event.to_event_bytes()).unwrap();
+        assert_eq!(decoded.amount, 3);
+        assert!(decoded.flag.get());
+        assert!(Tagged::decode_event(&[8, 3, 0, 0, 0, 0, 0, 0, 0, 1]).is_none());
+
+        let event = Hashed { amount: 5 };
+        assert_eq!(Hashed::EVENT_LEN, 4 + 4);
+        assert_eq!(event.to_event_bytes()[..4], *Hashed::DISCRIMINATOR);
+        assert_eq!(Hashed::decode_event(&event.to_event_bytes()).unwrap().amount, 5);
+
+        // off-chain the log and the CPI are no-ops, they must not panic on the offsets
+        event.emit();
+        let mut account = RuntimeAccount {
+            borrow_state: NOT_BORROWED,
+            is_signer: 1,
+            is_writable: 0,
+            executable: 0,
+            resize_delta: 0,
+            address: Address::new_from_array([1; 32]),
+            owner: Address::new_from_array([0; 32]),
+            lamports: 0,
+            data_len: 0,
+        };
+        let event_authority = unsafe { AccountView::new_unchecked(&mut account) };
+        let program_id = Address::new_from_array([2; 32]);
+        let event_cpi = EventCpi::new(&program_id, &event_authority, 255);
+        event.emit_cpi(&event_cpi).unwrap();
+    }
+}
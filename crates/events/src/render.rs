@@ -0,0 +1,72 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Human-readable rendering of `#[event_field(hex)]` and `#[event_field(base58)]` fields.
+//!
+//! `emit` logs these fields on a second line, `Name: field=<rendered> ..`, after the `EVENT: <hex>`
+//! line, so addresses and hashes can be read in explorer logs. `emit_cpi` and `decode_event` are
+//! unchanged.
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+const BASE58: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Characters [`write_hex`] writes for `len` bytes
+#[inline(always)]
+pub const fn hex_len(len: usize) -> usize {
+    2 * len
+}
+
+/// Upper bound on the characters [`write_base58`] writes for `len` bytes: 45 for an address,
+/// which never renders in more than 44
+#[inline(always)]
+pub const fn base58_max_len(len: usize) -> usize {
+    // log(256) / log(58) < 1.38
+    len * 138 / 100 + 1
+}
+
+/// Writes `bytes` as lowercase hex at the start of `out` and returns the number of characters
+/// written, `out` must hold [`hex_len`] of them.
+#[inline(always)]
+pub fn write_hex(bytes: &[u8], out: &mut [u8]) -> usize {
+    for (i, byte) in bytes.iter().enumerate() {
+        out[2 * i] = HEX[(byte >> 4) as usize];
+        out[2 * i + 1] = HEX[(byte & 0x0f) as usize];
+    }
+
+    hex_len(bytes.len())
+}
+
+/// Writes `bytes` in base58 at the start of `out` and returns the number of characters written,
+/// `out` must hold [`base58_max_len`] of them.
+///
+/// Quadratic in the length of `bytes`, a few thousand CU for an address.
+pub fn write_base58(bytes: &[u8], out: &mut [u8]) -> usize {
+    // base58 digits, least significant first
+    let mut len = 0;
+    for &byte in bytes {
+        let mut carry = byte as u32;
+        for digit in &mut out[..len] {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            out[len] = (carry % 58) as u8;
+            len += 1;
+            carry /= 58;
+        }
+    }
+
+    // every leading zero byte is a `1`
+    for _ in bytes.iter().take_while(|byte| **byte == 0) {
+        out[len] = 0;
+        len += 1;
+    }
+
+    out[..len].reverse();
+    for digit in &mut out[..len] {
+        *digit = BASE58[*digit as usize];
+    }
+
+    len
+}
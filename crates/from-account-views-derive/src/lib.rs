@@ -2,12 +2,61 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote, quote_spanned};
 use syn::{
-    parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields, Type,
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Data, DeriveInput, Expr, Fields, GenericArgument, Path, PathArguments, Token, Type,
 };
 
-#[proc_macro_derive(FromAccountViews, attributes(meta))]
+/// Parses one account view per field, in declaration order.
+///
+/// `#[meta(close = field)]` closes the account into `field` once the handler succeeded, only
+/// through `Mut` for zero-copy accounts:
+///
+/// ```
+/// use hayabusa::prelude::*;
+///
+/// declare_id!("HPoDm7Kf63B6TpFKV7S8YSd7sGde6sVdztiDBEVkfuxz");
+///
+/// #[account]
+/// #[derive(OwnerProgram)]
+/// pub struct Vault {
+///     pub amount: PodU64,
+/// }
+///
+/// #[derive(FromAccountViews)]
+/// struct Close<'ix> {
+///     pub receiver: Mut<UncheckedAccount<'ix>>,
+///     #[meta(close = receiver)]
+///     pub vault: Mut<ZcAccount<'ix, Vault>>,
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ```compile_fail,E0277
+/// use hayabusa::prelude::*;
+///
+/// declare_id!("HPoDm7Kf63B6TpFKV7S8YSd7sGde6sVdztiDBEVkfuxz");
+///
+/// #[account]
+/// #[derive(OwnerProgram)]
+/// pub struct Vault {
+///     pub amount: PodU64,
+/// }
+///
+/// #[derive(FromAccountViews)]
+/// struct Close<'ix> {
+///     pub receiver: Mut<UncheckedAccount<'ix>>,
+///     #[meta(close = receiver)]
+///     pub vault: ZcAccount<'ix, Vault>,
+/// }
+/// # fn main() {}
+/// ```
+#[proc_macro_derive(FromAccountViews, attributes(accounts, meta))]
 pub fn derive_from_account_views(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -59,73 +108,1128 @@ pub fn derive_from_account_views(input: TokenStream) -> TokenStream {
         }
     };
 
+    let accounts_args = match parse_accounts_args(&input.attrs) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let vis = &input.vis;
+    let bumps_name = format_ident!("{}Bumps", struct_name);
+
     let mut bindings = Vec::new();
+    let mut init_bindings = Vec::new();
     let mut field_idents = Vec::new();
+    let mut bump_idents = Vec::new();
+    let mut constraints = Vec::new();
+    let mut closes = Vec::new();
+    let mut reallocs = Vec::new();
+    let mut no_dups = Vec::new();
+    let mut writable = Vec::new();
+    let mut specs = Vec::new();
+    let mut cpi_docs = Vec::new();
+    let mut cpi_accounts = Vec::new();
+    let mut client_metas = Vec::new();
 
-    for field in fields {
+    let has_system_program = fields
+        .iter()
+        .any(|f| f.ident.as_ref().is_some_and(|i| i == "system_program"));
+
+    for (index, field) in fields.iter().enumerate() {
         let ident = field.ident.as_ref().unwrap();
         let ty = &field.ty;
 
         field_idents.push(ident);
 
-        let meta_expr = match parse_meta(&field.attrs, ty, info_lt) {
+        let spec_name = ident.unraw().to_string();
+        let spec_writable = is_mut(ty);
+        if spec_writable {
+            writable.push(spec_name.clone());
+        }
+
+        let spec_docs = docs(&field.attrs);
+        let spec_signer = is_signer(ty);
+        let spec_constraints = meta_sources(&field.attrs);
+        specs.push(quote! {
+            AccountSpec {
+                name: #spec_name,
+                docs: #spec_docs,
+                writable: #spec_writable,
+                signer: #spec_signer,
+                constraints: &[#(#spec_constraints),*],
+            }
+        });
+
+        let meta = match parse_meta(&field.attrs, ty, info_lt) {
             Ok(m) => m,
             Err(e) => return e.to_compile_error().into(),
         };
+        let meta_expr = &meta.meta_expr;
+
+        // Accounts created by `init` without seeds sign their `create_account`
+        let cpi_signer = spec_signer
+            || (meta.init.is_some() && meta.seeds.is_none() && meta.associated_token.is_none());
+        cpi_docs.push(
+            field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("doc"))
+                .collect::<Vec<_>>(),
+        );
+        cpi_accounts.push(quote! {
+            instruction::InstructionAccount::new(
+                self.#ident.address(),
+                #spec_writable,
+                #cpi_signer,
+            )
+        });
+        client_metas.push(quote! {
+            client::AccountMeta {
+                pubkey: self.#ident,
+                is_writable: #spec_writable,
+                is_signer: #cpi_signer,
+            }
+        });
+
+        if meta.no_dup {
+            no_dups.push(index);
+        }
+
+        if let Some(receiver) = &meta.close {
+            closes.push((index, ident, ty, receiver.clone()));
+        }
+
+        if let Some(Realloc { new_len, payer }) = &meta.realloc {
+            if !has_system_program {
+                return syn::Error::new(
+                    ident.span(),
+                    "realloc requires a `system_program` field",
+                )
+                .to_compile_error()
+                .into();
+            }
+
+            reallocs.push(quote! {
+                try_realloc_account(
+                    #ident.to_account_view(),
+                    #new_len,
+                    (#payer).to_account_view(),
+                    system_program.to_account_view(),
+                )?;
+            });
+        }
+
+        for (name, expected) in [("mint", &meta.token_mint), ("owner", &meta.token_authority)] {
+            let Some(expected) = expected else {
+                continue;
+            };
+
+            let getter = format_ident!("{}", name);
+            let expected = address_of(expected, fields);
+            let msg = syn::LitStr::new(
+                &format!("{struct_name}::{ident}: token {name} mismatch"),
+                ident.span(),
+            );
+
+            constraints.push(quote! {
+                if unlikely(!address::address_eq(
+                    #ident.try_deserialize_raw()?.#getter(),
+                    #expected,
+                )) {
+                    error_msg!(#msg, ErrorCode::InvalidAccount);
+                }
+            });
+        }
+
+        // `init` fields are checked by the associated token program when it creates them
+        if let (Some(ata), None) = (&meta.associated_token, &meta.init) {
+            let wallet = address_of(&ata.authority, fields);
+            let mint = address_of(&ata.mint, fields);
+            let token_program = token_program_of(ata, fields);
+
+            constraints.push(quote! {
+                associated_token::check_associated_token_account(
+                    #ident.to_account_view(),
+                    #wallet,
+                    #mint,
+                    #token_program,
+                    false,
+                )?;
+            });
+        }
+
+        if meta.not_in_flight {
+            constraints.push(quote! {
+                #ident.try_check_not_in_flight()?;
+            });
+        }
+
+        for (constraint, error) in &meta.constraints {
+            let msg = syn::LitStr::new(
+                &format!("{struct_name}::{ident}: constraint violated"),
+                ident.span(),
+            );
+            let error = match error {
+                Some(error) => quote! { #error },
+                None => quote! { ErrorCode::ConstraintViolated },
+            };
+
+            constraints.push(quote! {
+                if unlikely(!(#constraint)) {
+                    error_msg!(#msg, #error);
+                }
+            });
+        }
+
+        // Checks on `account_view`, before the account is created for `init` fields
+        let mut checks = Vec::new();
+
+        if let Some(address) = &meta.address {
+            let msg = syn::LitStr::new(
+                &format!("{struct_name}::{ident}: address mismatch"),
+                ident.span(),
+            );
+
+            checks.push(quote! {
+                if unlikely(!address::address_eq(
+                    account_view.address(),
+                    core::borrow::Borrow::<Address>::borrow(&(#address)),
+                )) {
+                    error_msg!(#msg, ErrorCode::InvalidAccount);
+                }
+            });
+        }
+
+        if let Some(seeds) = &meta.seeds {
+            // Seeds may reference previously parsed fields by name
+            let check = match &meta.bump {
+                Some(bump) => quote! {
+                    let bump: u8 = #bump;
+                    check_seeds_against_addr(
+                        &[#((#seeds).as_ref(),)* &[bump]],
+                        account_view.address(),
+                        &crate::ID,
+                    )?;
+                },
+                None => quote! {
+                    let (_, bump) = check_seeds_against_addr_no_bump(
+                        &[#((#seeds).as_ref()),*],
+                        account_view.address(),
+                        &crate::ID,
+                    )?;
+                },
+            };
+
+            checks.push(quote! {
+                let bump = {
+                    #check
+                    bump
+                };
+                bumps.#ident = bump;
+            });
+
+            bump_idents.push(ident);
+        }
+
+        // `Mut` fields must wrap a `WritableAllowed` type, reported on the field rather than
+        // deep in the `FromAccountView` bounds
+        let writable_check = match mut_inner(ty) {
+            Some(inner) => quote_spanned! {inner.span()=>
+                assert_writable_allowed::<#inner>();
+            },
+            None => {
+                let field = format!("{struct_name}::{ident}");
+                quote! { audit_read_only(account_view, #field)?; }
+            }
+        };
+
+        let Some(init) = &meta.init else {
+            bindings.push(quote! {
+                let account_view = account_views.next()?;
+                #writable_check
+                let #ident =
+                    <#ty as FromAccountView<#info_lt>>::try_from_account_view(
+                        account_view,
+                        #meta_expr,
+                    )?;
+                #(#checks)*
+            });
+
+            continue;
+        };
+
+        if !has_system_program {
+            return syn::Error::new(
+                ident.span(),
+                "init requires a `system_program` field",
+            )
+            .to_compile_error()
+            .into();
+        }
+
+        // `init` fields are created once every other field is parsed, so payer and
+        // system program can be declared in any order
+        let view_ident = format_ident!("{}_account_view", ident);
 
         bindings.push(quote! {
-            let #ident =
+            let #view_ident = account_views.next()?;
+            let account_view = #view_ident;
+            #writable_check
+        });
+
+        let payer = &init.payer;
+
+        if let Some(ata) = &meta.associated_token {
+            for required in ["associated_token_program", "token_program"] {
+                if !fields.iter().any(|f| f.ident.as_ref().is_some_and(|i| i == required)) {
+                    return syn::Error::new(
+                        ident.span(),
+                        format!("init with associated_token requires a `{required}` field"),
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+            }
+
+            let (Some(wallet), Some(mint)) = (
+                field_ident(&ata.authority, fields),
+                field_ident(&ata.mint, fields),
+            ) else {
+                return syn::Error::new(
+                    ident.span(),
+                    "init with associated_token requires mint and authority to be fields",
+                )
+                .to_compile_error()
+                .into();
+            };
+
+            init_bindings.push(quote! {
+                let #ident = {
+                    let account_view = #view_ident;
+                    #(#checks)*
+
+                    associated_token::instructions::create(CpiCtx::try_new_without_signer(
+                        associated_token_program.to_account_view(),
+                        associated_token::instructions::Create {
+                            payer: (#payer).to_account_view(),
+                            account: account_view,
+                            wallet: #wallet.to_account_view(),
+                            mint: #mint.to_account_view(),
+                            system_program: system_program.to_account_view(),
+                            token_program: token_program.to_account_view(),
+                        },
+                    )?)?;
+
+                    <#ty as FromAccountView<#info_lt>>::try_from_account_view(
+                        account_view,
+                        #meta_expr,
+                    )?
+                };
+            });
+
+            continue;
+        }
+
+        let space = match &init.space {
+            Some(space) => quote! { #space },
+            None => quote! { <#ty as InitAccount>::DEFAULT_SPACE },
+        };
+        let owner = match &init.owner {
+            Some(owner) => quote! { #owner },
+            None => quote! { &crate::ID },
+        };
+
+        // PDAs sign the create_account CPI with their seeds and bump
+        let create = match &meta.seeds {
+            Some(seeds) => quote! {
+                let bump_seed = [bump];
+                let signer_seeds = [
+                    #({
+                        let seed: &[u8] = (#seeds).as_ref();
+                        instruction::Seed::from(seed)
+                    },)*
+                    instruction::Seed::from(&bump_seed),
+                ];
+                let signers = [instruction::PdaSigner::from(&signer_seeds)];
+
+                <#ty as InitAccount>::try_init_account(
+                    account_view,
+                    init_accounts,
+                    #space,
+                    Some(&signers),
+                )?;
+            },
+            None => quote! {
+                <#ty as InitAccount>::try_init_account(
+                    account_view,
+                    init_accounts,
+                    #space,
+                    None,
+                )?;
+            },
+        };
+
+        init_bindings.push(quote! {
+            let #ident = {
+                let account_view = #view_ident;
+                #(#checks)*
+
+                let init_accounts = InitAccounts::new(
+                    #owner,
+                    (#payer).to_account_view(),
+                    system_program.to_account_view(),
+                );
+                #create
+
                 <#ty as FromAccountView<#info_lt>>::try_from_account_view(
-                    account_views.next()?,
+                    account_view,
                     #meta_expr,
-                )?;
+                )?
+            };
+        });
+    }
+
+    // Every field is parsed from exactly one account view, in declaration order
+    let mut close_calls = Vec::new();
+
+    for (index, ident, ty, receiver) in closes {
+        let Some(receiver_index) = fields
+            .iter()
+            .position(|f| f.ident.as_ref() == Some(&receiver))
+        else {
+            return syn::Error::new(
+                receiver.span(),
+                format!("close: no field named `{receiver}`"),
+            )
+            .to_compile_error()
+            .into();
+        };
+
+        if receiver_index == index {
+            return syn::Error::new(
+                receiver.span(),
+                format!("close: `{ident}` can't be closed into itself"),
+            )
+            .to_compile_error()
+            .into();
+        }
+
+        close_calls.push(quote! {
+            <#ty as CloseAccount>::try_close_account(
+                &account_views[#index],
+                &account_views[#receiver_index],
+            )?;
         });
     }
 
+    // Indexes are relative to the first account of the struct, `exit` gets the views it was
+    // parsed from whatever their position in the instruction accounts
+    let exit = if close_calls.is_empty() {
+        quote! {}
+    } else {
+        let account_count = fields.len();
+        let msg = format!(
+            "{struct_name}::exit: expected the {account_count} account views it was parsed from"
+        );
+
+        quote! {
+            #[inline(always)]
+            fn exit(account_views: &#info_lt [AccountView]) -> Result<()> {
+                if unlikely(account_views.len() < #account_count) {
+                    error_msg!(#msg, ErrorCode::InvalidAccount);
+                }
+
+                #(#close_calls)*
+
+                Ok(())
+            }
+        }
+    };
+
+    // Every field is parsed from exactly one account view, so the fields are the views consumed
+    // since `no_dup_start`
+    let (no_dup_start, no_dup_check) = if no_dups.is_empty() {
+        (quote! {}, quote! {})
+    } else {
+        (
+            quote! { let __no_dup_start = account_views.index(); },
+            quote! {
+                try_check_no_dup(
+                    &account_views.consumed()[__no_dup_start..],
+                    [#(#no_dups),*],
+                )?;
+            },
+        )
+    };
+
+    let bumps_ident = if bump_idents.is_empty() {
+        format_ident!("_bumps")
+    } else {
+        format_ident!("bumps")
+    };
+
+    let cpi_name = format_ident!("{}Cpi", struct_name);
+    let cpi_doc = format!(
+        "Accounts of [`{struct_name}`] for invoking the instruction from another program, see \
+         `#[instruction(cpi)]`."
+    );
+    let account_count = field_idents.len();
+
+    // Program side of `#[instruction(cpi)]`, only with `#[accounts(cpi)]` as it needs the
+    // `ID` of the program crate
+    let cpi = if accounts_args.cpi {
+        quote! {
+            #[doc = #cpi_doc]
+            #[allow(dead_code)]
+            #vis struct #cpi_name<#info_lt> {
+                #(
+                    #(#cpi_docs)*
+                    pub #field_idents: &#info_lt AccountView,
+                )*
+            }
+
+            impl CheckProgramId for #cpi_name<'_> {
+                const ID: Address = crate::ID;
+            }
+
+            #[allow(dead_code)]
+            impl<#info_lt> #cpi_name<#info_lt> {
+                /// Instruction accounts in declaration order, writable and signer as the callee
+                /// expects them.
+                #[inline(always)]
+                pub fn instruction_accounts(
+                    &self,
+                ) -> [instruction::InstructionAccount<#info_lt>; #account_count] {
+                    [#(#cpi_accounts),*]
+                }
+
+                #[inline(always)]
+                pub fn account_views(&self) -> [&#info_lt AccountView; #account_count] {
+                    [#(self.#field_idents),*]
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Off-chain counterpart of the CPI accounts, with `#[accounts(client)]` and hayabusa's
+    // `client` feature
+    let client = if accounts_args.client && cfg!(feature = "client") {
+        let client_name = format_ident!("{}Client", struct_name);
+        let client_doc = format!(
+            "Accounts of [`{struct_name}`] for building the instruction off-chain, see \
+             `#[instruction(client)]`."
+        );
+
+        quote! {
+            #[doc = #client_doc]
+            #[derive(Clone, Copy, Debug)]
+            #vis struct #client_name {
+                #(
+                    #(#cpi_docs)*
+                    pub #field_idents: client::Pubkey,
+                )*
+            }
+
+            impl client::ToAccountMetas for #client_name {
+                fn to_account_metas(&self) -> client::Vec<client::AccountMeta> {
+                    client::Vec::from([#(#client_metas),*])
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     let expanded = quote! {
+        #[derive(Clone, Copy, Default)]
+        #vis struct #bumps_name {
+            #(pub #bump_idents: u8,)*
+        }
+
+        #cpi
+
+        #client
+
         impl #impl_generics FromAccountViews<#info_lt>
             for #struct_name #ty_generics #where_clause
         {
+            type Bumps = #bumps_name;
+
+            const WRITABLE_ACCOUNTS: &'static [&'static str] = &[#(#writable),*];
+
+            const ACCOUNTS: &'static [AccountSpec] = &[#(#specs),*];
+
             #[inline(always)]
             fn try_from_account_views(
-                account_views: &mut AccountIter<#info_lt>
+                account_views: &mut AccountIter<#info_lt>,
+                #bumps_ident: &mut Self::Bumps,
             ) -> Result<Self> {
+                #no_dup_start
                 #(#bindings)*
 
+                // Before any account is created
+                #no_dup_check
+
+                #(#init_bindings)*
+
+                // Reallocs run once payer and system program are parsed, before constraints
+                // so these see the new length
+                #(#reallocs)*
+
+                // Constraints run after every field is parsed, so they can reference any field
+                #(#constraints)*
+
                 Ok(Self {
                     #(#field_idents,)*
                 })
             }
+
+            #exit
         }
     };
 
     expanded.into()
 }
 
+struct FieldMeta {
+    /// Expression passed as `Meta` to `FromAccountView::try_from_account_view`
+    meta_expr: proc_macro2::TokenStream,
+    /// `address = expr`, the account must be exactly this address
+    address: Option<Expr>,
+    /// `seeds = [...]`, the address must be the PDA derived from these seeds
+    seeds: Option<Vec<Expr>>,
+    /// `bump = expr`, without it the canonical bump is searched for
+    bump: Option<Expr>,
+    /// `constraint = expr [@ error]`, checked once every field is parsed
+    constraints: Vec<(Expr, Option<Expr>)>,
+    /// `init, payer = expr [, space = expr] [, owner = expr]`
+    init: Option<Init>,
+    /// `close = field`, the account is closed into `field` once the handler succeeded, which must
+    /// be the recorded rent payer of accounts tracking one
+    close: Option<syn::Ident>,
+    /// `realloc = expr, realloc::payer = expr [, realloc::zero = true]`
+    realloc: Option<Realloc>,
+    /// `token::mint = field_or_address`, mint of the SPL token account
+    token_mint: Option<Expr>,
+    /// `token::authority = field_or_address`, owner of the SPL token account
+    token_authority: Option<Expr>,
+    /// `associated_token::mint = .., associated_token::authority = .. [, associated_token::token_program = ..]`
+    associated_token: Option<AssociatedToken>,
+    /// `no_dup`, no other field may have the same address
+    no_dup: bool,
+    /// `not_in_flight`, the account must not be inside `ZcAccount::try_guarded`
+    not_in_flight: bool,
+}
+
+struct AssociatedToken {
+    mint: Expr,
+    authority: Expr,
+    token_program: Option<Expr>,
+}
+
+struct Realloc {
+    new_len: Expr,
+    payer: Expr,
+}
+
+struct Init {
+    payer: Expr,
+    space: Option<Expr>,
+    owner: Option<Expr>,
+}
+
+/// A single `#[meta(...)]` argument: `name`, `name = value` or `name = value @ error`
+struct MetaArg {
+    path: Path,
+    value: Option<Expr>,
+    error: Option<Expr>,
+}
+
+impl Parse for MetaArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = Path::parse_mod_style(input)?;
+
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse::<Expr>()?)
+        } else {
+            None
+        };
+
+        let error = if value.is_some() && input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            Some(input.parse::<Expr>()?)
+        } else {
+            None
+        };
+
+        Ok(Self { path, value, error })
+    }
+}
+
 fn parse_meta(
     attrs: &[syn::Attribute],
     ty: &Type,
     info_lt: &syn::Lifetime,
-) -> Result<proc_macro2::TokenStream, syn::Error> {
-    for attr in attrs {
-        if attr.path().is_ident("meta") {
-            let args = attr.parse_args_with(
-                syn::punctuated::Punctuated::<
-                    syn::MetaNameValue,
-                    syn::Token![,],
-                >::parse_terminated,
-            )?;
+) -> Result<FieldMeta, syn::Error> {
+    let mut field_meta = FieldMeta {
+        meta_expr: quote! { NoMeta },
+        address: None,
+        seeds: None,
+        bump: None,
+        constraints: Vec::new(),
+        init: None,
+        close: None,
+        realloc: None,
+        token_mint: None,
+        token_authority: None,
+        associated_token: None,
+        no_dup: false,
+        not_in_flight: false,
+    };
+
+    // every `#[meta(...)]` of the field adds to the same checks, as if written in one attribute
+    let meta_attrs: Vec<_> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("meta"))
+        .collect();
+
+    let Some(attr) = meta_attrs.first() else {
+        return Ok(field_meta);
+    };
+
+    let mut args = Vec::new();
+    for meta_attr in &meta_attrs {
+        args.extend(meta_attr.parse_args_with(
+            Punctuated::<MetaArg, Token![,]>::parse_terminated,
+        )?);
+    }
+
+    let mut values = Vec::new();
+    let mut has_bump = false;
+    let mut is_init = false;
+    let mut payer = None;
+    let mut space = None;
+    let mut owner = None;
+    let mut realloc = None;
+    let mut realloc_payer = None;
+    let mut has_realloc_zero = false;
+    let mut ata_mint = None;
+    let mut ata_authority = None;
+    let mut ata_token_program = None;
+
+    for arg in args {
+        let MetaArg { path, value, error } = arg;
 
+        if let Some(error) = &error {
+            if !path.is_ident("constraint") {
+                return Err(syn::Error::new(
+                    error.span(),
+                    "custom errors are only supported on constraint",
+                ));
+            }
+        }
+
+        match value {
+            Some(value) if path.is_ident("address") => {
+                field_meta.address = Some(value);
+            }
+            Some(value) if path.is_ident("seeds") => {
+                let Expr::Array(array) = value else {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "seeds must be an array, e.g. seeds = [b\"vault\", user.address()]",
+                    ));
+                };
+                field_meta.seeds = Some(array.elems.into_iter().collect());
+            }
+            Some(value) if path.is_ident("bump") => {
+                has_bump = true;
+                field_meta.bump = Some(value);
+            }
+            None if path.is_ident("bump") => {
+                has_bump = true;
+            }
+            Some(value) if path.is_ident("constraint") => {
+                field_meta.constraints.push((value, error));
+            }
+            Some(value) if path.is_ident("close") => {
+                let Expr::Path(receiver) = &value else {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "close must name another field, e.g. close = authority",
+                    ));
+                };
+                let Some(receiver) = receiver.path.get_ident() else {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "close must name another field, e.g. close = authority",
+                    ));
+                };
+                field_meta.close = Some(receiver.clone());
+            }
+            Some(value) if path.is_ident("realloc") => realloc = Some(value),
+            Some(value) if is_path(&path, "realloc", "payer") => {
+                realloc_payer = Some(value);
+            }
+            // `AccountView::resize` always zero extends, the flag is accepted so
+            // `realloc::zero = true` can be spelled out
+            Some(value) if is_path(&path, "realloc", "zero") => {
+                if !matches!(
+                    &value,
+                    Expr::Lit(syn::ExprLit { lit: syn::Lit::Bool(b), .. }) if b.value
+                ) {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "realloc always zeroes the new bytes, only realloc::zero = true is supported",
+                    ));
+                }
+                has_realloc_zero = true;
+            }
+            Some(value) if is_path(&path, "token", "mint") => {
+                field_meta.token_mint = Some(value);
+            }
+            Some(value) if is_path(&path, "token", "authority") => {
+                field_meta.token_authority = Some(value);
+            }
+            Some(value) if is_path(&path, "associated_token", "mint") => {
+                ata_mint = Some(value);
+            }
+            Some(value) if is_path(&path, "associated_token", "authority") => {
+                ata_authority = Some(value);
+            }
+            Some(value) if is_path(&path, "associated_token", "token_program") => {
+                ata_token_program = Some(value);
+            }
+            None if path.is_ident("init") => is_init = true,
+            None if path.is_ident("no_dup") => field_meta.no_dup = true,
+            None if path.is_ident("not_in_flight") => field_meta.not_in_flight = true,
+            Some(value) if path.is_ident("payer") => payer = Some(value),
+            Some(value) if path.is_ident("space") => space = Some(value),
+            Some(value) if path.is_ident("owner") => owner = Some(value),
             // Named args only; values are passed in declaration order
-            let values = args.iter().map(|kv| &kv.value);
+            Some(value) => values.push(value),
+            None => {
+                return Err(syn::Error::new(
+                    path.span(),
+                    "expected `name = value`",
+                ));
+            }
+        }
+    }
 
-            return Ok(quote! {
-                <#ty as FromAccountView<#info_lt>>::Meta::new(
-                    #(#values),*
-                )
+    if field_meta.seeds.is_some() != has_bump {
+        return Err(syn::Error::new(
+            attr.span(),
+            "seeds and bump must be used together",
+        ));
+    }
+
+    match (ata_mint, ata_authority) {
+        (Some(mint), Some(authority)) => {
+            if is_init && (space.is_some() || owner.is_some()) {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "space and owner can't be used with associated_token",
+                ));
+            }
+
+            field_meta.associated_token = Some(AssociatedToken {
+                mint,
+                authority,
+                token_program: ata_token_program,
             });
         }
+        (None, None) if ata_token_program.is_none() => {}
+        _ => {
+            return Err(syn::Error::new(
+                attr.span(),
+                "associated_token requires both associated_token::mint and associated_token::authority",
+            ));
+        }
+    }
+
+    if is_init && field_meta.close.is_some() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "init and close can't be used on the same field",
+        ));
+    }
+
+    if is_init {
+        let Some(payer) = payer else {
+            return Err(syn::Error::new(
+                attr.span(),
+                "init requires payer = <account>",
+            ));
+        };
+
+        field_meta.init = Some(Init { payer, space, owner });
+    } else if payer.is_some() || space.is_some() || owner.is_some() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "payer, space and owner can only be used with init",
+        ));
     }
 
-    Ok(quote! { NoMeta })
+    match (realloc, realloc_payer) {
+        (Some(new_len), Some(payer)) => {
+            if is_init {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "init and realloc can't be used on the same field",
+                ));
+            }
+
+            field_meta.realloc = Some(Realloc { new_len, payer });
+        }
+        (Some(_), None) => {
+            return Err(syn::Error::new(
+                attr.span(),
+                "realloc requires realloc::payer = <account>",
+            ));
+        }
+        (None, payer) if payer.is_some() || has_realloc_zero => {
+            return Err(syn::Error::new(
+                attr.span(),
+                "realloc::payer and realloc::zero can only be used with realloc",
+            ));
+        }
+        (None, _) => {}
+    }
+
+    if !values.is_empty() {
+        field_meta.meta_expr = quote! {
+            <#ty as FromAccountView<#info_lt>>::Meta::new(
+                #(#values),*
+            )
+        };
+    }
+
+    Ok(field_meta)
+}
+
+/// `#[accounts(...)]` on the struct
+struct AccountsArgs {
+    /// `cpi`, generates the `<Accounts>Cpi` struct used by `#[instruction(cpi)]`
+    cpi: bool,
+    /// `client`, generates the `<Accounts>Client` struct used by `#[instruction(client)]`, with
+    /// hayabusa's `client` feature
+    client: bool,
+}
+
+fn parse_accounts_args(attrs: &[syn::Attribute]) -> syn::Result<AccountsArgs> {
+    let mut args = AccountsArgs {
+        cpi: false,
+        client: false,
+    };
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("accounts")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("cpi") {
+                args.cpi = true;
+                Ok(())
+            } else if meta.path.is_ident("client") {
+                args.client = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `cpi` or `client`"))
+            }
+        })?;
+    }
+
+    Ok(args)
+}
+
+/// `T` of a `Mut<T>` field
+fn mut_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Mut" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+/// `ty` is wrapped in `Mut`, i.e. the account is written
+fn is_mut(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+
+    path.path.segments.last().is_some_and(|segment| segment.ident == "Mut")
+}
+
+/// `ty` is a `Signer`, possibly wrapped, e.g. `Mut<Signer<'ix>>`
+fn is_signer(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+
+    if segment.ident == "Signer" || segment.ident == "SignerAddress" {
+        return true;
+    }
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().any(|arg| match arg {
+            syn::GenericArgument::Type(ty) => is_signer(ty),
+            _ => false,
+        }),
+        _ => false,
+    }
+}
+
+/// Doc comment lines of a field
+fn docs(attrs: &[syn::Attribute]) -> String {
+    attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(doc) if doc.path.is_ident("doc") => match &doc.value {
+                Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(line),
+                    ..
+                }) => Some(line.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Arguments of the `#[meta(...)]` attributes, as written in the source when available
+fn meta_sources(attrs: &[syn::Attribute]) -> Vec<String> {
+    let mut sources = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("meta")) {
+        let syn::Meta::List(list) = &attr.meta else {
+            continue;
+        };
+
+        let Some(text) = list.delimiter.span().join().source_text() else {
+            sources.extend(
+                list.parse_args_with(Punctuated::<MetaArg, Token![,]>::parse_terminated)
+                    .into_iter()
+                    .flatten()
+                    .map(|MetaArg { path, value, error }| {
+                        let value = value.map(|value| quote! { = #value });
+                        let error = error.map(|error| quote! { @ #error });
+                        quote! { #path #value #error }.to_string()
+                    }),
+            );
+            continue;
+        };
+
+        // strip the parentheses and split on the top level commas
+        let inner = &text[1..text.len() - 1];
+        let mut depth = 0usize;
+        let mut in_str = false;
+        let mut escaped = false;
+        let mut start = 0;
+
+        for (i, c) in inner.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if in_str => escaped = true,
+                '"' => in_str = !in_str,
+                '(' | '[' | '{' if !in_str => depth += 1,
+                ')' | ']' | '}' if !in_str => depth = depth.saturating_sub(1),
+                ',' if !in_str && depth == 0 => {
+                    sources.push(inner[start..i].trim().to_string());
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        sources.push(inner[start..].trim().to_string());
+    }
+
+    sources.retain(|source| !source.is_empty());
+    sources
+}
+
+/// `path` is exactly `first::second`
+fn is_path(path: &Path, first: &str, second: &str) -> bool {
+    path.segments.len() == 2 && path.segments[0].ident == first && path.segments[1].ident == second
+}
+
+/// `expr` if it is a bare field name
+fn field_ident<'a>(
+    expr: &'a Expr,
+    fields: &Punctuated<syn::Field, Token![,]>,
+) -> Option<&'a syn::Ident> {
+    let Expr::Path(path) = expr else {
+        return None;
+    };
+
+    path.path
+        .get_ident()
+        .filter(|ident| fields.iter().any(|f| f.ident.as_ref() == Some(*ident)))
+}
+
+/// `&Address` of `expr`, a bare field name refers to the address of that field's account
+fn address_of(
+    expr: &Expr,
+    fields: &Punctuated<syn::Field, Token![,]>,
+) -> proc_macro2::TokenStream {
+    match field_ident(expr, fields) {
+        Some(ident) => quote! { #ident.address() },
+        None => quote! { core::borrow::Borrow::<Address>::borrow(&(#expr)) },
+    }
+}
+
+/// Token program of an associated token account: `associated_token::token_program`, else the
+/// `token_program` field, else SPL Token
+fn token_program_of(
+    ata: &AssociatedToken,
+    fields: &Punctuated<syn::Field, Token![,]>,
+) -> proc_macro2::TokenStream {
+    if let Some(token_program) = &ata.token_program {
+        return address_of(token_program, fields);
+    }
+
+    if fields.iter().any(|f| f.ident.as_ref().is_some_and(|i| i == "token_program")) {
+        return quote! { token_program.address() };
+    }
+
+    quote! { &associated_token::TOKEN_PROGRAM_ID }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn meta(field: syn::Field) -> Result<FieldMeta, syn::Error> {
+        parse_meta(&field.attrs, &field.ty, &parse_quote!('ix))
+    }
+
+    #[test]
+    fn test_realloc_zero() {
+        let field: syn::Field = parse_quote! {
+            #[meta(realloc = 64, realloc::payer = payer, realloc::zero = true)]
+            pub data: Mut<UncheckedAccount<'ix>>
+        };
+        assert!(meta(field).unwrap().realloc.is_some());
+
+        let field: syn::Field = parse_quote! {
+            #[meta(realloc = 64, realloc::payer = payer, realloc::zero = false)]
+            pub data: Mut<UncheckedAccount<'ix>>
+        };
+        assert!(meta(field).is_err());
+    }
 }
@@ -0,0 +1,44 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Built with hayabusa's `audit-writable` feature, see the dev-dependencies. Without
+//! `declare_id!`, deriving `FromAccountViews` doesn't need the `ID` of a program crate.
+
+use hayabusa::prelude::*;
+use hayabusa_common::mock::MockAccount;
+
+const OWNER: Address = Address::new_from_array([9; 32]);
+
+#[allow(dead_code)]
+#[derive(FromAccountViews)]
+struct Transfer<'ix> {
+    pub authority: Signer<'ix>,
+    pub vault: Mut<UncheckedAccount<'ix>>,
+    pub mint: UncheckedAccount<'ix>,
+}
+
+fn parse(authority_writable: bool, mint_writable: bool) -> Result<()> {
+    let mut authority = MockAccount::new(Address::new_from_array([1; 32]), OWNER, &[]).signer();
+    if !authority_writable {
+        authority = authority.read_only();
+    }
+    let mut vault = MockAccount::new(Address::new_from_array([2; 32]), OWNER, &[]);
+    let mut mint = MockAccount::new(Address::new_from_array([3; 32]), OWNER, &[]);
+    if !mint_writable {
+        mint = mint.read_only();
+    }
+
+    let account_views = [authority.view(), vault.view(), mint.view()];
+    let mut iter = AccountIter::new(&account_views);
+    Transfer::try_from_account_views(&mut iter, &mut Default::default()).map(|_| ())
+}
+
+#[test]
+fn test_audit_read_only() {
+    assert_eq!(parse(false, false), Ok(()));
+
+    // passed as writable, not declared `Mut`
+    let unexpected = Err(ErrorCode::UnexpectedWritableAccount.into());
+    assert_eq!(parse(false, true), unexpected);
+    assert_eq!(parse(true, false), unexpected);
+}
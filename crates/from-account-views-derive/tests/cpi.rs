@@ -0,0 +1,35 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use hayabusa::prelude::*;
+use hayabusa_common::mock::MockAccount;
+
+declare_id!("11111111111111111111111111111111");
+
+#[allow(dead_code)]
+#[derive(FromAccountViews)]
+#[accounts(cpi)]
+struct Bump<'ix> {
+    pub authority: Signer<'ix>,
+    pub vault: Mut<UncheckedAccount<'ix>>,
+}
+
+#[test]
+fn test_cpi_accounts() {
+    let mut authority = MockAccount::new(Address::new_from_array([1; 32]), ID, &[]);
+    let mut vault = MockAccount::new(Address::new_from_array([2; 32]), ID, &[]);
+    let (authority, vault) = (authority.view(), vault.view());
+
+    let accounts = BumpCpi {
+        authority: &authority,
+        vault: &vault,
+    }
+    .instruction_accounts();
+
+    assert_eq!(accounts[0].address, authority.address());
+    assert!(accounts[0].is_signer && !accounts[0].is_writable);
+    assert_eq!(accounts[1].address, vault.address());
+    assert!(!accounts[1].is_signer && accounts[1].is_writable);
+
+    assert_eq!(<BumpCpi as CheckProgramId>::ID, ID);
+}
@@ -0,0 +1,92 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use hayabusa::prelude::{
+    account_view::{RuntimeAccount, NOT_BORROWED},
+    *,
+};
+
+declare_id!("11111111111111111111111111111111");
+
+/// Moves the lamports to the destination on close, without touching the data. `Mut` only closes
+/// zero-copy accounts, other account types implement `CloseAccount` themselves
+struct Closable<'ix> {
+    account_view: &'ix AccountView,
+}
+
+impl<'ix> FromAccountView<'ix> for Closable<'ix> {
+    type Meta<'a>
+        = NoMeta
+    where
+        'ix: 'a;
+
+    fn try_from_account_view<'a>(account_view: &'ix AccountView, _: NoMeta) -> Result<Self>
+    where
+        'ix: 'a,
+    {
+        Ok(Closable { account_view })
+    }
+}
+
+impl ToAccountView for Closable<'_> {
+    fn to_account_view(&self) -> &AccountView {
+        self.account_view
+    }
+}
+
+impl WritableAllowed for Closable<'_> {}
+
+impl CloseAccount for Closable<'_> {
+    fn try_close_account(account_view: &AccountView, destination: &AccountView) -> Result<()> {
+        destination.set_lamports(destination.lamports() + account_view.lamports());
+        account_view.set_lamports(0);
+        Ok(())
+    }
+}
+
+#[derive(FromAccountViews)]
+struct Close<'ix> {
+    pub receiver: Mut<Closable<'ix>>,
+    #[meta(close = receiver)]
+    pub vault: Closable<'ix>,
+}
+
+fn account(seed: u8, lamports: u64, is_writable: u8) -> RuntimeAccount {
+    RuntimeAccount {
+        borrow_state: NOT_BORROWED,
+        is_signer: 0,
+        is_writable,
+        executable: 0,
+        resize_delta: 0,
+        address: Address::new_from_array([seed; 32]),
+        owner: ID,
+        lamports,
+        data_len: 0,
+    }
+}
+
+#[test]
+fn test_exit_after_skip() {
+    // `vault` isn't declared `Mut`, the `audit-writable` check rejects it if writable
+    let mut accounts = [account(1, 1, 1), account(2, 10, 1), account(3, 100, 0)];
+    let account_views = accounts
+        .each_mut()
+        .map(|account| unsafe { AccountView::new_unchecked(account) });
+
+    // `Close` starts at the second account
+    let mut iter = AccountIter::new(&account_views);
+    iter.skip(1).unwrap();
+    let checkpoint = iter.checkpoint();
+    let close = Close::try_from_account_views(&mut iter, &mut Default::default()).unwrap();
+    assert_eq!(close.receiver.account_view.address(), account_views[1].address());
+    assert_eq!(close.vault.account_view.address(), account_views[2].address());
+
+    Close::exit(iter.consumed_since(checkpoint)).unwrap();
+
+    assert_eq!(account_views[0].lamports(), 1);
+    assert_eq!(account_views[1].lamports(), 110);
+    assert_eq!(account_views[2].lamports(), 0);
+
+    // fewer views than `Close` was parsed from
+    assert!(Close::exit(&account_views[..1]).is_err());
+}
@@ -0,0 +1,80 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Off chain the `create_account` CPI is a no-op, the mock accounts are sized as the system
+//! program would create them.
+
+use hayabusa::prelude::*;
+use hayabusa_common::mock::MockAccount;
+
+declare_id!("HPoDm7Kf63B6TpFKV7S8YSd7sGde6sVdztiDBEVkfuxz");
+
+/// 4 byte tag, so `8 + size_of::<Tally>()` is not its length
+#[account(disc_len = 4)]
+#[derive(OwnerProgram, InitSpace)]
+pub struct Tally {
+    pub count: PodU32,
+}
+
+#[allow(dead_code)]
+#[derive(FromAccountViews)]
+struct Create<'ix> {
+    pub payer: Mut<Signer<'ix>>,
+    #[meta(init, payer = payer)]
+    pub tally: Mut<ZcAccount<'ix, Tally>>,
+    pub system_program: Program<'ix, System>,
+}
+
+#[allow(dead_code)]
+#[derive(FromAccountViews)]
+struct CreatePadded<'ix> {
+    pub payer: Mut<Signer<'ix>>,
+    #[meta(init, payer = payer, space = 8 + core::mem::size_of::<Tally>())]
+    pub tally: Mut<ZcAccount<'ix, Tally>>,
+    pub system_program: Program<'ix, System>,
+}
+
+fn accounts(tally_len: usize) -> [MockAccount; 3] {
+    [
+        MockAccount::new(Address::new_from_array([1; 32]), Address::default(), &[]).signer(),
+        MockAccount::new(Address::new_from_array([2; 32]), ID, &vec![0; tally_len]),
+        MockAccount::new(system_program::ID, NATIVE_LOADER_ID, &[])
+            .executable()
+            .read_only(),
+    ]
+}
+
+#[test]
+fn test_init_deserializes() {
+    let mut accounts = accounts(Tally::DISCRIMINATED_LEN);
+    let account_views = accounts.each_mut().map(|account| account.view());
+
+    let mut iter = AccountIter::new(&account_views);
+    let create = Create::try_from_account_views(&mut iter, &mut Default::default()).unwrap();
+
+    assert_eq!(create.tally.try_deserialize().unwrap().count.get(), 0);
+    create.tally.try_deserialize_mut().unwrap().count = 3.into();
+    assert_eq!(create.tally.try_deserialize().unwrap().count.get(), 3);
+}
+
+#[test]
+fn test_init_rejects_other_space() {
+    let mut accounts = accounts(8 + core::mem::size_of::<Tally>());
+    let account_views = accounts.each_mut().map(|account| account.view());
+
+    let mut iter = AccountIter::new(&account_views);
+    assert_eq!(
+        CreatePadded::try_from_account_views(&mut iter, &mut Default::default()).err(),
+        Some(ProgramError::InvalidArgument)
+    );
+}
+
+#[test]
+fn test_init_space() {
+    assert_eq!(Tally::INIT_SPACE, 4);
+    assert_eq!(Tally::DISCRIMINATED_INIT_SPACE, Tally::DISCRIMINATED_LEN);
+    assert_eq!(
+        <Mut<ZcAccount<Tally>> as InitAccount>::DEFAULT_SPACE,
+        Tally::DISCRIMINATED_INIT_SPACE
+    );
+}
@@ -0,0 +1,238 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use hayabusa::prelude::*;
+use hayabusa_common::mock::MockAccount;
+
+declare_id!("11111111111111111111111111111111");
+
+const AUTHORITY: Address = Address::new_from_array([1; 32]);
+
+fn mock(address: Address) -> MockAccount {
+    MockAccount::new(address, ID, &[]).read_only()
+}
+
+/// Views of `accounts`, which must outlive them
+fn views<const N: usize>(accounts: &mut [MockAccount; N]) -> [AccountView; N] {
+    accounts.each_mut().map(|account| account.view())
+}
+
+fn parse<'ix, T: FromAccountViews<'ix>>(account_views: &'ix [AccountView]) -> Result<T::Bumps> {
+    let mut bumps = T::Bumps::default();
+    T::try_from_account_views(&mut AccountIter::new(account_views), &mut bumps)?;
+
+    Ok(bumps)
+}
+
+#[allow(dead_code)]
+#[derive(FromAccountViews)]
+struct Seeded<'ix> {
+    pub authority: UncheckedAccount<'ix>,
+    #[meta(seeds = [b"vault", authority.account_view.address()], bump)]
+    pub vault: UncheckedAccount<'ix>,
+}
+
+#[test]
+fn test_seeds() {
+    let (vault, bump) =
+        syscalls::try_find_program_address(&[b"vault", AUTHORITY.as_ref()], &ID).unwrap();
+
+    let mut accounts = [mock(AUTHORITY), mock(vault)];
+    assert_eq!(parse::<Seeded>(&views(&mut accounts)).unwrap().vault, bump);
+
+    // the PDA of another authority
+    let mut accounts = [mock(Address::new_from_array([2; 32])), mock(vault)];
+    assert!(parse::<Seeded>(&views(&mut accounts)).is_err());
+}
+
+#[allow(dead_code)]
+#[derive(FromAccountViews)]
+struct Pinned<'ix> {
+    #[meta(address = AUTHORITY)]
+    pub authority: UncheckedAccount<'ix>,
+}
+
+#[test]
+fn test_address() {
+    let mut accounts = [mock(AUTHORITY)];
+    assert!(parse::<Pinned>(&views(&mut accounts)).is_ok());
+
+    let mut accounts = [mock(ID)];
+    assert_eq!(
+        parse::<Pinned>(&views(&mut accounts)).err(),
+        Some(ErrorCode::InvalidAccount.into())
+    );
+}
+
+#[allow(dead_code)]
+#[derive(FromAccountViews)]
+struct Constrained<'ix> {
+    pub authority: UncheckedAccount<'ix>,
+    // checked once every field is parsed, so it may reference a later field
+    #[meta(constraint = vault.account_view.owned_by(authority.account_view.address()))]
+    pub other: UncheckedAccount<'ix>,
+    #[meta(constraint = vault.account_view.lamports() > 0 @ ProgramError::InsufficientFunds)]
+    pub vault: UncheckedAccount<'ix>,
+}
+
+#[test]
+fn test_constraint() {
+    let vault = Address::new_from_array([2; 32]);
+    let owned_by = |owner| MockAccount::new(vault, owner, &[]).read_only();
+
+    let mut accounts = [mock(AUTHORITY), mock(ID), owned_by(AUTHORITY).with_lamports(1)];
+    assert!(parse::<Constrained>(&views(&mut accounts)).is_ok());
+
+    let mut accounts = [mock(AUTHORITY), mock(ID), owned_by(ID).with_lamports(1)];
+    assert_eq!(
+        parse::<Constrained>(&views(&mut accounts)).err(),
+        Some(ErrorCode::ConstraintViolated.into())
+    );
+
+    // custom error
+    let mut accounts = [mock(AUTHORITY), mock(ID), owned_by(AUTHORITY)];
+    assert_eq!(
+        parse::<Constrained>(&views(&mut accounts)).err(),
+        Some(ProgramError::InsufficientFunds)
+    );
+}
+
+#[allow(dead_code)]
+#[derive(FromAccountViews)]
+struct Stacked<'ix> {
+    #[meta(address = AUTHORITY)]
+    #[meta(constraint = authority.account_view.lamports() > 0 @ ProgramError::InsufficientFunds)]
+    pub authority: UncheckedAccount<'ix>,
+}
+
+#[test]
+fn test_stacked_meta() {
+    let mut accounts = [mock(AUTHORITY).with_lamports(1)];
+    assert!(parse::<Stacked>(&views(&mut accounts)).is_ok());
+
+    // both attributes are checked
+    let mut accounts = [mock(ID).with_lamports(1)];
+    assert_eq!(
+        parse::<Stacked>(&views(&mut accounts)).err(),
+        Some(ErrorCode::InvalidAccount.into())
+    );
+
+    let mut accounts = [mock(AUTHORITY)];
+    assert_eq!(
+        parse::<Stacked>(&views(&mut accounts)).err(),
+        Some(ProgramError::InsufficientFunds)
+    );
+}
+
+#[allow(dead_code)]
+#[derive(FromAccountViews)]
+struct Swap<'ix> {
+    pub authority: UncheckedAccount<'ix>,
+    #[meta(no_dup)]
+    pub source: UncheckedAccount<'ix>,
+    pub destination: UncheckedAccount<'ix>,
+}
+
+#[test]
+fn test_no_dup() {
+    let vault = Address::new_from_array([2; 32]);
+    let other = Address::new_from_array([3; 32]);
+
+    let mut accounts = [mock(AUTHORITY), mock(vault), mock(other)];
+    assert!(parse::<Swap>(&views(&mut accounts)).is_ok());
+
+    let mut accounts = [mock(AUTHORITY), mock(vault), mock(vault)];
+    assert_eq!(
+        parse::<Swap>(&views(&mut accounts)).err(),
+        Some(ErrorCode::DuplicateAccount.into())
+    );
+
+    // fields without `no_dup` may share an address with each other
+    let mut accounts = [mock(other), mock(vault), mock(other)];
+    assert!(parse::<Swap>(&views(&mut accounts)).is_ok());
+
+    // only the accounts of the struct are compared, not the ones parsed before it
+    let mut accounts = [mock(vault), mock(AUTHORITY), mock(vault), mock(other)];
+    let account_views = views(&mut accounts);
+    let mut iter = AccountIter::new(&account_views);
+    iter.skip(1).unwrap();
+    assert!(Swap::try_from_account_views(&mut iter, &mut Default::default()).is_ok());
+}
+
+const MINT: Address = Address::new_from_array([4; 32]);
+
+#[allow(dead_code)]
+#[derive(FromAccountViews)]
+struct Vault<'ix> {
+    pub authority: UncheckedAccount<'ix>,
+    #[meta(token::mint = MINT, token::authority = authority)]
+    pub vault: ZcAccount<'ix, hayabusa_token::state::TokenAccount>,
+}
+
+fn token_account(mint: Address, owner: Address) -> MockAccount {
+    token_account_at(Address::new_from_array([2; 32]), mint, owner)
+}
+
+fn token_account_at(address: Address, mint: Address, owner: Address) -> MockAccount {
+    let mut data = [0u8; hayabusa_token::state::TokenAccount::LEN];
+    data[..32].copy_from_slice(mint.as_ref());
+    data[32..64].copy_from_slice(owner.as_ref());
+    // initialized
+    data[108] = 1;
+
+    MockAccount::new(address, hayabusa_token::ID, &data).read_only()
+}
+
+#[test]
+fn test_token() {
+    let mut accounts = [mock(AUTHORITY), token_account(MINT, AUTHORITY)];
+    assert!(parse::<Vault>(&views(&mut accounts)).is_ok());
+
+    for vault in [token_account(ID, AUTHORITY), token_account(MINT, ID)] {
+        let mut accounts = [mock(AUTHORITY), vault];
+        assert_eq!(
+            parse::<Vault>(&views(&mut accounts)).err(),
+            Some(ErrorCode::InvalidAccount.into())
+        );
+    }
+}
+
+#[allow(dead_code)]
+#[derive(FromAccountViews)]
+struct Ata<'ix> {
+    pub user: UncheckedAccount<'ix>,
+    pub mint: UncheckedAccount<'ix>,
+    #[meta(associated_token::mint = mint, associated_token::authority = user)]
+    pub user_ata: ZcAccount<'ix, hayabusa_token::state::TokenAccount>,
+}
+
+#[test]
+fn test_associated_token() {
+    let (ata, _) = associated_token::get_associated_token_address(
+        &AUTHORITY,
+        &MINT,
+        &associated_token::TOKEN_PROGRAM_ID,
+    )
+    .unwrap();
+
+    let mut accounts = [
+        mock(AUTHORITY),
+        mock(MINT),
+        token_account_at(ata, MINT, AUTHORITY),
+    ];
+    assert!(parse::<Ata>(&views(&mut accounts)).is_ok());
+
+    // a token account of the right mint and owner, but not at the associated address
+    let mut accounts = [mock(AUTHORITY), mock(MINT), token_account(MINT, AUTHORITY)];
+    assert_eq!(
+        parse::<Ata>(&views(&mut accounts)).err(),
+        Some(ErrorCode::InvalidAccount.into())
+    );
+
+    // the associated account of another wallet
+    let mut accounts = [mock(ID), mock(MINT), token_account_at(ata, MINT, AUTHORITY)];
+    assert_eq!(
+        parse::<Ata>(&views(&mut accounts)).err(),
+        Some(ErrorCode::InvalidAccount.into())
+    );
+}
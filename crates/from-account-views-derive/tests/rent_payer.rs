@@ -0,0 +1,90 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Off chain the `create_account` CPI is a no-op, the mock accounts are sized as the system
+//! program would create them.
+
+use hayabusa::prelude::*;
+use hayabusa_common::mock::MockAccount;
+
+declare_id!("HPoDm7Kf63B6TpFKV7S8YSd7sGde6sVdztiDBEVkfuxz");
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct Vault {
+    pub rent_payer: Address,
+    pub amount: PodU64,
+}
+
+#[allow(dead_code)]
+#[derive(FromAccountViews)]
+struct Create<'ix> {
+    pub payer: Mut<Signer<'ix>>,
+    #[meta(init, payer = payer)]
+    pub vault: Mut<ZcAccount<'ix, Vault>>,
+    pub system_program: Program<'ix, System>,
+}
+
+#[allow(dead_code)]
+#[derive(FromAccountViews)]
+struct Close<'ix> {
+    pub receiver: Mut<UncheckedAccount<'ix>>,
+    #[meta(close = receiver)]
+    pub vault: Mut<ZcAccount<'ix, Vault>>,
+}
+
+const PAYER: Address = Address::new_from_array([1; 32]);
+
+fn create(vault: &mut MockAccount) {
+    let mut accounts = [
+        MockAccount::new(PAYER, Address::default(), &[]).signer(),
+        MockAccount::new(system_program::ID, NATIVE_LOADER_ID, &[])
+            .executable()
+            .read_only(),
+    ];
+    let [payer, system_program] = accounts.each_mut().map(|account| account.view());
+    let account_views = [payer, vault.view(), system_program];
+
+    let mut iter = AccountIter::new(&account_views);
+    Create::try_from_account_views(&mut iter, &mut Default::default()).unwrap();
+}
+
+fn vault() -> MockAccount {
+    MockAccount::new(
+        Address::new_from_array([2; 32]),
+        ID,
+        &[0; Vault::DISCRIMINATED_LEN],
+    )
+    .with_lamports(10)
+}
+
+#[test]
+fn test_init_records_rent_payer() {
+    let mut vault = vault();
+    create(&mut vault);
+
+    let view = vault.view();
+    assert_eq!(Vault::try_deserialize(&view).unwrap().rent_payer, PAYER);
+}
+
+#[test]
+fn test_close_refunds_rent_payer() {
+    let mut vault = vault();
+    create(&mut vault);
+
+    let mut other = MockAccount::new(Address::new_from_array([3; 32]), Address::default(), &[]);
+    let mut payer = MockAccount::new(PAYER, Address::default(), &[]);
+
+    for (receiver, result) in [
+        (&mut other, Err(ErrorCode::InvalidAccount.into())),
+        (&mut payer, Ok(())),
+    ] {
+        let account_views = [receiver.view(), vault.view()];
+        let mut iter = AccountIter::new(&account_views);
+        Close::try_from_account_views(&mut iter, &mut Default::default()).unwrap();
+
+        assert_eq!(Close::exit(&account_views), result);
+    }
+
+    assert_eq!((other.view().lamports(), payer.view().lamports()), (0, 10));
+}
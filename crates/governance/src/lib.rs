@@ -0,0 +1,27 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! SPL Governance (Realms) account parsing.
+//!
+//! DAOs often run their own deployment of the governance program, so the views take the program
+//! the accounts must be owned by, [`ID`] being the shared Realms deployment. Parameter updates
+//! controlled by a DAO check a passed proposal with [`Proposal::try_check_passed`].
+
+#![no_std]
+
+pub mod pda;
+pub mod state;
+
+pub use pda::*;
+pub use state::*;
+
+hayabusa_common::declare_id!("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
+
+use hayabusa_accounts::ProgramId;
+use hayabusa_common::Address;
+
+pub struct Governance;
+
+impl ProgramId for Governance {
+    const ID: Address = ID;
+}
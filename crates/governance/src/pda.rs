@@ -0,0 +1,32 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use hayabusa_common::Address;
+use hayabusa_errors::Result;
+use hayabusa_syscalls::try_find_program_address;
+
+pub const SEED_PREFIX: &[u8] = b"governance";
+
+#[inline(always)]
+pub fn find_realm_address(name: &str, program_id: &Address) -> Result<(Address, u8)> {
+    try_find_program_address(&[SEED_PREFIX, name.as_bytes()], program_id)
+}
+
+/// Deposit record of `governing_token_owner` for `governing_token_mint` in `realm`.
+#[inline(always)]
+pub fn find_token_owner_record_address(
+    realm: &Address,
+    governing_token_mint: &Address,
+    governing_token_owner: &Address,
+    program_id: &Address,
+) -> Result<(Address, u8)> {
+    try_find_program_address(
+        &[
+            SEED_PREFIX,
+            realm.as_ref(),
+            governing_token_mint.as_ref(),
+            governing_token_owner.as_ref(),
+        ],
+        program_id,
+    )
+}
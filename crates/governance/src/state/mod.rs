@@ -0,0 +1,242 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod proposal;
+pub mod realm;
+pub mod token_owner_record;
+
+pub use proposal::*;
+pub use realm::*;
+pub use token_owner_record::*;
+
+use hayabusa_common::{AccountView, Address, Ref};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+/// `GovernanceAccountType` values, the first byte of every governance account.
+pub struct AccountType;
+
+impl AccountType {
+    pub const REALM_V1: u8 = 1;
+    pub const TOKEN_OWNER_RECORD_V1: u8 = 2;
+    pub const PROPOSAL_V1: u8 = 5;
+    pub const PROPOSAL_V2: u8 = 14;
+    pub const REALM_V2: u8 = 16;
+    pub const TOKEN_OWNER_RECORD_V2: u8 = 17;
+}
+
+/// Borrows the data of a governance account after checking the owner, the length and the
+/// account type.
+#[inline(always)]
+fn try_borrow_governance_account<'a>(
+    account_view: &'a AccountView,
+    program_id: &Address,
+    account_types: [u8; 2],
+    min_len: usize,
+) -> Result<Ref<'a, [u8]>> {
+    if unlikely(!account_view.owned_by(program_id)) {
+        error_msg!(
+            "try_borrow_governance_account: invalid owner",
+            ProgramError::InvalidAccountOwner,
+        );
+    }
+
+    let data = account_view.try_borrow()?;
+
+    if unlikely(data.len() < min_len) {
+        error_msg!(
+            "try_borrow_governance_account: account data too small",
+            ProgramError::AccountDataTooSmall,
+        );
+    }
+
+    if unlikely(!account_types.contains(&data[0])) {
+        error_msg!(
+            "try_borrow_governance_account: invalid account type",
+            ErrorCode::InvalidAccountDiscriminator,
+        );
+    }
+
+    Ok(data)
+}
+
+#[inline(always)]
+fn read_address(data: &[u8], offset: usize) -> &Address {
+    // SAFETY: `Address` is a 32 byte array with an alignment of 1, callers check the bounds
+    unsafe { &*(data[offset..offset + 32].as_ptr() as *const Address) }
+}
+
+/// Offset past the borsh `Option<Address>` at `offset`.
+#[inline(always)]
+fn try_skip_option_address(data: &[u8], offset: usize) -> Result<usize> {
+    let end = match data.get(offset) {
+        Some(0) => offset + 1,
+        Some(1) => offset + 33,
+        _ => {
+            error_msg!(
+                "try_skip_option_address: invalid option",
+                ProgramError::InvalidAccountData,
+            );
+        }
+    };
+
+    if unlikely(end > data.len()) {
+        error_msg!(
+            "try_skip_option_address: account data too small",
+            ProgramError::InvalidAccountData,
+        );
+    }
+
+    Ok(end)
+}
+
+/// borsh `Option<Address>` at `offset`, checked with [`try_skip_option_address`].
+#[inline(always)]
+fn read_option_address(data: &[u8], offset: usize) -> Option<&Address> {
+    match data[offset] {
+        0 => None,
+        _ => Some(read_address(data, offset + 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use hayabusa_common::mock::MockAccount;
+    use std::vec::Vec;
+
+    const ADDRESS: Address = Address::new_from_array([3; 32]);
+
+    fn push_option(data: &mut Vec<u8>, address: Option<Address>) {
+        match address {
+            Some(address) => {
+                data.push(1);
+                data.extend_from_slice(address.as_ref());
+            }
+            None => data.push(0),
+        }
+    }
+
+    fn realm_data(
+        council_mint: Option<Address>,
+        authority: Option<Address>,
+        name: &[u8],
+    ) -> Vec<u8> {
+        let mut data = std::vec![AccountType::REALM_V2];
+        data.extend_from_slice(&[1; 32]);
+        // legacy and reserved config fields
+        data.extend_from_slice(&[0; 8]);
+        data.extend_from_slice(&100u64.to_le_bytes());
+        // max voter weight source
+        data.extend_from_slice(&[0; 9]);
+        push_option(&mut data, council_mint);
+        // reserved and legacy
+        data.extend_from_slice(&[0; 8]);
+        push_option(&mut data, authority);
+        data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        data.extend_from_slice(name);
+        data
+    }
+
+    #[test]
+    fn test_realm() {
+        let council = Address::new_from_array([2; 32]);
+        let authority = Address::new_from_array([4; 32]);
+
+        for (council_mint, authority) in [(None, None), (Some(council), Some(authority))] {
+            let data = realm_data(council_mint, authority, b"dao");
+            let mut account = MockAccount::new(ADDRESS, crate::ID, &data);
+            let account_view = account.view();
+            let realm = Realm::from_account_view(&account_view, &crate::ID).unwrap();
+
+            assert_eq!(realm.community_mint(), &Address::new_from_array([1; 32]));
+            assert_eq!(realm.min_community_weight_to_create_governance(), 100);
+            assert_eq!(realm.council_mint(), council_mint.as_ref());
+            assert_eq!(realm.authority(), authority.as_ref());
+            assert_eq!(realm.name().unwrap(), "dao");
+            assert!(realm.is_governing_mint(&Address::new_from_array([1; 32])));
+            assert_eq!(realm.is_governing_mint(&council), council_mint.is_some());
+        }
+
+        // another deployment of the governance program
+        let data = realm_data(None, None, b"dao");
+        let mut account = MockAccount::new(ADDRESS, crate::ID, &data);
+        assert!(Realm::from_account_view(&account.view(), &ADDRESS).is_err());
+
+        // name past the end of the data
+        let mut account = MockAccount::new(ADDRESS, crate::ID, &data[..data.len() - 1]);
+        assert!(Realm::from_account_view(&account.view(), &crate::ID).is_err());
+
+        let data = realm_data(None, None, &[0xff]);
+        let mut account = MockAccount::new(ADDRESS, crate::ID, &data);
+        let account_view = account.view();
+        let realm = Realm::from_account_view(&account_view, &crate::ID).unwrap();
+        assert!(realm.name().is_err());
+    }
+
+    #[test]
+    fn test_token_owner_record() {
+        let owner = Address::new_from_array([5; 32]);
+        let delegate = Address::new_from_array([6; 32]);
+
+        let mut data = std::vec![AccountType::TOKEN_OWNER_RECORD_V2];
+        data.extend_from_slice(&[1; 32]);
+        data.extend_from_slice(&[2; 32]);
+        data.extend_from_slice(owner.as_ref());
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.extend_from_slice(&[0; 16]);
+        push_option(&mut data, Some(delegate));
+
+        let mut account = MockAccount::new(ADDRESS, crate::ID, &data);
+        let account_view = account.view();
+        let record = TokenOwnerRecord::from_account_view(&account_view, &crate::ID).unwrap();
+
+        assert_eq!(record.realm(), &Address::new_from_array([1; 32]));
+        assert_eq!(record.governing_token_mint(), &Address::new_from_array([2; 32]));
+        assert_eq!(record.governing_token_deposit_amount(), 42);
+        assert!(record.is_owner_or_delegate(&owner));
+        assert!(record.is_owner_or_delegate(&delegate));
+        assert!(!record.is_owner_or_delegate(&ADDRESS));
+
+        // a realm is not a token owner record
+        let data = realm_data(None, None, b"dao");
+        let mut account = MockAccount::new(ADDRESS, crate::ID, &data);
+        assert!(TokenOwnerRecord::from_account_view(&account.view(), &crate::ID).is_err());
+    }
+
+    #[test]
+    fn test_proposal_passed() {
+        let governance = Address::new_from_array([1; 32]);
+
+        let proposal = |state: u8| {
+            let mut data = std::vec![AccountType::PROPOSAL_V2];
+            data.extend_from_slice(governance.as_ref());
+            data.extend_from_slice(&[2; 32]);
+            data.push(state);
+            data.extend_from_slice(&[4; 32]);
+            data.extend_from_slice(&[2, 1]);
+            MockAccount::new(ADDRESS, crate::ID, &data)
+        };
+
+        let mut account = proposal(3);
+        let account_view = account.view();
+        let succeeded = Proposal::from_account_view(&account_view, &crate::ID).unwrap();
+        assert_eq!(succeeded.state(), ProposalState::Succeeded);
+        assert_eq!(succeeded.signatories_count(), 2);
+        assert_eq!(succeeded.signatories_signed_off_count(), 1);
+        assert!(succeeded.try_check_passed(&governance).is_ok());
+        // passed, in another governance
+        assert!(succeeded.try_check_passed(&ADDRESS).is_err());
+
+        let mut account = proposal(7);
+        let account_view = account.view();
+        let defeated = Proposal::from_account_view(&account_view, &crate::ID).unwrap();
+        assert_eq!(defeated.state(), ProposalState::Defeated);
+        assert!(defeated.try_check_passed(&governance).is_err());
+
+        let mut account = proposal(10);
+        assert!(Proposal::from_account_view(&account.view(), &crate::ID).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{read_address, try_borrow_governance_account, AccountType};
+use hayabusa_common::{address_eq, AccountView, Address, Ref};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalState {
+    Draft,
+    SigningOff,
+    Voting,
+    Succeeded,
+    Executing,
+    Completed,
+    Cancelled,
+    Defeated,
+    ExecutingWithErrors,
+    Vetoed,
+}
+
+impl ProposalState {
+    /// Whether the vote succeeded, before or after its transactions ran.
+    #[inline(always)]
+    pub fn is_passed(&self) -> bool {
+        matches!(
+            self,
+            Self::Succeeded | Self::Executing | Self::Completed | Self::ExecutingWithErrors
+        )
+    }
+}
+
+/// Zero-copy view over the header of a proposal account, v1 or v2.
+///
+/// Layout: account type (u8), governance (32 bytes), governing token mint (32 bytes), state (u8),
+/// token owner record (32 bytes), signatories count (u8) and signed off count (u8), followed by
+/// the vote options and timestamps, which are not exposed.
+pub struct Proposal<'a> {
+    data: Ref<'a, [u8]>,
+    state: ProposalState,
+}
+
+impl<'a> Proposal<'a> {
+    const GOVERNANCE: usize = 1;
+    const GOVERNING_TOKEN_MINT: usize = 33;
+    const STATE: usize = 65;
+    const TOKEN_OWNER_RECORD: usize = 66;
+    const SIGNATORIES_COUNT: usize = 98;
+    const SIGNATORIES_SIGNED_OFF_COUNT: usize = 99;
+
+    /// Length of the account data up to the signed off count.
+    pub const HEADER_LEN: usize = 100;
+
+    /// Return the `Proposal` of the given account view.
+    ///
+    /// This method checks the owner, the account type, the header length and the state.
+    #[inline]
+    pub fn from_account_view(account_view: &'a AccountView, program_id: &Address) -> Result<Self> {
+        let data = try_borrow_governance_account(
+            account_view,
+            program_id,
+            [AccountType::PROPOSAL_V1, AccountType::PROPOSAL_V2],
+            Self::HEADER_LEN,
+        )?;
+
+        let state = match data[Self::STATE] {
+            0 => ProposalState::Draft,
+            1 => ProposalState::SigningOff,
+            2 => ProposalState::Voting,
+            3 => ProposalState::Succeeded,
+            4 => ProposalState::Executing,
+            5 => ProposalState::Completed,
+            6 => ProposalState::Cancelled,
+            7 => ProposalState::Defeated,
+            8 => ProposalState::ExecutingWithErrors,
+            9 => ProposalState::Vetoed,
+            _ => {
+                error_msg!(
+                    "Proposal::from_account_view: invalid state",
+                    ProgramError::InvalidAccountData,
+                );
+            }
+        };
+
+        Ok(Self { data, state })
+    }
+
+    /// Governance the proposal was created in, whose rules decided the vote.
+    #[inline(always)]
+    pub fn governance(&self) -> &Address {
+        read_address(&self.data, Self::GOVERNANCE)
+    }
+
+    /// Community or council mint of the realm the proposal is voted with.
+    #[inline(always)]
+    pub fn governing_token_mint(&self) -> &Address {
+        read_address(&self.data, Self::GOVERNING_TOKEN_MINT)
+    }
+
+    #[inline(always)]
+    pub fn state(&self) -> ProposalState {
+        self.state
+    }
+
+    /// Token owner record of the proposal creator.
+    #[inline(always)]
+    pub fn token_owner_record(&self) -> &Address {
+        read_address(&self.data, Self::TOKEN_OWNER_RECORD)
+    }
+
+    #[inline(always)]
+    pub fn signatories_count(&self) -> u8 {
+        self.data[Self::SIGNATORIES_COUNT]
+    }
+
+    #[inline(always)]
+    pub fn signatories_signed_off_count(&self) -> u8 {
+        self.data[Self::SIGNATORIES_SIGNED_OFF_COUNT]
+    }
+
+    /// Checks that the proposal passed in `governance`.
+    ///
+    /// Any realm member can create a proposal in any governance, always pin the governance the
+    /// parameters are controlled by. Programs that act on the same proposal only once also have
+    /// to record the proposal address.
+    #[inline]
+    pub fn try_check_passed(&self, governance: &Address) -> Result<()> {
+        if unlikely(!address_eq(self.governance(), governance)) {
+            error_msg!(
+                "Proposal::try_check_passed: wrong governance",
+                ErrorCode::InvalidAccount,
+            );
+        }
+
+        if unlikely(!self.state.is_passed()) {
+            error_msg!(
+                "Proposal::try_check_passed: proposal did not pass",
+                ErrorCode::ProposalNotPassed,
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    read_address, read_option_address, try_borrow_governance_account, try_skip_option_address,
+    AccountType,
+};
+use hayabusa_common::{address_eq, AccountView, Address, Ref};
+use hayabusa_errors::{ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+/// Zero-copy view over a realm account, v1 or v2.
+///
+/// Layout: account type (u8), community mint (32 bytes), config (min community weight to create a
+/// governance, max voter weight source, council mint `Option<Address>`), reserved (6 bytes),
+/// legacy (u16), authority (`Option<Address>`) and the name (borsh string). Integers are little
+/// endian.
+pub struct Realm<'a> {
+    data: Ref<'a, [u8]>,
+    authority: usize,
+    name: usize,
+}
+
+impl<'a> Realm<'a> {
+    const COMMUNITY_MINT: usize = 1;
+    const MIN_COMMUNITY_WEIGHT: usize = 41;
+    const COUNCIL_MINT: usize = 58;
+
+    /// Shortest account data, without council, authority nor name.
+    pub const MIN_LEN: usize = 72;
+
+    /// Return the `Realm` of the given account view.
+    ///
+    /// This method checks the owner, the account type and that the variable length fields are in
+    /// bounds.
+    #[inline]
+    pub fn from_account_view(account_view: &'a AccountView, program_id: &Address) -> Result<Self> {
+        let data = try_borrow_governance_account(
+            account_view,
+            program_id,
+            [AccountType::REALM_V1, AccountType::REALM_V2],
+            Self::MIN_LEN,
+        )?;
+
+        // reserved and legacy fields after the council mint
+        let authority = try_skip_option_address(&data, Self::COUNCIL_MINT)? + 8;
+        let name = try_skip_option_address(&data, authority)?;
+
+        let name_len = data
+            .get(name..name + 4)
+            .map(|len| u32::from_le_bytes(len.try_into().unwrap()) as usize);
+
+        if unlikely(!matches!(name_len, Some(len) if name + 4 + len <= data.len())) {
+            error_msg!(
+                "Realm::from_account_view: name out of bounds",
+                ProgramError::InvalidAccountData,
+            );
+        }
+
+        Ok(Self {
+            data,
+            authority,
+            name,
+        })
+    }
+
+    #[inline(always)]
+    pub fn community_mint(&self) -> &Address {
+        read_address(&self.data, Self::COMMUNITY_MINT)
+    }
+
+    #[inline(always)]
+    pub fn council_mint(&self) -> Option<&Address> {
+        read_option_address(&self.data, Self::COUNCIL_MINT)
+    }
+
+    /// Community tokens needed to create a governance.
+    #[inline(always)]
+    pub fn min_community_weight_to_create_governance(&self) -> u64 {
+        u64::from_le_bytes(
+            self.data[Self::MIN_COMMUNITY_WEIGHT..Self::MIN_COMMUNITY_WEIGHT + 8]
+                .try_into()
+                .unwrap(),
+        )
+    }
+
+    /// `None` once the realm is fully governed by its own proposals.
+    #[inline(always)]
+    pub fn authority(&self) -> Option<&Address> {
+        read_option_address(&self.data, self.authority)
+    }
+
+    /// Whether `mint` is the community or the council mint of the realm.
+    #[inline(always)]
+    pub fn is_governing_mint(&self, mint: &Address) -> bool {
+        address_eq(self.community_mint(), mint)
+            || self
+                .council_mint()
+                .is_some_and(|council| address_eq(council, mint))
+    }
+
+    /// Fails if the name is not UTF-8.
+    #[inline(always)]
+    pub fn name(&self) -> Result<&str> {
+        let len = u32::from_le_bytes(self.data[self.name..self.name + 4].try_into().unwrap());
+        let start = self.name + 4;
+
+        let Ok(name) = core::str::from_utf8(&self.data[start..start + len as usize]) else {
+            error_msg!("Realm::name: invalid utf-8", ProgramError::InvalidAccountData);
+        };
+
+        Ok(name)
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    read_address, read_option_address, try_borrow_governance_account, try_skip_option_address,
+    AccountType,
+};
+use hayabusa_common::{address_eq, AccountView, Address, Ref};
+use hayabusa_errors::Result;
+
+/// Zero-copy view over a token owner record, the governing tokens deposited by a voter, v1 or v2.
+///
+/// Layout: account type (u8), realm (32 bytes), governing token mint (32 bytes), governing token
+/// owner (32 bytes), deposit amount (u64), vote counters and reserved bytes (16 bytes) and the
+/// governance delegate (`Option<Address>`). Integers are little endian.
+pub struct TokenOwnerRecord<'a> {
+    data: Ref<'a, [u8]>,
+}
+
+impl<'a> TokenOwnerRecord<'a> {
+    const REALM: usize = 1;
+    const GOVERNING_TOKEN_MINT: usize = 33;
+    const GOVERNING_TOKEN_OWNER: usize = 65;
+    const DEPOSIT_AMOUNT: usize = 97;
+    const GOVERNANCE_DELEGATE: usize = 121;
+
+    /// Shortest account data, without delegate.
+    pub const MIN_LEN: usize = 122;
+
+    /// Return the `TokenOwnerRecord` of the given account view.
+    ///
+    /// This method checks the owner, the account type and the delegate bounds.
+    #[inline]
+    pub fn from_account_view(account_view: &'a AccountView, program_id: &Address) -> Result<Self> {
+        let data = try_borrow_governance_account(
+            account_view,
+            program_id,
+            [
+                AccountType::TOKEN_OWNER_RECORD_V1,
+                AccountType::TOKEN_OWNER_RECORD_V2,
+            ],
+            Self::MIN_LEN,
+        )?;
+
+        try_skip_option_address(&data, Self::GOVERNANCE_DELEGATE)?;
+
+        Ok(Self { data })
+    }
+
+    #[inline(always)]
+    pub fn realm(&self) -> &Address {
+        read_address(&self.data, Self::REALM)
+    }
+
+    #[inline(always)]
+    pub fn governing_token_mint(&self) -> &Address {
+        read_address(&self.data, Self::GOVERNING_TOKEN_MINT)
+    }
+
+    #[inline(always)]
+    pub fn governing_token_owner(&self) -> &Address {
+        read_address(&self.data, Self::GOVERNING_TOKEN_OWNER)
+    }
+
+    /// Governing tokens deposited, the voting weight without voter weight plugins.
+    #[inline(always)]
+    pub fn governing_token_deposit_amount(&self) -> u64 {
+        u64::from_le_bytes(
+            self.data[Self::DEPOSIT_AMOUNT..Self::DEPOSIT_AMOUNT + 8]
+                .try_into()
+                .unwrap(),
+        )
+    }
+
+    /// Can vote and create proposals in the name of the owner.
+    #[inline(always)]
+    pub fn governance_delegate(&self) -> Option<&Address> {
+        read_option_address(&self.data, Self::GOVERNANCE_DELEGATE)
+    }
+
+    /// Whether `authority` is the owner or the delegate.
+    #[inline(always)]
+    pub fn is_owner_or_delegate(&self, authority: &Address) -> bool {
+        address_eq(self.governing_token_owner(), authority)
+            || self
+                .governance_delegate()
+                .is_some_and(|delegate| address_eq(delegate, authority))
+    }
+}
@@ -0,0 +1,497 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Anchor-compatible on-chain IDL storage.
+//!
+//! The IDL account lives at `create_with_seed(base, "anchor:idl", program_id)`, `base` being the
+//! PDA of the program without seeds, and holds:
+//!
+//! ```text
+//! discriminator: [u8; 8] | authority: Address | data_len: u32 (le) | data: [u8; data_len]
+//! ```
+//!
+//! `data` is the zlib-compressed IDL json. The instructions use the Anchor encoding, a
+//! [`IDL_IX_TAG`] prefix followed by a borsh `IdlInstruction`, and are routed by `dispatch!`, so
+//! `anchor idl init/write/set-authority` and explorers work unchanged. They are only handled with
+//! the `idl` feature of `hayabusa`.
+//!
+//! Like in Anchor, whoever creates the IDL account becomes its authority: initialize it right
+//! after deploying the program.
+
+#![no_std]
+
+use hayabusa_common::{AccountView, Address};
+use hayabusa_errors::Result;
+use hayabusa_syscalls::{sha256, try_find_program_address};
+
+/// Tag of the IDL instructions, `0x0a69e9a778bcf440` in little endian.
+pub const IDL_IX_TAG: &[u8] = &[0x40, 0xf4, 0xbc, 0x78, 0xa7, 0xe9, 0x69, 0x0a];
+
+/// Seed of the IDL account address, see [`find_idl_address`].
+pub const IDL_SEED: &str = "anchor:idl";
+
+/// Anchor discriminator of `IdlAccount`.
+pub const IDL_ACCOUNT_DISCRIMINATOR: [u8; 8] = [140, 36, 166, 2, 103, 197, 33, 164];
+
+/// Length of the IDL account header, discriminator, authority and data length.
+pub const IDL_HEADER_LEN: usize = 8 + 32 + 4;
+
+/// Most an IDL account is created with or grows by in a single instruction, as in Anchor.
+pub const IDL_MAX_GROWTH: usize = 10_000;
+
+/// Address of the IDL account of `program_id` and the bump of its base PDA.
+#[inline]
+pub fn find_idl_address(program_id: &Address) -> Result<(Address, u8)> {
+    let (base, bump) = try_find_program_address(&[], program_id)?;
+
+    Ok((
+        Address::new_from_array(sha256(&[
+            base.as_ref(),
+            IDL_SEED.as_bytes(),
+            program_id.as_ref(),
+        ])),
+        bump,
+    ))
+}
+
+/// Handles an IDL instruction, `data` is the instruction data after [`IDL_IX_TAG`].
+#[inline(never)]
+pub fn try_process_idl_ix(
+    program_id: &Address,
+    account_views: &[AccountView],
+    data: &[u8],
+) -> Result<()> {
+    #[cfg(feature = "instructions")]
+    return instructions::try_process(program_id, account_views, data);
+
+    #[cfg(not(feature = "instructions"))]
+    {
+        use hayabusa_errors::{ErrorCode, ProgramError};
+        use hayabusa_utility::error_msg;
+
+        let _ = (program_id, account_views, data);
+
+        error_msg!(
+            "try_process_idl_ix: IDL instructions are not enabled",
+            ErrorCode::UnknownInstruction,
+        );
+    }
+}
+
+#[cfg(feature = "instructions")]
+mod instructions {
+    use super::*;
+    use hayabusa_common::address_eq;
+    use hayabusa_cpi::CpiCtx;
+    use hayabusa_errors::{ErrorCode, ProgramError};
+    use hayabusa_ser::try_realloc_account;
+    use hayabusa_system_program::instructions::{create_account_with_seed, CreateAccountWithSeed};
+    use hayabusa_utility::{error_msg, hint::unlikely};
+    use solana_instruction_view::cpi::{Seed, Signer};
+
+    const CREATE: u8 = 0;
+    const WRITE: u8 = 2;
+    const SET_AUTHORITY: u8 = 4;
+    const RESIZE: u8 = 6;
+
+    pub(super) fn try_process(
+        program_id: &Address,
+        account_views: &[AccountView],
+        data: &[u8],
+    ) -> Result<()> {
+        let Some((&variant, args)) = data.split_first() else {
+            error_msg!(
+                "try_process_idl_ix: missing instruction",
+                ProgramError::InvalidInstructionData,
+            );
+        };
+
+        match variant {
+            CREATE => try_idl_init(program_id, account_views, read_u64(args)?),
+            WRITE => try_idl_write(program_id, account_views, read_bytes(args)?),
+            SET_AUTHORITY => try_idl_set_authority(program_id, account_views, &read_address(args)?),
+            RESIZE => try_idl_resize(program_id, account_views, read_u64(args)?),
+            _ => {
+                error_msg!(
+                    "try_process_idl_ix: unsupported IDL instruction",
+                    ErrorCode::UnknownInstruction,
+                );
+            }
+        }
+    }
+
+    /// Accounts: `[payer (signer, writable), idl (writable), base, system_program, program]`
+    fn try_idl_init(
+        program_id: &Address,
+        account_views: &[AccountView],
+        data_len: u64,
+    ) -> Result<()> {
+        let [payer, idl, base, system_program, ..] = account_views else {
+            error_msg!(
+                "idl_init: not enough accounts",
+                ProgramError::NotEnoughAccountKeys,
+            );
+        };
+
+        if unlikely(!payer.is_signer()) {
+            error_msg!(
+                "idl_init: payer is not a signer",
+                ErrorCode::AccountNotSigner
+            );
+        }
+
+        let (expected, bump) = find_idl_address(program_id)?;
+
+        if unlikely(!address_eq(idl.address(), &expected)) {
+            error_msg!("idl_init: invalid IDL account", ErrorCode::InvalidAccount);
+        }
+
+        let space = (IDL_HEADER_LEN as u64)
+            .saturating_add(data_len)
+            .min(IDL_MAX_GROWTH as u64);
+
+        let bump = [bump];
+        let seeds = [Seed::from(&bump)];
+
+        create_account_with_seed(
+            CpiCtx::try_new_with_signer(
+                system_program,
+                CreateAccountWithSeed {
+                    from: payer,
+                    to: idl,
+                    base,
+                },
+                &[Signer::from(&seeds)],
+            )?,
+            IDL_SEED,
+            program_id,
+            space,
+        )?;
+
+        let mut data = idl.try_borrow_mut()?;
+        data[..8].copy_from_slice(&IDL_ACCOUNT_DISCRIMINATOR);
+        data[8..40].copy_from_slice(payer.address().as_ref());
+
+        Ok(())
+    }
+
+    /// Accounts: `[idl (writable), authority (signer)]`, appends `bytes` to the IDL data.
+    fn try_idl_write(
+        program_id: &Address,
+        account_views: &[AccountView],
+        bytes: &[u8],
+    ) -> Result<()> {
+        let idl = try_idl_account(program_id, account_views)?;
+        let mut data = idl.try_borrow_mut()?;
+
+        let data_len = u32::from_le_bytes(data[40..44].try_into().unwrap()) as usize;
+        let start = IDL_HEADER_LEN + data_len;
+        let end = start + bytes.len();
+
+        if unlikely(end > data.len()) {
+            error_msg!(
+                "idl_write: IDL account too small",
+                ProgramError::AccountDataTooSmall
+            );
+        }
+
+        data[start..end].copy_from_slice(bytes);
+        data[40..44].copy_from_slice(&((data_len + bytes.len()) as u32).to_le_bytes());
+
+        Ok(())
+    }
+
+    /// Accounts: `[idl (writable), authority (signer)]`
+    fn try_idl_set_authority(
+        program_id: &Address,
+        account_views: &[AccountView],
+        new_authority: &Address,
+    ) -> Result<()> {
+        let idl = try_idl_account(program_id, account_views)?;
+        idl.try_borrow_mut()?[8..40].copy_from_slice(new_authority.as_ref());
+
+        Ok(())
+    }
+
+    /// Accounts: `[idl (writable), authority (signer, writable), system_program]`, grows the IDL
+    /// account towards `data_len` bytes by at most [`IDL_MAX_GROWTH`].
+    fn try_idl_resize(
+        program_id: &Address,
+        account_views: &[AccountView],
+        data_len: u64,
+    ) -> Result<()> {
+        let idl = try_idl_account(program_id, account_views)?;
+
+        let [_, authority, system_program, ..] = account_views else {
+            error_msg!(
+                "idl_resize: not enough accounts",
+                ProgramError::NotEnoughAccountKeys,
+            );
+        };
+
+        let current_len = idl.data_len();
+        let stored_len = IDL_HEADER_LEN
+            + u32::from_le_bytes(idl.try_borrow()?[40..44].try_into().unwrap()) as usize;
+
+        if unlikely((data_len as usize) < stored_len) {
+            error_msg!(
+                "idl_resize: new length smaller than the stored IDL",
+                ProgramError::InvalidArgument,
+            );
+        }
+
+        let new_len = current_len
+            + (data_len as usize)
+                .saturating_sub(current_len)
+                .min(IDL_MAX_GROWTH);
+
+        if new_len > current_len {
+            try_realloc_account(idl, new_len, authority, system_program)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks the IDL account (owner, discriminator) and that its authority signed.
+    fn try_idl_account<'a>(
+        program_id: &Address,
+        account_views: &'a [AccountView],
+    ) -> Result<&'a AccountView> {
+        let [idl, authority, ..] = account_views else {
+            error_msg!(
+                "try_idl_account: not enough accounts",
+                ProgramError::NotEnoughAccountKeys,
+            );
+        };
+
+        if unlikely(!idl.owned_by(program_id)) {
+            error_msg!(
+                "try_idl_account: wrong account owner",
+                ProgramError::InvalidAccountOwner,
+            );
+        }
+
+        let data = idl.try_borrow()?;
+
+        if unlikely(data.len() < IDL_HEADER_LEN || data[..8] != IDL_ACCOUNT_DISCRIMINATOR) {
+            error_msg!(
+                "try_idl_account: invalid IDL account",
+                ProgramError::InvalidAccountData,
+            );
+        }
+
+        if unlikely(!authority.is_signer() || authority.address().as_ref() != &data[8..40]) {
+            error_msg!(
+                "try_idl_account: authority mismatch",
+                ErrorCode::AccountNotSigner,
+            );
+        }
+
+        Ok(idl)
+    }
+
+    #[inline(always)]
+    fn read_u64(args: &[u8]) -> Result<u64> {
+        let Some(bytes) = args.get(..8) else {
+            error_msg!(
+                "try_process_idl_ix: invalid instruction data",
+                ProgramError::InvalidInstructionData,
+            );
+        };
+
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    #[inline(always)]
+    fn read_address(args: &[u8]) -> Result<Address> {
+        let Some(bytes) = args.get(..32) else {
+            error_msg!(
+                "try_process_idl_ix: invalid instruction data",
+                ProgramError::InvalidInstructionData,
+            );
+        };
+
+        Ok(Address::new_from_array(bytes.try_into().unwrap()))
+    }
+
+    /// borsh `Vec<u8>`, a `u32` length followed by the bytes
+    #[inline(always)]
+    fn read_bytes(args: &[u8]) -> Result<&[u8]> {
+        let bytes = args.get(..4).and_then(|len| {
+            let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+            args.get(4..4 + len)
+        });
+
+        let Some(bytes) = bytes else {
+            error_msg!(
+                "try_process_idl_ix: invalid instruction data",
+                ProgramError::InvalidInstructionData,
+            );
+        };
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use hayabusa_common::mock::MockAccount;
+    use hayabusa_errors::{ErrorCode, ProgramError};
+    use std::vec::Vec;
+
+    const PROGRAM_ID: Address = Address::new_from_array([7; 32]);
+    const AUTHORITY: Address = Address::new_from_array([1; 32]);
+
+    #[test]
+    fn test_anchor_constants() {
+        let mut tag = sha256(&[IDL_SEED.as_bytes()]);
+        tag[..8].reverse();
+        assert_eq!(IDL_IX_TAG, &tag[..8]);
+
+        let discriminator = sha256(&[b"account:IdlAccount"]);
+        assert_eq!(IDL_ACCOUNT_DISCRIMINATOR, discriminator[..8]);
+    }
+
+    /// IDL account of `PROGRAM_ID` holding `stored`, with room for `capacity` bytes of data
+    fn idl_account(stored: &[u8], capacity: usize) -> MockAccount {
+        let mut data = Vec::from(IDL_ACCOUNT_DISCRIMINATOR);
+        data.extend_from_slice(AUTHORITY.as_ref());
+        data.extend_from_slice(&(stored.len() as u32).to_le_bytes());
+        data.extend_from_slice(stored);
+        data.resize(IDL_HEADER_LEN + capacity, 0);
+
+        let (address, _) = find_idl_address(&PROGRAM_ID).unwrap();
+        MockAccount::new(address, PROGRAM_ID, &data)
+    }
+
+    fn write(bytes: &[u8]) -> Vec<u8> {
+        let mut data = std::vec![2];
+        data.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        data.extend_from_slice(bytes);
+        data
+    }
+
+    fn stored(idl: &AccountView) -> Vec<u8> {
+        let data = idl.try_borrow().unwrap();
+        let len = u32::from_le_bytes(data[40..44].try_into().unwrap()) as usize;
+        data[IDL_HEADER_LEN..IDL_HEADER_LEN + len].to_vec()
+    }
+
+    #[test]
+    fn test_write() {
+        let mut accounts = [
+            idl_account(b"ab", 4),
+            MockAccount::new(AUTHORITY, Address::default(), &[]).signer(),
+        ];
+        let account_views = accounts.each_mut().map(|account| account.view());
+
+        try_process_idl_ix(&PROGRAM_ID, &account_views, &write(b"cd")).unwrap();
+        assert_eq!(stored(&account_views[0]), b"abcd");
+
+        assert_eq!(
+            try_process_idl_ix(&PROGRAM_ID, &account_views, &write(b"e")).err(),
+            Some(ProgramError::AccountDataTooSmall)
+        );
+        assert_eq!(
+            try_process_idl_ix(&PROGRAM_ID, &account_views, &[2, 1, 0, 0, 0]).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_set_authority() {
+        let new_authority = Address::new_from_array([2; 32]);
+        let mut data = std::vec![4];
+        data.extend_from_slice(new_authority.as_ref());
+
+        let mut accounts = [
+            idl_account(&[], 0),
+            MockAccount::new(AUTHORITY, Address::default(), &[]).signer(),
+        ];
+        let account_views = accounts.each_mut().map(|account| account.view());
+
+        try_process_idl_ix(&PROGRAM_ID, &account_views, &data).unwrap();
+        assert_eq!(
+            &account_views[0].try_borrow().unwrap()[8..40],
+            new_authority.as_ref()
+        );
+
+        // the previous authority can no longer write
+        assert_eq!(
+            try_process_idl_ix(&PROGRAM_ID, &account_views, &data).err(),
+            Some(ErrorCode::AccountNotSigner.into())
+        );
+    }
+
+    #[test]
+    fn test_invalid_idl_account() {
+        // not signed by the authority
+        let mut accounts = [
+            idl_account(&[], 1),
+            MockAccount::new(AUTHORITY, Address::default(), &[]),
+        ];
+        let account_views = accounts.each_mut().map(|account| account.view());
+        assert_eq!(
+            try_process_idl_ix(&PROGRAM_ID, &account_views, &write(b"a")).err(),
+            Some(ErrorCode::AccountNotSigner.into())
+        );
+
+        // owned by another program
+        let mut accounts = [
+            idl_account(&[], 1),
+            MockAccount::new(AUTHORITY, Address::default(), &[]).signer(),
+        ];
+        let account_views = accounts.each_mut().map(|account| account.view());
+        assert_eq!(
+            try_process_idl_ix(&AUTHORITY, &account_views, &write(b"a")).err(),
+            Some(ProgramError::InvalidAccountOwner)
+        );
+
+        // not an IDL account
+        let (address, _) = find_idl_address(&PROGRAM_ID).unwrap();
+        let mut accounts = [
+            MockAccount::new(address, PROGRAM_ID, &[0; IDL_HEADER_LEN + 1]),
+            MockAccount::new(AUTHORITY, Address::default(), &[]).signer(),
+        ];
+        let account_views = accounts.each_mut().map(|account| account.view());
+        assert_eq!(
+            try_process_idl_ix(&PROGRAM_ID, &account_views, &write(b"a")).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_init_checks() {
+        let mut payer = MockAccount::new(AUTHORITY, Address::default(), &[]).signer();
+        let mut idl = MockAccount::new(Address::new_from_array([3; 32]), Address::default(), &[]);
+        let mut base = MockAccount::new(Address::default(), Address::default(), &[]);
+        let mut system_program = MockAccount::new(Address::default(), Address::default(), &[]);
+        let account_views = [
+            payer.view(),
+            idl.view(),
+            base.view(),
+            system_program.view(),
+        ];
+
+        let mut data = std::vec![0];
+        data.extend_from_slice(&100u64.to_le_bytes());
+
+        // not the IDL address of the program
+        assert_eq!(
+            try_process_idl_ix(&PROGRAM_ID, &account_views, &data).err(),
+            Some(ErrorCode::InvalidAccount.into())
+        );
+        assert_eq!(
+            try_process_idl_ix(&PROGRAM_ID, &account_views[..3], &data).err(),
+            Some(ProgramError::NotEnoughAccountKeys)
+        );
+        // unsupported variant, e.g. Anchor's `CreateBuffer`
+        assert_eq!(
+            try_process_idl_ix(&PROGRAM_ID, &account_views, &[1]).err(),
+            Some(ErrorCode::UnknownInstruction.into())
+        );
+    }
+}
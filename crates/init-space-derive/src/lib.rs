@@ -0,0 +1,61 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parenthesized, parse_macro_input, DeriveInput, Expr, Token, Type};
+
+/// Implements `InitSpace` from the size of the struct plus the regions declared after it, and
+/// the length of its `Discriminator`.
+///
+/// ```ignore
+/// #[account]
+/// #[derive(InitSpace)]
+/// #[init_space(tail(Entry, 16))]
+/// pub struct Registry { .. }
+/// ```
+///
+///   - `tail(T, N)`: room for `N` elements of a `ZcAccountWithTail` tail
+///   - `zc_vec(T, N)`: a `ZcVec` of capacity `N`, length prefix included
+#[proc_macro_derive(InitSpace, attributes(init_space))]
+pub fn derive_init_space(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let mut regions = Vec::new();
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("init_space")) {
+        let parsed = attr.parse_nested_meta(|meta| {
+            let content;
+            parenthesized!(content in meta.input);
+            let ty: Type = content.parse()?;
+            content.parse::<Token![,]>()?;
+            let capacity: Expr = content.parse()?;
+
+            if meta.path.is_ident("tail") {
+                regions.push(quote! { (#capacity) * core::mem::size_of::<#ty>() });
+            } else if meta.path.is_ident("zc_vec") {
+                regions.push(quote! { zc_vec_space::<#ty>(#capacity) });
+            } else {
+                return Err(meta.error("expected `tail(T, N)` or `zc_vec(T, N)`"));
+            }
+
+            Ok(())
+        });
+
+        if let Err(e) = parsed {
+            return e.to_compile_error().into();
+        }
+    }
+
+    let expanded = quote! {
+        impl InitSpace for #name {
+            const INIT_SPACE: usize = core::mem::size_of::<#name>() #( + #regions )*;
+
+            const DISCRIMINATED_INIT_SPACE: usize =
+                <Self as Discriminator>::DISCRIMINATOR.len() + Self::INIT_SPACE;
+        }
+    };
+
+    TokenStream::from(expanded)
+}
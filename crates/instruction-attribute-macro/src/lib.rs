@@ -0,0 +1,791 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    spanned::Spanned,
+    Expr, FnArg, Ident, ItemFn, Pat, Result,
+};
+
+/// Usage:
+///   #[instruction]
+///   fn update_counter<'ix>(ctx: Ctx<'ix, UpdateCounter<'ix>>, amount: u64) -> Result<()> { .. }
+///
+/// Expands to the handler plus:
+///   #[repr(C, packed)]
+///   struct UpdateCounterInstruction { pub amount: u64 }   // + Pod, Discriminator
+///   impl DecodeIx for UpdateCounterInstruction { .. }
+///   impl ProcessIx for UpdateCounterInstruction { .. }    // decode + Ctx::run_with_instruction(handler)
+///
+/// `#[instruction(inline(always))]` (the default) inlines the `ProcessIx` glue into the
+/// `dispatch!` match, `#[instruction(inline(never))]` keeps it out of line to save binary size and
+/// stack in large programs, at the cost of a function call per instruction.
+///
+/// `#[instruction(varint)]` packs the arguments as varints (see `hayabusa_decode_instruction::varint`)
+/// instead of their raw bytes, which shortens instructions with many small integers. Clients build
+/// the data with the generated `encode`.
+///
+/// `#[instruction(borsh)]` decodes the arguments with borsh instead, so they can be dynamic types
+/// like `Vec<u8>`, `String` or `Option<T>` that the pod layout can't express. Requires hayabusa's
+/// `borsh` feature, which brings in an allocator. Clients build the data with the generated
+/// `encode`. Arguments without a fixed bound, like `Vec<u8>`, take one with `#[max_len(N)]`.
+///
+/// `#[instruction(zero_copy)]` lets the handler borrow slices from the instruction data,
+/// `memo: &'ix [u8], proofs: &'ix [[u8; 32]]`, without an allocator. The fixed-size arguments
+/// come first, packed, then every slice as a `u32` element count followed by its elements, which
+/// must have an alignment of 1. Clients build the data with the generated `encode`.
+///
+/// Arguments are validated right after decoding, before the accounts are parsed:
+/// `#[range(1..=10_000)]` rejects values outside the range with `ErrorCode::ArgumentOutOfRange`,
+/// `#[non_zero]` rejects the default value (`0`, the zero address, ..) with
+/// `ErrorCode::ArgumentZero`.
+///
+/// Every instruction gets a `MAX_DATA_LEN` const, the longest data it accepts, discriminator
+/// included. `dispatch!` rejects longer data before decoding and clients can validate user input
+/// against it.
+///
+/// `#[instruction(discriminator = [1])]` replaces the sha256 based discriminator, e.g. to keep
+/// the instruction tags of a native or Anchor program (`discriminator = "global:update_counter"`)
+/// being ported, see `#[derive(Discriminator)]`. `#[instruction(disc_len = 1)]` shortens the
+/// hash to 1 or 4 bytes, `dispatch!` then matches on that length.
+///
+/// `#[instruction(cpi)]` also generates `UpdateCounterInstruction::cpi`, which invokes the
+/// instruction from another program through a `CpiCtx` of the `UpdateCounterCpi` accounts
+/// generated by `#[derive(FromAccountViews)]` with `#[accounts(cpi)]`, so integrators depending
+/// on the program crate (with its `no-entrypoint` feature) don't rebuild the instruction data by
+/// hand. Pod and varint instructions only, the data is built on the stack.
+///
+/// `#[instruction(client)]` with hayabusa's `client` feature also generates an off-chain
+/// builder, `update_counter_ix(&program_id, accounts, amount) -> Instruction`, taking the
+/// `UpdateCounterClient` account addresses generated by `#[derive(FromAccountViews)]` with
+/// `#[accounts(client)]` (or the account metas of hand-written accounts structs). Without the
+/// feature, e.g. when building the program itself, nothing is generated.
+#[proc_macro_attribute]
+pub fn instruction(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as InstructionArgs);
+    let handler = parse_macro_input!(item as ItemFn);
+
+    match expand_instruction(args, handler) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+enum Inline {
+    Always,
+    Never,
+}
+
+enum Mode {
+    Pod,
+    Varint,
+    Borsh,
+    ZeroCopy,
+}
+
+struct InstructionArgs {
+    inline: Inline,
+    mode: Mode,
+    discriminator: Option<Expr>,
+    disc_len: Option<syn::LitInt>,
+    cpi: bool,
+    client: bool,
+}
+
+impl Parse for InstructionArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = Self {
+            inline: Inline::Always,
+            mode: Mode::Pod,
+            discriminator: None,
+            disc_len: None,
+            cpi: false,
+            client: false,
+        };
+
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+
+            if key == "varint" || key == "borsh" || key == "zero_copy" {
+                if !matches!(args.mode, Mode::Pod) {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "`varint`, `borsh` and `zero_copy` are mutually exclusive",
+                    ));
+                }
+
+                args.mode = if key == "varint" {
+                    Mode::Varint
+                } else if key == "borsh" {
+                    Mode::Borsh
+                } else {
+                    Mode::ZeroCopy
+                };
+            } else if key == "discriminator" {
+                input.parse::<syn::Token![=]>()?;
+                args.discriminator = Some(input.parse()?);
+            } else if key == "disc_len" {
+                input.parse::<syn::Token![=]>()?;
+                args.disc_len = Some(input.parse()?);
+            } else if key == "cpi" {
+                args.cpi = true;
+            } else if key == "client" {
+                args.client = true;
+            } else if key == "inline" {
+                let content;
+                syn::parenthesized!(content in input);
+                let value: Ident = content.parse()?;
+
+                args.inline = match value.to_string().as_str() {
+                    "always" => Inline::Always,
+                    "never" => Inline::Never,
+                    _ => {
+                        return Err(syn::Error::new(
+                            value.span(),
+                            "expected `always` or `never`",
+                        ))
+                    }
+                };
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "expected `inline(always|never)`, `varint`, `borsh`, `zero_copy`, `cpi`, `client`, `discriminator = ..` or `disc_len = N`",
+                ));
+            }
+
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+fn expand_instruction(
+    args: InstructionArgs,
+    mut handler: ItemFn,
+) -> Result<proc_macro2::TokenStream> {
+    let max_lens = take_max_lens(&mut handler, &args.mode)?;
+    let arg_checks = take_arg_checks(&mut handler, &args.mode)?;
+
+    let vis = &handler.vis;
+    let handler_name = &handler.sig.ident;
+    let ix_name = format_ident!("{}Instruction", to_pascal_case(&handler_name.to_string()));
+
+    let mut inputs = handler.sig.inputs.iter();
+
+    // first argument is the context
+    let Some(ctx_arg) = inputs.next() else {
+        return Err(syn::Error::new(
+            handler.sig.span(),
+            "#[instruction] handlers take a `Ctx` as first argument",
+        ));
+    };
+
+    let mut arg_names = Vec::new();
+    let mut arg_types = Vec::new();
+
+    for input in inputs {
+        let FnArg::Typed(arg) = input else {
+            return Err(syn::Error::new(input.span(), "unexpected receiver"));
+        };
+
+        let Pat::Ident(pat) = arg.pat.as_ref() else {
+            return Err(syn::Error::new(
+                arg.pat.span(),
+                "#[instruction] arguments must be plain identifiers",
+            ));
+        };
+
+        arg_names.push(&pat.ident);
+        arg_types.push(arg.ty.as_ref());
+    }
+
+    // zero-copy instructions borrow their slice arguments from the instruction data
+    let ix_lifetime = match args.mode {
+        Mode::ZeroCopy => {
+            let Some(lifetime) = handler.sig.generics.lifetimes().next() else {
+                return Err(syn::Error::new(
+                    handler.sig.span(),
+                    "`zero_copy` handlers take the lifetime of their slice arguments, e.g. `fn handler<'ix>(..)`",
+                ));
+            };
+            Some(lifetime.lifetime.clone())
+        }
+        _ => None,
+    };
+    let ix_generics = match &ix_lifetime {
+        Some(lifetime) => quote! { <#lifetime> },
+        None => quote! {},
+    };
+    let (ix_ty, decode_ty) = match &ix_lifetime {
+        Some(_) => (quote! { #ix_name<'_> }, quote! { #ix_name<'_> }),
+        None => (quote! { #ix_name }, quote! { Self }),
+    };
+    let slice_elems = match args.mode {
+        Mode::ZeroCopy => zero_copy_slice_elems(&arg_types)?,
+        _ => Vec::new(),
+    };
+
+    let disc_len = quote! { <Self as Discriminator>::DISCRIMINATOR.len() };
+
+    let name = handler_name.to_string();
+    let accounts = match accounts_type(ctx_arg) {
+        Some(mut accounts) => {
+            with_lifetime(&mut accounts, "'static");
+            quote! { <#accounts as FromAccountViews<'static>>::ACCOUNTS }
+        }
+        None => quote! { &[] },
+    };
+
+    let max_data_len = match args.mode {
+        Mode::Pod => quote! { #disc_len + core::mem::size_of::<Self>() },
+        Mode::Varint => quote! { Self::MAX_ENCODED_LEN },
+        Mode::ZeroCopy => quote! { usize::MAX },
+        Mode::Borsh => {
+            let arg_max_lens = arg_types
+                .iter()
+                .zip(&max_lens)
+                .map(|(ty, max_len)| match max_len {
+                    Some(max_len) => quote! { #max_len },
+                    None => quote! { <#ty as BorshMaxLen>::BORSH_MAX_LEN },
+                });
+            quote! { #disc_len #(+ #arg_max_lens)* }
+        }
+    };
+
+    let inline = match args.inline {
+        Inline::Always => quote! { #[inline(always)] },
+        Inline::Never => quote! { #[inline(never)] },
+    };
+
+    let decode = match args.mode {
+        Mode::Varint => quote! {
+            impl<'ix> DecodeIx<'ix> for #ix_name {
+                const MAX_DATA_LEN: usize = #ix_name::MAX_DATA_LEN;
+
+                #[inline(always)]
+                fn decode(bytes: &'ix [u8]) -> Result<Self> {
+                    let mut bytes = bytes;
+                    let ix = Self {
+                        #(#arg_names: <#arg_types as Varint>::try_read_varint(&mut bytes)?,)*
+                    };
+
+                    if unlikely(!bytes.is_empty()) {
+                        return Err(ProgramError::InvalidInstructionData);
+                    }
+
+                    Ok(ix)
+                }
+            }
+
+            impl #ix_name {
+                /// Longest instruction data, discriminator included.
+                pub const MAX_ENCODED_LEN: usize = #disc_len #(+ <#arg_types as Varint>::MAX_LEN)*;
+
+                /// Writes the instruction data, discriminator then the varint packed arguments,
+                /// returning its length or `None` if `out` is too short.
+                pub fn encode(&self, out: &mut [u8]) -> Option<usize> {
+                    let disc = <Self as Discriminator>::DISCRIMINATOR;
+                    out.get_mut(..disc.len())?.copy_from_slice(disc);
+
+                    let mut len = disc.len();
+                    #(
+                        let value = self.#arg_names;
+                        len += Varint::write_varint(&value, out.get_mut(len..)?)?;
+                    )*
+
+                    Some(len)
+                }
+            }
+        },
+        Mode::Borsh => quote! {
+            impl<'ix> DecodeIx<'ix> for #ix_name {
+                const MAX_DATA_LEN: usize = #ix_name::MAX_DATA_LEN;
+
+                #[inline(always)]
+                fn decode(bytes: &'ix [u8]) -> Result<Self> {
+                    borsh::from_slice(bytes).map_err(|_| ProgramError::InvalidInstructionData)
+                }
+            }
+
+            impl #ix_name {
+                /// Writes the instruction data, discriminator then the borsh encoded arguments.
+                pub fn encode<W: borsh::io::Write>(&self, out: &mut W) -> borsh::io::Result<()> {
+                    out.write_all(<Self as Discriminator>::DISCRIMINATOR)?;
+                    borsh::BorshSerialize::serialize(self, out)
+                }
+            }
+        },
+        Mode::ZeroCopy => {
+            let reads = slice_elems.iter().map(|elem| match elem {
+                Some(_) => quote! { try_read_slice_arg(&mut bytes)? },
+                None => quote! { try_read_pod_arg(&mut bytes)? },
+            });
+            let lens =
+                arg_types
+                    .iter()
+                    .zip(&arg_names)
+                    .zip(&slice_elems)
+                    .map(|((ty, name), elem)| match elem {
+                        Some(elem) => quote! { slice_arg_len::<#elem>(self.#name.len()) },
+                        None => quote! { core::mem::size_of::<#ty>() },
+                    });
+            let writes = arg_names
+                .iter()
+                .zip(&slice_elems)
+                .map(|(name, elem)| match elem {
+                    Some(_) => quote! { write_slice_arg(self.#name, out, &mut len)?; },
+                    None => quote! { write_pod_arg(&self.#name, out, &mut len)?; },
+                });
+
+            quote! {
+                impl #ix_generics DecodeIx #ix_generics for #ix_name #ix_generics {
+                    #[inline(always)]
+                    fn decode(bytes: &#ix_lifetime [u8]) -> Result<Self> {
+                        let mut bytes = bytes;
+                        let ix = Self {
+                            #(#arg_names: #reads,)*
+                        };
+
+                        if unlikely(!bytes.is_empty()) {
+                            return Err(ProgramError::InvalidInstructionData);
+                        }
+
+                        Ok(ix)
+                    }
+                }
+
+                impl #ix_ty {
+                    /// Length of the instruction data, discriminator included.
+                    pub fn encoded_len(&self) -> usize {
+                        #disc_len #(+ #lens)*
+                    }
+
+                    /// Writes the instruction data, discriminator, fixed-size arguments then the
+                    /// length-prefixed slices, returning its length or `None` if `out` is too
+                    /// short.
+                    pub fn encode(&self, out: &mut [u8]) -> Option<usize> {
+                        let disc = <Self as Discriminator>::DISCRIMINATOR;
+                        out.get_mut(..disc.len())?.copy_from_slice(disc);
+
+                        let mut len = disc.len();
+                        #(#writes)*
+
+                        Some(len)
+                    }
+                }
+            }
+        }
+        Mode::Pod => quote! {
+            impl<'ix> DecodeIx<'ix> for #ix_name {
+                const MAX_DATA_LEN: usize = #ix_name::MAX_DATA_LEN;
+
+                #[inline(always)]
+                fn decode(bytes: &'ix [u8]) -> Result<Self> {
+                    ::bytemuck::try_pod_read_unaligned(bytes)
+                        .map_err(|_| ProgramError::InvalidInstructionData)
+                }
+            }
+        },
+    };
+
+    let discriminator = match (&args.discriminator, &args.disc_len) {
+        (None, None) => None,
+        (Some(discriminator), None) => Some(quote! { #[discriminator(#discriminator)] }),
+        (None, Some(len)) => Some(quote! { #[discriminator(len = #len)] }),
+        (Some(discriminator), Some(len)) => {
+            Some(quote! { #[discriminator(#discriminator, len = #len)] })
+        }
+    };
+
+    let ix_struct = match args.mode {
+        Mode::Borsh => quote! {
+            #[derive(Discriminator, borsh::BorshSerialize, borsh::BorshDeserialize)]
+            #[borsh(crate = "borsh")]
+            #discriminator
+            #vis struct #ix_name {
+                #(pub #arg_names: #arg_types,)*
+            }
+        },
+        Mode::ZeroCopy => quote! {
+            #[derive(Clone, Copy, Discriminator)]
+            #discriminator
+            #vis struct #ix_name #ix_generics {
+                #(pub #arg_names: #arg_types,)*
+            }
+        },
+        Mode::Pod | Mode::Varint => quote! {
+            #[derive(Clone, Copy, ::bytemuck::Pod, ::bytemuck::Zeroable, Discriminator)]
+            #discriminator
+            #[repr(C, packed)]
+            #vis struct #ix_name {
+                #(pub #arg_names: #arg_types,)*
+            }
+        },
+    };
+
+    let cpi = if args.cpi {
+        let cpi_accounts = cpi_accounts_type(ctx_arg)?;
+
+        let data = match args.mode {
+            Mode::Pod => quote! {
+                let mut data = [0u8; #ix_name::MAX_DATA_LEN];
+                data[..disc.len()].copy_from_slice(disc);
+                data[disc.len()..].copy_from_slice(::bytemuck::bytes_of(&ix));
+                let data = &data[..];
+            },
+            Mode::Varint => quote! {
+                let mut data = [0u8; #ix_name::MAX_ENCODED_LEN];
+                let Some(len) = ix.encode(&mut data) else {
+                    return Err(ProgramError::InvalidInstructionData);
+                };
+                let data = &data[..len];
+            },
+            Mode::Borsh | Mode::ZeroCopy => return Err(syn::Error::new(
+                handler.sig.span(),
+                "`cpi` supports pod and varint instructions, borsh and zero_copy data is not built on the stack",
+            )),
+        };
+
+        let doc = format!("Invokes `{handler_name}` from another program.");
+
+        quote! {
+            impl #ix_name {
+                #[doc = #doc]
+                #[inline(always)]
+                pub fn cpi(
+                    cpi_ctx: CpiCtx<'_, '_, '_, '_, #cpi_accounts>,
+                    #(#arg_names: #arg_types),*
+                ) -> Result<()> {
+                    let ix = Self { #(#arg_names),* };
+                    let disc = <Self as Discriminator>::DISCRIMINATOR;
+                    #data
+
+                    cpi_ctx.try_invoke(
+                        &cpi_ctx.instruction_accounts(),
+                        &cpi_ctx.account_views(),
+                        data,
+                    )
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // off-chain builder, with `client` and hayabusa's `client` feature
+    let client = if args.client && cfg!(feature = "client") {
+        let builder_name = format_ident!("{}_ix", handler_name);
+
+        let data = match args.mode {
+            Mode::Pod => quote! {
+                let mut data = client::Vec::with_capacity(#ix_name::MAX_DATA_LEN);
+                data.extend_from_slice(<#ix_name as Discriminator>::DISCRIMINATOR);
+                data.extend_from_slice(::bytemuck::bytes_of(&ix));
+            },
+            Mode::Varint => quote! {
+                let mut buffer = [0u8; #ix_name::MAX_ENCODED_LEN];
+                let len = ix.encode(&mut buffer).expect("MAX_ENCODED_LEN fits every encoding");
+                let data = client::Vec::from(&buffer[..len]);
+            },
+            Mode::Borsh => quote! {
+                let mut data = client::Vec::new();
+                ix.encode(&mut data).expect("writing to a Vec doesn't fail");
+            },
+            Mode::ZeroCopy => quote! {
+                let mut data = client::Vec::new();
+                data.resize(ix.encoded_len(), 0);
+                ix.encode(&mut data).expect("encoded_len fits the encoding");
+            },
+        };
+
+        let doc = format!(
+            "Builds the `{handler_name}` instruction of `program_id` off-chain, from the \
+             `<Accounts>Client` generated by `#[derive(FromAccountViews)]` or the account metas."
+        );
+
+        quote! {
+            #[doc = #doc]
+            pub fn #builder_name #ix_generics(
+                program_id: &Address,
+                accounts: impl client::ToAccountMetas,
+                #(#arg_names: #arg_types),*
+            ) -> client::Instruction {
+                let ix = #ix_name { #(#arg_names),* };
+                #data
+
+                client::Instruction {
+                    program_id: client::Pubkey::new_from_array(program_id.to_bytes()),
+                    accounts: client::ToAccountMetas::to_account_metas(&accounts),
+                    data,
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        #handler
+
+        #ix_struct
+
+        #cpi
+
+        #client
+
+        impl #ix_ty {
+            /// Longest instruction data, discriminator included, longer data is rejected by
+            /// `dispatch!` before decoding. Clients can check user input against it.
+            pub const MAX_DATA_LEN: usize = #max_data_len;
+
+            /// Name of the handler, listed by `program_manifest!`.
+            pub const NAME: &'static str = #name;
+
+            /// Accounts of the instruction, listed by `program_manifest!`.
+            pub const ACCOUNTS: &'static [AccountSpec] = #accounts;
+        }
+
+        #decode
+
+        impl ProcessIx for #ix_ty {
+            #inline
+            fn process_at(
+                program_id: &Address,
+                accounts: &[AccountView],
+                instruction_data: &[u8],
+                offset: usize,
+            ) -> Result<()> {
+                let disc_len = offset + <Self as Discriminator>::DISCRIMINATOR.len();
+                let Some(args) = instruction_data.get(disc_len..) else {
+                    return Err(ProgramError::InvalidInstructionData);
+                };
+                let ix = <#decode_ty as DecodeIx<'_>>::decode(args)?;
+                #(#arg_checks)*
+
+                Ctx::run_with_instruction(program_id, accounts, instruction_data, |ctx| {
+                    #handler_name(ctx, #(ix.#arg_names),*)
+                })
+            }
+        }
+    })
+}
+
+/// `Ctx<'ix, UpdateCounter<'ix>>` -> `UpdateCounter<'ix>`
+fn accounts_type(ctx_arg: &FnArg) -> Option<syn::TypePath> {
+    let FnArg::Typed(arg) = ctx_arg else {
+        return None;
+    };
+    let syn::Type::Path(ctx) = arg.ty.as_ref() else {
+        return None;
+    };
+    let Some(syn::PathArguments::AngleBracketed(ctx_args)) =
+        ctx.path.segments.last().map(|segment| &segment.arguments)
+    else {
+        return None;
+    };
+
+    ctx_args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(syn::Type::Path(accounts)) => Some(accounts.clone()),
+        _ => None,
+    })
+}
+
+/// Replaces the lifetimes of the accounts type, e.g. `UpdateCounter<'ix>` -> `UpdateCounter<'_>`
+fn with_lifetime(accounts: &mut syn::TypePath, lifetime: &str) {
+    let Some(segment) = accounts.path.segments.last_mut() else {
+        return;
+    };
+
+    if let syn::PathArguments::AngleBracketed(args) = &mut segment.arguments {
+        for arg in args.args.iter_mut() {
+            if let syn::GenericArgument::Lifetime(current) = arg {
+                *current = syn::Lifetime::new(lifetime, current.span());
+            }
+        }
+    }
+}
+
+/// `Ctx<'ix, UpdateCounter<'ix>>` -> `UpdateCounterCpi<'_>`, the accounts struct generated by
+/// `#[derive(FromAccountViews)]` with `#[accounts(cpi)]`
+fn cpi_accounts_type(ctx_arg: &FnArg) -> Result<syn::Type> {
+    let error = || {
+        syn::Error::new(
+            ctx_arg.span(),
+            "`cpi` requires a `Ctx<'ix, Accounts<'ix>>` first argument, with `Accounts` deriving `FromAccountViews` and marked `#[accounts(cpi)]`",
+        )
+    };
+
+    let mut accounts = accounts_type(ctx_arg).ok_or_else(error)?;
+
+    let Some(segment) = accounts.path.segments.last_mut() else {
+        return Err(error());
+    };
+    if segment.ident == "NoAccounts" {
+        return Err(error());
+    }
+
+    segment.ident = format_ident!("{}Cpi", segment.ident);
+    with_lifetime(&mut accounts, "'_");
+
+    Ok(syn::Type::Path(accounts))
+}
+
+/// Removes the `#[max_len(N)]` attributes of the arguments, only allowed in borsh mode, and
+/// returns their bounds in argument order.
+fn take_max_lens(handler: &mut ItemFn, mode: &Mode) -> Result<Vec<Option<Expr>>> {
+    let mut max_lens = Vec::new();
+
+    for input in handler.sig.inputs.iter_mut().skip(1) {
+        let FnArg::Typed(arg) = input else {
+            continue;
+        };
+
+        let mut max_len = None;
+        let mut error = None;
+
+        arg.attrs.retain(|attr| {
+            if !attr.path().is_ident("max_len") {
+                return true;
+            }
+
+            if !matches!(mode, Mode::Borsh) {
+                error = Some(syn::Error::new(
+                    attr.span(),
+                    "`#[max_len(N)]` only applies to `#[instruction(borsh)]` arguments",
+                ));
+            }
+
+            match attr.parse_args::<Expr>() {
+                Ok(expr) => max_len = Some(expr),
+                Err(e) => error = Some(e),
+            }
+
+            false
+        });
+
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        max_lens.push(max_len);
+    }
+
+    Ok(max_lens)
+}
+
+/// Removes the `#[range(..)]` and `#[non_zero]` attributes of the arguments and returns the
+/// checks they generate, run on the decoded instruction `ix` before the accounts are parsed.
+fn take_arg_checks(handler: &mut ItemFn, mode: &Mode) -> Result<Vec<proc_macro2::TokenStream>> {
+    let handler_name = handler.sig.ident.to_string();
+    let mut checks = Vec::new();
+
+    for input in handler.sig.inputs.iter_mut().skip(1) {
+        let FnArg::Typed(arg) = input else {
+            continue;
+        };
+        let Pat::Ident(pat) = arg.pat.as_ref() else {
+            continue;
+        };
+
+        let name = &pat.ident;
+        let ty = &arg.ty;
+        // pod and varint instructions are packed, their fields are copied out
+        let value = match mode {
+            Mode::Borsh | Mode::ZeroCopy => quote! { &ix.#name },
+            Mode::Pod | Mode::Varint => quote! { &{ ix.#name } },
+        };
+
+        let mut error = None;
+
+        arg.attrs.retain(|attr| {
+            if attr.path().is_ident("range") {
+                match attr.parse_args::<Expr>() {
+                    Ok(range) => {
+                        let msg = format!("{handler_name}: `{name}` out of range");
+                        checks.push(quote! {
+                            if unlikely(!(#range).contains(#value)) {
+                                error_msg!(#msg, ErrorCode::ArgumentOutOfRange);
+                            }
+                        });
+                    }
+                    Err(e) => error = Some(e),
+                }
+                false
+            } else if attr.path().is_ident("non_zero") {
+                if let Err(e) = attr.meta.require_path_only() {
+                    error = Some(e);
+                }
+                let msg = format!("{handler_name}: `{name}` is zero");
+                checks.push(quote! {
+                    if unlikely(*#value == <#ty as Default>::default()) {
+                        error_msg!(#msg, ErrorCode::ArgumentZero);
+                    }
+                });
+                false
+            } else {
+                true
+            }
+        });
+
+        if let Some(error) = error {
+            return Err(error);
+        }
+    }
+
+    Ok(checks)
+}
+
+/// Element type of every slice argument of a zero-copy instruction, `&'ix [T]` -> `T`, `None`
+/// for the fixed-size arguments, which come first.
+fn zero_copy_slice_elems<'a>(arg_types: &[&'a syn::Type]) -> Result<Vec<Option<&'a syn::Type>>> {
+    let mut elems = Vec::new();
+    let mut in_slices = false;
+
+    for ty in arg_types {
+        let elem = match ty {
+            syn::Type::Reference(reference) => match reference.elem.as_ref() {
+                syn::Type::Slice(slice) if reference.mutability.is_none() => {
+                    Some(slice.elem.as_ref())
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        ty.span(),
+                        "`zero_copy` borrows slices only, e.g. `data: &'ix [u8]`",
+                    ))
+                }
+            },
+            _ => None,
+        };
+
+        if elem.is_none() && in_slices {
+            return Err(syn::Error::new(
+                ty.span(),
+                "`zero_copy` fixed-size arguments come before the slice arguments",
+            ));
+        }
+        in_slices |= elem.is_some();
+
+        elems.push(elem);
+    }
+
+    Ok(elems)
+}
+
+/// `update_counter` -> `UpdateCounter`
+fn to_pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
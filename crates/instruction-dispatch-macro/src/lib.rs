@@ -3,9 +3,190 @@
 
 #![no_std]
 
-#[allow(clippy::crate_in_macro_def)]
+use hayabusa_discriminator::Discriminator;
+
+/// Logs the `trace: ix=<name>` line of the entrypoint trace, a no-op without the `trace` feature.
+#[doc(hidden)]
+#[inline(always)]
+pub fn trace_instruction(_name: &str) {
+    #[cfg(feature = "trace")]
+    pinocchio_log::log!("trace: ix={}", _name);
+}
+
+/// First field of the failure event, see [`report_failure`]
+pub const FAILURE_EVENT_TAG: &[u8; 8] = b"ixfailed";
+
+/// Emits a failure event when `result` is an error, a no-op without the `failure-events`
+/// feature.
+///
+/// The event is a `sol_log_data` line with three fields, so monitoring can match failures
+/// without parsing free-form logs:
+///
+/// ```text
+/// Program data: <FAILURE_EVENT_TAG> <instruction discriminator> <error code: u64 le>
+/// ```
+///
+/// The error code is the value returned to the runtime. The error is propagated unchanged.
+#[doc(hidden)]
+#[inline(always)]
+pub fn report_failure<E>(_disc: &[u8], result: Result<(), E>) -> Result<(), E>
+where
+    E: Clone + Into<u64>,
+{
+    #[cfg(feature = "failure-events")]
+    if let Err(error) = &result {
+        emit_failure(_disc, error.clone().into());
+    }
+
+    result
+}
+
+#[cfg(feature = "failure-events")]
+#[cold]
+fn emit_failure(disc: &[u8], code: u64) {
+    hayabusa_syscalls::log_data(&[FAILURE_EVENT_TAG, disc, &code.to_le_bytes()]);
+}
+
+/// Length `dispatch!` matches discriminators on: the most common among the dispatched
+/// instructions, so a program using `#[instruction(disc_len = 1)]` throughout matches on a single
+/// byte and a few custom discriminators of another length don't change it.
+#[doc(hidden)]
+pub const fn disc_len(discriminators: &[&[u8]]) -> usize {
+    let mut len = 8;
+    let mut best = 0;
+    let mut i = 0;
+
+    while i < discriminators.len() {
+        let mut count = 0;
+        let mut j = 0;
+        while j < discriminators.len() {
+            if discriminators[j].len() == discriminators[i].len() {
+                count += 1;
+            }
+            j += 1;
+        }
+
+        if count > best {
+            best = count;
+            len = discriminators[i].len();
+        }
+        i += 1;
+    }
+
+    len
+}
+
+/// Compile-time check of the instructions of a [`dispatch!`]: `match` arms are tried in order, so
+/// a discriminator listed twice, or the prefix of another, would silently route every call to
+/// whichever comes first. The event and IDL tags are matched before any instruction, so neither
+/// they nor the discriminators may be a prefix of the other.
+#[doc(hidden)]
+pub const fn assert_distinct_discriminators(discriminators: &[&[u8]], tags: &[&[u8]]) {
+    let mut i = 0;
+
+    while i < discriminators.len() {
+        let mut j = 0;
+        while j < i {
+            assert!(
+                !is_prefix(discriminators[j], discriminators[i])
+                    && !is_prefix(discriminators[i], discriminators[j]),
+                "dispatch!: two instructions share a discriminator, or one is a prefix of the other",
+            );
+            j += 1;
+        }
+
+        let mut t = 0;
+        while t < tags.len() {
+            assert!(
+                !is_prefix(tags[t], discriminators[i]) && !is_prefix(discriminators[i], tags[t]),
+                "dispatch!: an instruction discriminator collides with the event or IDL tag",
+            );
+            t += 1;
+        }
+        i += 1;
+    }
+}
+
+const fn is_prefix(prefix: &[u8], of: &[u8]) -> bool {
+    if prefix.len() > of.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < prefix.len() {
+        if prefix[i] != of[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+/// Routes the instruction data to the handler of the matching instruction type.
+///
+/// Discriminators are matched on the length most of them share, 8 bytes unless the program
+/// shortens them with `disc_len` (see `#[derive(Discriminator)]`). Types with a discriminator of
+/// another length are matched by prefix afterwards, which costs nothing for the others as the
+/// lengths are known at compile time.
+///
+/// Data longer than `DecodeIx::MAX_DATA_LEN` of the matched type is rejected before decoding.
+///
+/// Two instructions with the same discriminator, or one being the prefix of another, fail to
+/// compile instead of the first one silently taking every call.
 #[macro_export]
 macro_rules! dispatch {
+    (@handle $program_id:ident, $ix_data:expr, $accounts:expr, $disc:expr, $rest:expr,
+        $IxTy:ty => $handler:ident ( $($field:ident),* )
+    ) => {{
+        $crate::trace_instruction(stringify!($IxTy));
+
+        if unlikely($ix_data.len() > <$IxTy as DecodeIx<'_>>::MAX_DATA_LEN) {
+            error_msg!(
+                "dispatch!: instruction data too long",
+                ProgramError::InvalidInstructionData,
+            );
+        }
+
+        // unused by handlers without arguments, e.g. `BuildInfoIx => build_info()`
+        #[allow(unused_variables)]
+        let ix = <$IxTy as DecodeIx<'_>>::decode($rest)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        return $crate::report_failure(
+            $disc,
+            Ctx::run_with_instruction($program_id, $accounts, $ix_data, |ctx| {
+                $handler(ctx, $(ix.$field),*)
+            }),
+        );
+    }};
+    // The event and IDL tags are 8 bytes, with shorter discriminators they are checked up front
+    // rather than in the match
+    (@tags $program_id:ident, $ix_data:expr, $accounts:expr) => {
+        if $ix_data.starts_with(EVENT_IX_TAG) {
+            return try_process_event_cpi($program_id, $accounts);
+        }
+        if let Some(rest) = $ix_data.strip_prefix(IDL_IX_TAG) {
+            return try_process_idl_ix($program_id, $accounts, rest);
+        }
+    };
+    // `$offset` is where the instruction starts in the data, after the group discriminator of
+    // `dispatch_nested!`
+    (@process $program_id:ident, $ix_data:expr, $accounts:expr, $disc:expr, $offset:expr, $IxTy:ty) => {{
+        $crate::trace_instruction(stringify!($IxTy));
+
+        if unlikely($ix_data.len() - $offset > <$IxTy as DecodeIx<'_>>::MAX_DATA_LEN) {
+            error_msg!(
+                "dispatch!: instruction data too long",
+                ProgramError::InvalidInstructionData,
+            );
+        }
+
+        return $crate::report_failure(
+            $disc,
+            <$IxTy as ProcessIx>::process_at($program_id, $accounts, $ix_data, $offset),
+        );
+    }};
     (
         $program_id:expr,
         $ix_data:expr,
@@ -14,14 +195,99 @@ macro_rules! dispatch {
             $IxTy:ty => $handler:ident ( $($field:ident),* $(,)? )
         ),+ $(,)?
     ) => {{
-        if unlikely($program_id != &crate::ID) {
+        // The program id is checked once by `program_entrypoint!`
+        let program_id: &Address = $program_id;
+
+        const _: () = $crate::assert_distinct_discriminators(
+            &[$(<$IxTy>::DISCRIMINATOR),+],
+            &[EVENT_IX_TAG, IDL_IX_TAG],
+        );
+        const DISC_LEN: usize = $crate::disc_len(&[$(<$IxTy>::DISCRIMINATOR),+]);
+
+        if DISC_LEN != 8 {
+            $crate::dispatch!(@tags program_id, $ix_data, $accounts);
+        }
+
+        if let Some((disc, rest)) = $ix_data.split_at_checked(DISC_LEN) {
+            match disc {
+                // self-CPI events, see `EventCpi`
+                EVENT_IX_TAG => return try_process_event_cpi(program_id, $accounts),
+                // Anchor IDL instructions, see `hayabusa_idl`
+                IDL_IX_TAG => return try_process_idl_ix(program_id, $accounts, rest),
+                $(
+                    <$IxTy>::DISCRIMINATOR => $crate::dispatch!(
+                        @handle program_id, $ix_data, $accounts, disc, rest,
+                        $IxTy => $handler($($field),*)
+                    ),
+                )+
+                _ => {}
+            }
+        }
+
+        $(
+            let disc = <$IxTy>::DISCRIMINATOR;
+            if disc.len() != DISC_LEN && $ix_data.starts_with(disc) {
+                $crate::dispatch!(
+                    @handle program_id, $ix_data, $accounts, disc, &$ix_data[disc.len()..],
+                    $IxTy => $handler($($field),*)
+                );
+            }
+        )+
+
+        if unlikely($ix_data.len() < DISC_LEN) {
             error_msg!(
-                "dispatch!: incorrect program id.",
-                ProgramError::IncorrectProgramId,
+                "dispatch!: instruction data too short",
+                ProgramError::InvalidInstructionData,
             );
         }
 
-        const DISC_LEN: usize = 8;
+        error_msg!(
+            "dispatch!: unknown instruction",
+            ErrorCode::UnknownInstruction,
+        );
+    }};
+    // Instructions generated by `#[instruction]`, the decode/ctx/handler glue lives in
+    // `ProcessIx::process` so its inlining follows `#[instruction(inline(..))]`
+    (
+        $program_id:expr,
+        $ix_data:expr,
+        $accounts:expr,
+        $($IxTy:ty),+ $(,)?
+    ) => {{
+        // The program id is checked once by `program_entrypoint!`
+        let program_id: &Address = $program_id;
+
+        const _: () = $crate::assert_distinct_discriminators(
+            &[$(<$IxTy>::DISCRIMINATOR),+],
+            &[EVENT_IX_TAG, IDL_IX_TAG],
+        );
+        const DISC_LEN: usize = $crate::disc_len(&[$(<$IxTy>::DISCRIMINATOR),+]);
+
+        if DISC_LEN != 8 {
+            $crate::dispatch!(@tags program_id, $ix_data, $accounts);
+        }
+
+        if let Some((disc, rest)) = $ix_data.split_at_checked(DISC_LEN) {
+            match disc {
+                // self-CPI events, see `EventCpi`
+                EVENT_IX_TAG => return try_process_event_cpi(program_id, $accounts),
+                // Anchor IDL instructions, see `hayabusa_idl`
+                IDL_IX_TAG => return try_process_idl_ix(program_id, $accounts, rest),
+                $(
+                    <$IxTy>::DISCRIMINATOR => $crate::dispatch!(
+                        @process program_id, $ix_data, $accounts, disc, 0, $IxTy
+                    ),
+                )+
+                _ => {}
+            }
+        }
+
+        $(
+            let disc = <$IxTy>::DISCRIMINATOR;
+            if disc.len() != DISC_LEN && $ix_data.starts_with(disc) {
+                $crate::dispatch!(@process program_id, $ix_data, $accounts, disc, 0, $IxTy);
+            }
+        )+
 
         if unlikely($ix_data.len() < DISC_LEN) {
             error_msg!(
@@ -30,25 +296,271 @@ macro_rules! dispatch {
             );
         }
 
-        let (disc, rest) = $ix_data.split_at(DISC_LEN);
+        error_msg!(
+            "dispatch!: unknown instruction",
+            ErrorCode::UnknownInstruction,
+        );
+    }};
+}
+
+/// Sub-op byte of an instruction routed by [`dispatch_nested!`], its one byte discriminator.
+///
+/// An `u8` constant, so the sub-op `match` compiles to a jump table.
+#[doc(hidden)]
+pub trait SubOp {
+    const SUB_OP: u8;
+}
+
+impl<T: Discriminator> SubOp for T {
+    const SUB_OP: u8 = T::DISCRIMINATOR[0];
+}
+
+/// Compile-time check of the instructions of a [`dispatch_nested!`] group: one byte
+/// discriminators, all different.
+#[doc(hidden)]
+pub const fn assert_sub_ops(discriminators: &[&[u8]]) {
+    let mut i = 0;
 
-        match disc {
+    while i < discriminators.len() {
+        assert!(
+            discriminators[i].len() == 1,
+            "dispatch_nested!: sub-instructions need a one byte discriminator, e.g. `#[instruction(discriminator = [0])]`",
+        );
+
+        let mut j = 0;
+        while j < i {
+            assert!(
+                discriminators[j][0] != discriminators[i][0],
+                "dispatch_nested!: two sub-instructions of a group share a discriminator",
+            );
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+/// Two-level [`dispatch!`] for programs with many instructions: the outer discriminator selects
+/// a group, then the next byte selects the instruction within it.
+///
+/// ```ignore
+/// #[derive(Discriminator)]
+/// pub struct VaultOps;
+///
+/// #[instruction(discriminator = [0])]
+/// fn deposit<'ix>(ctx: Ctx<'ix, Deposit<'ix>>, amount: u64) -> Result<()> { .. }
+///
+/// dispatch_nested!(program_id, instruction_data, accounts,
+///     VaultOps => { DepositInstruction, WithdrawInstruction },
+///     AdminOps => { SetConfigInstruction },
+/// );
+/// ```
+///
+/// Instruction data is the group discriminator, the sub-instruction discriminator then its
+/// arguments. Groups are matched like the types of [`dispatch!`], the sub-op is a single byte
+/// matched with an integer `match`, which compiles to a jump table rather than one comparison
+/// per instruction. The sub-instructions decode the data after the group discriminator and their
+/// `MAX_DATA_LEN` doesn't count it, while `Ctx::instruction_data` is the whole data, group
+/// discriminator included.
+#[macro_export]
+macro_rules! dispatch_nested {
+    (@group $program_id:ident, $accounts:expr, $ix_data:expr, $offset:expr, $($IxTy:ty),+) => {{
+        const _: () = $crate::assert_sub_ops(&[$(<$IxTy>::DISCRIMINATOR),+]);
+
+        let ix_data: &[u8] = $ix_data;
+        let offset: usize = $offset;
+
+        let Some((&sub_op, _)) = ix_data[offset..].split_first() else {
+            error_msg!(
+                "dispatch_nested!: missing sub-instruction",
+                ProgramError::InvalidInstructionData,
+            );
+        };
+
+        match sub_op {
             $(
-                <$IxTy>::DISCRIMINATOR => {
-                    let ix = <$IxTy as DecodeIx<'_>>::decode(rest)
-                        .map_err(|_| ProgramError::InvalidInstructionData)?;
-
-                    let ctx = Ctx::construct($accounts)?;
-                    return $handler(ctx, $(ix.$field),*)
-                        .map_err(Into::into);
-                }
+                <$IxTy as $crate::SubOp>::SUB_OP => $crate::dispatch!(
+                    @process $program_id, ix_data, $accounts, &ix_data[offset..offset + 1], offset,
+                    $IxTy
+                ),
             )+
             _ => {
                 error_msg!(
-                    "dispatch!: unknown instruction",
+                    "dispatch_nested!: unknown sub-instruction",
                     ErrorCode::UnknownInstruction,
                 );
             }
         }
     }};
+    (
+        $program_id:expr,
+        $ix_data:expr,
+        $accounts:expr,
+        $(
+            $Group:ty => { $($IxTy:ty),+ $(,)? }
+        ),+ $(,)?
+    ) => {{
+        // The program id is checked once by `program_entrypoint!`
+        let program_id: &Address = $program_id;
+
+        const _: () = $crate::assert_distinct_discriminators(
+            &[$(<$Group>::DISCRIMINATOR),+],
+            &[EVENT_IX_TAG, IDL_IX_TAG],
+        );
+        const DISC_LEN: usize = $crate::disc_len(&[$(<$Group>::DISCRIMINATOR),+]);
+
+        if DISC_LEN != 8 {
+            $crate::dispatch!(@tags program_id, $ix_data, $accounts);
+        }
+
+        if let Some((disc, rest)) = $ix_data.split_at_checked(DISC_LEN) {
+            match disc {
+                // self-CPI events, see `EventCpi`
+                EVENT_IX_TAG => return try_process_event_cpi(program_id, $accounts),
+                // Anchor IDL instructions, see `hayabusa_idl`
+                IDL_IX_TAG => return try_process_idl_ix(program_id, $accounts, rest),
+                $(
+                    <$Group>::DISCRIMINATOR => $crate::dispatch_nested!(
+                        @group program_id, $accounts, $ix_data, DISC_LEN, $($IxTy),+
+                    ),
+                )+
+                _ => {}
+            }
+        }
+
+        $(
+            let disc = <$Group>::DISCRIMINATOR;
+            if disc.len() != DISC_LEN && $ix_data.starts_with(disc) {
+                $crate::dispatch_nested!(
+                    @group program_id, $accounts, $ix_data, disc.len(), $($IxTy),+
+                );
+            }
+        )+
+
+        if unlikely($ix_data.len() < DISC_LEN) {
+            error_msg!(
+                "dispatch_nested!: instruction data too short",
+                ProgramError::InvalidInstructionData,
+            );
+        }
+
+        error_msg!(
+            "dispatch_nested!: unknown instruction",
+            ErrorCode::UnknownInstruction,
+        );
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::sync::atomic::{AtomicU8, Ordering};
+    use hayabusa::prelude::*;
+
+    #[derive(Discriminator)]
+    struct Ops;
+
+    struct NoAccounts;
+
+    impl<'ix> FromAccountViews<'ix> for NoAccounts {
+        type Bumps = NoBumps;
+
+        fn try_from_account_views(_: &mut AccountIter<'ix>, _: &mut NoBumps) -> Result<Self> {
+            Ok(NoAccounts)
+        }
+    }
+
+    static AMOUNT: AtomicU8 = AtomicU8::new(0);
+
+    #[instruction(discriminator = [3])]
+    fn grouped<'ix>(ctx: Ctx<'ix, NoAccounts>, amount: u8) -> Result<()> {
+        // the whole data, group discriminator included
+        let data = ctx.instruction_data().unwrap();
+        assert_eq!(&data[..8], Ops::DISCRIMINATOR);
+        assert_eq!(&data[8..], &[3, amount]);
+
+        AMOUNT.store(amount, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn process(data: &[u8]) -> Result<()> {
+        let program_id = Address::new_from_array([1; 32]);
+        crate::dispatch_nested!(&program_id, data, &[],
+            Ops => { GroupedInstruction },
+        );
+    }
+
+    fn rejected(discriminators: &[&[u8]]) -> bool {
+        std::panic::catch_unwind(|| {
+            crate::assert_distinct_discriminators(discriminators, &[EVENT_IX_TAG, IDL_IX_TAG])
+        })
+        .is_err()
+    }
+
+    #[test]
+    fn test_tag_prefixes() {
+        assert!(!rejected(&[&[1], &[2, 3, 4, 5]]));
+
+        // a discriminator starting with a tag, and ones the tags start with
+        assert!(rejected(&[&[EVENT_IX_TAG, &[0]].concat()]));
+        assert!(rejected(&[&EVENT_IX_TAG[..1]]));
+        assert!(rejected(&[&IDL_IX_TAG[..4]]));
+    }
+
+    #[test]
+    fn test_group_instruction_data() {
+        let mut data = Ops::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[3, 42]);
+
+        process(&data).unwrap();
+        assert_eq!(AMOUNT.load(Ordering::Relaxed), 42);
+
+        data[8] = 4;
+        assert!(process(&data).is_err());
+    }
+
+    build_info!(version = "1.0.0", git_hash = "", idl_hash = "");
+
+    #[test]
+    fn test_view_instruction() {
+        // the return data is dropped off chain, the handler still runs in host tests
+        let program_id = Address::new_from_array([1; 32]);
+        let process = |data: &[u8]| -> Result<()> {
+            crate::dispatch!(&program_id, data, &[], BuildInfoIx => build_info());
+        };
+
+        assert!(process(BuildInfoIx::DISCRIMINATOR).is_ok());
+    }
+
+    #[cfg(feature = "failure-events")]
+    #[test]
+    fn test_report_failure() {
+        use std::{cell::RefCell, vec::Vec};
+
+        std::thread_local! {
+            static LOGGED: RefCell<Vec<Vec<Vec<u8>>>> = const { RefCell::new(Vec::new()) };
+        }
+
+        hayabusa_syscalls::set_log_data_hook(|fields| {
+            LOGGED.with_borrow_mut(|logged| {
+                logged.push(fields.iter().map(|field| field.to_vec()).collect())
+            })
+        });
+
+        let disc = [7, 7];
+        assert!(crate::report_failure::<u64>(&disc, Ok(())).is_ok());
+        assert!(LOGGED.with_borrow(Vec::is_empty));
+
+        assert_eq!(crate::report_failure(&disc, Err(42u64)), Err(42));
+        LOGGED.with_borrow(|logged| {
+            assert_eq!(
+                logged,
+                &[Vec::from([
+                    crate::FAILURE_EVENT_TAG.to_vec(),
+                    disc.to_vec(),
+                    42u64.to_le_bytes().to_vec(),
+                ])]
+            )
+        });
+    }
 }
@@ -5,13 +5,19 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, DeriveInput};
 
+/// `DISCRIMINATED_LEN` is the length of the discriminator plus the size of the struct, so the type
+/// must also implement `Discriminator`.
 #[proc_macro_derive(Len)]
 pub fn derive_len(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let expanded = quote! {
-        impl Len for #name {}
+        impl #impl_generics Len for #name #ty_generics #where_clause {
+            const DISCRIMINATED_LEN: usize =
+                <Self as Discriminator>::DISCRIMINATOR.len() + core::mem::size_of::<Self>();
+        }
     };
 
     TokenStream::from(expanded)
@@ -5,49 +5,95 @@
 
 pub mod instruction {
     pub use solana_instruction_view::{
-        cpi::{Seed, Signer as PdaSigner},
+        cpi::{invoke_signed_with_bounds, Seed, Signer as PdaSigner},
         seeds, InstructionAccount, InstructionView,
     };
 }
 
+pub mod associated_token {
+    pub use hayabusa_associated_token::*;
+}
+
 pub mod system_program {
     pub use hayabusa_system_program::*;
 }
 
+/// Types of the off-chain instruction builders generated with the `client` feature, see
+/// `#[instruction]`.
+#[cfg(feature = "client")]
+pub mod client {
+    extern crate alloc;
+
+    pub use alloc::vec::Vec;
+    pub use solana_instruction::{AccountMeta, Instruction};
+    pub use solana_pubkey::Pubkey;
+
+    /// Accounts of an instruction built off-chain: the `<Accounts>Client` structs generated by
+    /// `#[derive(FromAccountViews)]`, or the metas as is for hand-written accounts structs.
+    pub trait ToAccountMetas {
+        fn to_account_metas(&self) -> Vec<AccountMeta>;
+    }
+
+    impl ToAccountMetas for Vec<AccountMeta> {
+        fn to_account_metas(&self) -> Vec<AccountMeta> {
+            self.clone()
+        }
+    }
+
+    impl ToAccountMetas for hayabusa_context::NoAccounts {
+        fn to_account_metas(&self) -> Vec<AccountMeta> {
+            Vec::new()
+        }
+    }
+}
+
 pub mod prelude {
-    pub use super::{instruction, system_program};
+    pub use super::{associated_token, instruction, system_program};
+    #[cfg(feature = "client")]
+    pub use super::client;
 
     pub use hayabusa_account_attribute_macro::account;
     pub use hayabusa_accounts::*;
+    pub use hayabusa_accumulator as accumulator;
+    pub use hayabusa_bitfield_attribute_macro::bitfield;
     pub use hayabusa_context::*;
     pub use hayabusa_cpi::*;
+    pub use hayabusa_crank as crank;
     pub use hayabusa_decode_instruction::*;
     pub use hayabusa_discriminator::*;
     pub use hayabusa_discriminator_derive::Discriminator;
     pub use hayabusa_errors::{ErrorCode, Result};
     pub use hayabusa_errors_attribute_macro::error;
     pub use hayabusa_from_account_views_derive::FromAccountViews;
-    pub use hayabusa_instruction_dispatch_macro::dispatch;
+    pub use hayabusa_idl::{try_process_idl_ix, IDL_IX_TAG};
+    pub use hayabusa_instruction_attribute_macro::instruction;
+    pub use hayabusa_instruction_dispatch_macro::{dispatch, dispatch_nested};
+    pub use hayabusa_init_space_derive::InitSpace;
     pub use hayabusa_len_derive::Len;
     pub use hayabusa_owner_program_derive::OwnerProgram;
     pub use hayabusa_pda::*;
+    pub use hayabusa_pod_enum_derive::PodEnum;
+    pub use hayabusa_random as random;
     pub use hayabusa_ser::*;
     pub use hayabusa_ser_derive::*;
+    pub use hayabusa_user_stats_attribute_macro::user_stats;
     pub use hayabusa_utility::{hint::unlikely, take_bytes, *};
     pub use hayabusa_events::*;
     pub use hayabusa_events_attribute_macro::event;
 
     #[cfg(feature = "std")]
-    pub use hayabusa_entrpouint::default_panic_handler;
+    pub use hayabusa_entrypoint::default_panic_handler;
     pub use hayabusa_entrypoint::{self, no_allocator, program_entrypoint};
 
     #[cfg(not(feature = "std"))]
     pub use hayabusa_entrypoint::nostd_panic_handler;
 
     #[cfg(feature = "alloc")]
-    pub use hayabusa_entrypoint::{default_allocator, entrypoint};
+    pub use hayabusa_entrypoint::{default_allocator, entrypoint, heap_usage, log_heap_usage};
+    #[cfg(feature = "borsh")]
+    pub use borsh;
     pub use hayabusa_syscalls as syscalls;
-    pub use hayabusa_sysvars::{self as sysvars, clock::Clock, Sysvar};
+    pub use hayabusa_sysvars::{self as sysvars, clock::Clock, slot_hashes::SlotHashes, Sysvar, SysvarAccountData};
 
     pub use solana_account_view::{self as account_view, AccountView, Ref, RefMut};
     pub use solana_address::{self as address, declare_id, Address};
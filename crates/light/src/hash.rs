@@ -0,0 +1,53 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Data hashes of compressed accounts.
+//!
+//! Trees only store a hash of each account, built by the light system program from the owner,
+//! the tree, the leaf index, the lamports, the address, the discriminator and the data hash. The
+//! data hash is up to the owning program: here it is the SHA-256 of the account bytes in their
+//! declared layout, with the first byte zeroed so it fits the BN254 field the trees are built
+//! over (the `LightHasherSha` scheme of the Light sdk).
+
+use bytemuck::Pod;
+use hayabusa_syscalls::sha256;
+
+/// Zeroes the first byte of `hash`, the result is smaller than the BN254 field modulus.
+#[inline(always)]
+pub fn truncate_to_field(hash: &mut [u8; 32]) {
+    hash[0] = 0;
+}
+
+/// Data hash of raw account data.
+#[inline(always)]
+pub fn hash_data(data: &[u8]) -> [u8; 32] {
+    let mut hash = sha256(&[data]);
+    truncate_to_field(&mut hash);
+
+    hash
+}
+
+/// Data hash of a Pod account, over its bytes without discriminator.
+#[inline(always)]
+pub fn data_hash<T: Pod>(value: &T) -> [u8; 32] {
+    hash_data(bytemuck::bytes_of(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_data() {
+        // sha256("abc") with the first byte zeroed
+        let mut expected = [
+            0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+            0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+            0xf2, 0x00, 0x15, 0xad,
+        ];
+        expected[0] = 0;
+
+        assert_eq!(hash_data(b"abc"), expected);
+        assert_eq!(data_hash(b"abc"), expected);
+    }
+}
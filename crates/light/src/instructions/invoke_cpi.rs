@@ -0,0 +1,400 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{data_hash, CompressedProof, PackedStateTreeInfo};
+use bytemuck::Pod;
+use hayabusa_common::{AccountView, Address};
+use hayabusa_cpi::{CheckProgramId, CpiCtx};
+use hayabusa_discriminator::Discriminator;
+use hayabusa_errors::{ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely};
+use solana_instruction_view::{
+    cpi::invoke_signed_with_bounds, InstructionAccount, InstructionView,
+};
+
+pub struct InvokeCpi<'ix> {
+    /// Pays the tree fees
+    pub fee_payer: &'ix AccountView,
+    /// CPI authority of the invoking program, see `find_cpi_authority_address`
+    pub authority: &'ix AccountView,
+    /// See `find_registered_program_address`
+    pub registered_program_pda: &'ix AccountView,
+    /// Noop program
+    pub noop_program: &'ix AccountView,
+    /// See `find_account_compression_authority_address`
+    pub account_compression_authority: &'ix AccountView,
+    /// Account compression program
+    pub account_compression_program: &'ix AccountView,
+    /// Program calling `invoke_cpi`, owner of the compressed accounts
+    pub invoking_program: &'ix AccountView,
+    /// Sol pool, required to compress or decompress lamports, see `find_sol_pool_address`
+    pub sol_pool_pda: Option<&'ix AccountView>,
+    /// Receives decompressed lamports
+    pub decompression_recipient: Option<&'ix AccountView>,
+    /// System program
+    pub system_program: &'ix AccountView,
+}
+
+impl CheckProgramId for InvokeCpi<'_> {
+    const ID: Address = crate::ID;
+}
+
+const DISCRIMINATOR: [u8; 8] = [49, 212, 191, 129, 39, 194, 43, 196];
+
+const FIXED_ACCOUNTS: usize = 11;
+
+/// Address to create in an address tree.
+pub struct NewAddressParams {
+    pub seed: [u8; 32],
+    pub address_queue_index: u8,
+    pub address_tree_index: u8,
+    pub root_index: u16,
+}
+
+/// Compressed account consumed by the instruction, only its data hash is needed.
+pub struct InputAccount<'a> {
+    pub owner: &'a Address,
+    pub lamports: u64,
+    pub address: Option<[u8; 32]>,
+    pub discriminator: [u8; 8],
+    pub data_hash: [u8; 32],
+    pub tree_info: &'a PackedStateTreeInfo,
+}
+
+/// Compressed account created by the instruction, appended to the state tree at `tree_index`.
+pub struct OutputAccount<'a> {
+    pub owner: &'a Address,
+    pub lamports: u64,
+    pub address: Option<[u8; 32]>,
+    pub discriminator: [u8; 8],
+    /// Logged for indexers, the tree only stores `data_hash`
+    pub data: &'a [u8],
+    pub data_hash: [u8; 32],
+    pub tree_index: u8,
+}
+
+impl<'a> OutputAccount<'a> {
+    /// Output holding `value` in its Pod layout, hashed with [`data_hash`].
+    #[inline(always)]
+    pub fn from_pod<T: Pod + Discriminator>(
+        owner: &'a Address,
+        value: &'a T,
+        address: Option<[u8; 32]>,
+        tree_index: u8,
+    ) -> Self {
+        Self {
+            owner,
+            lamports: 0,
+            address,
+            discriminator: padded_discriminator::<T>(),
+            data: bytemuck::bytes_of(value),
+            data_hash: data_hash(value),
+            tree_index,
+        }
+    }
+}
+
+/// Discriminator of `T` as stored in a compressed account, zero-padded to 8 bytes.
+#[inline(always)]
+fn padded_discriminator<T: Discriminator>() -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator[..T::DISCRIMINATOR.len()].copy_from_slice(T::DISCRIMINATOR);
+
+    discriminator
+}
+
+pub struct InvokeCpiArgs<'a> {
+    /// Required unless every input is proven by index and no address is created
+    pub proof: Option<&'a CompressedProof>,
+    pub new_addresses: &'a [NewAddressParams],
+    pub inputs: &'a [InputAccount<'a>],
+    pub outputs: &'a [OutputAccount<'a>],
+    /// Lamports moved into (`is_compress`) or out of the sol pool
+    pub compress_or_decompress_lamports: Option<u64>,
+    pub is_compress: bool,
+}
+
+/// Borsh writer over the instruction data buffer.
+struct DataWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl DataWriter<'_> {
+    #[inline(always)]
+    fn put(&mut self, bytes: &[u8]) -> Result<()> {
+        let Some(dst) = self.buf.get_mut(self.len..self.len + bytes.len()) else {
+            error_msg!(
+                "invoke_cpi: instruction data exceeds MAX_DATA_LEN",
+                ProgramError::InvalidArgument,
+            );
+        };
+
+        dst.copy_from_slice(bytes);
+        self.len += bytes.len();
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn put_len(&mut self, len: usize) -> Result<()> {
+        self.put(&(len as u32).to_le_bytes())
+    }
+
+    #[inline(always)]
+    fn put_option(&mut self, value: Option<&[u8]>) -> Result<()> {
+        match value {
+            Some(value) => {
+                self.put(&[1])?;
+                self.put(value)
+            }
+            None => self.put(&[0]),
+        }
+    }
+
+    /// `CompressedAccount`, always with data
+    #[inline(always)]
+    fn put_compressed_account(
+        &mut self,
+        owner: &Address,
+        lamports: u64,
+        address: Option<&[u8; 32]>,
+        discriminator: &[u8; 8],
+        data: &[u8],
+        data_hash: &[u8; 32],
+    ) -> Result<()> {
+        self.put(owner.as_ref())?;
+        self.put(&lamports.to_le_bytes())?;
+        self.put_option(address.map(|a| &a[..]))?;
+        self.put(&[1])?;
+        self.put(discriminator)?;
+        self.put_len(data.len())?;
+        self.put(data)?;
+        self.put(data_hash)
+    }
+}
+
+/// Writes `InstructionDataInvokeCpi`, returning its length.
+fn write_args(args: &InvokeCpiArgs, buf: &mut [u8]) -> Result<usize> {
+    let mut w = DataWriter { buf, len: 0 };
+
+    w.put_option(args.proof.map(bytemuck::bytes_of))?;
+
+    w.put_len(args.new_addresses.len())?;
+    for new_address in args.new_addresses {
+        w.put(&new_address.seed)?;
+        w.put(&[new_address.address_queue_index, new_address.address_tree_index])?;
+        w.put(&new_address.root_index.to_le_bytes())?;
+    }
+
+    w.put_len(args.inputs.len())?;
+    for input in args.inputs {
+        w.put_compressed_account(
+            input.owner,
+            input.lamports,
+            input.address.as_ref(),
+            &input.discriminator,
+            &[],
+            &input.data_hash,
+        )?;
+        // merkle context, root index and read only flag
+        let tree_info = input.tree_info;
+        w.put(&[tree_info.merkle_tree_pubkey_index, tree_info.queue_pubkey_index])?;
+        w.put(&tree_info.leaf_index().to_le_bytes())?;
+        w.put(&[tree_info.prove_by_index() as u8])?;
+        w.put(&tree_info.root_index().to_le_bytes())?;
+        w.put(&[0])?;
+    }
+
+    w.put_len(args.outputs.len())?;
+    for output in args.outputs {
+        w.put_compressed_account(
+            output.owner,
+            output.lamports,
+            output.address.as_ref(),
+            &output.discriminator,
+            output.data,
+            &output.data_hash,
+        )?;
+        w.put(&[output.tree_index])?;
+    }
+
+    // no relay fee
+    w.put_option(None)?;
+    w.put_option(
+        args.compress_or_decompress_lamports
+            .map(u64::to_le_bytes)
+            .as_ref()
+            .map(|l| &l[..]),
+    )?;
+    w.put(&[args.is_compress as u8])?;
+    // no cpi context
+    w.put_option(None)?;
+
+    Ok(w.len)
+}
+
+/// Creates, updates and closes compressed accounts owned by the invoking program.
+///
+/// `tree_accounts` are the trees and queues the packed indices of `args` refer to, forwarded
+/// with their writable flag. `cpi_ctx.signers` must sign for the CPI authority of the invoking
+/// program. `MAX_ACCOUNTS` bounds the total number of accounts (11 fixed ones plus the trees),
+/// `MAX_DATA_LEN` the instruction data.
+#[inline(always)]
+pub fn invoke_cpi<'ix, const MAX_ACCOUNTS: usize, const MAX_DATA_LEN: usize>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, InvokeCpi<'ix>>,
+    args: &InvokeCpiArgs,
+    tree_accounts: &'ix [AccountView],
+) -> Result<()> {
+    let len = FIXED_ACCOUNTS + tree_accounts.len();
+
+    if unlikely(len > MAX_ACCOUNTS) {
+        error_msg!("invoke_cpi: too many accounts", ProgramError::InvalidArgument);
+    }
+
+    // ix data layout
+    // - [0..8]: discriminator
+    // - [8..12]: length of the borsh encoded arguments
+    // - [12..]: arguments
+    let mut ix_data = [0u8; MAX_DATA_LEN];
+
+    let Some((header, body)) = ix_data.split_at_mut_checked(12) else {
+        error_msg!(
+            "invoke_cpi: instruction data exceeds MAX_DATA_LEN",
+            ProgramError::InvalidArgument,
+        );
+    };
+
+    let args_len = write_args(args, body)?;
+    header[..8].copy_from_slice(&DISCRIMINATOR);
+    header[8..].copy_from_slice(&(args_len as u32).to_le_bytes());
+
+    // optional accounts are replaced by the light system program
+    let fixed = [
+        cpi_ctx.fee_payer,
+        cpi_ctx.authority,
+        cpi_ctx.registered_program_pda,
+        cpi_ctx.noop_program,
+        cpi_ctx.account_compression_authority,
+        cpi_ctx.account_compression_program,
+        cpi_ctx.invoking_program,
+        cpi_ctx.sol_pool_pda.unwrap_or(cpi_ctx.program),
+        cpi_ctx.decompression_recipient.unwrap_or(cpi_ctx.program),
+        cpi_ctx.system_program,
+        cpi_ctx.program,
+    ];
+
+    let account_views: [&AccountView; MAX_ACCOUNTS] = core::array::from_fn(|i| match i {
+        0..FIXED_ACCOUNTS => fixed[i],
+        _ => tree_accounts
+            .get(i - FIXED_ACCOUNTS)
+            .unwrap_or(cpi_ctx.program),
+    });
+
+    let instruction_accounts: [InstructionAccount; MAX_ACCOUNTS] =
+        core::array::from_fn(|i| match i {
+            0 => InstructionAccount::writable_signer(account_views[i].address()),
+            1 => InstructionAccount::readonly_signer(account_views[i].address()),
+            7 if cpi_ctx.sol_pool_pda.is_some() => {
+                InstructionAccount::writable(account_views[i].address())
+            }
+            8 if cpi_ctx.decompression_recipient.is_some() => {
+                InstructionAccount::writable(account_views[i].address())
+            }
+            2..FIXED_ACCOUNTS => InstructionAccount::readonly(account_views[i].address()),
+            _ => InstructionAccount::new(
+                account_views[i].address(),
+                account_views[i].is_writable(),
+                false,
+            ),
+        });
+
+    let instruction = InstructionView {
+        program_id: &crate::ID,
+        accounts: &instruction_accounts[..len],
+        data: &ix_data[..12 + args_len],
+    };
+
+    invoke_signed_with_bounds::<MAX_ACCOUNTS>(
+        &instruction,
+        &account_views[..len],
+        cpi_ctx.signers.unwrap_or(&[]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_args() {
+        let owner = Address::new_from_array([1; 32]);
+        let outputs = [OutputAccount {
+            owner: &owner,
+            lamports: 0,
+            address: Some([2; 32]),
+            discriminator: [3; 8],
+            data: &[4, 5],
+            data_hash: [6; 32],
+            tree_index: 7,
+        }];
+        let args = InvokeCpiArgs {
+            proof: None,
+            new_addresses: &[],
+            inputs: &[],
+            outputs: &outputs,
+            compress_or_decompress_lamports: None,
+            is_compress: false,
+        };
+
+        let mut buf = [0u8; 256];
+        let len = write_args(&args, &mut buf).unwrap();
+
+        // proof, 2 empty vecs, 1 output, relay fee, lamports, is_compress, cpi context
+        let account_len = 32 + 8 + 33 + 1 + 8 + 4 + 2 + 32;
+        assert_eq!(len, 1 + 4 + 4 + 4 + account_len + 1 + 1 + 1 + 1 + 1);
+        assert_eq!(buf[13 + account_len], 7);
+        assert!(write_args(&args, &mut [0u8; 64]).is_err());
+    }
+
+    #[test]
+    fn test_padded_discriminator() {
+        struct Short;
+        struct Full;
+
+        impl Discriminator for Short {
+            const DISCRIMINATOR: &'static [u8] = &[9, 8, 7, 6];
+        }
+        impl Discriminator for Full {
+            const DISCRIMINATOR: &'static [u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+        }
+
+        assert_eq!(padded_discriminator::<Short>(), [9, 8, 7, 6, 0, 0, 0, 0]);
+        assert_eq!(padded_discriminator::<Full>(), [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_output_from_pod() {
+        #[derive(Clone, Copy)]
+        #[repr(C)]
+        struct CounterData {
+            count: u64,
+        }
+
+        unsafe impl bytemuck::Zeroable for CounterData {}
+        unsafe impl Pod for CounterData {}
+
+        impl Discriminator for CounterData {
+            const DISCRIMINATOR: &'static [u8] = &[9, 8, 7, 6];
+        }
+
+        let owner = Address::new_from_array([1; 32]);
+        let value = CounterData { count: 5 };
+        let output = OutputAccount::from_pod(&owner, &value, None, 2);
+
+        assert_eq!(output.discriminator, [9, 8, 7, 6, 0, 0, 0, 0]);
+        assert_eq!(output.data, &5u64.to_le_bytes());
+        assert_eq!(output.data_hash, crate::hash_data(&5u64.to_le_bytes()));
+        assert_eq!(output.tree_index, 2);
+    }
+}
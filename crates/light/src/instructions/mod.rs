@@ -0,0 +1,6 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod invoke_cpi;
+
+pub use invoke_cpi::*;
@@ -0,0 +1,41 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Light Protocol (ZK compression) bindings.
+//!
+//! Compressed accounts live as leaves of state Merkle trees owned by the account compression
+//! program instead of as rent paying accounts. A program reads one by receiving its data in the
+//! instruction together with a validity proof (see [`proof`]), and updates or creates them
+//! through the light system program (see [`instructions::invoke_cpi`]), which checks the proof,
+//! nullifies the inputs and appends the outputs. Only the hash of the data is stored on-chain,
+//! see [`hash`].
+
+#![no_std]
+
+pub mod hash;
+pub mod instructions;
+pub mod pda;
+pub mod proof;
+
+pub use hash::*;
+pub use pda::*;
+pub use proof::*;
+
+hayabusa_common::declare_id!("SySTEM1eSU2p4BGQfQpimFEWWSC1XDFeun3Nqzz3rT7");
+
+use hayabusa_accounts::ProgramId;
+use hayabusa_common::{address, Address};
+
+/// Owner of the state trees, address trees and their queues.
+pub const ACCOUNT_COMPRESSION_PROGRAM_ID: Address =
+    address!("compr6CUsB5m2jS4Y3831ztGSTnDpnKJTKS95d64XVq");
+
+/// Logs the compressed account data for indexers.
+pub const NOOP_PROGRAM_ID: Address = address!("noopb9bkMVfRPU8AsbpTUg8AWkHkKwYLcjhq4hxa4tP");
+
+/// Light system program
+pub struct LightSystem;
+
+impl ProgramId for LightSystem {
+    const ID: Address = ID;
+}
@@ -0,0 +1,35 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ACCOUNT_COMPRESSION_PROGRAM_ID;
+use hayabusa_common::Address;
+use hayabusa_errors::Result;
+use hayabusa_syscalls::try_find_program_address;
+
+pub const CPI_AUTHORITY_SEED: &[u8] = b"cpi_authority";
+pub const SOL_POOL_SEED: &[u8] = b"sol_pool_pda";
+
+/// Signer of the invoking program for `invoke_cpi`, derived from `program_id` with
+/// [`CPI_AUTHORITY_SEED`]. It owns the compressed accounts the program creates.
+#[inline(always)]
+pub fn find_cpi_authority_address(program_id: &Address) -> Result<(Address, u8)> {
+    try_find_program_address(&[CPI_AUTHORITY_SEED], program_id)
+}
+
+/// Authority of the light system program over the account compression program.
+#[inline(always)]
+pub fn find_account_compression_authority_address() -> Result<(Address, u8)> {
+    try_find_program_address(&[CPI_AUTHORITY_SEED], &crate::ID)
+}
+
+/// Registration of the light system program with the account compression program.
+#[inline(always)]
+pub fn find_registered_program_address() -> Result<(Address, u8)> {
+    try_find_program_address(&[crate::ID.as_ref()], &ACCOUNT_COMPRESSION_PROGRAM_ID)
+}
+
+/// Holds the lamports of compressed accounts.
+#[inline(always)]
+pub fn find_sol_pool_address() -> Result<(Address, u8)> {
+    try_find_program_address(&[SOL_POOL_SEED], &crate::ID)
+}
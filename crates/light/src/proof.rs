@@ -0,0 +1,201 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Validity proofs and packed tree references, as sent by clients in the instruction data.
+//!
+//! Trees and queues are passed as accounts after the fixed accounts of the instruction and
+//! referenced by their index among them, see [`TreeAccounts`]. The structs mirror the borsh
+//! layout of the Light sdk, integers are little endian and there is no padding, so they are read
+//! in place with [`try_read_packed`].
+
+use crate::ACCOUNT_COMPRESSION_PROGRAM_ID;
+use bytemuck::{Pod, Zeroable};
+use hayabusa_common::{address_eq, AccountView};
+use hayabusa_errors::{ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+/// Compressed Groth16 proof that the input accounts are in their trees and the new addresses are
+/// not in theirs.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompressedProof {
+    pub a: [u8; 32],
+    pub b: [u8; 64],
+    pub c: [u8; 32],
+}
+
+/// Position of an existing compressed account.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PackedStateTreeInfo {
+    root_index: [u8; 2],
+    prove_by_index: u8,
+    pub merkle_tree_pubkey_index: u8,
+    pub queue_pubkey_index: u8,
+    leaf_index: [u8; 4],
+}
+
+/// Address tree a new address is created in.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PackedAddressTreeInfo {
+    pub address_merkle_tree_pubkey_index: u8,
+    pub address_queue_pubkey_index: u8,
+    root_index: [u8; 2],
+}
+
+/// Existing compressed account with an address, and the tree its new state goes to.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompressedAccountMeta {
+    pub tree_info: PackedStateTreeInfo,
+    pub address: [u8; 32],
+    pub output_state_tree_index: u8,
+}
+
+// SAFETY: byte fields only, no padding and every bit pattern is valid
+unsafe impl Zeroable for CompressedProof {}
+unsafe impl Pod for CompressedProof {}
+unsafe impl Zeroable for PackedStateTreeInfo {}
+unsafe impl Pod for PackedStateTreeInfo {}
+unsafe impl Zeroable for PackedAddressTreeInfo {}
+unsafe impl Pod for PackedAddressTreeInfo {}
+unsafe impl Zeroable for CompressedAccountMeta {}
+unsafe impl Pod for CompressedAccountMeta {}
+
+impl PackedStateTreeInfo {
+    /// Root of the tree the proof was made against, ignored when proving by index.
+    #[inline(always)]
+    pub fn root_index(&self) -> u16 {
+        u16::from_le_bytes(self.root_index)
+    }
+
+    /// The account is still in the output queue, its leaf index is checked instead of a proof.
+    #[inline(always)]
+    pub fn prove_by_index(&self) -> bool {
+        self.prove_by_index != 0
+    }
+
+    #[inline(always)]
+    pub fn leaf_index(&self) -> u32 {
+        u32::from_le_bytes(self.leaf_index)
+    }
+}
+
+impl PackedAddressTreeInfo {
+    #[inline(always)]
+    pub fn root_index(&self) -> u16 {
+        u16::from_le_bytes(self.root_index)
+    }
+}
+
+/// Reads a `T` from the front of `bytes` and advances past it.
+#[inline(always)]
+pub fn try_read_packed<'a, T: Pod>(bytes: &mut &'a [u8]) -> Result<&'a T> {
+    const { assert!(core::mem::align_of::<T>() == 1) };
+
+    let Some((value, rest)) = bytes.split_at_checked(core::mem::size_of::<T>()) else {
+        error_msg!(
+            "try_read_packed: instruction data too short",
+            ProgramError::InvalidInstructionData,
+        );
+    };
+
+    *bytes = rest;
+    Ok(bytemuck::from_bytes(value))
+}
+
+/// Reads an optional proof (a borsh `Option<CompressedProof>`) from the front of `bytes` and
+/// advances past it. `None` is valid when every input is proven by index and no address is
+/// created.
+#[inline(always)]
+pub fn try_read_validity_proof<'a>(bytes: &mut &'a [u8]) -> Result<Option<&'a CompressedProof>> {
+    let Some((&flag, rest)) = bytes.split_first() else {
+        error_msg!(
+            "try_read_validity_proof: instruction data too short",
+            ProgramError::InvalidInstructionData,
+        );
+    };
+
+    *bytes = rest;
+
+    match flag {
+        0 => Ok(None),
+        1 => try_read_packed(bytes).map(Some),
+        _ => {
+            error_msg!(
+                "try_read_validity_proof: invalid option flag",
+                ProgramError::InvalidInstructionData,
+            );
+        }
+    }
+}
+
+/// Trees and queues referenced by the packed indices.
+#[derive(Clone, Copy)]
+pub struct TreeAccounts<'ix> {
+    accounts: &'ix [AccountView],
+}
+
+impl<'ix> TreeAccounts<'ix> {
+    #[inline(always)]
+    pub fn new(accounts: &'ix [AccountView]) -> Self {
+        Self { accounts }
+    }
+
+    #[inline(always)]
+    pub fn as_slice(&self) -> &'ix [AccountView] {
+        self.accounts
+    }
+
+    /// Account at `index`, checked to be owned by the account compression program.
+    #[inline(always)]
+    pub fn try_get(&self, index: u8) -> Result<&'ix AccountView> {
+        let Some(account) = self.accounts.get(index as usize) else {
+            error_msg!(
+                "TreeAccounts::try_get: index out of bounds",
+                ProgramError::NotEnoughAccountKeys,
+            );
+        };
+
+        // SAFETY: the owner is only compared, no reference is kept
+        if unlikely(!address_eq(
+            unsafe { account.owner() },
+            &ACCOUNT_COMPRESSION_PROGRAM_ID,
+        )) {
+            error_msg!(
+                "TreeAccounts::try_get: not a tree account",
+                ProgramError::InvalidAccountOwner,
+            );
+        }
+
+        Ok(account)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_packed() {
+        let mut data = [0u8; 1 + 128 + 42];
+        data[0] = 1;
+        data[129..131].copy_from_slice(&7u16.to_le_bytes());
+        data[131] = 1;
+        data[134..138].copy_from_slice(&42u32.to_le_bytes());
+
+        let mut bytes = &data[..];
+        assert!(try_read_validity_proof(&mut bytes).unwrap().is_some());
+
+        let meta = try_read_packed::<CompressedAccountMeta>(&mut bytes).unwrap();
+        assert_eq!(meta.tree_info.root_index(), 7);
+        assert!(meta.tree_info.prove_by_index());
+        assert_eq!(meta.tree_info.leaf_index(), 42);
+        assert!(bytes.is_empty());
+
+        assert_eq!(try_read_validity_proof(&mut &[0u8][..]).unwrap(), None);
+        assert!(try_read_validity_proof(&mut &[2u8][..]).is_err());
+        assert!(try_read_validity_proof(&mut &[1u8; 100][..]).is_err());
+    }
+}
@@ -8,10 +8,11 @@ use syn::{parse_macro_input, DeriveInput};
 #[proc_macro_derive(OwnerProgram)]
 pub fn derive_owner_program(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let expanded = quote! {
-        impl OwnerProgram for #name {
+        impl #impl_generics OwnerProgram for #name #ty_generics #where_clause {
             const OWNER: Address = crate::ID;
 
             fn owner() -> Address {
@@ -0,0 +1,71 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput};
+
+/// Implements `PodEnum`, `TryFrom<u8>` and `From<Enum> for u8` on a fieldless `#[repr(u8)]` enum,
+/// so it can be stored as a `PodU8Enum<Enum>`.
+#[proc_macro_derive(PodEnum)]
+pub fn derive_pod_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let Data::Enum(data) = &input.data else {
+        return syn::Error::new_spanned(&input, "PodEnum requires an enum")
+            .to_compile_error()
+            .into();
+    };
+
+    let is_repr_u8 = input.attrs.iter().any(|attr| {
+        attr.path().is_ident("repr")
+            && attr
+                .parse_args::<syn::Ident>()
+                .is_ok_and(|repr| repr == "u8")
+    });
+    if !is_repr_u8 {
+        return syn::Error::new_spanned(&input, "PodEnum requires #[repr(u8)]")
+            .to_compile_error()
+            .into();
+    }
+
+    if let Some(variant) = data.variants.iter().find(|v| !v.fields.is_empty()) {
+        return syn::Error::new_spanned(variant, "PodEnum requires a fieldless enum")
+            .to_compile_error()
+            .into();
+    }
+
+    let variants: Vec<_> = data.variants.iter().map(|v| &v.ident).collect();
+
+    let expanded = quote! {
+        impl TryFrom<u8> for #name {
+            type Error = ProgramError;
+
+            #[inline(always)]
+            fn try_from(value: u8) -> Result<Self> {
+                #(
+                    if value == #name::#variants as u8 {
+                        return Ok(#name::#variants);
+                    }
+                )*
+
+                error_msg!(
+                    "PodEnum::try_from: unknown variant",
+                    ProgramError::InvalidAccountData,
+                );
+            }
+        }
+
+        impl From<#name> for u8 {
+            #[inline(always)]
+            fn from(value: #name) -> u8 {
+                value as u8
+            }
+        }
+
+        impl PodEnum for #name {}
+    };
+
+    TokenStream::from(expanded)
+}
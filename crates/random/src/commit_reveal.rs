@@ -0,0 +1,78 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commit-reveal randomness.
+//!
+//! The user commits to [`commitment`] of a 32 byte secret, and the program records the
+//! commitment together with the slot it landed in. In a later transaction the user reveals the
+//! secret, and [`reveal`] returns `sha256(secret || hash of the first slot after the commit)`:
+//!
+//! - the user can't predict the slot hash when committing
+//! - the leader of that slot doesn't know the secret
+//!
+//! The remaining bias is the user's option to never reveal an unfavorable outcome, programs
+//! should make that costly, e.g. by forfeiting a deposit. [`SlotHashes`] only keeps the last
+//! [`MAX_ENTRIES`](hayabusa_sysvars::slot_hashes::MAX_ENTRIES) slots (about 3.4 minutes), the reveal has to land within that window.
+
+use hayabusa_errors::{ErrorCode, Result};
+use hayabusa_syscalls::sha256;
+use hayabusa_sysvars::slot_hashes::SlotHashes;
+use hayabusa_utility::{error_msg, hint::unlikely};
+use solana_program_error::ProgramError;
+
+/// Commitment to `secret`, to be stored along with the current slot.
+#[inline]
+pub fn commitment(secret: &[u8; 32]) -> [u8; 32] {
+    sha256(&[secret])
+}
+
+/// Checks that `secret` opens `commitment`.
+#[inline]
+pub fn verify_reveal(commitment: &[u8; 32], secret: &[u8; 32]) -> Result<()> {
+    if unlikely(self::commitment(secret) != *commitment) {
+        error_msg!(
+            "verify_reveal: secret does not match commitment",
+            ErrorCode::InvalidReveal,
+        );
+    }
+
+    Ok(())
+}
+
+/// Verifies the revealed `secret` and returns the random value of a commitment made in
+/// `commit_slot`.
+///
+/// Fails with [`ErrorCode::RevealNotReady`] in the commit slot itself and with
+/// [`ErrorCode::RevealExpired`] once the slot following the commit left [`SlotHashes`].
+#[inline]
+pub fn reveal(
+    slot_hashes: &SlotHashes,
+    commitment: &[u8; 32],
+    commit_slot: u64,
+    secret: &[u8; 32],
+) -> Result<[u8; 32]> {
+    verify_reveal(commitment, secret)?;
+
+    let entries = slot_hashes.entries();
+
+    // entries are sorted most recent first
+    let index = entries.partition_point(|entry| entry.slot() > commit_slot);
+
+    if unlikely(index == 0) {
+        error_msg!(
+            "reveal: no slot after the commit slot yet",
+            ErrorCode::RevealNotReady,
+        );
+    }
+
+    // Without an entry at or before the commit slot, the first slot after it may already have
+    // been evicted and a later hash would be picked
+    if unlikely(index == entries.len()) {
+        error_msg!(
+            "reveal: commit slot no longer in slot hashes",
+            ErrorCode::RevealExpired,
+        );
+    }
+
+    Ok(sha256(&[secret, &entries[index - 1].hash]))
+}
@@ -0,0 +1,63 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Randomness derived from the slot hashes sysvar.
+//!
+//! ## Manipulability
+//!
+//! [`pseudo_random`] hashes the most recent entry of [`SlotHashes`], the hash of the parent
+//! slot, together with a caller provided seed. That hash is public before the transaction
+//! lands, so the result is only unpredictable to parties that can't act on it:
+//!
+//! - the sender (or a bot) can simulate the instruction and only submit favorable outcomes
+//! - the leader can reorder, delay or drop the transaction, changing the slot it sees
+//!
+//! Use it for shuffling, tie-breaking and similar cases where predicting the value gains
+//! nothing. Whenever value is at stake, use [`commit_reveal`] instead.
+
+#![no_std]
+
+pub mod commit_reveal;
+
+use hayabusa_errors::Result;
+use hayabusa_syscalls::sha256;
+use hayabusa_sysvars::slot_hashes::SlotHashes;
+use hayabusa_utility::{error_msg, hint::unlikely};
+use solana_program_error::ProgramError;
+
+/// 32 pseudo-random bytes, `sha256(latest slot hash || latest slot || seed)`.
+///
+/// Deterministic for a given slot and seed, see the crate docs for who can predict it.
+#[inline]
+pub fn pseudo_random(slot_hashes: &SlotHashes, seed: &[u8]) -> Result<[u8; 32]> {
+    let Some(latest) = slot_hashes.latest() else {
+        error_msg!(
+            "pseudo_random: slot hashes sysvar is empty",
+            ProgramError::UnsupportedSysvar,
+        );
+    };
+
+    Ok(sha256(&[&latest.hash, &latest.slot().to_le_bytes(), seed]))
+}
+
+/// [`pseudo_random`] truncated to a `u64`.
+#[inline]
+pub fn pseudo_random_u64(slot_hashes: &SlotHashes, seed: &[u8]) -> Result<u64> {
+    Ok(to_u64(&pseudo_random(slot_hashes, seed)?))
+}
+
+/// First 8 bytes of `random`, little endian.
+#[inline(always)]
+pub fn to_u64(random: &[u8; 32]) -> u64 {
+    u64::from_le_bytes(random[..8].try_into().unwrap())
+}
+
+/// Maps `random` into `0..bound` with a multiply-shift, the bias is below `bound / 2^64`.
+#[inline(always)]
+pub fn bounded(random: u64, bound: u64) -> Result<u64> {
+    if unlikely(bound == 0) {
+        error_msg!("bounded: bound must be non-zero", ProgramError::InvalidArgument);
+    }
+
+    Ok(((random as u128 * bound as u128) >> 64) as u64)
+}
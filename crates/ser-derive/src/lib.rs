@@ -8,10 +8,11 @@ use syn::{parse_macro_input, DeriveInput};
 #[proc_macro_derive(ZcDeserialize)]
 pub fn derive_zc_deserialize(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let expanded = quote! {
-        impl ZcDeserialize for #name {}
+        impl #impl_generics ZcDeserialize for #name #ty_generics #where_clause {}
     };
 
     TokenStream::from(expanded)
@@ -20,10 +21,11 @@ pub fn derive_zc_deserialize(input: TokenStream) -> TokenStream {
 #[proc_macro_derive(ZcDeserializeMut)]
 pub fn derive_zc_deserialize_mut(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let expanded = quote! {
-        impl ZcDeserializeMut for #name {}
+        impl #impl_generics ZcDeserializeMut for #name #ty_generics #where_clause {}
     };
 
     TokenStream::from(expanded)
@@ -32,10 +34,11 @@ pub fn derive_zc_deserialize_mut(input: TokenStream) -> TokenStream {
 #[proc_macro_derive(Deserialize)]
 pub fn derive_deserialize(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let expanded = quote! {
-        impl Deserialize for #name {}
+        impl #impl_generics Deserialize for #name #ty_generics #where_clause {}
     };
 
     TokenStream::from(expanded)
@@ -44,10 +47,11 @@ pub fn derive_deserialize(input: TokenStream) -> TokenStream {
 #[proc_macro_derive(DeserializeMut)]
 pub fn derive_deserialize_mut(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let expanded = quote! {
-        impl DeserializeMut for #name {}
+        impl #impl_generics DeserializeMut for #name #ty_generics #where_clause {}
     };
 
     TokenStream::from(expanded)
@@ -56,10 +60,11 @@ pub fn derive_deserialize_mut(input: TokenStream) -> TokenStream {
 #[proc_macro_derive(Zc)]
 pub fn derive_zc(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let expanded = quote! {
-        impl Zc for #name {}
+        impl #impl_generics Zc for #name #ty_generics #where_clause {}
     };
 
     TokenStream::from(expanded)
@@ -68,10 +73,11 @@ pub fn derive_zc(input: TokenStream) -> TokenStream {
 #[proc_macro_derive(ZcInitialize)]
 pub fn derive_zc_initialize(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let expanded = quote! {
-        impl ZcInitialize for #name {}
+        impl #impl_generics ZcInitialize for #name #ty_generics #where_clause {}
     };
 
     TokenStream::from(expanded)
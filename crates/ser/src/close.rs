@@ -0,0 +1,150 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use bytemuck::AnyBitPattern;
+use hayabusa_common::{address_eq, AccountView};
+use hayabusa_discriminator::Discriminator;
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely, Len, OwnerProgram, RentPayer};
+
+use crate::try_deserialize_zc;
+
+/// Discriminator left in accounts closed with [`tombstone_account`]. No account type can
+/// deserialize from it and the program keeps ownership, so the account can neither be reused
+/// nor re-created before the runtime garbage collects it at the end of the transaction.
+pub const CLOSED_ACCOUNT_DISCRIMINATOR: [u8; 8] = [0xff; 8];
+
+/// Moves all lamports of `account_view` to `destination` and closes it.
+///
+/// The account must not be borrowed.
+#[inline(always)]
+pub fn close_account(account_view: &AccountView, destination: &AccountView) -> Result<()> {
+    if unlikely(address_eq(account_view.address(), destination.address())) {
+        error_msg!(
+            "close_account: destination is the closed account",
+            ErrorCode::InvalidAccount,
+        );
+    }
+
+    let Some(lamports) = destination.lamports().checked_add(account_view.lamports()) else {
+        error_msg!(
+            "close_account: destination lamports overflow",
+            ProgramError::ArithmeticOverflow,
+        );
+    };
+
+    destination.set_lamports(lamports);
+    account_view.set_lamports(0);
+
+    account_view.close()
+}
+
+/// Closes a zero-copy account of type `T`, refunding its lamports to `destination`.
+#[inline(always)]
+pub fn try_close_zc<T>(account_view: &AccountView, destination: &AccountView) -> Result<()>
+where
+    T: AnyBitPattern + Discriminator + Len + OwnerProgram,
+{
+    // validates owner, length and discriminator
+    drop(try_deserialize_zc::<T>(account_view)?);
+
+    close_account(account_view, destination)
+}
+
+/// Closes a zero-copy account of type `T`, refunding its lamports to the recorded
+/// [`RentPayer::rent_payer`]. Anyone allowed to close the account can call this, the refund
+/// always goes to the original payer.
+#[inline(always)]
+pub fn try_close_zc_to_rent_payer<T>(account_view: &AccountView, rent_payer: &AccountView) -> Result<()>
+where
+    T: AnyBitPattern + Discriminator + Len + OwnerProgram + RentPayer,
+{
+    let expected = *try_deserialize_zc::<T>(account_view)?.rent_payer();
+
+    if unlikely(!address_eq(rent_payer.address(), &expected)) {
+        error_msg!(
+            "try_close_zc_to_rent_payer: rent payer mismatch",
+            ErrorCode::InvalidAccount,
+        );
+    }
+
+    close_account(account_view, rent_payer)
+}
+
+/// Moves all lamports of `account_view` to `destination`, zeroes its data and writes
+/// [`CLOSED_ACCOUNT_DISCRIMINATOR`].
+///
+/// Unlike [`close_account`] the owner and data length are kept, the account can't be revived by
+/// refunding it later in the same transaction.
+#[inline(always)]
+pub fn tombstone_account(account_view: &AccountView, destination: &AccountView) -> Result<()> {
+    if unlikely(address_eq(account_view.address(), destination.address())) {
+        error_msg!(
+            "tombstone_account: destination is the closed account",
+            ErrorCode::InvalidAccount,
+        );
+    }
+
+    let Some(lamports) = destination.lamports().checked_add(account_view.lamports()) else {
+        error_msg!(
+            "tombstone_account: destination lamports overflow",
+            ProgramError::ArithmeticOverflow,
+        );
+    };
+
+    let mut data = account_view.try_borrow_mut()?;
+
+    if unlikely(data.len() < CLOSED_ACCOUNT_DISCRIMINATOR.len()) {
+        error_msg!(
+            "tombstone_account: account data too small",
+            ProgramError::AccountDataTooSmall,
+        );
+    }
+
+    data.fill(0);
+    data[..CLOSED_ACCOUNT_DISCRIMINATOR.len()].copy_from_slice(&CLOSED_ACCOUNT_DISCRIMINATOR);
+    drop(data);
+
+    destination.set_lamports(lamports);
+    account_view.set_lamports(0);
+
+    Ok(())
+}
+
+/// Tombstones a zero-copy account of type `T` (see [`tombstone_account`]), refunding its
+/// lamports to `destination`.
+#[inline(always)]
+pub fn try_tombstone_zc<T>(account_view: &AccountView, destination: &AccountView) -> Result<()>
+where
+    T: AnyBitPattern + Discriminator + Len + OwnerProgram,
+{
+    // validates owner, length and discriminator
+    drop(try_deserialize_zc::<T>(account_view)?);
+
+    tombstone_account(account_view, destination)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hayabusa_common::{mock::MockAccount, Address};
+
+    #[test]
+    fn test_close_into_itself() {
+        let address = Address::new_from_array([1; 32]);
+        let mut account = MockAccount::new(address, Address::default(), &[0; 8]).with_lamports(10);
+        let mut alias = MockAccount::new(address, Address::default(), &[0; 8]).with_lamports(10);
+        let (account, alias) = (account.view(), alias.view());
+
+        for close in [close_account, tombstone_account] {
+            assert_eq!(close(&account, &alias).err(), Some(ErrorCode::InvalidAccount.into()));
+            assert_eq!(account.lamports(), 10);
+        }
+
+        let mut destination =
+            MockAccount::new(Address::new_from_array([2; 32]), Address::default(), &[]);
+        let destination = destination.view();
+        tombstone_account(&account, &destination).unwrap();
+        assert_eq!((account.lamports(), destination.lamports()), (0, 10));
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use hayabusa_common::{AccountView, Address};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_sysvars::{rent::Rent, Sysvar};
+use hayabusa_utility::{error_msg, hint::unlikely, Lamports};
+
+/// Adds `amount` to the lamports of `account_view`, returning the new balance.
+///
+/// Any writable account can be credited, the lamports must be debited from another account in
+/// the same instruction.
+#[inline(always)]
+pub fn try_credit_lamports(account_view: &AccountView, amount: Lamports) -> Result<Lamports> {
+    if unlikely(!account_view.is_writable()) {
+        error_msg!(
+            "try_credit_lamports: account not writable",
+            ErrorCode::AccountNotWritable,
+        );
+    }
+
+    let Some(lamports) = account_view.lamports().checked_add(amount.get()) else {
+        error_msg!(
+            "try_credit_lamports: lamports overflow",
+            ProgramError::ArithmeticOverflow,
+        );
+    };
+
+    account_view.set_lamports(lamports);
+
+    Ok(Lamports::new(lamports))
+}
+
+/// Subtracts `amount` from the lamports of `account_view`, returning the new balance.
+///
+/// Only accounts owned by the executing program can be debited, `program_id` is checked against
+/// the owner so a mistake fails here instead of at the end of the instruction.
+#[inline(always)]
+pub fn try_debit_lamports(
+    account_view: &AccountView,
+    program_id: &Address,
+    amount: Lamports,
+) -> Result<Lamports> {
+    if unlikely(!account_view.owned_by(program_id)) {
+        error_msg!(
+            "try_debit_lamports: account not owned by the program",
+            ProgramError::InvalidAccountOwner,
+        );
+    }
+
+    if unlikely(!account_view.is_writable()) {
+        error_msg!(
+            "try_debit_lamports: account not writable",
+            ErrorCode::AccountNotWritable,
+        );
+    }
+
+    let Some(lamports) = account_view.lamports().checked_sub(amount.get()) else {
+        error_msg!(
+            "try_debit_lamports: insufficient lamports",
+            ProgramError::InsufficientFunds,
+        );
+    };
+
+    account_view.set_lamports(lamports);
+
+    Ok(Lamports::new(lamports))
+}
+
+/// Moves `amount` lamports from `from`, owned by `program_id`, to `to`.
+#[inline(always)]
+pub fn try_move_lamports(
+    from: &AccountView,
+    to: &AccountView,
+    program_id: &Address,
+    amount: Lamports,
+) -> Result<()> {
+    try_debit_lamports(from, program_id, amount)?;
+    try_credit_lamports(to, amount)?;
+
+    Ok(())
+}
+
+/// Moves every lamport of `from`, owned by `program_id`, above its rent exempt minimum to `to`,
+/// returning the amount moved.
+///
+/// For "withdraw everything" instructions that must keep the source account alive, see
+/// `system_program::instructions::transfer_all_except_rent` for system owned sources.
+#[inline(always)]
+pub fn try_transfer_all_except_rent(
+    from: &AccountView,
+    to: &AccountView,
+    program_id: &Address,
+) -> Result<Lamports> {
+    let rent_exempt = Rent::get()?.try_minimum_lamports(from.data_len())?;
+    let amount = Lamports::new(from.lamports()).saturating_sub(rent_exempt);
+
+    if !amount.is_zero() {
+        try_move_lamports(from, to, program_id, amount)?;
+    }
+
+    Ok(amount)
+}
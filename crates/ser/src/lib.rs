@@ -3,12 +3,24 @@
 
 #![no_std]
 
+pub mod close;
+pub mod lamports;
+pub mod migrate;
+pub mod realloc;
+pub mod tail;
 pub mod zc;
+pub mod zc_vec;
 
 use core::ops::Deref;
 use hayabusa_common::{AccountView, Ref, RefMut};
 use hayabusa_errors::Result;
+pub use close::*;
+pub use lamports::*;
+pub use migrate::*;
+pub use realloc::*;
+pub use tail::*;
 pub use zc::*;
+pub use zc_vec::*;
 
 // marker traits
 
@@ -0,0 +1,100 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use bytemuck::Pod;
+use hayabusa_common::{AccountView, RefMut};
+use hayabusa_discriminator::Discriminator;
+use hayabusa_errors::{ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely, Len, OwnerProgram};
+
+use crate::{try_deserialize_zc_mut, try_realloc_account};
+
+/// A zero-copy layout that can be upgraded in place from the layout it replaces.
+///
+/// Versions are told apart by their discriminators, so every version is its own `#[account]`
+/// type. Longer histories are a chain of `Migrate` impls (`V2: Migrate<Previous = V1>`,
+/// `V3: Migrate<Previous = V2>`, ...), each account being upgraded one step at a time.
+pub trait Migrate
+where
+    Self: Pod + Discriminator + Len + OwnerProgram,
+{
+    type Previous: Pod + Discriminator + Len;
+
+    fn migrate(previous: &Self::Previous) -> Self;
+}
+
+/// Layout an account of a [`Migrate`] type is currently stored with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Version {
+    Previous,
+    Current,
+}
+
+/// Returns which layout `account_view` is stored with, failing if it is owned by another
+/// program or matches neither layout.
+#[inline(always)]
+pub fn try_version_zc<T>(account_view: &AccountView) -> Result<Version>
+where
+    T: Migrate,
+{
+    if unlikely(!account_view.owned_by(&T::OWNER)) {
+        error_msg!(
+            "try_version_zc: wrong account owner",
+            ProgramError::InvalidAccountOwner,
+        );
+    }
+
+    let data = account_view.try_borrow()?;
+
+    if data.len() == T::DISCRIMINATED_LEN && data.starts_with(T::DISCRIMINATOR) {
+        return Ok(Version::Current);
+    }
+
+    if data.len() == T::Previous::DISCRIMINATED_LEN && data.starts_with(T::Previous::DISCRIMINATOR)
+    {
+        return Ok(Version::Previous);
+    }
+
+    error_msg!(
+        "try_version_zc: unknown account layout",
+        ProgramError::InvalidAccountData,
+    );
+}
+
+/// Upgrades `account_view` to `T` if it is still stored as `T::Previous`, returning the current
+/// state either way.
+///
+/// When the layout size changes the account is reallocated, see [`try_realloc_account`]: `payer`
+/// funds the extra rent when growing and is refunded when shrinking.
+#[inline(always)]
+pub fn try_migrate_zc<'ix, T>(
+    account_view: &'ix AccountView,
+    payer: &AccountView,
+    system_program: &AccountView,
+) -> Result<RefMut<'ix, T>>
+where
+    T: Migrate,
+{
+    if try_version_zc::<T>(account_view)? == Version::Current {
+        return try_deserialize_zc_mut::<T>(account_view);
+    }
+
+    let migrated = {
+        let data = account_view.try_borrow()?;
+        T::migrate(&bytemuck::pod_read_unaligned::<T::Previous>(
+            &data[T::Previous::DISCRIMINATOR.len()..T::Previous::DISCRIMINATED_LEN],
+        ))
+    };
+
+    if T::DISCRIMINATED_LEN != T::Previous::DISCRIMINATED_LEN {
+        try_realloc_account(account_view, T::DISCRIMINATED_LEN, payer, system_program)?;
+    }
+
+    let mut data = account_view.try_borrow_mut()?;
+    data[..T::DISCRIMINATOR.len()].copy_from_slice(T::DISCRIMINATOR);
+    data[T::DISCRIMINATOR.len()..T::DISCRIMINATED_LEN]
+        .copy_from_slice(bytemuck::bytes_of(&migrated));
+    drop(data);
+
+    try_deserialize_zc_mut::<T>(account_view)
+}
@@ -0,0 +1,127 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use hayabusa_common::AccountView;
+use hayabusa_cpi::CpiCtx;
+use hayabusa_errors::{ProgramError, Result};
+use hayabusa_system_program::instructions::{transfer, Transfer};
+use hayabusa_sysvars::{rent::Rent, Sysvar};
+use hayabusa_utility::{error_msg, Lamports};
+
+/// Resizes `account_view` to `new_len` bytes and keeps it rent exempt.
+///
+/// Growing transfers the missing rent from `payer` (which must sign), shrinking refunds the rent
+/// that is no longer needed to `payer`, any other lamports stay in the account. New bytes are
+/// always zeroed.
+#[inline(always)]
+pub fn try_realloc_account(
+    account_view: &AccountView,
+    new_len: usize,
+    payer: &AccountView,
+    system_program: &AccountView,
+) -> Result<()> {
+    try_realloc_account_with_rent(account_view, new_len, payer, system_program, &Rent::get()?)
+}
+
+#[inline(always)]
+fn try_realloc_account_with_rent(
+    account_view: &AccountView,
+    new_len: usize,
+    payer: &AccountView,
+    system_program: &AccountView,
+    rent: &Rent,
+) -> Result<()> {
+    let required_lamports = rent.try_minimum_balance(new_len)?;
+    let current_lamports = account_view.lamports();
+
+    if required_lamports > current_lamports {
+        transfer(
+            CpiCtx::try_new_without_signer(
+                system_program,
+                Transfer {
+                    from: payer,
+                    to: account_view,
+                },
+            )?,
+            Lamports::new(required_lamports - current_lamports),
+        )?;
+    } else if new_len < account_view.data_len() {
+        // the rent of the removed bytes, as long as the account stays rent exempt
+        let refund = (rent.try_minimum_balance(account_view.data_len())? - required_lamports)
+            .min(current_lamports - required_lamports);
+
+        let Some(payer_lamports) = payer.lamports().checked_add(refund) else {
+            error_msg!(
+                "try_realloc_account: payer lamports overflow",
+                ProgramError::ArithmeticOverflow,
+            );
+        };
+
+        payer.set_lamports(payer_lamports);
+        account_view.set_lamports(current_lamports - refund);
+    }
+
+    account_view.resize(new_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hayabusa_common::{mock::MockAccount, Address};
+    use hayabusa_sysvars::rent::DEFAULT_LAMPORTS_PER_BYTE;
+
+    fn rent() -> Rent {
+        // default rate and the 2 years exemption threshold
+        let words = [DEFAULT_LAMPORTS_PER_BYTE, 2.0f64.to_bits()];
+        Rent::from_bytes(bytemuck::bytes_of(&words))
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn test_shrink_refunds_only_rent() {
+        let rent = rent();
+        let old_rent = rent.try_minimum_balance(100).unwrap();
+        let new_rent = rent.try_minimum_balance(10).unwrap();
+        // a vault holding funds on top of its rent
+        let funds = 1_000_000;
+
+        let mut vault = MockAccount::new(
+            Address::new_from_array([1; 32]),
+            Address::default(),
+            &[0; 100],
+        )
+        .with_lamports(old_rent + funds);
+        let mut payer = MockAccount::new(Address::new_from_array([2; 32]), Address::default(), &[]);
+        let mut system_program = MockAccount::new(Address::default(), Address::default(), &[]);
+        let (vault, payer) = (vault.view(), payer.view());
+
+        try_realloc_account_with_rent(&vault, 10, &payer, &system_program.view(), &rent).unwrap();
+
+        assert_eq!(vault.data_len(), 10);
+        assert_eq!(vault.lamports(), new_rent + funds);
+        assert_eq!(payer.lamports(), old_rent - new_rent);
+    }
+
+    #[test]
+    fn test_shrink_refund_capped_by_surplus() {
+        let rent = rent();
+        let new_rent = rent.try_minimum_balance(10).unwrap();
+
+        // not rent exempt at its current size, only 7 lamports above the new minimum
+        let mut account = MockAccount::new(
+            Address::new_from_array([1; 32]),
+            Address::default(),
+            &[0; 100],
+        )
+        .with_lamports(new_rent + 7);
+        let mut payer = MockAccount::new(Address::new_from_array([2; 32]), Address::default(), &[]);
+        let mut system_program = MockAccount::new(Address::default(), Address::default(), &[]);
+        let (account, payer) = (account.view(), payer.view());
+
+        try_realloc_account_with_rent(&account, 10, &payer, &system_program.view(), &rent).unwrap();
+
+        assert_eq!(account.lamports(), new_rent);
+        assert_eq!(payer.lamports(), 7);
+    }
+}
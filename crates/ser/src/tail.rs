@@ -0,0 +1,212 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Zero-copy accounts made of a fixed `H` header followed by a `[T]` tail whose length is
+//! derived from the account data length: `discriminator | H | T * n`.
+
+use bytemuck::Pod;
+use core::mem::size_of;
+use hayabusa_common::{AccountView, Ref, RefMut};
+use hayabusa_discriminator::Discriminator;
+use hayabusa_errors::{ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely, Len, OwnerProgram};
+use solana_instruction_view::cpi::Signer;
+
+use crate::{try_create_zc, InitAccounts};
+
+/// Number of `T` in the tail of an account of `data_len` bytes with an `H` header.
+///
+/// Fails if the data is shorter than the header or the tail is not a whole number of `T`.
+#[inline(always)]
+pub fn try_tail_len<H, T>(data_len: usize) -> Result<usize>
+where
+    H: Len,
+    T: Pod,
+{
+    if unlikely(size_of::<T>() == 0) {
+        error_msg!(
+            "try_tail_len: zero sized tail element",
+            ProgramError::InvalidArgument,
+        );
+    }
+
+    let Some(tail_bytes) = data_len.checked_sub(H::DISCRIMINATED_LEN) else {
+        error_msg!(
+            "try_tail_len: data shorter than header",
+            ProgramError::InvalidAccountData,
+        );
+    };
+
+    if unlikely(tail_bytes % size_of::<T>() != 0) {
+        error_msg!(
+            "try_tail_len: partial tail element",
+            ProgramError::InvalidAccountData,
+        );
+    }
+
+    Ok(tail_bytes / size_of::<T>())
+}
+
+/// Space of an account with an `H` header and room for `capacity` elements.
+#[inline(always)]
+pub fn try_tail_space<H, T>(capacity: usize) -> Result<usize>
+where
+    H: Len,
+    T: Pod,
+{
+    let space = capacity
+        .checked_mul(size_of::<T>())
+        .and_then(|tail| tail.checked_add(H::DISCRIMINATED_LEN));
+
+    let Some(space) = space else {
+        error_msg!(
+            "try_tail_space: capacity overflow",
+            ProgramError::ArithmeticOverflow,
+        );
+    };
+
+    Ok(space)
+}
+
+#[inline(always)]
+fn check_tail_account<H, T>(account_view: &AccountView, data: &[u8]) -> Result<()>
+where
+    H: Discriminator + Len + OwnerProgram,
+    T: Pod,
+{
+    if unlikely(!account_view.owned_by(&H::OWNER)) {
+        error_msg!(
+            "check_tail_account: wrong account owner",
+            ProgramError::InvalidAccountOwner,
+        );
+    }
+
+    try_tail_len::<H, T>(data.len())?;
+
+    if unlikely(&data[..H::DISCRIMINATOR.len()] != H::DISCRIMINATOR) {
+        error_msg!(
+            "check_tail_account: invalid discriminator",
+            ProgramError::InvalidAccountData,
+        );
+    }
+
+    Ok(())
+}
+
+#[inline(always)]
+fn cast_tail<T: Pod>(bytes: &[u8]) -> Result<&[T]> {
+    let Ok(tail) = bytemuck::try_cast_slice(bytes) else {
+        error_msg!(
+            "cast_tail: misaligned tail",
+            ProgramError::InvalidAccountData,
+        );
+    };
+
+    Ok(tail)
+}
+
+#[inline(always)]
+fn cast_tail_mut<T: Pod>(bytes: &mut [u8]) -> Result<&mut [T]> {
+    let Ok(tail) = bytemuck::try_cast_slice_mut(bytes) else {
+        error_msg!(
+            "cast_tail_mut: misaligned tail",
+            ProgramError::InvalidAccountData,
+        );
+    };
+
+    Ok(tail)
+}
+
+#[inline(always)]
+pub fn try_deserialize_zc_header<H, T>(account_view: &AccountView) -> Result<Ref<'_, H>>
+where
+    H: Pod + Discriminator + Len + OwnerProgram,
+    T: Pod,
+{
+    let data = account_view.try_borrow()?;
+    check_tail_account::<H, T>(account_view, &data)?;
+
+    Ok(Ref::map(data, |d| {
+        bytemuck::from_bytes(&d[H::DISCRIMINATOR.len()..H::DISCRIMINATED_LEN])
+    }))
+}
+
+#[inline(always)]
+pub fn try_deserialize_zc_header_mut<H, T>(account_view: &AccountView) -> Result<RefMut<'_, H>>
+where
+    H: Pod + Discriminator + Len + OwnerProgram,
+    T: Pod,
+{
+    let data = account_view.try_borrow_mut()?;
+    check_tail_account::<H, T>(account_view, &data)?;
+
+    Ok(RefMut::map(data, |d| {
+        bytemuck::from_bytes_mut(&mut d[H::DISCRIMINATOR.len()..H::DISCRIMINATED_LEN])
+    }))
+}
+
+#[inline(always)]
+pub fn try_deserialize_zc_tail<H, T>(account_view: &AccountView) -> Result<Ref<'_, [T]>>
+where
+    H: Pod + Discriminator + Len + OwnerProgram,
+    T: Pod,
+{
+    let data = account_view.try_borrow()?;
+    check_tail_account::<H, T>(account_view, &data)?;
+    cast_tail::<T>(&data[H::DISCRIMINATED_LEN..])?;
+
+    // the cast was checked above, it can't panic on the same bytes
+    Ok(Ref::map(data, |d| {
+        bytemuck::cast_slice(&d[H::DISCRIMINATED_LEN..])
+    }))
+}
+
+#[inline(always)]
+pub fn try_deserialize_zc_tail_mut<H, T>(account_view: &AccountView) -> Result<RefMut<'_, [T]>>
+where
+    H: Pod + Discriminator + Len + OwnerProgram,
+    T: Pod,
+{
+    let mut data = account_view.try_borrow_mut()?;
+    check_tail_account::<H, T>(account_view, &data)?;
+    cast_tail_mut::<T>(&mut data[H::DISCRIMINATED_LEN..])?;
+
+    // the cast was checked above, it can't panic on the same bytes
+    Ok(RefMut::map(data, |d| {
+        bytemuck::cast_slice_mut(&mut d[H::DISCRIMINATED_LEN..])
+    }))
+}
+
+/// Runs `f` on the header and the tail at once, which separate mutable borrows can't do.
+#[inline(always)]
+pub fn try_split_zc_tail_mut<H, T, R, F>(account_view: &AccountView, f: F) -> Result<R>
+where
+    H: Pod + Discriminator + Len + OwnerProgram,
+    T: Pod,
+    F: FnOnce(&mut H, &mut [T]) -> R,
+{
+    let mut data = account_view.try_borrow_mut()?;
+    check_tail_account::<H, T>(account_view, &data)?;
+
+    let (header, tail) = data[H::DISCRIMINATOR.len()..].split_at_mut(size_of::<H>());
+
+    Ok(f(bytemuck::from_bytes_mut(header), cast_tail_mut(tail)?))
+}
+
+/// Creates the account with room for `capacity` zeroed tail elements and writes the discriminator
+/// of `H`.
+#[inline(always)]
+pub fn try_initialize_zc_with_tail<H, T>(
+    target_account: &AccountView,
+    init_accounts: InitAccounts<'_, '_>,
+    signers: Option<&[Signer]>,
+    capacity: usize,
+) -> Result<()>
+where
+    H: Pod + Discriminator + Len + OwnerProgram,
+    T: Pod,
+{
+    let space = try_tail_space::<H, T>(capacity)?;
+
+    try_create_zc::<H>(target_account, init_accounts, space, signers)
+}
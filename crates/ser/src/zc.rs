@@ -5,10 +5,12 @@ use super::{Deserialize, DeserializeMut, Zc};
 use bytemuck::{AnyBitPattern, Pod};
 use hayabusa_common::{AccountView, Address, Ref, RefMut};
 use hayabusa_cpi::CpiCtx;
-use hayabusa_discriminator::Discriminator;
+use hayabusa_discriminator::{Discriminator, InitializingDiscriminator};
 use hayabusa_errors::{ErrorCode, ProgramError, Result};
-use hayabusa_system_program::instructions::{create_account, CreateAccount};
-use hayabusa_utility::{error_msg, hint::unlikely, Len, OwnerProgram};
+use hayabusa_system_program::instructions::{
+    create_account, create_account_allow_prefunded, CreateAccount,
+};
+use hayabusa_utility::{error_msg, hint::unlikely, Len, OwnerProgram, RentPayer};
 use solana_instruction_view::cpi::Signer;
 
 /// # Safety
@@ -17,7 +19,7 @@ pub unsafe trait RawZcDeserialize
 where
     Self: Sized + FromBytesUnchecked + Zc + Deserialize,
 {
-    fn try_deserialize_raw(account_view: &AccountView) -> Result<Ref<Self>>;
+    fn try_deserialize_raw(account_view: &AccountView) -> Result<Ref<'_, Self>>;
 }
 
 // # Safety
@@ -27,7 +29,7 @@ where
     T: Sized + FromBytesUnchecked + Zc + Deserialize + Discriminator + Len + OwnerProgram + Pod,
 {
     #[inline(always)]
-    fn try_deserialize_raw(account_view: &AccountView) -> Result<Ref<T>> {
+    fn try_deserialize_raw(account_view: &AccountView) -> Result<Ref<'_, T>> {
         if unlikely(!account_view.owned_by(&T::OWNER)) {
             error_msg!(
                 "try_deserialize_raw: wrong account owner",
@@ -43,7 +45,7 @@ where
         }
 
         Ok(Ref::map(account_view.try_borrow()?, |d| unsafe {
-            T::from_bytes_unchecked(&d[8..])
+            T::from_bytes_unchecked(&d[T::DISCRIMINATOR.len()..])
         }))
     }
 }
@@ -54,7 +56,7 @@ pub unsafe trait RawZcDeserializeMut
 where
     Self: Sized + FromBytesUnchecked + Zc + Deserialize + DeserializeMut,
 {
-    fn try_deserialize_raw_mut(account_view: &AccountView) -> Result<RefMut<Self>>;
+    fn try_deserialize_raw_mut(account_view: &AccountView) -> Result<RefMut<'_, Self>>;
 }
 
 // # Safety
@@ -71,7 +73,7 @@ where
         + OwnerProgram
         + Pod,
 {
-    fn try_deserialize_raw_mut(account_view: &AccountView) -> Result<RefMut<Self>> {
+    fn try_deserialize_raw_mut(account_view: &AccountView) -> Result<RefMut<'_, Self>> {
         if unlikely(!account_view.owned_by(&T::OWNER)) {
             error_msg!(
                 "try_deserialize_raw_mut: wrong account owner",
@@ -87,7 +89,7 @@ where
         }
 
         Ok(RefMut::map(account_view.try_borrow_mut()?, |d| unsafe {
-            T::from_bytes_unchecked_mut(&mut d[8..])
+            T::from_bytes_unchecked_mut(&mut d[T::DISCRIMINATOR.len()..])
         }))
     }
 }
@@ -101,7 +103,7 @@ where
     ///
     /// and that there are no mutable references to the underlying `AccountView` data
     ///
-    /// and that the `AccountView` data slice len is >8 (to account for discriminator, account data starts at index 8)
+    /// and that the `AccountView` data slice len is > `T::DISCRIMINATOR.len()`, where the account data starts
     unsafe fn try_deserialize_raw_unchecked(account_view: &AccountView) -> Result<&Self>;
 }
 
@@ -127,14 +129,14 @@ where
 
         let data = account_view.borrow_unchecked();
 
-        if unlikely(&data[..8] != T::DISCRIMINATOR) {
+        if unlikely(&data[..T::DISCRIMINATOR.len()] != T::DISCRIMINATOR) {
             error_msg!(
                 "try_deserialize_raw_unchecked: invalid discriminator",
                 ErrorCode::InvalidAccountDiscriminator,
             );
         }
 
-        let undiscriminated_account_data = &data[8..];
+        let undiscriminated_account_data = &data[T::DISCRIMINATOR.len()..];
 
         Ok(Self::from_bytes_unchecked(undiscriminated_account_data))
     }
@@ -149,7 +151,8 @@ where
     ///
     /// that there are no other references to the underlying `AccountView` data,
     ///
-    /// and that the `AccountView` data slice len is >8 (to account for discriminator, account data starts at index 8)
+    /// and that the `AccountView` data slice len is > `T::DISCRIMINATOR.len()`, where the account data starts
+    #[allow(clippy::mut_from_ref)]
     unsafe fn try_deserialize_raw_unchecked_mut(account_view: &AccountView) -> Result<&mut Self>;
 }
 
@@ -180,7 +183,8 @@ where
             );
         }
 
-        let undiscriminated_account_data = &mut account_view.borrow_unchecked_mut()[8..];
+        let undiscriminated_account_data =
+            &mut account_view.borrow_unchecked_mut()[T::DISCRIMINATOR.len()..];
 
         Ok(Self::from_bytes_unchecked_mut(undiscriminated_account_data))
     }
@@ -206,7 +210,7 @@ pub trait ZcDeserialize
 where
     Self: AnyBitPattern + Discriminator + Len + OwnerProgram + Zc + Deserialize,
 {
-    fn try_deserialize(account_view: &AccountView) -> Result<Ref<Self>> {
+    fn try_deserialize(account_view: &AccountView) -> Result<Ref<'_, Self>> {
         try_deserialize_zc::<Self>(account_view)
     }
 }
@@ -215,7 +219,7 @@ pub trait ZcDeserializeMut
 where
     Self: Pod + Discriminator + Len + OwnerProgram + Zc + Deserialize + DeserializeMut,
 {
-    fn try_deserialize_mut(account_view: &AccountView) -> Result<RefMut<Self>> {
+    fn try_deserialize_mut(account_view: &AccountView) -> Result<RefMut<'_, Self>> {
         try_deserialize_zc_mut::<Self>(account_view)
     }
 }
@@ -231,10 +235,70 @@ where
     ) -> Result<RefMut<'ix, Self>> {
         try_initialize_zc::<Self>(target_account, init_accounts, signers)
     }
+
+    /// Creates the account and runs `init` on it before it is handed back, see
+    /// [`try_initialize_zc_with`]
+    fn try_initialize_with<'ix, F>(
+        target_account: &'ix AccountView,
+        init_accounts: InitAccounts<'ix, '_>,
+        signers: Option<&[Signer]>,
+        init: F,
+    ) -> Result<RefMut<'ix, Self>>
+    where
+        F: FnOnce(&mut Self),
+    {
+        try_initialize_zc_with::<Self, F>(target_account, init_accounts, signers, init)
+    }
+
+    /// Creates the account unless it already exists, see [`try_initialize_zc_if_needed`]
+    fn try_initialize_if_needed<'ix>(
+        target_account: &'ix AccountView,
+        init_accounts: InitAccounts<'ix, '_>,
+        signers: Option<&[Signer]>,
+    ) -> Result<RefMut<'ix, Self>> {
+        try_initialize_zc_if_needed::<Self>(target_account, init_accounts, signers)
+    }
+
+    /// Creates the account and records `init_accounts.payer_account` as its [`RentPayer`]
+    fn try_initialize_with_rent_payer<'ix>(
+        target_account: &'ix AccountView,
+        init_accounts: InitAccounts<'ix, '_>,
+        signers: Option<&[Signer]>,
+    ) -> Result<RefMut<'ix, Self>>
+    where
+        Self: RentPayer,
+    {
+        let rent_payer = *init_accounts.payer_account.address();
+
+        try_initialize_zc_with::<Self, _>(target_account, init_accounts, signers, |account| {
+            account.set_rent_payer(rent_payer)
+        })
+    }
+
+    /// Creates the account in the initializing state, see [`try_begin_initialize_zc`]
+    fn try_begin_initialize<'ix>(
+        target_account: &'ix AccountView,
+        init_accounts: InitAccounts<'ix, '_>,
+        signers: Option<&[Signer]>,
+    ) -> Result<RefMut<'ix, Self>> {
+        try_begin_initialize_zc::<Self>(target_account, init_accounts, signers)
+    }
+
+    /// Creates the account with `state` as its initial value
+    fn try_initialize_with_state<'ix>(
+        target_account: &'ix AccountView,
+        init_accounts: InitAccounts<'ix, '_>,
+        signers: Option<&[Signer]>,
+        state: Self,
+    ) -> Result<RefMut<'ix, Self>> {
+        try_initialize_zc_with::<Self, _>(target_account, init_accounts, signers, |account| {
+            *account = state
+        })
+    }
 }
 
 #[inline(always)]
-pub fn try_deserialize_zc<T>(account_view: &AccountView) -> Result<Ref<T>>
+pub fn try_deserialize_zc<T>(account_view: &AccountView) -> Result<Ref<'_, T>>
 where
     T: AnyBitPattern + Discriminator + Len + OwnerProgram,
 {
@@ -254,7 +318,7 @@ where
         );
     }
 
-    let disc_bytes = &data[..8];
+    let disc_bytes = &data[..T::DISCRIMINATOR.len()];
 
     if unlikely(disc_bytes != T::DISCRIMINATOR) {
         error_msg!(
@@ -264,12 +328,12 @@ where
     }
 
     Ok(Ref::map(data, |d| {
-        bytemuck::from_bytes(&d[8..T::DISCRIMINATED_LEN])
+        bytemuck::from_bytes(&d[T::DISCRIMINATOR.len()..T::DISCRIMINATED_LEN])
     }))
 }
 
 #[inline(always)]
-pub fn try_deserialize_zc_mut<T>(account_view: &AccountView) -> Result<RefMut<T>>
+pub fn try_deserialize_zc_mut<T>(account_view: &AccountView) -> Result<RefMut<'_, T>>
 where
     T: Pod + Discriminator + Len + OwnerProgram,
 {
@@ -289,7 +353,7 @@ where
         );
     }
 
-    let disc_bytes = &data[..8];
+    let disc_bytes = &data[..T::DISCRIMINATOR.len()];
 
     if unlikely(disc_bytes != T::DISCRIMINATOR) {
         error_msg!(
@@ -299,7 +363,7 @@ where
     }
 
     Ok(RefMut::map(data, |d| {
-        bytemuck::from_bytes_mut(&mut d[8..T::DISCRIMINATED_LEN])
+        bytemuck::from_bytes_mut(&mut d[T::DISCRIMINATOR.len()..T::DISCRIMINATED_LEN])
     }))
 }
 
@@ -338,6 +402,23 @@ pub fn try_initialize_zc<'ix, T>(
 ) -> Result<RefMut<'ix, T>>
 where
     T: Pod + Discriminator + Len + OwnerProgram,
+{
+    try_initialize_zc_with::<T, _>(target_account, init_accounts, signers, |_| {})
+}
+
+/// Same as [`try_initialize_zc`], but `init` is applied to the zeroed account right after the
+/// discriminator is written, before the account data borrow is released. The account is never
+/// observable as discriminated but all-zero.
+#[inline(always)]
+pub fn try_initialize_zc_with<'ix, T, F>(
+    target_account: &'ix AccountView,
+    init_accounts: InitAccounts<'ix, '_>,
+    signers: Option<&[Signer]>,
+    init: F,
+) -> Result<RefMut<'ix, T>>
+where
+    T: Pod + Discriminator + Len + OwnerProgram,
+    F: FnOnce(&mut T),
 {
     // if the account already allocated, this will fail, guarantees that the account is uninitialized
     let cpi_ctx = CpiCtx::try_new(
@@ -357,9 +438,204 @@ where
 
     let mut data = target_account.try_borrow_mut()?;
 
-    data[..8].copy_from_slice(T::DISCRIMINATOR);
+    data[..T::DISCRIMINATOR.len()].copy_from_slice(T::DISCRIMINATOR);
+
+    let mut account = RefMut::map(data, |d| {
+        bytemuck::from_bytes_mut::<T>(&mut d[T::DISCRIMINATOR.len()..T::DISCRIMINATED_LEN])
+    });
+
+    init(&mut account);
+
+    Ok(account)
+}
+
+/// Idempotent [`try_initialize_zc`].
+///
+/// An account that is still owned by the system program with no data is created (also when it
+/// was prefunded), anything else must already be a valid `T`: owner, length and discriminator
+/// are verified, so an existing account is never re-initialized or reset to zero.
+#[inline(always)]
+pub fn try_initialize_zc_if_needed<'ix, T>(
+    target_account: &'ix AccountView,
+    init_accounts: InitAccounts<'ix, '_>,
+    signers: Option<&[Signer]>,
+) -> Result<RefMut<'ix, T>>
+where
+    T: Pod + Discriminator + Len + OwnerProgram,
+{
+    if !(target_account.owned_by(&hayabusa_system_program::ID) && target_account.is_data_empty()) {
+        return try_deserialize_zc_mut::<T>(target_account);
+    }
+
+    let cpi_ctx = CpiCtx::try_new(
+        init_accounts.system_program,
+        CreateAccount {
+            from: init_accounts.payer_account,
+            to: target_account,
+        },
+        signers,
+    )?;
+
+    create_account_allow_prefunded(
+        cpi_ctx,
+        init_accounts.owner_program_id,
+        T::DISCRIMINATED_LEN as u64,
+    )?;
+
+    let mut data = target_account.try_borrow_mut()?;
+
+    data[..T::DISCRIMINATOR.len()].copy_from_slice(T::DISCRIMINATOR);
+
+    Ok(RefMut::map(data, |d| {
+        bytemuck::from_bytes_mut(&mut d[T::DISCRIMINATOR.len()..T::DISCRIMINATED_LEN])
+    }))
+}
+
+/// Creates the account with `space` bytes and writes the discriminator of `T`, without borrowing
+/// the account data as `T`. Used by the `init` meta constraint, where `space` exceeds
+/// [`Len::DISCRIMINATED_LEN`] for accounts with a tail, plain `ZcAccount`s require it exactly.
+#[inline(always)]
+pub fn try_create_zc<T>(
+    target_account: &AccountView,
+    init_accounts: InitAccounts<'_, '_>,
+    space: usize,
+    signers: Option<&[Signer]>,
+) -> Result<()>
+where
+    T: Discriminator,
+{
+    if unlikely(space < T::DISCRIMINATOR.len()) {
+        error_msg!(
+            "try_create_zc: space smaller than discriminator",
+            ProgramError::InvalidArgument,
+        );
+    }
+
+    let cpi_ctx = CpiCtx::try_new(
+        init_accounts.system_program,
+        CreateAccount {
+            from: init_accounts.payer_account,
+            to: target_account,
+        },
+        signers,
+    )?;
+
+    create_account(cpi_ctx, init_accounts.owner_program_id, space as u64)?;
+
+    target_account.try_borrow_mut()?[..T::DISCRIMINATOR.len()].copy_from_slice(T::DISCRIMINATOR);
+
+    Ok(())
+}
+
+/// First phase of a two-phase init, for accounts that can't be fully written in one instruction.
+///
+/// Creates the account like [`try_initialize_zc`] but writes the
+/// [`InitializingDiscriminator::INITIALIZING_DISCRIMINATOR`], so every regular deserialization of
+/// the account fails until [`try_finalize_zc`] is called.
+#[inline(always)]
+pub fn try_begin_initialize_zc<'ix, T>(
+    target_account: &'ix AccountView,
+    init_accounts: InitAccounts<'ix, '_>,
+    signers: Option<&[Signer]>,
+) -> Result<RefMut<'ix, T>>
+where
+    T: Pod + Discriminator + Len + OwnerProgram,
+{
+    let cpi_ctx = CpiCtx::try_new(
+        init_accounts.system_program,
+        CreateAccount {
+            from: init_accounts.payer_account,
+            to: target_account,
+        },
+        signers,
+    )?;
+
+    create_account(
+        cpi_ctx,
+        init_accounts.owner_program_id,
+        T::DISCRIMINATED_LEN as u64,
+    )?;
+
+    let mut data = target_account.try_borrow_mut()?;
+
+    let disc_len = T::DISCRIMINATOR.len();
+    data[..disc_len].copy_from_slice(&T::INITIALIZING_DISCRIMINATOR[..disc_len]);
 
     Ok(RefMut::map(data, |d| {
-        bytemuck::from_bytes_mut(&mut d[8..T::DISCRIMINATED_LEN])
+        bytemuck::from_bytes_mut(&mut d[T::DISCRIMINATOR.len()..T::DISCRIMINATED_LEN])
     }))
 }
+
+/// Deserializes an account that was created with [`try_begin_initialize_zc`] and is not
+/// finalized yet.
+#[inline(always)]
+pub fn try_deserialize_initializing_zc_mut<T>(account_view: &AccountView) -> Result<RefMut<'_, T>>
+where
+    T: Pod + Discriminator + Len + OwnerProgram,
+{
+    if unlikely(!account_view.owned_by(&T::OWNER)) {
+        error_msg!(
+            "try_deserialize_initializing_zc_mut: wrong account owner",
+            ProgramError::InvalidAccountOwner,
+        );
+    }
+
+    let data = account_view.try_borrow_mut()?;
+
+    if unlikely(data.len() != T::DISCRIMINATED_LEN) {
+        error_msg!(
+            "try_deserialize_initializing_zc_mut: wrong data length",
+            ProgramError::InvalidAccountData,
+        );
+    }
+
+    let disc_len = T::DISCRIMINATOR.len();
+
+    if unlikely(data[..disc_len] != T::INITIALIZING_DISCRIMINATOR[..disc_len]) {
+        error_msg!(
+            "try_deserialize_initializing_zc_mut: account not initializing",
+            ErrorCode::InvalidAccountDiscriminator,
+        );
+    }
+
+    Ok(RefMut::map(data, |d| {
+        bytemuck::from_bytes_mut(&mut d[T::DISCRIMINATOR.len()..T::DISCRIMINATED_LEN])
+    }))
+}
+
+/// Second phase of a two-phase init, flips the initializing discriminator to
+/// [`Discriminator::DISCRIMINATOR`] which makes the account usable.
+#[inline(always)]
+pub fn try_finalize_zc<T>(account_view: &AccountView) -> Result<()>
+where
+    T: Pod + Discriminator + Len + OwnerProgram,
+{
+    if unlikely(!account_view.owned_by(&T::OWNER)) {
+        error_msg!(
+            "try_finalize_zc: wrong account owner",
+            ProgramError::InvalidAccountOwner,
+        );
+    }
+
+    let mut data = account_view.try_borrow_mut()?;
+
+    if unlikely(data.len() != T::DISCRIMINATED_LEN) {
+        error_msg!(
+            "try_finalize_zc: wrong data length",
+            ProgramError::InvalidAccountData,
+        );
+    }
+
+    let disc_len = T::DISCRIMINATOR.len();
+
+    if unlikely(data[..disc_len] != T::INITIALIZING_DISCRIMINATOR[..disc_len]) {
+        error_msg!(
+            "try_finalize_zc: account not initializing",
+            ErrorCode::InvalidAccountDiscriminator,
+        );
+    }
+
+    data[..disc_len].copy_from_slice(T::DISCRIMINATOR);
+
+    Ok(())
+}
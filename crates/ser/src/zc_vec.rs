@@ -0,0 +1,282 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use bytemuck::Pod;
+use core::mem::size_of;
+use hayabusa_common::AccountView;
+use hayabusa_errors::{ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+use crate::try_realloc_account;
+
+/// Length prefix of a [`ZcVec`], a `u64` so the elements stay 8 byte aligned.
+pub const ZC_VEC_PREFIX_LEN: usize = 8;
+
+/// Most an account can grow by in a single instruction.
+const MAX_GROWTH: usize = 10 * 1024;
+
+/// Bytes taken by a [`ZcVec`] of `capacity` elements.
+#[inline(always)]
+pub const fn zc_vec_space<T>(capacity: usize) -> usize {
+    ZC_VEC_PREFIX_LEN + capacity * size_of::<T>()
+}
+
+/// Length prefixed vector over a region of account data, `len: u64 | T * capacity`.
+///
+/// The capacity is whatever fits in the region, so a vector placed at the end of the account
+/// grows with the account, see [`try_push_or_grow_zc_vec`].
+pub struct ZcVec<'a, T>
+where
+    T: Pod,
+{
+    len: &'a mut [u8; ZC_VEC_PREFIX_LEN],
+    items: &'a mut [T],
+}
+
+impl<'a, T> ZcVec<'a, T>
+where
+    T: Pod,
+{
+    /// Fails if the region is shorter than the prefix, misaligned for `T`, or if the stored length
+    /// exceeds the capacity.
+    #[inline(always)]
+    pub fn try_from_bytes(bytes: &'a mut [u8]) -> Result<Self> {
+        let capacity = try_capacity::<T>(bytes.len())?;
+        let (len, items) = bytes.split_at_mut(ZC_VEC_PREFIX_LEN);
+        let len: &mut [u8; ZC_VEC_PREFIX_LEN] = len.try_into().unwrap();
+
+        let Ok(items) = bytemuck::try_cast_slice_mut(&mut items[..capacity * size_of::<T>()]) else {
+            error_msg!(
+                "ZcVec::try_from_bytes: misaligned items",
+                ProgramError::InvalidAccountData,
+            );
+        };
+
+        if unlikely(u64::from_le_bytes(*len) > capacity as u64) {
+            error_msg!(
+                "ZcVec::try_from_bytes: length exceeds capacity",
+                ProgramError::InvalidAccountData,
+            );
+        }
+
+        Ok(Self { len, items })
+    }
+
+    /// Read-only access to the elements of the vector stored in `bytes`.
+    #[inline(always)]
+    pub fn try_read(bytes: &[u8]) -> Result<&[T]> {
+        let capacity = try_capacity::<T>(bytes.len())?;
+        let len = u64::from_le_bytes(bytes[..ZC_VEC_PREFIX_LEN].try_into().unwrap());
+
+        if unlikely(len > capacity as u64) {
+            error_msg!(
+                "ZcVec::try_read: length exceeds capacity",
+                ProgramError::InvalidAccountData,
+            );
+        }
+
+        let end = ZC_VEC_PREFIX_LEN + len as usize * size_of::<T>();
+
+        let Ok(items) = bytemuck::try_cast_slice(&bytes[ZC_VEC_PREFIX_LEN..end]) else {
+            error_msg!(
+                "ZcVec::try_read: misaligned items",
+                ProgramError::InvalidAccountData,
+            );
+        };
+
+        Ok(items)
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        u64::from_le_bytes(*self.len) as usize
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.items.len()
+    }
+
+    #[inline(always)]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[T] {
+        &self.items[..self.len()]
+    }
+
+    #[inline(always)]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let len = self.len();
+        &mut self.items[..len]
+    }
+
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.as_slice().get(index)
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.as_mut_slice().get_mut(index)
+    }
+
+    /// Fails if the vector is at capacity.
+    #[inline(always)]
+    pub fn try_push(&mut self, value: T) -> Result<()> {
+        let len = self.len();
+
+        if unlikely(len == self.capacity()) {
+            error_msg!(
+                "ZcVec::try_push: vector is full",
+                ProgramError::AccountDataTooSmall,
+            );
+        }
+
+        self.items[len] = value;
+        self.set_len(len + 1);
+
+        Ok(())
+    }
+
+    /// Removes the last element, its slot is zeroed.
+    #[inline(always)]
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len().checked_sub(1)?;
+        let value = core::mem::replace(&mut self.items[len], T::zeroed());
+        self.set_len(len);
+
+        Some(value)
+    }
+
+    /// Removes the element at `index` and moves the last element in its place.
+    #[inline(always)]
+    pub fn swap_remove(&mut self, index: usize) -> Option<T> {
+        let len = self.len();
+
+        if index >= len {
+            return None;
+        }
+
+        self.items.swap(index, len - 1);
+        self.pop()
+    }
+
+    /// Zeroes every element and sets the length to 0.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        let len = self.len();
+        self.items[..len].fill(T::zeroed());
+        self.set_len(0);
+    }
+
+    #[inline(always)]
+    fn set_len(&mut self, len: usize) {
+        *self.len = (len as u64).to_le_bytes();
+    }
+}
+
+#[inline(always)]
+fn try_capacity<T>(region_len: usize) -> Result<usize> {
+    if unlikely(size_of::<T>() == 0) {
+        error_msg!(
+            "ZcVec: zero sized element",
+            ProgramError::InvalidArgument,
+        );
+    }
+
+    let Some(items_len) = region_len.checked_sub(ZC_VEC_PREFIX_LEN) else {
+        error_msg!(
+            "ZcVec: region shorter than the length prefix",
+            ProgramError::InvalidAccountData,
+        );
+    };
+
+    Ok(items_len / size_of::<T>())
+}
+
+/// Pushes `value` to the vector stored from `offset` to the end of `account_view` data, growing
+/// the account through [`try_realloc_account`] when the vector is full.
+///
+/// The capacity doubles on every growth (within the per instruction realloc limit), `payer` funds
+/// the extra rent.
+#[inline(always)]
+pub fn try_push_or_grow_zc_vec<T>(
+    account_view: &AccountView,
+    offset: usize,
+    value: T,
+    payer: &AccountView,
+    system_program: &AccountView,
+) -> Result<()>
+where
+    T: Pod,
+{
+    let Some(region_len) = account_view.data_len().checked_sub(offset) else {
+        error_msg!(
+            "try_push_or_grow_zc_vec: offset past the end of the data",
+            ProgramError::InvalidArgument,
+        );
+    };
+
+    let (len, capacity) = {
+        let mut data = account_view.try_borrow_mut()?;
+        let vec = ZcVec::<T>::try_from_bytes(&mut data[offset..])?;
+        (vec.len(), vec.capacity())
+    };
+
+    if len == capacity {
+        let additional = capacity.clamp(1, (MAX_GROWTH / size_of::<T>()).max(1));
+        // bytes past the last whole element are reused before growing
+        let new_region_len = zc_vec_space::<T>(capacity + additional);
+        let grow_by = new_region_len.saturating_sub(region_len);
+
+        try_realloc_account(
+            account_view,
+            account_view.data_len() + grow_by,
+            payer,
+            system_program,
+        )?;
+    }
+
+    let mut data = account_view.try_borrow_mut()?;
+    ZcVec::<T>::try_from_bytes(&mut data[offset..])?.try_push(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_pop() {
+        let mut buf = [0u64; 4];
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut buf);
+
+        let mut vec = ZcVec::<u64>::try_from_bytes(bytes).unwrap();
+        assert_eq!(vec.capacity(), 3);
+        assert!(vec.is_empty());
+
+        vec.try_push(1).unwrap();
+        vec.try_push(2).unwrap();
+        vec.try_push(3).unwrap();
+        assert!(vec.try_push(4).is_err());
+        assert_eq!(vec.as_slice(), &[1, 2, 3]);
+
+        assert_eq!(vec.swap_remove(0), Some(1));
+        assert_eq!(vec.as_slice(), &[3, 2]);
+        assert_eq!(vec.pop(), Some(2));
+        assert_eq!(vec.len(), 1);
+
+        assert_eq!(ZcVec::<u64>::try_read(bytes).unwrap(), &[3]);
+
+        // corrupted length
+        bytes[..8].copy_from_slice(&4u64.to_le_bytes());
+        assert!(ZcVec::<u64>::try_from_bytes(bytes).is_err());
+    }
+}
@@ -0,0 +1,10 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod proposal_approve;
+pub mod proposal_create;
+pub mod vault_transaction_execute;
+
+pub use proposal_approve::*;
+pub use proposal_create::*;
+pub use vault_transaction_execute::*;
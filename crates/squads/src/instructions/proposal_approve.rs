@@ -0,0 +1,52 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use hayabusa_common::{AccountView, Address};
+use hayabusa_cpi::{CheckProgramId, CpiCtx};
+use hayabusa_errors::Result;
+use solana_instruction_view::{
+    cpi::{invoke, invoke_signed},
+    InstructionAccount, InstructionView,
+};
+
+pub struct ProposalApprove<'ix> {
+    /// Multisig account
+    pub multisig: &'ix AccountView,
+    /// Member with the vote permission
+    pub member: &'ix AccountView,
+    /// Proposal account
+    pub proposal: &'ix AccountView,
+}
+
+impl CheckProgramId for ProposalApprove<'_> {
+    const ID: Address = crate::ID;
+}
+
+// ix data layout
+// - [0..8]: discriminator
+// - [8]: memo, always `None`
+const IX_DATA: [u8; 9] = [144, 37, 164, 136, 188, 216, 42, 248, 0];
+
+/// Votes for the proposal, which becomes `Approved` once the multisig threshold is reached.
+#[inline(always)]
+pub fn proposal_approve<'ix>(cpi_ctx: CpiCtx<'ix, '_, '_, '_, ProposalApprove<'ix>>) -> Result<()> {
+    let account_views = [cpi_ctx.multisig, cpi_ctx.member, cpi_ctx.proposal];
+
+    let instruction_accounts = [
+        InstructionAccount::readonly(cpi_ctx.multisig.address()),
+        InstructionAccount::writable_signer(cpi_ctx.member.address()),
+        InstructionAccount::writable(cpi_ctx.proposal.address()),
+    ];
+
+    let instruction = InstructionView {
+        program_id: &crate::ID,
+        accounts: &instruction_accounts,
+        data: &IX_DATA,
+    };
+
+    if let Some(signers) = cpi_ctx.signers {
+        invoke_signed(&instruction, &account_views, signers)
+    } else {
+        invoke(&instruction, &account_views)
+    }
+}
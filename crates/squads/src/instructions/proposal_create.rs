@@ -0,0 +1,78 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use core::slice::from_raw_parts;
+use hayabusa_common::{AccountView, Address};
+use hayabusa_cpi::{CheckProgramId, CpiCtx};
+use hayabusa_errors::Result;
+use hayabusa_utility::{write_uninit_bytes, UNINIT_BYTE};
+use solana_instruction_view::{
+    cpi::{invoke, invoke_signed},
+    InstructionAccount, InstructionView,
+};
+
+pub struct ProposalCreate<'ix> {
+    /// Multisig account
+    pub multisig: &'ix AccountView,
+    /// New proposal account, see `find_proposal_address`
+    pub proposal: &'ix AccountView,
+    /// Member with the initiate permission
+    pub creator: &'ix AccountView,
+    /// Pays the proposal rent
+    pub rent_payer: &'ix AccountView,
+    /// System program
+    pub system_program: &'ix AccountView,
+}
+
+impl CheckProgramId for ProposalCreate<'_> {
+    const ID: Address = crate::ID;
+}
+
+const DISCRIMINATOR: [u8; 8] = [220, 60, 73, 224, 30, 108, 79, 159];
+
+/// Creates the proposal of transaction `transaction_index`, `draft` proposals need a
+/// `proposal_activate` before members can vote.
+#[inline(always)]
+pub fn proposal_create<'ix>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, ProposalCreate<'ix>>,
+    transaction_index: u64,
+    draft: bool,
+) -> Result<()> {
+    let account_views = [
+        cpi_ctx.multisig,
+        cpi_ctx.proposal,
+        cpi_ctx.creator,
+        cpi_ctx.rent_payer,
+        cpi_ctx.system_program,
+    ];
+
+    let instruction_accounts = [
+        InstructionAccount::readonly(cpi_ctx.multisig.address()),
+        InstructionAccount::writable(cpi_ctx.proposal.address()),
+        InstructionAccount::readonly_signer(cpi_ctx.creator.address()),
+        InstructionAccount::writable_signer(cpi_ctx.rent_payer.address()),
+        InstructionAccount::readonly(cpi_ctx.system_program.address()),
+    ];
+
+    // ix data layout
+    // - [0..8]: discriminator
+    // - [8..16]: transaction index
+    // - [16]: draft
+    let mut ix_data = [UNINIT_BYTE; 17];
+
+    write_uninit_bytes(&mut ix_data, &DISCRIMINATOR);
+    write_uninit_bytes(&mut ix_data[8..16], &transaction_index.to_le_bytes());
+    write_uninit_bytes(&mut ix_data[16..], &[draft as u8]);
+
+    let instruction = InstructionView {
+        program_id: &crate::ID,
+        accounts: &instruction_accounts,
+        data: unsafe { from_raw_parts(ix_data.as_ptr() as _, 17) },
+    };
+
+    if let Some(signers) = cpi_ctx.signers {
+        invoke_signed(&instruction, &account_views, signers)
+    } else {
+        invoke(&instruction, &account_views)
+    }
+}
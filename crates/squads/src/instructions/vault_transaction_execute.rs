@@ -0,0 +1,87 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use hayabusa_common::{AccountView, Address};
+use hayabusa_cpi::{CheckProgramId, CpiCtx};
+use hayabusa_errors::{ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely};
+use solana_instruction_view::{
+    cpi::invoke_signed_with_bounds, InstructionAccount, InstructionView,
+};
+
+pub struct VaultTransactionExecute<'ix> {
+    /// Multisig account
+    pub multisig: &'ix AccountView,
+    /// Approved proposal of the transaction
+    pub proposal: &'ix AccountView,
+    /// Vault transaction account
+    pub transaction: &'ix AccountView,
+    /// Member with the execute permission
+    pub member: &'ix AccountView,
+}
+
+impl CheckProgramId for VaultTransactionExecute<'_> {
+    const ID: Address = crate::ID;
+}
+
+const DISCRIMINATOR: [u8; 8] = [194, 8, 161, 87, 153, 164, 25, 171];
+
+/// Executes an approved vault transaction.
+///
+/// `remaining_accounts` are the address lookup tables of the transaction followed by the accounts
+/// of its message, in order. They are forwarded with their writable flag and without signature,
+/// Squads signs for the vault itself. `MAX_ACCOUNTS` bounds the total number of accounts.
+#[inline(always)]
+pub fn vault_transaction_execute<'ix, const MAX_ACCOUNTS: usize>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, VaultTransactionExecute<'ix>>,
+    remaining_accounts: &[&'ix AccountView],
+) -> Result<()> {
+    let len = 4 + remaining_accounts.len();
+
+    if unlikely(len > MAX_ACCOUNTS) {
+        error_msg!(
+            "vault_transaction_execute: too many accounts",
+            ProgramError::InvalidArgument,
+        );
+    }
+
+    let fixed = [
+        cpi_ctx.multisig,
+        cpi_ctx.proposal,
+        cpi_ctx.transaction,
+        cpi_ctx.member,
+    ];
+
+    let account_views: [&AccountView; MAX_ACCOUNTS] = core::array::from_fn(|i| match i {
+        0..4 => fixed[i],
+        _ => remaining_accounts
+            .get(i - 4)
+            .copied()
+            .unwrap_or(cpi_ctx.multisig),
+    });
+
+    let instruction_accounts: [InstructionAccount; MAX_ACCOUNTS] =
+        core::array::from_fn(|i| match i {
+            0 => InstructionAccount::readonly(cpi_ctx.multisig.address()),
+            1 => InstructionAccount::writable(cpi_ctx.proposal.address()),
+            2 => InstructionAccount::readonly(cpi_ctx.transaction.address()),
+            3 => InstructionAccount::readonly_signer(cpi_ctx.member.address()),
+            _ => InstructionAccount::new(
+                account_views[i].address(),
+                account_views[i].is_writable(),
+                false,
+            ),
+        });
+
+    let instruction = InstructionView {
+        program_id: &crate::ID,
+        accounts: &instruction_accounts[..len],
+        data: &DISCRIMINATOR,
+    };
+
+    invoke_signed_with_bounds::<MAX_ACCOUNTS>(
+        &instruction,
+        &account_views[..len],
+        cpi_ctx.signers.unwrap_or(&[]),
+    )
+}
@@ -0,0 +1,29 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Squads v4 multisig bindings.
+//!
+//! Admin instructions are gated by requiring a multisig vault as signer, see
+//! [`try_check_vault_signer`]. The vault only signs when a vault transaction approved by the
+//! multisig members is executed, which [`instructions::vault_transaction_execute`] can also
+//! trigger from another program.
+
+#![no_std]
+
+pub mod instructions;
+pub mod pda;
+pub mod state;
+
+pub use pda::*;
+pub use state::*;
+
+hayabusa_common::declare_id!("SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf");
+
+use hayabusa_accounts::ProgramId;
+use hayabusa_common::Address;
+
+pub struct Squads;
+
+impl ProgramId for Squads {
+    const ID: Address = ID;
+}
@@ -0,0 +1,109 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use hayabusa_common::{address_eq, AccountView, Address};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_syscalls::try_find_program_address;
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+pub const SEED_PREFIX: &[u8] = b"multisig";
+pub const SEED_MULTISIG: &[u8] = b"multisig";
+pub const SEED_VAULT: &[u8] = b"vault";
+pub const SEED_TRANSACTION: &[u8] = b"transaction";
+pub const SEED_PROPOSAL: &[u8] = b"proposal";
+
+#[inline(always)]
+pub fn find_multisig_address(create_key: &Address) -> Result<(Address, u8)> {
+    try_find_program_address(
+        &[SEED_PREFIX, SEED_MULTISIG, create_key.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Vault `vault_index` of `multisig`, the address that holds the assets and signs for it.
+#[inline(always)]
+pub fn find_vault_address(multisig: &Address, vault_index: u8) -> Result<(Address, u8)> {
+    try_find_program_address(
+        &[SEED_PREFIX, multisig.as_ref(), SEED_VAULT, &[vault_index]],
+        &crate::ID,
+    )
+}
+
+#[inline(always)]
+pub fn find_transaction_address(
+    multisig: &Address,
+    transaction_index: u64,
+) -> Result<(Address, u8)> {
+    try_find_program_address(
+        &[
+            SEED_PREFIX,
+            multisig.as_ref(),
+            SEED_TRANSACTION,
+            &transaction_index.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+#[inline(always)]
+pub fn find_proposal_address(multisig: &Address, transaction_index: u64) -> Result<(Address, u8)> {
+    try_find_program_address(
+        &[
+            SEED_PREFIX,
+            multisig.as_ref(),
+            SEED_TRANSACTION,
+            &transaction_index.to_le_bytes(),
+            SEED_PROPOSAL,
+        ],
+        &crate::ID,
+    )
+}
+
+/// Checks that `account_view` is vault `vault_index` of `multisig` and that it signed.
+///
+/// Storing the vault address in the program config and comparing against it is cheaper, this
+/// derives the address on every call.
+#[inline]
+pub fn try_check_vault_signer(
+    account_view: &AccountView,
+    multisig: &Address,
+    vault_index: u8,
+) -> Result<()> {
+    if unlikely(!account_view.is_signer()) {
+        error_msg!(
+            "try_check_vault_signer: vault is not a signer",
+            ErrorCode::AccountNotSigner,
+        );
+    }
+
+    let (vault, _) = find_vault_address(multisig, vault_index)?;
+
+    if unlikely(!address_eq(account_view.address(), &vault)) {
+        error_msg!(
+            "try_check_vault_signer: not the multisig vault",
+            ProgramError::InvalidSeeds,
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hayabusa_common::mock::MockAccount;
+
+    #[test]
+    fn test_vault_signer() {
+        let multisig = Address::new_from_array([1; 32]);
+        let (vault, _) = find_vault_address(&multisig, 0).unwrap();
+
+        let mut signer = MockAccount::new(vault, Address::default(), &[]).signer();
+        assert!(try_check_vault_signer(&signer.view(), &multisig, 0).is_ok());
+        // another vault of the multisig
+        assert!(try_check_vault_signer(&signer.view(), &multisig, 1).is_err());
+
+        let mut unsigned = MockAccount::new(vault, Address::default(), &[]);
+        assert!(try_check_vault_signer(&unsigned.view(), &multisig, 0).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod multisig;
+pub mod proposal;
+pub mod vault_transaction;
+
+pub use multisig::*;
+pub use proposal::*;
+pub use vault_transaction::*;
+
+use hayabusa_common::{AccountView, Address, Ref};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+/// Borrows the data of a Squads account after checking the owner, the length and the Anchor
+/// discriminator.
+#[inline(always)]
+fn try_borrow_squads_account<'a>(
+    account_view: &'a AccountView,
+    discriminator: &[u8; 8],
+    min_len: usize,
+) -> Result<Ref<'a, [u8]>> {
+    if unlikely(!account_view.owned_by(&crate::ID)) {
+        error_msg!(
+            "try_borrow_squads_account: invalid owner",
+            ProgramError::InvalidAccountOwner,
+        );
+    }
+
+    let data = account_view.try_borrow()?;
+
+    if unlikely(data.len() < min_len) {
+        error_msg!(
+            "try_borrow_squads_account: account data too small",
+            ProgramError::AccountDataTooSmall,
+        );
+    }
+
+    if unlikely(&data[..8] != discriminator) {
+        error_msg!(
+            "try_borrow_squads_account: invalid discriminator",
+            ErrorCode::InvalidAccountDiscriminator,
+        );
+    }
+
+    Ok(data)
+}
+
+#[inline(always)]
+fn read_address(data: &[u8], offset: usize) -> &Address {
+    // SAFETY: `Address` is a 32 byte array with an alignment of 1, callers check the bounds
+    unsafe { &*(data[offset..offset + 32].as_ptr() as *const Address) }
+}
+
+/// borsh `Vec<Address>` at `offset`, returns the addresses and the offset past them.
+#[inline(always)]
+fn try_read_addresses(data: &[u8], offset: usize) -> Result<(&[Address], usize)> {
+    let len = data
+        .get(offset..offset + 4)
+        .map(|len| u32::from_le_bytes(len.try_into().unwrap()) as usize);
+
+    let Some(end) = len.and_then(|len| len.checked_mul(32)?.checked_add(offset + 4)) else {
+        error_msg!(
+            "try_read_addresses: invalid length",
+            ProgramError::InvalidAccountData,
+        );
+    };
+
+    if unlikely(end > data.len()) {
+        error_msg!(
+            "try_read_addresses: account data too small",
+            ProgramError::InvalidAccountData,
+        );
+    }
+
+    // SAFETY: `Address` is a 32 byte array with an alignment of 1, the range is in bounds
+    let addresses = unsafe {
+        core::slice::from_raw_parts(
+            data[offset + 4..].as_ptr() as *const Address,
+            (end - offset - 4) / 32,
+        )
+    };
+
+    Ok((addresses, end))
+}
@@ -0,0 +1,261 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{read_address, try_borrow_squads_account};
+use hayabusa_common::{address_eq, AccountView, Address, Ref};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+/// Anchor discriminator of `Multisig`.
+pub const MULTISIG_DISCRIMINATOR: [u8; 8] = [224, 116, 121, 186, 68, 161, 79, 236];
+
+/// Member permission bits.
+pub struct Permission;
+
+impl Permission {
+    pub const INITIATE: u8 = 1 << 0;
+    pub const VOTE: u8 = 1 << 1;
+    pub const EXECUTE: u8 = 1 << 2;
+}
+
+/// Zero-copy view over a Squads v4 multisig account.
+///
+/// Layout after the discriminator: create key (32 bytes), config authority (32 bytes), threshold
+/// (u16), time lock (u32), transaction index (u64), stale transaction index (u64), rent collector
+/// (`Option<Address>`), bump (u8) and the members, a `u32` count followed by the member address
+/// and its permission mask. Integers are little endian.
+pub struct Multisig<'a> {
+    data: Ref<'a, [u8]>,
+    members: usize,
+}
+
+impl<'a> Multisig<'a> {
+    const CREATE_KEY: usize = 8;
+    const CONFIG_AUTHORITY: usize = 40;
+    const THRESHOLD: usize = 72;
+    const TIME_LOCK: usize = 74;
+    const TRANSACTION_INDEX: usize = 78;
+    const STALE_TRANSACTION_INDEX: usize = 86;
+    const RENT_COLLECTOR: usize = 94;
+
+    /// Length of a member, address and permission mask.
+    pub const MEMBER_LEN: usize = 33;
+
+    /// Shortest account data, without rent collector nor members.
+    pub const MIN_LEN: usize = 100;
+
+    /// Return the `Multisig` of the given account view.
+    ///
+    /// This method checks the owner, the discriminator and that the members are in bounds.
+    #[inline]
+    pub fn from_account_view(account_view: &'a AccountView) -> Result<Self> {
+        let data = try_borrow_squads_account(account_view, &MULTISIG_DISCRIMINATOR, Self::MIN_LEN)?;
+
+        // bump after the rent collector
+        let members = match data[Self::RENT_COLLECTOR] {
+            0 => Self::RENT_COLLECTOR + 2,
+            _ => Self::RENT_COLLECTOR + 34,
+        };
+
+        let len = data
+            .get(members..members + 4)
+            .map(|len| u32::from_le_bytes(len.try_into().unwrap()) as usize);
+
+        if unlikely(!matches!(len, Some(len) if members + 4 + len * Self::MEMBER_LEN <= data.len()))
+        {
+            error_msg!(
+                "Multisig::from_account_view: members out of bounds",
+                ProgramError::InvalidAccountData,
+            );
+        }
+
+        Ok(Self { data, members })
+    }
+
+    #[inline(always)]
+    fn bytes<const N: usize>(&self, offset: usize) -> [u8; N] {
+        self.data[offset..offset + N].try_into().unwrap()
+    }
+
+    #[inline(always)]
+    pub fn create_key(&self) -> &Address {
+        read_address(&self.data, Self::CREATE_KEY)
+    }
+
+    /// Can change the multisig settings without a vote, the default address when the multisig is
+    /// autonomous.
+    #[inline(always)]
+    pub fn config_authority(&self) -> &Address {
+        read_address(&self.data, Self::CONFIG_AUTHORITY)
+    }
+
+    /// Approvals needed to pass a proposal.
+    #[inline(always)]
+    pub fn threshold(&self) -> u16 {
+        u16::from_le_bytes(self.bytes(Self::THRESHOLD))
+    }
+
+    /// Seconds between approval and execution.
+    #[inline(always)]
+    pub fn time_lock(&self) -> u32 {
+        u32::from_le_bytes(self.bytes(Self::TIME_LOCK))
+    }
+
+    /// Index of the last transaction, the next one is created at `transaction_index() + 1`.
+    #[inline(always)]
+    pub fn transaction_index(&self) -> u64 {
+        u64::from_le_bytes(self.bytes(Self::TRANSACTION_INDEX))
+    }
+
+    /// Transactions at or below this index can no longer be executed.
+    #[inline(always)]
+    pub fn stale_transaction_index(&self) -> u64 {
+        u64::from_le_bytes(self.bytes(Self::STALE_TRANSACTION_INDEX))
+    }
+
+    #[inline(always)]
+    pub fn rent_collector(&self) -> Option<&Address> {
+        match self.data[Self::RENT_COLLECTOR] {
+            0 => None,
+            _ => Some(read_address(&self.data, Self::RENT_COLLECTOR + 1)),
+        }
+    }
+
+    #[inline(always)]
+    pub fn bump(&self) -> u8 {
+        self.data[self.members - 1]
+    }
+
+    #[inline(always)]
+    pub fn members_len(&self) -> usize {
+        u32::from_le_bytes(self.bytes(self.members)) as usize
+    }
+
+    /// Members as `(address, permission mask)`.
+    #[inline(always)]
+    pub fn members(&self) -> impl Iterator<Item = (&Address, u8)> {
+        (0..self.members_len()).map(|i| {
+            let offset = self.members + 4 + i * Self::MEMBER_LEN;
+            (read_address(&self.data, offset), self.data[offset + 32])
+        })
+    }
+
+    /// Permission mask of `key`, `None` if it is not a member.
+    #[inline(always)]
+    pub fn permissions(&self, key: &Address) -> Option<u8> {
+        self.members()
+            .find(|(member, _)| address_eq(member, key))
+            .map(|(_, mask)| mask)
+    }
+
+    /// Checks that `member` signed and holds every bit of `permission`.
+    #[inline]
+    pub fn try_check_member(&self, member: &AccountView, permission: u8) -> Result<()> {
+        if unlikely(!member.is_signer()) {
+            error_msg!(
+                "Multisig::try_check_member: member is not a signer",
+                ErrorCode::AccountNotSigner,
+            );
+        }
+
+        match self.permissions(member.address()) {
+            Some(mask) if mask & permission == permission => Ok(()),
+            _ => {
+                error_msg!(
+                    "Multisig::try_check_member: missing permission",
+                    ProgramError::MissingRequiredSignature,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use hayabusa_common::mock::MockAccount;
+    use std::vec::Vec;
+
+    fn multisig_data(rent_collector: Option<Address>, members: &[(Address, u8)]) -> Vec<u8> {
+        let mut data = MULTISIG_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[1; 32]);
+        data.extend_from_slice(&[2; 32]);
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(&60u32.to_le_bytes());
+        data.extend_from_slice(&5u64.to_le_bytes());
+        data.extend_from_slice(&3u64.to_le_bytes());
+        match rent_collector {
+            Some(rent_collector) => {
+                data.push(1);
+                data.extend_from_slice(rent_collector.as_ref());
+            }
+            None => data.push(0),
+        }
+        data.push(254);
+        data.extend_from_slice(&(members.len() as u32).to_le_bytes());
+        for (member, mask) in members {
+            data.extend_from_slice(member.as_ref());
+            data.push(*mask);
+        }
+        data
+    }
+
+    #[test]
+    fn test_multisig() {
+        let voter = Address::new_from_array([7; 32]);
+        let executor = Address::new_from_array([8; 32]);
+        let members = [
+            (voter, Permission::VOTE),
+            (executor, Permission::VOTE | Permission::EXECUTE),
+        ];
+
+        for rent_collector in [None, Some(Address::new_from_array([9; 32]))] {
+            let data = multisig_data(rent_collector, &members);
+            let mut account = MockAccount::new(Address::new_from_array([3; 32]), crate::ID, &data);
+            let account_view = account.view();
+            let multisig = Multisig::from_account_view(&account_view).unwrap();
+
+            assert_eq!(multisig.create_key(), &Address::new_from_array([1; 32]));
+            assert_eq!(multisig.config_authority(), &Address::new_from_array([2; 32]));
+            assert_eq!(multisig.threshold(), 2);
+            assert_eq!(multisig.time_lock(), 60);
+            assert_eq!(multisig.transaction_index(), 5);
+            assert_eq!(multisig.stale_transaction_index(), 3);
+            assert_eq!(multisig.rent_collector(), rent_collector.as_ref());
+            assert_eq!(multisig.bump(), 254);
+            assert_eq!(multisig.members_len(), 2);
+            assert_eq!(multisig.permissions(&executor), Some(members[1].1));
+            assert_eq!(multisig.permissions(&crate::ID), None);
+
+            let mut voter = MockAccount::new(voter, Address::default(), &[]).signer();
+            let mut executor = MockAccount::new(executor, Address::default(), &[]);
+            assert!(multisig.try_check_member(&voter.view(), Permission::VOTE).is_ok());
+            assert!(multisig.try_check_member(&voter.view(), Permission::EXECUTE).is_err());
+            // not a signer
+            assert!(multisig.try_check_member(&executor.view(), Permission::VOTE).is_err());
+        }
+    }
+
+    #[test]
+    fn test_invalid_multisig() {
+        let member = (Address::new_from_array([7; 32]), Permission::VOTE);
+        let data = multisig_data(None, &[member]);
+        let address = Address::new_from_array([3; 32]);
+
+        // another owner
+        let mut account = MockAccount::new(address, Address::default(), &data);
+        assert!(Multisig::from_account_view(&account.view()).is_err());
+
+        // another account type
+        let mut other = data.clone();
+        other[0] ^= 1;
+        let mut account = MockAccount::new(address, crate::ID, &other);
+        assert!(Multisig::from_account_view(&account.view()).is_err());
+
+        // members past the end of the data
+        let mut account = MockAccount::new(address, crate::ID, &data[..data.len() - 1]);
+        assert!(Multisig::from_account_view(&account.view()).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{read_address, try_borrow_squads_account, try_read_addresses};
+use hayabusa_common::{AccountView, Address, Ref};
+use hayabusa_errors::{ProgramError, Result};
+use hayabusa_utility::error_msg;
+
+/// Anchor discriminator of `Proposal`.
+pub const PROPOSAL_DISCRIMINATOR: [u8; 8] = [26, 94, 189, 187, 116, 136, 53, 33];
+
+/// Status of a proposal with the unix timestamp it was reached at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Draft(i64),
+    Active(i64),
+    Rejected(i64),
+    Approved(i64),
+    /// Deprecated by Squads, kept for old proposals.
+    Executing,
+    Executed(i64),
+    Cancelled(i64),
+}
+
+/// Zero-copy view over a Squads v4 proposal account.
+///
+/// Layout after the discriminator: multisig (32 bytes), transaction index (u64), status (borsh
+/// enum, the variant and a timestamp except for `Executing`), bump (u8) and the approved, rejected
+/// and cancelled member lists, each a `u32` count followed by the addresses.
+pub struct Proposal<'a> {
+    data: Ref<'a, [u8]>,
+    status: ProposalStatus,
+    bump: usize,
+}
+
+impl<'a> Proposal<'a> {
+    const MULTISIG: usize = 8;
+    const TRANSACTION_INDEX: usize = 40;
+    const STATUS: usize = 48;
+
+    /// Shortest account data, an `Executing` proposal without votes.
+    pub const MIN_LEN: usize = 62;
+
+    /// Return the `Proposal` of the given account view.
+    ///
+    /// This method checks the owner, the discriminator, the status and that the vote lists are in
+    /// bounds.
+    #[inline]
+    pub fn from_account_view(account_view: &'a AccountView) -> Result<Self> {
+        let data = try_borrow_squads_account(account_view, &PROPOSAL_DISCRIMINATOR, Self::MIN_LEN)?;
+
+        let timestamp = data
+            .get(Self::STATUS + 1..Self::STATUS + 9)
+            .map(|ts| i64::from_le_bytes(ts.try_into().unwrap()))
+            .unwrap_or_default();
+
+        let status = match data[Self::STATUS] {
+            0 => ProposalStatus::Draft(timestamp),
+            1 => ProposalStatus::Active(timestamp),
+            2 => ProposalStatus::Rejected(timestamp),
+            3 => ProposalStatus::Approved(timestamp),
+            4 => ProposalStatus::Executing,
+            5 => ProposalStatus::Executed(timestamp),
+            6 => ProposalStatus::Cancelled(timestamp),
+            _ => {
+                error_msg!(
+                    "Proposal::from_account_view: invalid status",
+                    ProgramError::InvalidAccountData,
+                );
+            }
+        };
+
+        let bump = match status {
+            ProposalStatus::Executing => Self::STATUS + 1,
+            _ => Self::STATUS + 9,
+        };
+
+        // approved, rejected, cancelled
+        let (_, offset) = try_read_addresses(&data, bump + 1)?;
+        let (_, offset) = try_read_addresses(&data, offset)?;
+        try_read_addresses(&data, offset)?;
+
+        Ok(Self { data, status, bump })
+    }
+
+    #[inline(always)]
+    pub fn multisig(&self) -> &Address {
+        read_address(&self.data, Self::MULTISIG)
+    }
+
+    #[inline(always)]
+    pub fn transaction_index(&self) -> u64 {
+        u64::from_le_bytes(
+            self.data[Self::TRANSACTION_INDEX..Self::TRANSACTION_INDEX + 8]
+                .try_into()
+                .unwrap(),
+        )
+    }
+
+    #[inline(always)]
+    pub fn status(&self) -> ProposalStatus {
+        self.status
+    }
+
+    #[inline(always)]
+    pub fn is_approved(&self) -> bool {
+        matches!(self.status, ProposalStatus::Approved(_))
+    }
+
+    #[inline(always)]
+    pub fn bump(&self) -> u8 {
+        self.data[self.bump]
+    }
+
+    /// Members that approved, checked to be in bounds on construction.
+    #[inline(always)]
+    pub fn approved(&self) -> &[Address] {
+        try_read_addresses(&self.data, self.bump + 1).unwrap().0
+    }
+
+    #[inline(always)]
+    pub fn rejected(&self) -> &[Address] {
+        let (_, offset) = try_read_addresses(&self.data, self.bump + 1).unwrap();
+        try_read_addresses(&self.data, offset).unwrap().0
+    }
+
+    #[inline(always)]
+    pub fn cancelled(&self) -> &[Address] {
+        let (_, offset) = try_read_addresses(&self.data, self.bump + 1).unwrap();
+        let (_, offset) = try_read_addresses(&self.data, offset).unwrap();
+        try_read_addresses(&self.data, offset).unwrap().0
+    }
+}
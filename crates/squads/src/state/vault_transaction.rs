@@ -0,0 +1,75 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{read_address, try_borrow_squads_account};
+use hayabusa_common::{AccountView, Address, Ref};
+use hayabusa_errors::Result;
+
+/// Anchor discriminator of `VaultTransaction`.
+pub const VAULT_TRANSACTION_DISCRIMINATOR: [u8; 8] = [168, 250, 162, 100, 81, 14, 162, 207];
+
+/// Zero-copy view over the header of a Squads v4 vault transaction account.
+///
+/// Layout after the discriminator: multisig (32 bytes), creator (32 bytes), transaction index
+/// (u64), bump (u8), vault index (u8), vault bump (u8), then the ephemeral signer bumps and the
+/// transaction message, which are not exposed.
+pub struct VaultTransaction<'a> {
+    data: Ref<'a, [u8]>,
+}
+
+impl<'a> VaultTransaction<'a> {
+    const MULTISIG: usize = 8;
+    const CREATOR: usize = 40;
+    const INDEX: usize = 72;
+    const BUMP: usize = 80;
+    const VAULT_INDEX: usize = 81;
+    const VAULT_BUMP: usize = 82;
+
+    /// Length of the account data up to the vault bump.
+    pub const HEADER_LEN: usize = 83;
+
+    /// Return the `VaultTransaction` of the given account view.
+    ///
+    /// This method checks the owner, the discriminator and the header length.
+    #[inline]
+    pub fn from_account_view(account_view: &'a AccountView) -> Result<Self> {
+        let data = try_borrow_squads_account(
+            account_view,
+            &VAULT_TRANSACTION_DISCRIMINATOR,
+            Self::HEADER_LEN,
+        )?;
+
+        Ok(Self { data })
+    }
+
+    #[inline(always)]
+    pub fn multisig(&self) -> &Address {
+        read_address(&self.data, Self::MULTISIG)
+    }
+
+    #[inline(always)]
+    pub fn creator(&self) -> &Address {
+        read_address(&self.data, Self::CREATOR)
+    }
+
+    #[inline(always)]
+    pub fn index(&self) -> u64 {
+        u64::from_le_bytes(self.data[Self::INDEX..Self::INDEX + 8].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    pub fn bump(&self) -> u8 {
+        self.data[Self::BUMP]
+    }
+
+    /// Vault the transaction is signed by.
+    #[inline(always)]
+    pub fn vault_index(&self) -> u8 {
+        self.data[Self::VAULT_INDEX]
+    }
+
+    #[inline(always)]
+    pub fn vault_bump(&self) -> u8 {
+        self.data[Self::VAULT_BUMP]
+    }
+}
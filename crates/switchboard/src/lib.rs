@@ -0,0 +1,20 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_std]
+
+pub mod state;
+
+hayabusa_common::declare_id!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+use hayabusa_accounts::ProgramIds;
+use hayabusa_common::Address;
+
+/// Switchboard On-Demand program on devnet.
+pub const DEVNET_ID: Address = hayabusa_common::address!("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2");
+
+pub struct Switchboard;
+
+impl ProgramIds for Switchboard {
+    const IDS: &'static [Address] = &[ID, DEVNET_ID];
+}
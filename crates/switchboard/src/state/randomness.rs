@@ -0,0 +1,245 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Switchboard On-Demand randomness.
+//!
+//! The flow mirrors `hayabusa_random::commit_reveal`, with the oracle holding the secret:
+//!
+//! 1. the user commits to a randomness account in the slot right after its `seed_slot`
+//!    ([`RandomnessAccountData::try_check_committable`]), the program stores `seed_slot`
+//! 2. the oracle reveals the value, which is only readable in `reveal_slot`
+//!    ([`RandomnessAccountData::try_get_value`]) and must match the stored `seed_slot`
+//!
+//! The value is produced and proven by the oracle, the program only checks that it reads the
+//! account it committed to, in the slot the value was revealed in.
+
+use hayabusa_common::{address_eq, AccountView, Address, Ref};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_ser::{
+    Deserialize, FromBytesUnchecked, RawZcDeserialize, RawZcDeserializeUnchecked, Zc,
+};
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+/// Anchor discriminator of `RandomnessAccountData`.
+pub const RANDOMNESS_ACCOUNT_DISCRIMINATOR: [u8; 8] = [10, 66, 229, 135, 220, 239, 217, 114];
+
+/// Randomness account data, after the 8 byte discriminator.
+#[repr(C)]
+pub struct RandomnessAccountData {
+    authority: Address,
+    queue: Address,
+    seed_slothash: [u8; 32],
+    seed_slot: [u8; 8],
+    oracle: Address,
+    reveal_slot: [u8; 8],
+    value: [u8; 32],
+    _ebuf2: [u8; 96],
+    _ebuf1: [u8; 128],
+}
+
+impl FromBytesUnchecked for RandomnessAccountData {}
+impl Zc for RandomnessAccountData {}
+impl Deserialize for RandomnessAccountData {}
+
+unsafe impl RawZcDeserialize for RandomnessAccountData {
+    #[inline]
+    fn try_deserialize_raw(account_view: &AccountView) -> Result<Ref<'_, Self>> {
+        Self::check_account_view(account_view)?;
+
+        Ok(Ref::map(account_view.try_borrow()?, |d| unsafe {
+            Self::from_bytes_unchecked(&d[8..])
+        }))
+    }
+}
+
+impl RawZcDeserializeUnchecked for RandomnessAccountData {
+    #[inline(always)]
+    unsafe fn try_deserialize_raw_unchecked(account_view: &AccountView) -> Result<&Self> {
+        Self::check_account_view(account_view)?;
+
+        Ok(Self::from_bytes_unchecked(&account_view.borrow_unchecked()[8..]))
+    }
+}
+
+impl RandomnessAccountData {
+    pub const LEN: usize = core::mem::size_of::<RandomnessAccountData>();
+
+    #[inline(always)]
+    fn check_account_view(account_view: &AccountView) -> Result<()> {
+        // SAFETY: the owner is only compared, no reference is kept
+        let owner = unsafe { account_view.owner() };
+
+        if unlikely(!address_eq(owner, &crate::ID) && !address_eq(owner, &crate::DEVNET_ID)) {
+            error_msg!(
+                "RandomnessAccountData::try_deserialize_raw: invalid owner",
+                ProgramError::InvalidAccountOwner,
+            );
+        }
+
+        if unlikely(account_view.data_len() < 8 + Self::LEN) {
+            error_msg!(
+                "RandomnessAccountData::try_deserialize_raw: data length mismatch",
+                ProgramError::InvalidAccountData,
+            );
+        }
+
+        // SAFETY: the length was checked above and the borrow ends with this statement
+        let discriminator = unsafe { &account_view.borrow_unchecked()[..8] };
+
+        if unlikely(discriminator != RANDOMNESS_ACCOUNT_DISCRIMINATOR) {
+            error_msg!(
+                "RandomnessAccountData::try_deserialize_raw: invalid discriminator",
+                ErrorCode::InvalidAccountDiscriminator,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn authority(&self) -> &Address {
+        &self.authority
+    }
+
+    pub fn queue(&self) -> &Address {
+        &self.queue
+    }
+
+    pub fn oracle(&self) -> &Address {
+        &self.oracle
+    }
+
+    /// Hash of [`Self::seed_slot`], the oracle derives the value from it.
+    pub fn seed_slothash(&self) -> &[u8; 32] {
+        &self.seed_slothash
+    }
+
+    #[inline(always)]
+    pub fn seed_slot(&self) -> u64 {
+        u64::from_le_bytes(self.seed_slot)
+    }
+
+    #[inline(always)]
+    pub fn reveal_slot(&self) -> u64 {
+        u64::from_le_bytes(self.reveal_slot)
+    }
+
+    /// Checks that the randomness was requested in the previous slot and isn't revealed yet,
+    /// i.e. nobody can know the value at commit time.
+    #[inline]
+    pub fn try_check_committable(&self, current_slot: u64) -> Result<()> {
+        if unlikely(self.seed_slot() != current_slot.saturating_sub(1)) {
+            error_msg!(
+                "RandomnessAccountData::try_check_committable: seed slot is not the previous slot",
+                ErrorCode::RevealExpired,
+            );
+        }
+
+        if unlikely(self.reveal_slot() >= self.seed_slot()) {
+            error_msg!(
+                "RandomnessAccountData::try_check_committable: randomness already revealed",
+                ErrorCode::RevealExpired,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// The revealed value of a randomness account committed to in `committed_seed_slot`.
+    ///
+    /// Only readable in the reveal slot itself, afterwards the account may already be reused
+    /// for another request.
+    #[inline]
+    pub fn try_get_value(&self, committed_seed_slot: u64, current_slot: u64) -> Result<&[u8; 32]> {
+        if unlikely(self.seed_slot() != committed_seed_slot) {
+            error_msg!(
+                "RandomnessAccountData::try_get_value: seed slot changed since commit",
+                ErrorCode::RevealExpired,
+            );
+        }
+
+        if unlikely(self.reveal_slot() < self.seed_slot()) {
+            error_msg!(
+                "RandomnessAccountData::try_get_value: randomness not revealed yet",
+                ErrorCode::RevealNotReady,
+            );
+        }
+
+        if unlikely(self.reveal_slot() != current_slot) {
+            error_msg!(
+                "RandomnessAccountData::try_get_value: value was not revealed in this slot",
+                ErrorCode::RevealExpired,
+            );
+        }
+
+        Ok(&self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use hayabusa_common::mock::MockAccount;
+    use std::vec::Vec;
+
+    const ADDRESS: Address = Address::new_from_array([3; 32]);
+
+    fn randomness_data(seed_slot: u64, reveal_slot: u64) -> Vec<u8> {
+        let mut data = RANDOMNESS_ACCOUNT_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[1; 32]);
+        data.extend_from_slice(&[2; 32]);
+        data.extend_from_slice(&[0; 32]);
+        data.extend_from_slice(&seed_slot.to_le_bytes());
+        data.extend_from_slice(&[4; 32]);
+        data.extend_from_slice(&reveal_slot.to_le_bytes());
+        data.extend_from_slice(&[9; 32]);
+        data.extend_from_slice(&[0; 96 + 128]);
+        data
+    }
+
+    #[test]
+    fn test_commit_and_reveal() {
+        // requested in slot 100, committed to in slot 101
+        let data = randomness_data(100, 0);
+        let mut account = MockAccount::new(ADDRESS, crate::ID, &data);
+        let account_view = account.view();
+        let randomness = RandomnessAccountData::try_deserialize_raw(&account_view).unwrap();
+
+        assert_eq!(randomness.authority(), &Address::new_from_array([1; 32]));
+        assert_eq!(randomness.queue(), &Address::new_from_array([2; 32]));
+        assert_eq!(randomness.oracle(), &Address::new_from_array([4; 32]));
+        assert!(randomness.try_check_committable(101).is_ok());
+        assert!(randomness.try_check_committable(102).is_err());
+        assert!(randomness.try_get_value(100, 101).is_err());
+        drop(randomness);
+
+        // revealed in slot 105
+        let data = randomness_data(100, 105);
+        let mut account = MockAccount::new(ADDRESS, crate::DEVNET_ID, &data);
+        let account_view = account.view();
+        let randomness = RandomnessAccountData::try_deserialize_raw(&account_view).unwrap();
+
+        assert!(randomness.try_check_committable(101).is_err());
+        assert_eq!(randomness.try_get_value(100, 105).unwrap(), &[9; 32]);
+        // another request, or a later slot
+        assert!(randomness.try_get_value(99, 105).is_err());
+        assert!(randomness.try_get_value(100, 106).is_err());
+    }
+
+    #[test]
+    fn test_invalid_randomness_account() {
+        let data = randomness_data(100, 0);
+
+        let mut account = MockAccount::new(ADDRESS, ADDRESS, &data);
+        assert!(RandomnessAccountData::try_deserialize_raw(&account.view()).is_err());
+
+        let mut account = MockAccount::new(ADDRESS, crate::ID, &data[..data.len() - 1]);
+        assert!(RandomnessAccountData::try_deserialize_raw(&account.view()).is_err());
+
+        let mut other = data.clone();
+        other[0] ^= 1;
+        let mut account = MockAccount::new(ADDRESS, crate::ID, &other);
+        assert!(RandomnessAccountData::try_deserialize_raw(&account.view()).is_err());
+    }
+}
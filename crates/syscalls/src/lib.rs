@@ -4,6 +4,8 @@
 #![no_std]
 #![allow(unexpected_cfgs)] // silence warning about target_os = "solana"
 
+#[cfg(not(target_os = "solana"))]
+use core::sync::atomic::{AtomicPtr, Ordering};
 use hayabusa_errors::{ErrorCode, Result};
 use solana_address::Address;
 pub use solana_define_syscall::definitions::*;
@@ -12,50 +14,83 @@ pub const MAX_SEEDS: usize = 16;
 pub const MAX_SEED_LEN: usize = 32;
 pub const MAX_TOTAL_LEN: usize = MAX_SEEDS * MAX_SEED_LEN; // 512
 
+/// PDA of `seeds` and the canonical bump. Uses the syscall on chain and `solana-address`
+/// elsewhere, so the same code runs in host tests.
 pub fn try_find_program_address(seeds: &[&[u8]], program_id: &Address) -> Result<(Address, u8)> {
-    let mut seed_buf = [0u8; MAX_TOTAL_LEN];
-    let seed_len = flatten_seeds_raw(seeds, &mut seed_buf)?;
-
-    let mut pda = [0u8; 32];
-    let mut bump: u8 = 0;
-
-    let rc = unsafe {
-        sol_try_find_program_address(
-            seed_buf.as_ptr(),
-            seed_len as u64,
-            program_id.as_ref().as_ptr(),
-            pda.as_mut_ptr(),
-            (&mut bump) as *mut u8,
-        )
-    };
-
-    if rc == 0 {
-        Ok((Address::new_from_array(pda), bump))
-    } else {
-        Err(ErrorCode::SyscallFailed.into())
+    check_seeds(seeds)?;
+
+    #[cfg(target_os = "solana")]
+    {
+        let mut pda = [0u8; 32];
+        let mut bump: u8 = 0;
+
+        let rc = unsafe {
+            sol_try_find_program_address(
+                seeds.as_ptr() as *const u8,
+                seeds.len() as u64,
+                program_id.as_ref().as_ptr(),
+                pda.as_mut_ptr(),
+                (&mut bump) as *mut u8,
+            )
+        };
+
+        if rc == 0 {
+            Ok((Address::new_from_array(pda), bump))
+        } else {
+            Err(ErrorCode::SyscallFailed.into())
+        }
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        Address::try_find_program_address(seeds, program_id)
+            .ok_or_else(|| ErrorCode::SyscallFailed.into())
     }
 }
 
 pub fn try_create_program_address(seeds: &[&[u8]], program_id: &Address) -> Result<Address> {
-    let mut seed_buf = [0u8; MAX_TOTAL_LEN];
-    let seed_len = flatten_seeds_raw(seeds, &mut seed_buf)?;
-
-    let mut pda = [0u8; 32];
-
-    let rc = unsafe {
-        sol_create_program_address(
-            seed_buf.as_ptr(),
-            seed_len as u64,
-            program_id.as_ref().as_ptr(),
-            pda.as_mut_ptr(),
-        )
-    };
-
-    if rc == 0 {
-        Ok(Address::new_from_array(pda))
-    } else {
-        Err(ErrorCode::SyscallFailed.into())
+    check_seeds(seeds)?;
+
+    #[cfg(target_os = "solana")]
+    {
+        let mut pda = [0u8; 32];
+
+        let rc = unsafe {
+            sol_create_program_address(
+                seeds.as_ptr() as *const u8,
+                seeds.len() as u64,
+                program_id.as_ref().as_ptr(),
+                pda.as_mut_ptr(),
+            )
+        };
+
+        if rc == 0 {
+            Ok(Address::new_from_array(pda))
+        } else {
+            Err(ErrorCode::SyscallFailed.into())
+        }
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        Address::create_program_address(seeds, program_id)
+            .map_err(|_| ErrorCode::SyscallFailed.into())
+    }
+}
+
+/// Fails like the PDA syscalls on too many or too long seeds. The syscalls take `seeds` itself,
+/// an array of slices, not the seed bytes.
+#[inline(always)]
+fn check_seeds(seeds: &[&[u8]]) -> Result<()> {
+    if seeds.len() > MAX_SEEDS {
+        return Err(ErrorCode::TooManySeeds.into());
+    }
+
+    if seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+        return Err(ErrorCode::SeedsTooLong.into());
     }
+
+    Ok(())
 }
 
 /// Flattens `seeds` into `out`.
@@ -95,3 +130,115 @@ pub fn flatten_seeds_raw(seeds: &[&[u8]], out: &mut [u8; MAX_TOTAL_LEN]) -> Resu
 
     Ok(offset)
 }
+
+/// Curve id of ed25519 points for `sol_curve_validate_point`
+#[cfg(target_os = "solana")]
+const CURVE25519_EDWARDS: u64 = 0;
+
+/// Whether `address` is a valid ed25519 point, i.e. could have a private key.
+///
+/// PDAs are off the curve by construction, a wallet that must sign off chain is on it. Uses the
+/// curve syscall on chain and curve25519-dalek elsewhere.
+#[inline]
+pub fn is_on_curve(address: &Address) -> bool {
+    #[cfg(target_os = "solana")]
+    {
+        let mut result = 0u8;
+
+        // SAFETY: the point is 32 bytes, `result` is only written by the syscall
+        let rc = unsafe {
+            sol_curve_validate_point(CURVE25519_EDWARDS, address.as_ref().as_ptr(), &mut result)
+        };
+
+        rc == 0
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        address.is_on_curve()
+    }
+}
+
+/// SHA-256 of the concatenation of `vals`, with the syscall on chain and `sha2` elsewhere.
+#[inline]
+pub fn sha256(vals: &[&[u8]]) -> [u8; 32] {
+    #[cfg(target_os = "solana")]
+    {
+        let mut hash = [0u8; 32];
+
+        // SAFETY: `&[u8]` has the (pointer, length) layout the syscall expects for every value
+        unsafe {
+            sol_sha256(
+                vals.as_ptr() as *const u8,
+                vals.len() as u64,
+                hash.as_mut_ptr(),
+            );
+        }
+
+        hash
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        for val in vals {
+            hasher.update(val);
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Logs `fields` as a `Program data: <base64> <base64> ...` line, one base64 word per field.
+///
+/// Off chain the fields go to the hook set with [`set_log_data_hook`], if any.
+#[inline]
+pub fn log_data(fields: &[&[u8]]) {
+    #[cfg(target_os = "solana")]
+    // SAFETY: `&[u8]` has the (pointer, length) layout the syscall expects for every field
+    unsafe {
+        sol_log_data(fields.as_ptr() as *const u8, fields.len() as u64)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        let hook = LOG_DATA_HOOK.load(Ordering::Relaxed);
+
+        if !hook.is_null() {
+            // SAFETY: `set_log_data_hook` is the only writer and stores a `fn(&[&[u8]])`
+            let hook: fn(&[&[u8]]) = unsafe { core::mem::transmute(hook) };
+            hook(fields);
+        }
+    }
+}
+
+#[cfg(not(target_os = "solana"))]
+static LOG_DATA_HOOK: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Passes the fields of every later [`log_data`] to `hook`, for host tests checking the events
+/// of a program. Not available on chain.
+#[cfg(not(target_os = "solana"))]
+pub fn set_log_data_hook(hook: fn(&[&[u8]])) {
+    LOG_DATA_HOOK.store(hook as *mut (), Ordering::Relaxed);
+}
+
+/// Compute units left in the current transaction.
+#[inline(always)]
+pub fn remaining_compute_units() -> u64 {
+    unsafe { sol_remaining_compute_units() }
+}
+
+/// Sets the data returned to the caller of the current instruction, at most 1024 bytes.
+///
+/// A no-op off chain.
+#[inline(always)]
+pub fn set_return_data(data: &[u8]) {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        sol_set_return_data(data.as_ptr(), data.len() as u64)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    let _ = data;
+}
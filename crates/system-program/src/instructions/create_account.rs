@@ -1,9 +1,10 @@
 // Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
 // SPDX-License-Identifier: Apache-2.0
 
-use super::minimum_balance;
+use super::{allocate, assign, minimum_balance, transfer, Allocate, Assign, Transfer};
 use hayabusa_cpi::{CheckProgramId, CpiCtx};
 use hayabusa_errors::Result;
+use hayabusa_utility::Lamports;
 use solana_account_view::AccountView;
 use solana_address::Address;
 use solana_instruction_view::{
@@ -22,6 +23,7 @@ impl CheckProgramId for CreateAccount<'_> {
     const ID: Address = crate::ID;
 }
 
+/// Creates `to` with the rent exempt minimum for `space`.
 #[inline]
 pub fn create_account<'ix>(
     cpi_ctx: CpiCtx<'ix, '_, '_, '_, CreateAccount<'ix>>,
@@ -30,6 +32,17 @@ pub fn create_account<'ix>(
 ) -> Result<()> {
     let lamports = minimum_balance(space as usize)?;
 
+    create_account_with_lamports(cpi_ctx, owner_program, space, lamports)
+}
+
+/// Same as [`create_account`], funding `to` with exactly `lamports`.
+#[inline]
+pub fn create_account_with_lamports<'ix>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, CreateAccount<'ix>>,
+    owner_program: &Address,
+    space: u64,
+    lamports: Lamports,
+) -> Result<()> {
     let instruction_accounts = [
         InstructionAccount::writable_signer(cpi_ctx.from.address()),
         InstructionAccount::writable_signer(cpi_ctx.to.address()),
@@ -43,7 +56,7 @@ pub fn create_account<'ix>(
     // - [12..20]: account space
     // - [20..52]: owner pubkey
     let mut ix_data = [0; 52];
-    ix_data[4..12].copy_from_slice(&lamports.to_le_bytes());
+    ix_data[4..12].copy_from_slice(&lamports.get().to_le_bytes());
     ix_data[12..20].copy_from_slice(&space.to_le_bytes());
     ix_data[20..52].copy_from_slice(owner_program.as_ref());
 
@@ -59,3 +72,58 @@ pub fn create_account<'ix>(
         invoke(&instruction, &account_views)
     }
 }
+
+/// Same as [`create_account`], but also works when `to` already holds lamports.
+///
+/// Anyone can transfer lamports to an address before it is created, which makes the
+/// `create_account` instruction fail. In that case the missing rent is transferred from `from`
+/// and the account is allocated and assigned instead.
+#[inline]
+pub fn create_account_allow_prefunded<'ix>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, CreateAccount<'ix>>,
+    owner_program: &Address,
+    space: u64,
+) -> Result<()> {
+    let current_lamports = Lamports::new(cpi_ctx.to.lamports());
+
+    if current_lamports.is_zero() {
+        return create_account(cpi_ctx, owner_program, space);
+    }
+
+    let required_lamports = minimum_balance(space as usize)?.saturating_sub(current_lamports);
+
+    if !required_lamports.is_zero() {
+        transfer(
+            CpiCtx::try_new_without_signer(
+                cpi_ctx.program,
+                Transfer {
+                    from: cpi_ctx.from,
+                    to: cpi_ctx.to,
+                },
+            )?,
+            required_lamports,
+        )?;
+    }
+
+    allocate(
+        CpiCtx::try_new(
+            cpi_ctx.program,
+            Allocate {
+                account: cpi_ctx.to,
+            },
+            cpi_ctx.signers,
+        )?,
+        space,
+    )?;
+
+    assign(
+        CpiCtx::try_new(
+            cpi_ctx.program,
+            Assign {
+                account: cpi_ctx.to,
+            },
+            cpi_ctx.signers,
+        )?,
+        owner_program,
+    )
+}
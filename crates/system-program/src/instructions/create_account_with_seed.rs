@@ -0,0 +1,85 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::minimum_balance;
+use hayabusa_cpi::{CheckProgramId, CpiCtx};
+use hayabusa_errors::{ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely};
+use solana_account_view::AccountView;
+use solana_address::{Address, MAX_SEED_LEN};
+use solana_instruction_view::{
+    cpi::{invoke, invoke_signed},
+    InstructionAccount, InstructionView,
+};
+
+pub struct CreateAccountWithSeed<'ix> {
+    /// Funding account
+    pub from: &'ix AccountView,
+    /// New account, `create_with_seed(base, seed, owner)`
+    pub to: &'ix AccountView,
+    /// Base of the derived address, must sign
+    pub base: &'ix AccountView,
+}
+
+impl CheckProgramId for CreateAccountWithSeed<'_> {
+    const ID: Address = crate::ID;
+}
+
+#[inline]
+pub fn create_account_with_seed<'ix>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, CreateAccountWithSeed<'ix>>,
+    seed: &str,
+    owner_program: &Address,
+    space: u64,
+) -> Result<()> {
+    if unlikely(seed.len() > MAX_SEED_LEN) {
+        error_msg!(
+            "create_account_with_seed: seed too long",
+            ProgramError::MaxSeedLengthExceeded,
+        );
+    }
+
+    let lamports = minimum_balance(space as usize)?;
+
+    let instruction_accounts = [
+        InstructionAccount::writable_signer(cpi_ctx.from.address()),
+        InstructionAccount::writable(cpi_ctx.to.address()),
+        InstructionAccount::readonly_signer(cpi_ctx.base.address()),
+    ];
+
+    let account_views = [cpi_ctx.from, cpi_ctx.to, cpi_ctx.base];
+
+    // ix data
+    // - [0..4]: instruction discriminator, 3
+    // - [4..36]: base pubkey
+    // - [36..44]: seed length
+    // - [44..44 + n]: seed
+    // - [.. + 8]: lamports
+    // - [.. + 8]: account space
+    // - [.. + 32]: owner pubkey
+    let mut ix_data = [0; 44 + MAX_SEED_LEN + 48];
+    ix_data[..4].copy_from_slice(&3u32.to_le_bytes());
+    ix_data[4..36].copy_from_slice(cpi_ctx.base.address().as_ref());
+    ix_data[36..44].copy_from_slice(&(seed.len() as u64).to_le_bytes());
+
+    let mut offset = 44 + seed.len();
+    ix_data[44..offset].copy_from_slice(seed.as_bytes());
+    ix_data[offset..offset + 8].copy_from_slice(&lamports.get().to_le_bytes());
+    offset += 8;
+    ix_data[offset..offset + 8].copy_from_slice(&space.to_le_bytes());
+    offset += 8;
+    ix_data[offset..offset + 32].copy_from_slice(owner_program.as_ref());
+    offset += 32;
+
+    let instruction = InstructionView {
+        program_id: &crate::ID,
+        accounts: &instruction_accounts,
+        data: &ix_data[..offset],
+    };
+
+    if let Some(signers) = cpi_ctx.signers {
+        invoke_signed(&instruction, &account_views, signers)
+    } else {
+        invoke(&instruction, &account_views)
+    }
+}
@@ -4,18 +4,21 @@
 pub mod allocate;
 pub mod assign;
 pub mod create_account;
+pub mod create_account_with_seed;
 pub mod transfer;
 
 pub use allocate::*;
 pub use assign::*;
 pub use create_account::*;
+pub use create_account_with_seed::*;
 pub use transfer::*;
 
 use hayabusa_errors::Result;
 use hayabusa_sysvars::{rent::Rent, Sysvar};
+use hayabusa_utility::Lamports;
 
-fn minimum_balance(space: usize) -> Result<u64> {
+fn minimum_balance(space: usize) -> Result<Lamports> {
     let rent = Rent::get()?;
 
-    Ok(rent.try_minimum_balance(space)?)
+    rent.try_minimum_lamports(space)
 }
@@ -3,6 +3,8 @@
 
 use hayabusa_cpi::{CheckProgramId, CpiCtx};
 use hayabusa_errors::Result;
+use hayabusa_sysvars::{rent::Rent, Sysvar};
+use hayabusa_utility::Lamports;
 use solana_account_view::AccountView;
 use solana_address::Address;
 use solana_instruction_view::{
@@ -22,7 +24,10 @@ impl CheckProgramId for Transfer<'_> {
 }
 
 #[inline]
-pub fn transfer<'ix>(cpi_ctx: CpiCtx<'ix, '_, '_, '_, Transfer<'ix>>, lamports: u64) -> Result<()> {
+pub fn transfer<'ix>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, Transfer<'ix>>,
+    lamports: Lamports,
+) -> Result<()> {
     let account_views = [cpi_ctx.from, cpi_ctx.to];
     let instruction_accounts = [
         InstructionAccount::writable_signer(cpi_ctx.from.address()),
@@ -34,7 +39,7 @@ pub fn transfer<'ix>(cpi_ctx: CpiCtx<'ix, '_, '_, '_, Transfer<'ix>>, lamports:
     // - [4..12]: lamports amount
     let mut ix_data = [0; 12];
     ix_data[0] = 2;
-    ix_data[4..12].copy_from_slice(&lamports.to_le_bytes());
+    ix_data[4..12].copy_from_slice(&lamports.get().to_le_bytes());
 
     let instruction = InstructionView {
         program_id: &crate::ID,
@@ -48,3 +53,19 @@ pub fn transfer<'ix>(cpi_ctx: CpiCtx<'ix, '_, '_, '_, Transfer<'ix>>, lamports:
         invoke(&instruction, &account_views)
     }
 }
+
+/// Transfers every lamport of `from` above its rent exempt minimum, returning the amount
+/// transferred. Nothing is transferred, and no CPI made, if there is nothing to spare.
+#[inline]
+pub fn transfer_all_except_rent<'ix>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, Transfer<'ix>>,
+) -> Result<Lamports> {
+    let rent_exempt = Rent::get()?.try_minimum_lamports(cpi_ctx.from.data_len())?;
+    let amount = Lamports::new(cpi_ctx.from.lamports()).saturating_sub(rent_exempt);
+
+    if !amount.is_zero() {
+        transfer(cpi_ctx, amount)?;
+    }
+
+    Ok(amount)
+}
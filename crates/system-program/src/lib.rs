@@ -4,5 +4,6 @@
 #![no_std]
 
 pub mod instructions;
+pub mod nonce;
 
 solana_address::declare_id!("11111111111111111111111111111111");
@@ -0,0 +1,234 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use core::ops::Deref;
+use hayabusa_errors::{ErrorCode, Result};
+use hayabusa_sysvars::instructions::Instructions;
+use hayabusa_utility::{error_msg, hint::unlikely};
+use solana_account_view::AccountView;
+use solana_address::{address_eq, Address};
+use solana_program_error::ProgramError;
+
+/// Size of a nonce account
+pub const NONCE_ACCOUNT_LEN: usize = 80;
+
+/// System instruction discriminator of `AdvanceNonceAccount`
+const ADVANCE_NONCE_ACCOUNT: u32 = 4;
+
+/// Nonce account data, `Versions::Current` only
+const CURRENT_VERSION: u32 = 1;
+
+/// Nonce account state tag of `State::Initialized`
+const INITIALIZED: u32 = 1;
+
+/// Initialized durable nonce account, copied out of the account data.
+///
+/// ```text
+/// version: u32 | state: u32 | authority: [u8; 32] | durable_nonce: [u8; 32] | lamports_per_signature: u64
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NonceData {
+    /// Signs `AdvanceNonceAccount`
+    pub authority: Address,
+    /// Blockhash to sign the next durable transaction with
+    pub durable_nonce: [u8; 32],
+    pub lamports_per_signature: u64,
+}
+
+impl NonceData {
+    /// Parses an initialized nonce account owned by the system program.
+    #[inline(always)]
+    pub fn try_from_account_view(account_view: &AccountView) -> Result<Self> {
+        if unlikely(!account_view.owned_by(&crate::ID)) {
+            error_msg!(
+                "NonceData::try_from_account_view: nonce account not owned by the system program",
+                ProgramError::InvalidAccountOwner,
+            );
+        }
+
+        let data = account_view.try_borrow()?;
+
+        if unlikely(data.len() != NONCE_ACCOUNT_LEN) {
+            error_msg!(
+                "NonceData::try_from_account_view: wrong data length",
+                ProgramError::InvalidAccountData,
+            );
+        }
+
+        let word = |offset: usize| {
+            u32::from_le_bytes([
+                data[offset],
+                data[offset + 1],
+                data[offset + 2],
+                data[offset + 3],
+            ])
+        };
+
+        if unlikely(word(0) != CURRENT_VERSION || word(4) != INITIALIZED) {
+            error_msg!(
+                "NonceData::try_from_account_view: nonce account not initialized",
+                ProgramError::InvalidAccountData,
+            );
+        }
+
+        let mut authority = [0u8; 32];
+        let mut durable_nonce = [0u8; 32];
+        let mut lamports_per_signature = [0u8; 8];
+        authority.copy_from_slice(&data[8..40]);
+        durable_nonce.copy_from_slice(&data[40..72]);
+        lamports_per_signature.copy_from_slice(&data[72..80]);
+
+        Ok(Self {
+            authority: Address::new_from_array(authority),
+            durable_nonce,
+            lamports_per_signature: u64::from_le_bytes(lamports_per_signature),
+        })
+    }
+}
+
+/// Nonce account advanced by the transaction, `None` if it uses a recent blockhash.
+///
+/// The runtime only accepts a durable nonce transaction when its first instruction is a system
+/// `AdvanceNonceAccount`, so looking at index 0 of the instructions sysvar is enough.
+#[inline(always)]
+pub fn durable_nonce_address<T>(instructions: &Instructions<T>) -> Result<Option<Address>>
+where
+    T: Deref<Target = [u8]>,
+{
+    let first = instructions.load_instruction_at(0)?;
+
+    let data = first.get_instruction_data();
+    let is_advance = address_eq(first.get_program_id(), &crate::ID)
+        && data.len() >= 4
+        && u32::from_le_bytes([data[0], data[1], data[2], data[3]]) == ADVANCE_NONCE_ACCOUNT;
+
+    if !is_advance {
+        return Ok(None);
+    }
+
+    Ok(Some(first.get_instruction_account_at(0)?.key))
+}
+
+/// Fails with [`ErrorCode::DurableNonceNotAllowed`] if the transaction runs under a durable
+/// nonce.
+///
+/// A durable transaction can be signed long before it lands, so instructions relying on the
+/// signer having seen current state (e.g. oracle priced trades with a slippage bound) should
+/// require a recent blockhash instead.
+#[inline(always)]
+pub fn try_check_recent_blockhash<T>(instructions: &Instructions<T>) -> Result<()>
+where
+    T: Deref<Target = [u8]>,
+{
+    if unlikely(durable_nonce_address(instructions)?.is_some()) {
+        error_msg!(
+            "try_check_recent_blockhash: transaction uses a durable nonce",
+            ErrorCode::DurableNonceNotAllowed,
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use hayabusa_common::mock::MockAccount;
+    use std::{vec, vec::Vec};
+
+    const AUTHORITY: Address = Address::new_from_array([1; 32]);
+    const NONCE: Address = Address::new_from_array([2; 32]);
+
+    fn nonce_data(state: u32) -> [u8; NONCE_ACCOUNT_LEN] {
+        let mut data = [0u8; NONCE_ACCOUNT_LEN];
+        data[0..4].copy_from_slice(&CURRENT_VERSION.to_le_bytes());
+        data[4..8].copy_from_slice(&state.to_le_bytes());
+        data[8..40].copy_from_slice(AUTHORITY.as_ref());
+        data[40..72].copy_from_slice(&[3; 32]);
+        data[72..80].copy_from_slice(&5000u64.to_le_bytes());
+        data
+    }
+
+    /// Instructions sysvar data of a transaction made of a single instruction, current index 0
+    fn instructions_data(program_id: &Address, accounts: &[Address], data: &[u8]) -> Vec<u8> {
+        let mut sysvar = vec![];
+        sysvar.extend_from_slice(&1u16.to_le_bytes());
+        sysvar.extend_from_slice(&4u16.to_le_bytes());
+        sysvar.extend_from_slice(&(accounts.len() as u16).to_le_bytes());
+        for account in accounts {
+            sysvar.push(0b11);
+            sysvar.extend_from_slice(account.as_ref());
+        }
+        sysvar.extend_from_slice(program_id.as_ref());
+        sysvar.extend_from_slice(&(data.len() as u16).to_le_bytes());
+        sysvar.extend_from_slice(data);
+        sysvar.extend_from_slice(&0u16.to_le_bytes());
+        sysvar
+    }
+
+    #[test]
+    fn test_initialized_nonce() {
+        let mut account = MockAccount::new(NONCE, crate::ID, &nonce_data(INITIALIZED));
+
+        assert_eq!(
+            NonceData::try_from_account_view(&account.view()),
+            Ok(NonceData {
+                authority: AUTHORITY,
+                durable_nonce: [3; 32],
+                lamports_per_signature: 5000,
+            })
+        );
+    }
+
+    #[test]
+    fn test_uninitialized_nonce() {
+        let mut account = MockAccount::new(NONCE, crate::ID, &nonce_data(0));
+
+        assert_eq!(
+            NonceData::try_from_account_view(&account.view()),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_wrong_length_nonce() {
+        let data = nonce_data(INITIALIZED);
+        let mut account = MockAccount::new(NONCE, crate::ID, &data[..NONCE_ACCOUNT_LEN - 1]);
+
+        assert_eq!(
+            NonceData::try_from_account_view(&account.view()),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_durable_nonce_address() {
+        let advance = ADVANCE_NONCE_ACCOUNT.to_le_bytes();
+        let data = instructions_data(&crate::ID, &[NONCE, AUTHORITY], &advance);
+        let instructions = unsafe { Instructions::new_unchecked(data.as_slice()) };
+
+        assert_eq!(durable_nonce_address(&instructions), Ok(Some(NONCE)));
+        assert_eq!(
+            try_check_recent_blockhash(&instructions),
+            Err(ErrorCode::DurableNonceNotAllowed.into())
+        );
+    }
+
+    #[test]
+    fn test_recent_blockhash() {
+        // `Transfer`, first instruction but not an advance
+        let transfer = 2u32.to_le_bytes();
+        let data = instructions_data(&crate::ID, &[NONCE, AUTHORITY], &transfer);
+        let instructions = unsafe { Instructions::new_unchecked(data.as_slice()) };
+        assert_eq!(durable_nonce_address(&instructions), Ok(None));
+
+        // an advance tag sent to another program
+        let advance = ADVANCE_NONCE_ACCOUNT.to_le_bytes();
+        let data = instructions_data(&AUTHORITY, &[NONCE], &advance);
+        let instructions = unsafe { Instructions::new_unchecked(data.as_slice()) };
+        assert_eq!(durable_nonce_address(&instructions), Ok(None));
+        assert_eq!(try_check_recent_blockhash(&instructions), Ok(()));
+    }
+}
@@ -3,10 +3,10 @@
 
 //! Attribution: https://github.com/anza-xyz/pinocchio/blob/91ae743491e7f768b91662f442119c6caef640f4/sdk/src/sysvars/clock.rs
 
-use crate::{impl_sysvar_get, Sysvar};
+use crate::{impl_sysvar_get, Sysvar, SysvarAccountData};
 use hayabusa_common::{AccountView, Address, Ref};
-use hayabusa_errors::{ProgramError, Result};
-use hayabusa_utility::hint::unlikely;
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely};
 
 /// The ID of the clock sysvar.
 pub const CLOCK_ID: Address = Address::new_from_array([
@@ -74,6 +74,17 @@ pub const DEFAULT_TICKS_PER_SECOND: u64 = 160;
 // Actually calculation is supposed to be derived DEFAULT_TICKS_PER_SLOT / DEFAULT_TICKS_PER_SECOND
 pub const DEFAULT_MS_PER_SLOT: u64 = 1_000 * DEFAULT_TICKS_PER_SLOT / DEFAULT_TICKS_PER_SECOND;
 
+impl SysvarAccountData for Clock {
+    const ID: Address = CLOCK_ID;
+
+    type Data<'a> = Ref<'a, Clock>;
+
+    #[inline(always)]
+    fn from_sysvar_account(account_view: &AccountView) -> Result<Self::Data<'_>> {
+        Self::from_account_view(account_view)
+    }
+}
+
 impl Sysvar for Clock {
     impl_sysvar_get!(CLOCK_ID, 0);
 }
@@ -86,7 +97,7 @@ impl Clock {
     ///
     /// This method performs a check on the account view address.
     #[inline]
-    pub fn from_account_view(account_view: &AccountView) -> Result<Ref<Clock>> {
+    pub fn from_account_view(account_view: &AccountView) -> Result<Ref<'_, Clock>> {
         if unlikely(account_view.address() != &CLOCK_ID) {
             return Err(ProgramError::InvalidArgument);
         }
@@ -136,4 +147,55 @@ impl Clock {
     pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
         &*(bytes.as_ptr() as *const Clock)
     }
+
+    /// Fails with [`ErrorCode::TooEarly`] while `unix_timestamp` is before `start`, passes from
+    /// `start` on.
+    #[inline(always)]
+    pub fn try_check_reached(&self, start: UnixTimestamp) -> Result<()> {
+        if unlikely(self.unix_timestamp < start) {
+            error_msg!("Clock::try_check_reached: too early", ErrorCode::TooEarly);
+        }
+
+        Ok(())
+    }
+
+    /// Fails with [`ErrorCode::DeadlinePassed`] once `unix_timestamp` is past `deadline`, the
+    /// deadline itself still passes.
+    #[inline(always)]
+    pub fn try_check_deadline(&self, deadline: UnixTimestamp) -> Result<()> {
+        if unlikely(self.unix_timestamp > deadline) {
+            error_msg!(
+                "Clock::try_check_deadline: deadline passed",
+                ErrorCode::DeadlinePassed,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// [`Clock::try_check_reached`] for a slot.
+    #[inline(always)]
+    pub fn try_check_slot_reached(&self, start: Slot) -> Result<()> {
+        if unlikely(self.slot < start) {
+            error_msg!(
+                "Clock::try_check_slot_reached: too early",
+                ErrorCode::TooEarly,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// [`Clock::try_check_deadline`] for a slot.
+    #[inline(always)]
+    pub fn try_check_slot_deadline(&self, deadline: Slot) -> Result<()> {
+        if unlikely(self.slot > deadline) {
+            error_msg!(
+                "Clock::try_check_slot_deadline: deadline passed",
+                ErrorCode::DeadlinePassed,
+            );
+        }
+
+        Ok(())
+    }
 }
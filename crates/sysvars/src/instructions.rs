@@ -7,6 +7,8 @@ use core::{marker::PhantomData, mem::size_of, ops::Deref};
 use hayabusa_common::{AccountView, Address, Ref, ADDRESS_BYTES};
 use hayabusa_errors::ProgramError;
 
+use crate::SysvarAccountData;
+
 /// Instructions sysvar ID `Sysvar1nstructions1111111111111111111111111`.
 pub const INSTRUCTIONS_ID: Address = Address::new_from_array([
     0x06, 0xa7, 0xd5, 0x17, 0x18, 0x7b, 0xd1, 0x66, 0x35, 0xda, 0xd4, 0x04, 0x55, 0xfd, 0xc2, 0xc0,
@@ -66,7 +68,7 @@ where
     pub unsafe fn deserialize_instruction_unchecked(
         &self,
         index: usize,
-    ) -> IntrospectedInstruction {
+    ) -> IntrospectedInstruction<'_> {
         let offset = *(self
             .data
             .as_ptr()
@@ -80,7 +82,7 @@ where
     pub fn load_instruction_at(
         &self,
         index: usize,
-    ) -> Result<IntrospectedInstruction, ProgramError> {
+    ) -> Result<IntrospectedInstruction<'_>, ProgramError> {
         if index >= self.num_instructions() {
             return Err(ProgramError::InvalidInstructionData);
         }
@@ -95,7 +97,7 @@ where
     pub fn get_instruction_relative(
         &self,
         index_relative_to_current: i64,
-    ) -> Result<IntrospectedInstruction, ProgramError> {
+    ) -> Result<IntrospectedInstruction<'_>, ProgramError> {
         let current_index = self.load_current_index() as i64;
         let index = current_index.saturating_add(index_relative_to_current);
 
@@ -122,6 +124,19 @@ impl<'a> TryFrom<&'a AccountView> for Instructions<Ref<'a, [u8]>> {
     }
 }
 
+impl SysvarAccountData for Instructions<Ref<'_, [u8]>> {
+    const ID: Address = INSTRUCTIONS_ID;
+
+    type Data<'a> = Instructions<Ref<'a, [u8]>>;
+
+    #[inline(always)]
+    fn from_sysvar_account(
+        account_view: &AccountView,
+    ) -> hayabusa_errors::Result<Self::Data<'_>> {
+        Instructions::try_from(account_view)
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct IntrospectedInstruction<'a> {
@@ -7,10 +7,11 @@
 pub mod clock;
 pub mod instructions;
 pub mod rent;
+pub mod slot_hashes;
 
 #[cfg(not(any(target_os = "solana", target_arch = "bpf")))]
 use core::hint::black_box;
-use hayabusa_common::Address;
+use hayabusa_common::{AccountView, Address};
 use hayabusa_errors::{ProgramError, Result};
 #[cfg(any(target_os = "solana", target_arch = "bpf"))]
 use hayabusa_syscalls::sol_get_sysvar;
@@ -43,6 +44,18 @@ pub trait Sysvar: Sized {
     }
 }
 
+/// A sysvar that can be read from its account, for sysvars passed to the program as accounts.
+pub trait SysvarAccountData {
+    /// Address of the sysvar account.
+    const ID: Address;
+
+    /// Parsed view of the account data.
+    type Data<'a>;
+
+    /// Parses the sysvar from `account_view`, checking its address.
+    fn from_sysvar_account(account_view: &AccountView) -> Result<Self::Data<'_>>;
+}
+
 /// Implements the [`Sysvar::get`] method for both SBF and host targets.
 #[macro_export]
 macro_rules! impl_sysvar_get {
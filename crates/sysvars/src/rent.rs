@@ -9,11 +9,11 @@
 // It can be removed once the implementation uses `get_sysvar` instead.
 #![allow(deprecated)]
 
-use crate::{impl_sysvar_get, Sysvar};
+use crate::{Sysvar, SysvarAccountData};
 use core::mem::{align_of, size_of};
 use hayabusa_common::{AccountView, Address, Ref};
 use hayabusa_errors::{ProgramError, Result};
-use hayabusa_utility::hint::unlikely;
+use hayabusa_utility::{hint::unlikely, Lamports};
 
 /// The ID of the rent sysvar.
 pub const RENT_ID: Address = Address::new_from_array([
@@ -90,7 +90,7 @@ impl Rent {
     ///
     /// This method performs a check on the account view key.
     #[inline]
-    pub fn from_account_view(account_view: &AccountView) -> Result<Ref<Rent>> {
+    pub fn from_account_view(account_view: &AccountView) -> Result<Ref<'_, Rent>> {
         if unlikely(account_view.address() != &RENT_ID) {
             return Err(ProgramError::InvalidArgument);
         }
@@ -259,6 +259,12 @@ impl Rent {
         Ok(self.minimum_balance_unchecked(data_len))
     }
 
+    /// [`Rent::try_minimum_balance`] as [`Lamports`], what the transfer and sweep helpers take.
+    #[inline(always)]
+    pub fn try_minimum_lamports(&self, data_len: usize) -> Result<Lamports> {
+        self.try_minimum_balance(data_len).map(Lamports::new)
+    }
+
     /// Determines if an account can be considered rent exempt.
     ///
     /// # Arguments
@@ -275,8 +281,30 @@ impl Rent {
     }
 }
 
+impl SysvarAccountData for Rent {
+    const ID: Address = RENT_ID;
+
+    type Data<'a> = Ref<'a, Rent>;
+
+    #[inline(always)]
+    fn from_sysvar_account(account_view: &AccountView) -> Result<Self::Data<'_>> {
+        Self::from_account_view(account_view)
+    }
+}
+
 impl Sysvar for Rent {
-    impl_sysvar_get!(RENT_ID, 0);
+    #[cfg(target_os = "solana")]
+    crate::impl_sysvar_get!(RENT_ID, 0);
+
+    /// Off chain there is no sysvar to read, the default rent lets host tests create and resize
+    /// accounts.
+    #[cfg(not(target_os = "solana"))]
+    fn get() -> Result<Self> {
+        Ok(Rent {
+            lamports_per_byte: DEFAULT_LAMPORTS_PER_BYTE,
+            exemption_threshold: CURRENT_EXEMPTION_THRESHOLD,
+        })
+    }
 }
 
 #[cfg(test)]
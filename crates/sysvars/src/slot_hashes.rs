@@ -0,0 +1,121 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use hayabusa_common::{AccountView, Address, Ref};
+use hayabusa_errors::{ProgramError, Result};
+use hayabusa_utility::hint::unlikely;
+
+use crate::SysvarAccountData;
+
+/// The ID of the slot hashes sysvar.
+pub const SLOT_HASHES_ID: Address = Address::new_from_array([
+    6, 167, 213, 23, 25, 47, 10, 175, 198, 242, 101, 227, 251, 119, 204, 122, 218, 130, 197, 41,
+    208, 190, 59, 19, 110, 45, 0, 85, 32, 0, 0, 0,
+]);
+
+/// Maximum number of entries kept by the runtime.
+pub const MAX_ENTRIES: usize = 512;
+
+/// Bank hash of a recent slot.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct SlotHash {
+    slot: [u8; 8],
+    pub hash: [u8; 32],
+}
+
+impl SlotHash {
+    #[inline(always)]
+    pub fn slot(&self) -> u64 {
+        u64::from_le_bytes(self.slot)
+    }
+}
+
+/// View over the slot hashes sysvar account, entries are sorted by slot, most recent first.
+///
+/// The sysvar doesn't support `sol_get_sysvar`, the account has to be passed to the program.
+pub struct SlotHashes<'a> {
+    data: Ref<'a, [u8]>,
+}
+
+impl<'a> SlotHashes<'a> {
+    /// Length of the entry count prefix.
+    pub const PREFIX_LEN: usize = 8;
+
+    /// Return the `SlotHashes` of the given account view.
+    ///
+    /// This method performs a check on the account view address and on the data length.
+    #[inline]
+    pub fn from_account_view(account_view: &'a AccountView) -> Result<Self> {
+        if unlikely(account_view.address() != &SLOT_HASHES_ID) {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let data = account_view.try_borrow()?;
+
+        if unlikely(data.len() < Self::PREFIX_LEN) {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+
+        let len = u64::from_le_bytes(data[..Self::PREFIX_LEN].try_into().unwrap()) as usize;
+
+        if unlikely(
+            len > MAX_ENTRIES
+                || data.len() < Self::PREFIX_LEN + len * core::mem::size_of::<SlotHash>(),
+        ) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self { data })
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        u64::from_le_bytes(self.data[..Self::PREFIX_LEN].try_into().unwrap()) as usize
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// All entries, most recent first.
+    #[inline(always)]
+    pub fn entries(&self) -> &[SlotHash] {
+        // SAFETY: the length was validated on construction and `SlotHash` has an alignment of 1
+        unsafe {
+            core::slice::from_raw_parts(
+                self.data[Self::PREFIX_LEN..].as_ptr() as *const SlotHash,
+                self.len(),
+            )
+        }
+    }
+
+    /// Entry of the most recent slot, i.e. the parent of the current slot.
+    #[inline(always)]
+    pub fn latest(&self) -> Option<&SlotHash> {
+        self.entries().first()
+    }
+
+    /// Hash of `slot`, `None` if the slot was skipped or is no longer part of the sysvar.
+    #[inline]
+    pub fn get(&self, slot: u64) -> Option<&[u8; 32]> {
+        let entries = self.entries();
+
+        entries
+            .binary_search_by(|entry| slot.cmp(&entry.slot()))
+            .ok()
+            .map(|index| &entries[index].hash)
+    }
+}
+
+impl SysvarAccountData for SlotHashes<'_> {
+    const ID: Address = SLOT_HASHES_ID;
+
+    type Data<'a> = SlotHashes<'a>;
+
+    #[inline(always)]
+    fn from_sysvar_account(account_view: &AccountView) -> Result<Self::Data<'_>> {
+        SlotHashes::from_account_view(account_view)
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use core::ops::Deref;
+use hayabusa_accounts::{FromAccountView, NoMeta, ToAccountView, WritableAllowed};
+use hayabusa_common::{address_eq, AccountView, Ref};
+use hayabusa_errors::{ProgramError, Result};
+use hayabusa_ser::FromBytesUnchecked;
+use hayabusa_token::state::{Mint, TokenAccount};
+use hayabusa_token2022::state::{is_valid_data, ACCOUNT_TYPE_ACCOUNT, ACCOUNT_TYPE_MINT};
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+/// Token state whose base layout is shared by the token and the token-2022 program.
+///
+/// # Safety
+/// `LEN` must be the size of the base layout and `ACCOUNT_TYPE` the token-2022 account type
+/// written after it when the account has extensions.
+pub unsafe trait InterfaceState: FromBytesUnchecked {
+    const LEN: usize;
+    const ACCOUNT_TYPE: u8;
+}
+
+unsafe impl InterfaceState for TokenAccount {
+    const LEN: usize = TokenAccount::LEN;
+    const ACCOUNT_TYPE: u8 = ACCOUNT_TYPE_ACCOUNT;
+}
+
+unsafe impl InterfaceState for Mint {
+    const LEN: usize = Mint::LEN;
+    const ACCOUNT_TYPE: u8 = ACCOUNT_TYPE_MINT;
+}
+
+/// Token account or mint owned by either token program.
+///
+/// Token-2022 accounts may carry extensions after the base layout, only the base layout is
+/// exposed through [`InterfaceAccount::try_deserialize`].
+pub struct InterfaceAccount<'ix, T>
+where
+    T: InterfaceState,
+{
+    pub account_view: &'ix AccountView,
+    _phantom: core::marker::PhantomData<T>,
+}
+
+impl<'ix, T> InterfaceAccount<'ix, T>
+where
+    T: InterfaceState,
+{
+    #[inline(always)]
+    pub fn try_deserialize(&self) -> Result<Ref<'ix, T>> {
+        Ok(Ref::map(self.account_view.try_borrow()?, |d| unsafe {
+            T::from_bytes_unchecked(d)
+        }))
+    }
+
+    #[inline(always)]
+    pub fn is_token_2022(&self) -> bool {
+        self.account_view.owned_by(&hayabusa_token2022::ID)
+    }
+}
+
+impl<'ix, T> FromAccountView<'ix> for InterfaceAccount<'ix, T>
+where
+    T: InterfaceState,
+{
+    type Meta<'a>
+        = NoMeta
+    where
+        'ix: 'a;
+
+    #[inline(always)]
+    fn try_from_account_view<'a>(account_view: &'ix AccountView, _: Self::Meta<'a>) -> Result<Self>
+    where
+        'ix: 'a,
+    {
+        // SAFETY: the owner is only read
+        let owner = unsafe { account_view.owner() };
+
+        let valid = if address_eq(owner, &hayabusa_token::ID) {
+            account_view.data_len() == T::LEN
+        } else if address_eq(owner, &hayabusa_token2022::ID) {
+            is_valid_data(&account_view.try_borrow()?, T::LEN, T::ACCOUNT_TYPE)
+        } else {
+            error_msg!(
+                "InterfaceAccount::try_from_account_view: invalid owner",
+                ProgramError::InvalidAccountOwner,
+            );
+        };
+
+        if unlikely(!valid) {
+            error_msg!(
+                "InterfaceAccount::try_from_account_view: invalid account data",
+                ProgramError::InvalidAccountData,
+            );
+        }
+
+        Ok(InterfaceAccount {
+            account_view,
+            _phantom: core::marker::PhantomData,
+        })
+    }
+}
+
+impl<T> ToAccountView for InterfaceAccount<'_, T>
+where
+    T: InterfaceState,
+{
+    #[inline(always)]
+    fn to_account_view(&self) -> &AccountView {
+        self.account_view
+    }
+}
+
+impl<T> WritableAllowed for InterfaceAccount<'_, T> where T: InterfaceState {}
+
+impl<T> Deref for InterfaceAccount<'_, T>
+where
+    T: InterfaceState,
+{
+    type Target = AccountView;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.account_view
+    }
+}
@@ -3,6 +3,10 @@
 
 #![no_std]
 
+mod interface_account;
+
+pub use interface_account::*;
+
 use hayabusa_accounts::ProgramIds;
 use hayabusa_common::Address;
 
@@ -0,0 +1,68 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{DecryptableBalance, EXTENSION_DISCRIMINATOR};
+use core::slice::from_raw_parts;
+use hayabusa_common::{AccountView, Address};
+use hayabusa_cpi::{CheckProgramId, CpiCtx};
+use hayabusa_errors::Result;
+use hayabusa_utility::{write_uninit_bytes, UNINIT_BYTE};
+use solana_instruction_view::{
+    cpi::{invoke, invoke_signed},
+    InstructionAccount, InstructionView,
+};
+
+pub struct ApplyPendingBalance<'ix> {
+    /// Token account
+    pub account: &'ix AccountView,
+    /// Owner of the token account
+    pub owner: &'ix AccountView,
+}
+
+impl CheckProgramId for ApplyPendingBalance<'_> {
+    const ID: Address = crate::ID;
+}
+
+const DISCRIMINATOR: [u8; 2] = [EXTENSION_DISCRIMINATOR, 8];
+
+/// Adds the pending balance to the available balance.
+///
+/// `expected_pending_balance_credit_counter` is the number of credits the owner decrypted to
+/// compute `new_decryptable_available_balance`, credits received since then stay pending.
+#[inline(always)]
+pub fn apply_pending_balance<'ix>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, ApplyPendingBalance<'ix>>,
+    expected_pending_balance_credit_counter: u64,
+    new_decryptable_available_balance: &DecryptableBalance,
+) -> Result<()> {
+    let account_views = [cpi_ctx.account, cpi_ctx.owner];
+    let instruction_accounts = [
+        InstructionAccount::writable(cpi_ctx.account.address()),
+        InstructionAccount::readonly_signer(cpi_ctx.owner.address()),
+    ];
+
+    // ix data layout
+    // - [0..2]: discriminator
+    // - [2..10]: expected pending balance credit counter
+    // - [10..46]: new decryptable available balance
+    let mut ix_data = [UNINIT_BYTE; 46];
+
+    write_uninit_bytes(&mut ix_data, &DISCRIMINATOR);
+    write_uninit_bytes(
+        &mut ix_data[2..10],
+        &expected_pending_balance_credit_counter.to_le_bytes(),
+    );
+    write_uninit_bytes(&mut ix_data[10..], new_decryptable_available_balance);
+
+    let instruction_view = InstructionView {
+        program_id: &crate::ID,
+        accounts: &instruction_accounts,
+        data: unsafe { from_raw_parts(ix_data.as_ptr() as _, 46) },
+    };
+
+    if let Some(signers) = cpi_ctx.signers {
+        invoke_signed(&instruction_view, &account_views, signers)
+    } else {
+        invoke(&instruction_view, &account_views)
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::EXTENSION_DISCRIMINATOR;
+use hayabusa_common::{AccountView, Address};
+use hayabusa_cpi::{CheckProgramId, CpiCtx};
+use hayabusa_errors::Result;
+use solana_instruction_view::{
+    cpi::{invoke, invoke_signed},
+    InstructionAccount, InstructionView,
+};
+
+pub struct ApproveAccount<'ix> {
+    /// Token account to approve
+    pub account: &'ix AccountView,
+    /// Mint account
+    pub mint: &'ix AccountView,
+    /// Confidential transfer authority of the mint
+    pub authority: &'ix AccountView,
+}
+
+impl CheckProgramId for ApproveAccount<'_> {
+    const ID: Address = crate::ID;
+}
+
+const DISCRIMINATOR: [u8; 2] = [EXTENSION_DISCRIMINATOR, 3];
+
+/// Approves a configured account for confidential transfers, needed when the mint doesn't
+/// auto-approve new accounts.
+#[inline(always)]
+pub fn approve_account<'ix>(cpi_ctx: CpiCtx<'ix, '_, '_, '_, ApproveAccount<'ix>>) -> Result<()> {
+    let account_views = [cpi_ctx.account, cpi_ctx.mint, cpi_ctx.authority];
+    let instruction_accounts = [
+        InstructionAccount::writable(cpi_ctx.account.address()),
+        InstructionAccount::readonly(cpi_ctx.mint.address()),
+        InstructionAccount::readonly_signer(cpi_ctx.authority.address()),
+    ];
+
+    let instruction_view = InstructionView {
+        program_id: &crate::ID,
+        accounts: &instruction_accounts,
+        data: &DISCRIMINATOR,
+    };
+
+    if let Some(signers) = cpi_ctx.signers {
+        invoke_signed(&instruction_view, &account_views, signers)
+    } else {
+        invoke(&instruction_view, &account_views)
+    }
+}
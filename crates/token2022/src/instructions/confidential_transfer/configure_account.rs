@@ -0,0 +1,83 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{DecryptableBalance, EXTENSION_DISCRIMINATOR};
+use core::slice::from_raw_parts;
+use hayabusa_common::{AccountView, Address};
+use hayabusa_cpi::{CheckProgramId, CpiCtx};
+use hayabusa_errors::Result;
+use hayabusa_utility::{write_uninit_bytes, UNINIT_BYTE};
+use solana_instruction_view::{
+    cpi::{invoke, invoke_signed},
+    InstructionAccount, InstructionView,
+};
+
+pub struct ConfigureAccount<'ix> {
+    /// Token account to configure, its `ConfidentialTransferAccount` extension must have room
+    pub account: &'ix AccountView,
+    /// Mint account
+    pub mint: &'ix AccountView,
+    /// Instructions sysvar when the pubkey validity proof is in the same transaction, otherwise
+    /// the context state account holding the verified proof
+    pub proof_account: &'ix AccountView,
+    /// Owner of the token account
+    pub owner: &'ix AccountView,
+}
+
+impl CheckProgramId for ConfigureAccount<'_> {
+    const ID: Address = crate::ID;
+}
+
+const DISCRIMINATOR: [u8; 2] = [EXTENSION_DISCRIMINATOR, 2];
+
+/// Configures an account for confidential transfers.
+///
+/// `proof_instruction_offset` is the position of the `VerifyPubkeyValidity` instruction relative
+/// to this one, or 0 when `proof_account` is a context state account.
+#[inline(always)]
+pub fn configure_account<'ix>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, ConfigureAccount<'ix>>,
+    decryptable_zero_balance: &DecryptableBalance,
+    maximum_pending_balance_credit_counter: u64,
+    proof_instruction_offset: i8,
+) -> Result<()> {
+    let account_views = [
+        cpi_ctx.account,
+        cpi_ctx.mint,
+        cpi_ctx.proof_account,
+        cpi_ctx.owner,
+    ];
+    let instruction_accounts = [
+        InstructionAccount::writable(cpi_ctx.account.address()),
+        InstructionAccount::readonly(cpi_ctx.mint.address()),
+        InstructionAccount::readonly(cpi_ctx.proof_account.address()),
+        InstructionAccount::readonly_signer(cpi_ctx.owner.address()),
+    ];
+
+    // ix data layout
+    // - [0..2]: discriminator
+    // - [2..38]: decryptable zero balance
+    // - [38..46]: maximum pending balance credit counter
+    // - [46]: proof instruction offset
+    let mut ix_data = [UNINIT_BYTE; 47];
+
+    write_uninit_bytes(&mut ix_data, &DISCRIMINATOR);
+    write_uninit_bytes(&mut ix_data[2..38], decryptable_zero_balance);
+    write_uninit_bytes(
+        &mut ix_data[38..46],
+        &maximum_pending_balance_credit_counter.to_le_bytes(),
+    );
+    write_uninit_bytes(&mut ix_data[46..], &proof_instruction_offset.to_le_bytes());
+
+    let instruction_view = InstructionView {
+        program_id: &crate::ID,
+        accounts: &instruction_accounts,
+        data: unsafe { from_raw_parts(ix_data.as_ptr() as _, 47) },
+    };
+
+    if let Some(signers) = cpi_ctx.signers {
+        invoke_signed(&instruction_view, &account_views, signers)
+    } else {
+        invoke(&instruction_view, &account_views)
+    }
+}
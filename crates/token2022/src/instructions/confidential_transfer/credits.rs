@@ -0,0 +1,80 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::EXTENSION_DISCRIMINATOR;
+use hayabusa_common::{AccountView, Address};
+use hayabusa_cpi::{CheckProgramId, CpiCtx};
+use hayabusa_errors::Result;
+use solana_instruction_view::{
+    cpi::{invoke, invoke_signed},
+    InstructionAccount, InstructionView,
+};
+
+pub struct UpdateCredits<'ix> {
+    /// Token account
+    pub account: &'ix AccountView,
+    /// Owner of the token account
+    pub owner: &'ix AccountView,
+}
+
+impl CheckProgramId for UpdateCredits<'_> {
+    const ID: Address = crate::ID;
+}
+
+/// Lets the account receive confidential transfers.
+#[inline(always)]
+pub fn enable_confidential_credits<'ix>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, UpdateCredits<'ix>>,
+) -> Result<()> {
+    update_credits(cpi_ctx, 9)
+}
+
+/// Rejects incoming confidential transfers, the account can still send them.
+#[inline(always)]
+pub fn disable_confidential_credits<'ix>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, UpdateCredits<'ix>>,
+) -> Result<()> {
+    update_credits(cpi_ctx, 10)
+}
+
+/// Lets the account receive regular transfers.
+#[inline(always)]
+pub fn enable_non_confidential_credits<'ix>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, UpdateCredits<'ix>>,
+) -> Result<()> {
+    update_credits(cpi_ctx, 11)
+}
+
+/// Rejects incoming regular transfers, so the balance only moves confidentially.
+#[inline(always)]
+pub fn disable_non_confidential_credits<'ix>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, UpdateCredits<'ix>>,
+) -> Result<()> {
+    update_credits(cpi_ctx, 12)
+}
+
+#[inline(always)]
+fn update_credits<'ix>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, UpdateCredits<'ix>>,
+    instruction: u8,
+) -> Result<()> {
+    let account_views = [cpi_ctx.account, cpi_ctx.owner];
+    let instruction_accounts = [
+        InstructionAccount::writable(cpi_ctx.account.address()),
+        InstructionAccount::readonly_signer(cpi_ctx.owner.address()),
+    ];
+
+    let data = [EXTENSION_DISCRIMINATOR, instruction];
+
+    let instruction_view = InstructionView {
+        program_id: &crate::ID,
+        accounts: &instruction_accounts,
+        data: &data,
+    };
+
+    if let Some(signers) = cpi_ctx.signers {
+        invoke_signed(&instruction_view, &account_views, signers)
+    } else {
+        invoke(&instruction_view, &account_views)
+    }
+}
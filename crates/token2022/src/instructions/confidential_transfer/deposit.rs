@@ -0,0 +1,66 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::EXTENSION_DISCRIMINATOR;
+use core::slice::from_raw_parts;
+use hayabusa_common::{AccountView, Address};
+use hayabusa_cpi::{CheckProgramId, CpiCtx};
+use hayabusa_errors::Result;
+use hayabusa_utility::{write_uninit_bytes, UNINIT_BYTE};
+use solana_instruction_view::{
+    cpi::{invoke, invoke_signed},
+    InstructionAccount, InstructionView,
+};
+
+pub struct Deposit<'ix> {
+    /// Token account
+    pub account: &'ix AccountView,
+    /// Mint account
+    pub mint: &'ix AccountView,
+    /// Owner of the token account
+    pub owner: &'ix AccountView,
+}
+
+impl CheckProgramId for Deposit<'_> {
+    const ID: Address = crate::ID;
+}
+
+const DISCRIMINATOR: [u8; 2] = [EXTENSION_DISCRIMINATOR, 5];
+
+/// Moves `amount` from the public balance of the account to its pending confidential balance,
+/// see `apply_pending_balance`.
+#[inline(always)]
+pub fn deposit<'ix>(
+    cpi_ctx: CpiCtx<'ix, '_, '_, '_, Deposit<'ix>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let account_views = [cpi_ctx.account, cpi_ctx.mint, cpi_ctx.owner];
+    let instruction_accounts = [
+        InstructionAccount::writable(cpi_ctx.account.address()),
+        InstructionAccount::readonly(cpi_ctx.mint.address()),
+        InstructionAccount::readonly_signer(cpi_ctx.owner.address()),
+    ];
+
+    // ix data layout
+    // - [0..2]: discriminator
+    // - [2..10]: amount
+    // - [10]: decimals
+    let mut ix_data = [UNINIT_BYTE; 11];
+
+    write_uninit_bytes(&mut ix_data, &DISCRIMINATOR);
+    write_uninit_bytes(&mut ix_data[2..10], &amount.to_le_bytes());
+    write_uninit_bytes(&mut ix_data[10..], &[decimals]);
+
+    let instruction_view = InstructionView {
+        program_id: &crate::ID,
+        accounts: &instruction_accounts,
+        data: unsafe { from_raw_parts(ix_data.as_ptr() as _, 11) },
+    };
+
+    if let Some(signers) = cpi_ctx.signers {
+        invoke_signed(&instruction_view, &account_views, signers)
+    } else {
+        invoke(&instruction_view, &account_views)
+    }
+}
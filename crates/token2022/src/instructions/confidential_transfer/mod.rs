@@ -0,0 +1,90 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Confidential transfer extension instructions that need no zero-knowledge proof from the
+//! program: account setup, approval, public deposits and toggling the credits an account
+//! accepts. Proofs for `configure_account` are verified beforehand by the ZK ElGamal proof
+//! program, amounts moved confidentially (withdraw, transfer) are out of scope.
+
+pub mod apply_pending_balance;
+pub mod approve_account;
+pub mod configure_account;
+pub mod credits;
+pub mod deposit;
+
+pub use apply_pending_balance::*;
+pub use approve_account::*;
+pub use configure_account::*;
+pub use credits::*;
+pub use deposit::*;
+
+/// `TokenInstruction::ConfidentialTransferExtension`
+const EXTENSION_DISCRIMINATOR: u8 = 27;
+
+/// Balance encrypted under the authenticated encryption key of the owner, for the owner only.
+pub type DecryptableBalance = [u8; 36];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hayabusa_common::{mock::MockAccount, AccountView, Address};
+    use hayabusa_cpi::{CheckProgramId, CpiCtx};
+
+    fn mock(seed: u8) -> MockAccount {
+        MockAccount::new(Address::new_from_array([seed; 32]), crate::ID, &[0; 8])
+    }
+
+    fn ctx<'ix, T: CheckProgramId>(
+        program: &'ix AccountView,
+        accounts: T,
+    ) -> CpiCtx<'ix, 'static, 'static, 'static, T> {
+        CpiCtx::try_new_without_signer(program, accounts).unwrap()
+    }
+
+    #[test]
+    fn test_lifecycle() {
+        let mut program = MockAccount::new(crate::ID, Address::default(), &[]);
+        let (mut account, mut mint, mut proof, mut owner) = (mock(1), mock(2), mock(3), mock(4));
+        let program = program.view();
+        let (account, mint, proof, owner) =
+            (&account.view(), &mint.view(), &proof.view(), &owner.view());
+
+        let configure = ConfigureAccount {
+            account,
+            mint,
+            proof_account: proof,
+            owner,
+        };
+        configure_account(ctx(&program, configure), &[0; 36], 65536, 1).unwrap();
+
+        let approve = ApproveAccount {
+            account,
+            mint,
+            authority: owner,
+        };
+        approve_account(ctx(&program, approve)).unwrap();
+
+        deposit(ctx(&program, Deposit { account, mint, owner }), 100, 6).unwrap();
+
+        let apply = ApplyPendingBalance { account, owner };
+        apply_pending_balance(ctx(&program, apply), 1, &[0; 36]).unwrap();
+
+        for update in [
+            enable_confidential_credits,
+            disable_confidential_credits,
+            enable_non_confidential_credits,
+            disable_non_confidential_credits,
+        ] {
+            update(ctx(&program, UpdateCredits { account, owner })).unwrap();
+        }
+
+        // the token account is written, the caller can't hold a borrow of it during the CPI
+        let borrowed = account.try_borrow().unwrap();
+        assert!(deposit(ctx(&program, Deposit { account, mint, owner }), 100, 6).is_err());
+        drop(borrowed);
+
+        // another program
+        let accounts = UpdateCredits { account, owner };
+        assert!(CpiCtx::try_new_without_signer(mint, accounts).is_err());
+    }
+}
@@ -3,6 +3,7 @@
 
 pub mod burn;
 pub mod burn_checked;
+pub mod confidential_transfer;
 pub mod initialize_account3;
 pub mod initialize_mint2;
 pub mod mint_to;
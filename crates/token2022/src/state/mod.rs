@@ -7,3 +7,20 @@ pub mod multisig;
 pub mod token_account;
 
 use account_state::AccountState;
+
+/// Length of a token account, extended accounts (mints included) store their account type
+/// right after it, followed by the extensions.
+pub const BASE_ACCOUNT_LEN: usize = 165;
+
+pub const ACCOUNT_TYPE_MINT: u8 = 1;
+pub const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+/// Whether `data` is a `base_len` layout, optionally extended with extensions of
+/// `account_type`.
+#[inline(always)]
+pub fn is_valid_data(data: &[u8], base_len: usize, account_type: u8) -> bool {
+    data.len() == base_len
+        || (data.len() > BASE_ACCOUNT_LEN
+            && data.len() != multisig::Multisig::LEN
+            && data[BASE_ACCOUNT_LEN] == account_type)
+}
@@ -1,7 +1,7 @@
 // Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
 // SPDX-License-Identifier: Apache-2.0
 
-use super::AccountState;
+use super::{is_valid_data, AccountState, ACCOUNT_TYPE_ACCOUNT};
 use hayabusa_common::{AccountView, Address, Ref};
 use hayabusa_errors::{ProgramError, Result};
 use hayabusa_ser::{
@@ -56,45 +56,48 @@ impl Deserialize for TokenAccount {}
 
 unsafe impl RawZcDeserialize for TokenAccount {
     #[inline]
-    fn try_deserialize_raw(account_view: &AccountView) -> Result<Ref<Self>> {
-        if unlikely(account_view.data_len() != Self::LEN) {
+    fn try_deserialize_raw(account_view: &AccountView) -> Result<Ref<'_, Self>> {
+        if unlikely(!account_view.owned_by(&crate::ID)) {
             error_msg!(
-                "TokenAccount::try_deserialize_raw: data length mismatch",
-                ProgramError::InvalidAccountData,
+                "TokenAccount::try_deserialize_raw: invalid owner",
+                ProgramError::InvalidAccountOwner,
             );
         }
 
-        if unlikely(!account_view.owned_by(&crate::ID)) {
+        let data = account_view.try_borrow()?;
+
+        // accounts with extensions are longer than the base layout
+        if unlikely(!is_valid_data(&data, Self::LEN, ACCOUNT_TYPE_ACCOUNT)) {
             error_msg!(
-                "TokenAccount::try_deserialize_raw: invalid owner",
-                ProgramError::InvalidAccountOwner,
+                "TokenAccount::try_deserialize_raw: data length mismatch",
+                ProgramError::InvalidAccountData,
             );
         }
 
-        Ok(Ref::map(account_view.try_borrow()?, |d| unsafe {
-            Self::from_bytes_unchecked(d)
-        }))
+        Ok(Ref::map(data, |d| unsafe { Self::from_bytes_unchecked(d) }))
     }
 }
 
 impl RawZcDeserializeUnchecked for TokenAccount {
     #[inline(always)]
     unsafe fn try_deserialize_raw_unchecked(account_view: &AccountView) -> Result<&Self> {
-        if unlikely(account_view.data_len() != Self::LEN) {
+        if unlikely(!account_view.owned_by(&crate::ID)) {
             error_msg!(
-                "TokenAccount::try_deserialize_raw_unchecked: data length mismatch",
-                ProgramError::InvalidAccountData,
+                "TokenAccount::try_deserialize_raw_unchecked: invalid owner",
+                ProgramError::InvalidAccountOwner,
             );
         }
 
-        if unlikely(!account_view.owned_by(&crate::ID)) {
+        let data = account_view.borrow_unchecked();
+
+        if unlikely(!is_valid_data(data, Self::LEN, ACCOUNT_TYPE_ACCOUNT)) {
             error_msg!(
-                "TokenAccount::try_deserialize_raw_unchecked: invalid owner",
-                ProgramError::InvalidAccountOwner,
+                "TokenAccount::try_deserialize_raw_unchecked: data length mismatch",
+                ProgramError::InvalidAccountData,
             );
         }
 
-        Ok(Self::from_bytes_unchecked(account_view.borrow_unchecked()))
+        Ok(Self::from_bytes_unchecked(data))
     }
 }
 
@@ -0,0 +1,148 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Expr, ItemStruct, Result};
+
+/// Per-user stats accounts, i.e. a PDA of `[seed, user]` owned by the program, created on the
+/// first interaction of a wallet and updated on every following one.
+///
+/// ```ignore
+/// #[user_stats(seed = b"trader_stats")]
+/// #[account]
+/// #[derive(OwnerProgram)]
+/// pub struct TraderStats {
+///     pub volume: u64,
+///     pub trades: u64,
+/// }
+/// ```
+///
+/// The struct is left untouched (it still needs `#[account]` and `OwnerProgram`), the macro adds:
+///
+/// - `USER_STATS_SEED` and `find_user_stats_address(user)`
+/// - `try_check_user_stats_address(account_view, user)`, returning the canonical bump
+/// - `try_load_or_init(account_view, user, init_accounts)`, creating the zeroed account if it
+///   doesn't exist yet
+/// - `try_update(account_view, user, f)` and `try_upsert(account_view, user, init_accounts, f)`
+///
+/// The PDA is derived under the `OwnerProgram` of the struct.
+#[proc_macro_attribute]
+pub fn user_stats(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut seed: Option<Expr> = None;
+
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("seed") {
+            seed = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported user_stats argument, expected `seed = b\"...\"`"))
+        }
+    });
+
+    parse_macro_input!(attr with parser);
+
+    let input = parse_macro_input!(item as ItemStruct);
+
+    let Some(seed) = seed else {
+        return syn::Error::new_spanned(
+            &input.ident,
+            "#[user_stats] requires a seed, e.g. #[user_stats(seed = b\"stats\")]",
+        )
+        .to_compile_error()
+        .into();
+    };
+
+    match expand_user_stats(input, seed) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand_user_stats(input: ItemStruct, seed: Expr) -> Result<proc_macro2::TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "#[user_stats] does not support generics",
+        ));
+    }
+
+    let ident = &input.ident;
+
+    Ok(quote! {
+        #input
+
+        impl #ident {
+            /// Seed prefix of the stats PDA, the full seeds are `[USER_STATS_SEED, user]`
+            pub const USER_STATS_SEED: &'static [u8] = #seed;
+
+            #[inline(always)]
+            pub fn find_user_stats_address(user: &Address) -> Result<(Address, u8)> {
+                syscalls::try_find_program_address(
+                    &[Self::USER_STATS_SEED, user.as_ref()],
+                    &<Self as OwnerProgram>::OWNER,
+                )
+            }
+
+            /// Fails unless `account_view` is the stats PDA of `user`, returns the canonical bump
+            #[inline(always)]
+            pub fn try_check_user_stats_address(account_view: &AccountView, user: &Address) -> Result<u8> {
+                let (_, bump) = check_seeds_against_addr_no_bump(
+                    &[Self::USER_STATS_SEED, user.as_ref()],
+                    account_view.address(),
+                    &<Self as OwnerProgram>::OWNER,
+                )?;
+
+                Ok(bump)
+            }
+
+            /// Stats of `user`, the account is created zeroed (rent paid by
+            /// `init_accounts.payer_account`) if it doesn't exist yet
+            #[inline(always)]
+            pub fn try_load_or_init<'ix>(
+                account_view: &'ix AccountView,
+                user: &Address,
+                init_accounts: InitAccounts<'ix, '_>,
+            ) -> Result<RefMut<'ix, Self>> {
+                let bump = Self::try_check_user_stats_address(account_view, user)?;
+
+                let bump_seed = [bump];
+                let signer_seeds = [
+                    instruction::Seed::from(Self::USER_STATS_SEED),
+                    instruction::Seed::from(user.as_ref()),
+                    instruction::Seed::from(&bump_seed),
+                ];
+                let signers = [instruction::PdaSigner::from(&signer_seeds)];
+
+                try_initialize_zc_if_needed::<Self>(account_view, init_accounts, Some(&signers))
+            }
+
+            /// Applies `update` to the existing stats of `user`
+            #[inline(always)]
+            pub fn try_update<R>(
+                account_view: &AccountView,
+                user: &Address,
+                update: impl FnOnce(&mut Self) -> R,
+            ) -> Result<R> {
+                Self::try_check_user_stats_address(account_view, user)?;
+
+                let mut stats = try_deserialize_zc_mut::<Self>(account_view)?;
+
+                Ok(update(&mut stats))
+            }
+
+            /// [`Self::try_load_or_init`] followed by `update`
+            #[inline(always)]
+            pub fn try_upsert<'ix, R>(
+                account_view: &'ix AccountView,
+                user: &Address,
+                init_accounts: InitAccounts<'ix, '_>,
+                update: impl FnOnce(&mut Self) -> R,
+            ) -> Result<R> {
+                let mut stats = Self::try_load_or_init(account_view, user, init_accounts)?;
+
+                Ok(update(&mut stats))
+            }
+        }
+    })
+}
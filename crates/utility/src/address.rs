@@ -0,0 +1,65 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use solana_address::Address;
+
+/// Byte by byte equality usable in const context, e.g. to check ids at compile time.
+///
+/// Prefer `address_eq` at runtime, it compares words.
+#[inline(always)]
+pub const fn address_eq_const(a: &Address, b: &Address) -> bool {
+    address_common_prefix_len(a, b) == 32
+}
+
+/// Whether `address` starts with the bytes of `prefix`, e.g. for vanity ids.
+#[inline(always)]
+pub const fn address_starts_with(address: &Address, prefix: &[u8]) -> bool {
+    let bytes = address.as_array();
+    if prefix.len() > bytes.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < prefix.len() {
+        if bytes[i] != prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+/// Number of leading bytes `a` and `b` share, 32 when equal.
+#[inline(always)]
+pub const fn address_common_prefix_len(a: &Address, b: &Address) -> usize {
+    let (a, b) = (a.as_array(), b.as_array());
+
+    let mut i = 0;
+    while i < a.len() && a[i] == b[i] {
+        i += 1;
+    }
+
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_prefix() {
+        let a = Address::new_from_array([7; 32]);
+        let mut bytes = [7; 32];
+        bytes[3] = 0;
+        let b = Address::new_from_array(bytes);
+
+        assert!(address_eq_const(&a, &a));
+        assert!(!address_eq_const(&a, &b));
+        assert_eq!(address_common_prefix_len(&a, &b), 3);
+        assert!(address_starts_with(&b, &[7, 7, 7, 0]));
+        assert!(!address_starts_with(&b, &[7, 7, 7, 7]));
+        assert!(address_starts_with(&a, &[]));
+        assert!(!address_starts_with(&a, &[7; 33]));
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use hayabusa_errors::Result;
+use solana_program_error::ProgramError;
+
+use crate::hint::unlikely;
+
+/// Value stored in a `#[bitfield]` struct.
+///
+/// Implemented for `bool` and the unsigned integers, and generated by `#[bitfield]` on fieldless
+/// enums.
+pub trait BitfieldValue: Sized {
+    /// Bits needed to store every value.
+    const BITS: u32;
+
+    fn into_bits(self) -> u64;
+
+    /// Fails if `bits` is not a valid value, e.g. an unknown enum discriminant.
+    fn try_from_bits(bits: u64) -> Result<Self>;
+}
+
+impl BitfieldValue for bool {
+    const BITS: u32 = 1;
+
+    #[inline(always)]
+    fn into_bits(self) -> u64 {
+        self as u64
+    }
+
+    #[inline(always)]
+    fn try_from_bits(bits: u64) -> Result<Self> {
+        Ok(bits != 0)
+    }
+}
+
+macro_rules! impl_bitfield_value_int {
+    ($t:ty) => {
+        impl BitfieldValue for $t {
+            const BITS: u32 = <$t>::BITS;
+
+            #[inline(always)]
+            fn into_bits(self) -> u64 {
+                self as u64
+            }
+
+            #[inline(always)]
+            fn try_from_bits(bits: u64) -> Result<Self> {
+                Ok(bits as $t)
+            }
+        }
+    };
+}
+
+impl_bitfield_value_int!(u8);
+impl_bitfield_value_int!(u16);
+impl_bitfield_value_int!(u32);
+impl_bitfield_value_int!(u64);
+
+/// Bits needed to store `max`, at least 1.
+#[doc(hidden)]
+pub const fn bits_for(max: u64) -> u32 {
+    if max == 0 {
+        1
+    } else {
+        u64::BITS - max.leading_zeros()
+    }
+}
+
+/// Mask of the `width` low bits.
+#[doc(hidden)]
+#[inline(always)]
+pub const fn bit_mask(width: u32) -> u64 {
+    if width >= u64::BITS {
+        u64::MAX
+    } else {
+        (1 << width) - 1
+    }
+}
+
+/// Reads `width` bits at `offset` of a little endian word.
+#[doc(hidden)]
+#[inline(always)]
+pub fn read_bits(word: &[u8; 8], offset: u32, width: u32) -> u64 {
+    (u64::from_le_bytes(*word) >> offset) & bit_mask(width)
+}
+
+/// Writes the `width` low bits of `value` at `offset` of a little endian word, fails if `value`
+/// doesn't fit.
+#[doc(hidden)]
+#[inline(always)]
+pub fn try_write_bits(word: &mut [u8; 8], offset: u32, width: u32, value: u64) -> Result<()> {
+    if unlikely(value & !bit_mask(width) != 0) {
+        error_msg!(
+            "try_write_bits: value does not fit in the field",
+            ProgramError::InvalidArgument,
+        );
+    }
+
+    write_bits(word, offset, width, value);
+
+    Ok(())
+}
+
+/// Writes the `width` low bits of `value` at `offset` of a little endian word, higher bits are
+/// dropped.
+#[doc(hidden)]
+#[inline(always)]
+pub fn write_bits(word: &mut [u8; 8], offset: u32, width: u32, value: u64) {
+    let mask = bit_mask(width) << offset;
+    let bits = (u64::from_le_bytes(*word) & !mask) | ((value << offset) & mask);
+    *word = bits.to_le_bytes();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_write_bits() {
+        let mut word = [0u8; 8];
+
+        try_write_bits(&mut word, 0, 1, 1).unwrap();
+        try_write_bits(&mut word, 1, 3, 5).unwrap();
+        try_write_bits(&mut word, 4, 60, u64::MAX >> 4).unwrap();
+        assert!(try_write_bits(&mut word, 1, 3, 8).is_err());
+
+        assert_eq!(read_bits(&word, 0, 1), 1);
+        assert_eq!(read_bits(&word, 1, 3), 5);
+        assert_eq!(read_bits(&word, 4, 60), u64::MAX >> 4);
+
+        try_write_bits(&mut word, 1, 3, 2).unwrap();
+        // neighbours are untouched
+        assert_eq!(read_bits(&word, 0, 1), 1);
+        assert_eq!(read_bits(&word, 1, 3), 2);
+        assert_eq!(read_bits(&word, 4, 60), u64::MAX >> 4);
+        assert_eq!(bits_for(2), 2);
+    }
+}
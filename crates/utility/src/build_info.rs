@@ -0,0 +1,133 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use bytemuck::{Pod, Zeroable};
+
+/// Build metadata embedded in the program binary by [`build_info!`](crate::build_info), so a
+/// deployment can be matched to its source and IDL.
+///
+/// The static lives in `.rodata.build_info` and starts with [`BuildInfo::MAGIC`], off-chain tools
+/// find it by scanning the program data for the magic. The same bytes are returned by the
+/// generated `BuildInfoIx` view instruction.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub magic: [u8; 8],
+    /// Program version, utf8 and zero padded
+    pub version: [u8; 32],
+    /// Git commit hash in hex, zero padded
+    pub git_hash: [u8; 40],
+    /// SHA-256 of the IDL, all zero if unknown
+    pub idl_hash: [u8; 32],
+}
+
+// SAFETY: only byte arrays, no padding
+unsafe impl Zeroable for BuildInfo {}
+unsafe impl Pod for BuildInfo {}
+
+impl BuildInfo {
+    pub const MAGIC: [u8; 8] = *b"HYBSINFO";
+
+    /// `idl_hash` is 64 hex characters or empty. Panics, at compile time when used in a const, if
+    /// a value doesn't fit.
+    pub const fn new(version: &str, git_hash: &str, idl_hash: &str) -> Self {
+        Self {
+            magic: Self::MAGIC,
+            version: pad(version.as_bytes()),
+            git_hash: pad(git_hash.as_bytes()),
+            idl_hash: decode_hash(idl_hash.as_bytes()),
+        }
+    }
+
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
+
+const fn pad<const N: usize>(value: &[u8]) -> [u8; N] {
+    assert!(value.len() <= N, "build info value too long");
+
+    let mut out = [0u8; N];
+    let mut i = 0;
+    while i < value.len() {
+        out[i] = value[i];
+        i += 1;
+    }
+    out
+}
+
+const fn decode_hash(hex: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+
+    if hex.is_empty() {
+        return out;
+    }
+
+    assert!(hex.len() == 64, "idl hash must be 64 hex characters");
+
+    let mut i = 0;
+    while i < 32 {
+        out[i] = (hex_digit(hex[2 * i]) << 4) | hex_digit(hex[2 * i + 1]);
+        i += 1;
+    }
+    out
+}
+
+const fn hex_digit(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        b'A'..=b'F' => c - b'A' + 10,
+        _ => panic!("invalid hex character in idl hash"),
+    }
+}
+
+/// Embeds a [`BuildInfo`] in the binary and generates the `BuildInfoIx` view instruction
+/// returning it through return data, to be added to `dispatch!` as
+/// `BuildInfoIx => build_info()`.
+///
+/// Without arguments the version is the crate version and the hashes are read from the
+/// `HAYABUSA_GIT_HASH` and `HAYABUSA_IDL_HASH` environment variables at build time:
+///
+/// ```ignore
+/// build_info!();
+/// build_info!(version = "1.2.0", git_hash = env!("GIT_HASH"), idl_hash = "");
+/// ```
+#[macro_export]
+macro_rules! build_info {
+    () => {
+        $crate::build_info!(
+            version = env!("CARGO_PKG_VERSION"),
+            git_hash = match option_env!("HAYABUSA_GIT_HASH") {
+                Some(hash) => hash,
+                None => "",
+            },
+            idl_hash = match option_env!("HAYABUSA_IDL_HASH") {
+                Some(hash) => hash,
+                None => "",
+            },
+        );
+    };
+    (version = $version:expr, git_hash = $git_hash:expr, idl_hash = $idl_hash:expr $(,)?) => {
+        #[used]
+        #[link_section = ".rodata.build_info"]
+        pub static BUILD_INFO: BuildInfo = BuildInfo::new($version, $git_hash, $idl_hash);
+
+        #[derive(Clone, Copy, Discriminator)]
+        #[repr(C)]
+        pub struct BuildInfoIx {}
+
+        impl<'ix> DecodeIx<'ix> for BuildInfoIx {
+            #[inline(always)]
+            fn decode(_: &'ix [u8]) -> Result<Self> {
+                Ok(Self {})
+            }
+        }
+
+        pub fn build_info<'ix>(_: Ctx<'ix, NoAccounts>) -> Result<()> {
+            syscalls::set_return_data(BUILD_INFO.as_bytes());
+            Ok(())
+        }
+    };
+}
@@ -0,0 +1,157 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! CRC32 (IEEE) checksums over account data regions.
+//!
+//! Not a cryptographic guarantee, a checksum only detects data that was written by something
+//! other than the code that last updated it, e.g. a cranker writing raw bytes or an account
+//! migrated from another program.
+
+use core::ops::Range;
+use hayabusa_errors::Result;
+use solana_program_error::ProgramError;
+
+use crate::hint::unlikely;
+
+const CRC32_POLY: u32 = 0xEDB8_8320;
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ CRC32_POLY
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// CRC32 (IEEE 802.3, as used by zlib/png) of `data`.
+#[inline]
+pub const fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    let mut i = 0;
+    while i < data.len() {
+        crc = CRC32_TABLE[((crc ^ data[i] as u32) & 0xff) as usize] ^ (crc >> 8);
+        i += 1;
+    }
+    !crc
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumMode {
+    /// Fail if the stored checksum does not match the region.
+    Verify,
+    /// Overwrite the stored checksum with the one of the region.
+    Update,
+}
+
+/// Checksums `data[region]` and either verifies or updates the little endian `u32` stored at
+/// `data[checksum_offset..checksum_offset + 4]`, returning the checksum.
+///
+/// `data` is usually the full account data (discriminator included), the checksum slot must
+/// not overlap the region it covers.
+pub fn verify_or_update_checksum(
+    data: &mut [u8],
+    region: Range<usize>,
+    checksum_offset: usize,
+    mode: ChecksumMode,
+) -> Result<u32> {
+    let checksum_end = checksum_offset.wrapping_add(4);
+
+    if unlikely(
+        region.start > region.end
+            || region.end > data.len()
+            || checksum_end < checksum_offset
+            || checksum_end > data.len()
+            || (checksum_offset < region.end && region.start < checksum_end),
+    ) {
+        error_msg!(
+            "verify_or_update_checksum: invalid region",
+            ProgramError::InvalidArgument,
+        );
+    }
+
+    let checksum = crc32(&data[region]);
+    let stored = &mut data[checksum_offset..checksum_end];
+
+    match mode {
+        ChecksumMode::Verify => {
+            if unlikely(stored != checksum.to_le_bytes()) {
+                error_msg!(
+                    "verify_or_update_checksum: checksum mismatch",
+                    ProgramError::InvalidAccountData,
+                );
+            }
+        }
+        ChecksumMode::Update => stored.copy_from_slice(&checksum.to_le_bytes()),
+    }
+
+    Ok(checksum)
+}
+
+/// [`verify_or_update_checksum`] with [`ChecksumMode::Verify`], for read-only data.
+pub fn verify_checksum(data: &[u8], region: Range<usize>, checksum_offset: usize) -> Result<u32> {
+    let checksum_end = checksum_offset.wrapping_add(4);
+
+    if unlikely(
+        region.start > region.end
+            || region.end > data.len()
+            || checksum_end < checksum_offset
+            || checksum_end > data.len(),
+    ) {
+        error_msg!(
+            "verify_checksum: invalid region",
+            ProgramError::InvalidArgument,
+        );
+    }
+
+    let checksum = crc32(&data[region]);
+
+    if unlikely(data[checksum_offset..checksum_end] != checksum.to_le_bytes()) {
+        error_msg!(
+            "verify_checksum: checksum mismatch",
+            ProgramError::InvalidAccountData,
+        );
+    }
+
+    Ok(checksum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_verify_or_update() {
+        let mut data = [0u8; 16];
+        data[..8].copy_from_slice(b"hayabusa");
+
+        assert!(verify_or_update_checksum(&mut data, 0..8, 8, ChecksumMode::Verify).is_err());
+
+        let checksum = verify_or_update_checksum(&mut data, 0..8, 8, ChecksumMode::Update).unwrap();
+        assert_eq!(checksum, crc32(b"hayabusa"));
+        assert_eq!(verify_checksum(&data, 0..8, 8).unwrap(), checksum);
+
+        data[0] ^= 1;
+        assert!(verify_checksum(&data, 0..8, 8).is_err());
+
+        // checksum slot inside the region it covers
+        assert!(verify_or_update_checksum(&mut data, 0..12, 8, ChecksumMode::Update).is_err());
+    }
+}
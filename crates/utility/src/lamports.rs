@@ -0,0 +1,239 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use bytemuck::{Pod, Zeroable};
+use core::{cmp::min, fmt, mem::MaybeUninit};
+use hayabusa_errors::Result;
+use pinocchio_log::logger::{Argument, Log};
+use solana_program_error::ProgramError;
+
+/// Longest formatted amount, `18446744073.709551615 SOL`
+const MAX_FORMATTED_LEN: usize = 25;
+
+/// An amount of lamports.
+///
+/// Lamport amounts and token base units are both plain `u64`s, nothing stops passing a token
+/// amount to a system transfer or a balance to a token instruction. The transfer, rent and sweep
+/// helpers take and return `Lamports` instead, a `u64` only becomes one through
+/// [`Lamports::new`] or [`Lamports::from_sol`].
+///
+/// Logs and formats in SOL, `1.500000000 SOL`.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lamports(u64);
+
+// SAFETY: a transparent `u64`
+unsafe impl Zeroable for Lamports {}
+unsafe impl Pod for Lamports {}
+
+impl Lamports {
+    pub const ZERO: Self = Self(0);
+    pub const MAX: Self = Self(u64::MAX);
+
+    /// Lamports in one SOL
+    pub const PER_SOL: u64 = 1_000_000_000;
+
+    #[inline(always)]
+    pub const fn new(lamports: u64) -> Self {
+        Self(lamports)
+    }
+
+    /// `sol` whole SOL, `None` on overflow
+    #[inline(always)]
+    pub const fn from_sol(sol: u64) -> Option<Self> {
+        match sol.checked_mul(Self::PER_SOL) {
+            Some(lamports) => Some(Self(lamports)),
+            None => None,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    #[inline(always)]
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline(always)]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(lamports) => Some(Self(lamports)),
+            None => None,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(lamports) => Some(Self(lamports)),
+            None => None,
+        }
+    }
+
+    /// Scales the amount, e.g. a per-byte price by a length
+    #[inline(always)]
+    pub const fn checked_mul(self, rhs: u64) -> Option<Self> {
+        match self.0.checked_mul(rhs) {
+            Some(lamports) => Some(Self(lamports)),
+            None => None,
+        }
+    }
+
+    /// Splits the amount, e.g. a payout between recipients
+    #[inline(always)]
+    pub const fn checked_div(self, rhs: u64) -> Option<Self> {
+        match self.0.checked_div(rhs) {
+            Some(lamports) => Some(Self(lamports)),
+            None => None,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    #[inline(always)]
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    #[inline(always)]
+    pub fn try_add(self, rhs: Self) -> Result<Self> {
+        let Some(lamports) = self.checked_add(rhs) else {
+            error_msg!(
+                "Lamports::try_add: overflow",
+                ProgramError::ArithmeticOverflow,
+            );
+        };
+
+        Ok(lamports)
+    }
+
+    #[inline(always)]
+    pub fn try_sub(self, rhs: Self) -> Result<Self> {
+        let Some(lamports) = self.checked_sub(rhs) else {
+            error_msg!(
+                "Lamports::try_sub: underflow",
+                ProgramError::ArithmeticOverflow,
+            );
+        };
+
+        Ok(lamports)
+    }
+
+    /// Writes `<sol>.<9 decimals> SOL` to `out`, returning the length
+    fn format(self, out: &mut [u8; MAX_FORMATTED_LEN]) -> usize {
+        let mut digits = [0u8; 20];
+        let mut start = digits.len();
+        let mut value = self.0;
+
+        // at least `0.000000000`
+        while value > 0 || start > digits.len() - 10 {
+            start -= 1;
+            digits[start] = b'0' + (value % 10) as u8;
+            value /= 10;
+        }
+
+        let whole = &digits[start..digits.len() - 9];
+        let fraction = &digits[digits.len() - 9..];
+
+        let mut len = 0;
+        for part in [whole, b".", fraction, b" SOL"] {
+            out[len..len + part.len()].copy_from_slice(part);
+            len += part.len();
+        }
+
+        len
+    }
+}
+
+impl From<Lamports> for u64 {
+    #[inline(always)]
+    fn from(lamports: Lamports) -> Self {
+        lamports.0
+    }
+}
+
+impl fmt::Display for Lamports {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = [0; MAX_FORMATTED_LEN];
+        let len = self.format(&mut out);
+
+        // SAFETY: only ASCII digits, `.` and ` SOL` are written
+        f.write_str(unsafe { core::str::from_utf8_unchecked(&out[..len]) })
+    }
+}
+
+// SAFETY: the returned length is the number of bytes written to `buffer`
+unsafe impl Log for Lamports {
+    fn write_with_args(&self, buffer: &mut [MaybeUninit<u8>], _args: &[Argument]) -> usize {
+        let mut out = [0; MAX_FORMATTED_LEN];
+        let len = self.format(&mut out);
+        let written = min(len, buffer.len());
+
+        for (slot, byte) in buffer.iter_mut().zip(&out[..written]) {
+            slot.write(*byte);
+        }
+
+        // same truncation marker as the integer impls
+        if written < len && written > 0 {
+            buffer[written - 1].write(b'@');
+        }
+
+        written
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pinocchio_log::logger::Logger;
+
+    fn formatted(lamports: u64) -> [u8; MAX_FORMATTED_LEN] {
+        let mut out = [b' '; MAX_FORMATTED_LEN];
+        Lamports::new(lamports).format(&mut out);
+        out
+    }
+
+    #[test]
+    fn test_format() {
+        assert!(formatted(0).starts_with(b"0.000000000 SOL"));
+        assert!(formatted(1).starts_with(b"0.000000001 SOL"));
+        assert!(formatted(1_500_000_000).starts_with(b"1.500000000 SOL"));
+        assert_eq!(&formatted(u64::MAX), b"18446744073.709551615 SOL");
+    }
+
+    #[test]
+    fn test_log() {
+        let mut logger = Logger::<32>::default();
+        logger
+            .append("balance ")
+            .append(Lamports::new(2_000_000_001));
+        assert_eq!(&*logger, b"balance 2.000000001 SOL");
+
+        let mut logger = Logger::<8>::default();
+        logger.append(Lamports::new(2_000_000_001));
+        assert_eq!(&*logger, b"2.00000@");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let one = Lamports::from_sol(1).unwrap();
+        assert_eq!(one.get(), Lamports::PER_SOL);
+        assert!(Lamports::from_sol(u64::MAX).is_none());
+
+        assert_eq!(
+            one.try_add(one).unwrap(),
+            Lamports::new(2 * Lamports::PER_SOL)
+        );
+        assert!(Lamports::MAX.try_add(Lamports::new(1)).is_err());
+        assert!(Lamports::ZERO.try_sub(one).is_err());
+        assert_eq!(Lamports::ZERO.saturating_sub(one), Lamports::ZERO);
+        assert_eq!(one.checked_div(4), Some(Lamports::new(250_000_000)));
+        assert!(one.checked_div(0).is_none());
+    }
+}
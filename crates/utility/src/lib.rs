@@ -6,6 +6,35 @@
 #[macro_use]
 pub mod macros;
 
+mod address;
+pub mod bitfield;
+pub mod build_info;
+#[cfg(feature = "checksum")]
+pub mod checksum;
+mod lamports;
+mod padding;
+mod pod_bool;
+mod pod_enum;
+mod pod_int;
+mod pod_option;
+mod pod_str;
+mod zc_map;
+
+#[cfg(feature = "checksum")]
+pub use checksum::{crc32, verify_checksum, verify_or_update_checksum, ChecksumMode};
+
+pub use address::{address_common_prefix_len, address_eq_const, address_starts_with};
+pub use bitfield::BitfieldValue;
+pub use build_info::BuildInfo;
+pub use lamports::Lamports;
+pub use padding::Padding;
+pub use pod_bool::PodBool;
+pub use pod_enum::{PodEnum, PodU8Enum};
+pub use pod_int::{PodI128, PodI64, PodU128, PodU16, PodU32, PodU64};
+pub use pod_option::{PodOption, PodOptionKey};
+pub use pod_str::PodStr;
+pub use zc_map::ZcMap;
+
 use core::mem::MaybeUninit;
 use hayabusa_errors::Result;
 use solana_address::Address;
@@ -29,6 +58,17 @@ pub fn take_bytes(data: &[u8], n: usize) -> Result<(&[u8], &[u8])> {
     Ok(data.split_at(n))
 }
 
+/// Space of an account without its discriminator, see `#[derive(InitSpace)]`.
+///
+/// Unlike [`Len`] it includes the regions after the struct, so
+/// `space = Foo::DISCRIMINATED_INIT_SPACE` allocates the whole account.
+pub trait InitSpace {
+    const INIT_SPACE: usize;
+
+    /// [`InitSpace::INIT_SPACE`] plus the discriminator, whatever its length
+    const DISCRIMINATED_INIT_SPACE: usize;
+}
+
 pub trait OwnerProgram {
     const OWNER: Address;
 
@@ -37,6 +77,62 @@ pub trait OwnerProgram {
     }
 }
 
+/// Implemented by accounts carrying the address that paid their rent in a `rent_payer: Address`
+/// field (`#[account]` implements it automatically for such a field).
+///
+/// Init helpers record the payer on creation, close helpers refund it no matter who closes the
+/// account.
+pub trait RentPayer {
+    fn rent_payer(&self) -> &Address;
+
+    fn set_rent_payer(&mut self, rent_payer: Address);
+}
+
+/// Rent payer as seen by the `init` and `close` meta constraints of `FromAccountViews`.
+///
+/// `#[account]` implements it for every account: forwarding to [`RentPayer`] when the struct has
+/// a `rent_payer` field, recording nothing otherwise.
+pub trait MaybeRentPayer {
+    #[inline(always)]
+    fn maybe_rent_payer(&self) -> Option<&Address> {
+        None
+    }
+
+    #[inline(always)]
+    fn maybe_set_rent_payer(&mut self, _rent_payer: Address) {}
+}
+
+/// Implemented by accounts carrying an `in_flight: PodBool` field (`#[account]` implements it
+/// automatically for such a field).
+///
+/// `ZcAccount::try_guarded` sets the flag around an external CPI, and the `not_in_flight` meta
+/// constraint of `FromAccountViews` rejects instructions reaching the account meanwhile.
+pub trait ReentrancyGuard {
+    fn in_flight(&self) -> bool;
+
+    fn set_in_flight(&mut self, in_flight: bool);
+}
+
+/// State of a PDA account that stores its own bump, see `PdaAccount`.
+///
+/// ```ignore
+/// impl PdaState for Vault {
+///     fn bump(&self) -> u8 {
+///         self.bump
+///     }
+///
+///     fn with_seeds<R>(&self, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+///         f(&[b"vault", self.authority.as_ref()])
+///     }
+/// }
+/// ```
+pub trait PdaState {
+    fn bump(&self) -> u8;
+
+    /// Calls `f` with the seeds of the account, without the bump.
+    fn with_seeds<R>(&self, f: impl FnOnce(&[&[u8]]) -> R) -> R;
+}
+
 pub const UNINIT_BYTE: MaybeUninit<u8> = MaybeUninit::<u8>::uninit();
 
 #[inline(always)]
@@ -40,3 +40,15 @@ macro_rules! unix_ts {
         Clock::get()?.unix_timestamp
     };
 }
+
+/// Compile time check that `$ty` keeps its size, e.g. when new fields are carved out of a
+/// [`Padding`](crate::Padding) field of an account that is already deployed
+#[macro_export]
+macro_rules! assert_size {
+    ($ty:ty, $size:expr $(,)?) => {
+        const _: () = assert!(
+            core::mem::size_of::<$ty>() == $size,
+            concat!("size of `", stringify!($ty), "` changed"),
+        );
+    };
+}
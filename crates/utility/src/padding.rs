@@ -0,0 +1,49 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use bytemuck::{Pod, Zeroable};
+
+/// Space reserved in an account layout for future fields.
+///
+/// Always zero on creation, events skip it. Pair with [`assert_size!`](crate::assert_size) so
+/// carving a field out of the padding can't change the size of the account:
+///
+/// ```ignore
+/// #[account]
+/// pub struct Config {
+///     pub authority: Address,
+///     pub fee_bps: u64,
+///     pub _reserved: Padding<64>,
+/// }
+///
+/// assert_size!(Config, 104);
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Padding<const N: usize>([u8; N]);
+
+impl<const N: usize> Padding<N> {
+    pub const ZERO: Self = Self([0; N]);
+
+    #[inline(always)]
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().all(|b| *b == 0)
+    }
+}
+
+impl<const N: usize> Default for Padding<N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for Padding<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Padding<{N}>")
+    }
+}
+
+// SAFETY: a transparent wrapper of `[u8; N]`, any bit pattern is valid and there is no padding
+unsafe impl<const N: usize> Zeroable for Padding<N> {}
+unsafe impl<const N: usize> Pod for Padding<N> {}
@@ -0,0 +1,86 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use bytemuck::{Pod, Zeroable};
+use core::fmt;
+
+/// `bool` stored as a byte, for use inside `#[account]` structs.
+///
+/// A bare `bool` is not Pod, any byte other than 0 or 1 would be undefined behaviour. Any non-zero
+/// byte reads as `true`, writes are always 0 or 1.
+#[repr(transparent)]
+#[derive(Clone, Copy, Default)]
+pub struct PodBool(u8);
+
+// SAFETY: transparent over a byte, every value is valid
+unsafe impl Zeroable for PodBool {}
+unsafe impl Pod for PodBool {}
+
+impl PodBool {
+    pub const FALSE: Self = Self(0);
+    pub const TRUE: Self = Self(1);
+
+    #[inline(always)]
+    pub const fn new(value: bool) -> Self {
+        Self(value as u8)
+    }
+
+    #[inline(always)]
+    pub const fn get(&self) -> bool {
+        self.0 != 0
+    }
+
+    #[inline(always)]
+    pub fn set(&mut self, value: bool) {
+        *self = Self::new(value);
+    }
+}
+
+impl From<bool> for PodBool {
+    #[inline(always)]
+    fn from(value: bool) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<PodBool> for bool {
+    #[inline(always)]
+    fn from(value: PodBool) -> Self {
+        value.get()
+    }
+}
+
+/// Compares the values, not the bytes
+impl PartialEq for PodBool {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl Eq for PodBool {}
+
+impl fmt::Debug for PodBool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pod_bool() {
+        let mut flag = PodBool::default();
+        assert!(!flag.get());
+
+        flag.set(true);
+        assert_eq!(bytemuck::bytes_of(&flag), &[1]);
+
+        // any non-zero byte is true
+        let dirty: PodBool = bytemuck::cast(7u8);
+        assert!(dirty.get());
+        assert_eq!(dirty, PodBool::TRUE);
+    }
+}
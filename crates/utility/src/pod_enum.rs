@@ -0,0 +1,108 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use bytemuck::{Pod, Zeroable};
+use core::fmt;
+use core::marker::PhantomData;
+use hayabusa_errors::Result;
+use solana_program_error::ProgramError;
+
+/// Fieldless `#[repr(u8)]` enum stored as its discriminant, see `#[derive(PodEnum)]`.
+pub trait PodEnum: Copy + TryFrom<u8, Error = ProgramError> + Into<u8> + 'static {}
+
+/// Enum stored as its `u8` discriminant, for use inside `#[account]` structs.
+///
+/// Account data can hold any byte, so reads are checked against the variants.
+///
+/// ```ignore
+/// #[derive(Clone, Copy, PodEnum)]
+/// #[repr(u8)]
+/// pub enum Status {
+///     Open,
+///     Closed,
+/// }
+///
+/// #[account]
+/// pub struct Market {
+///     pub status: PodU8Enum<Status>,
+/// }
+/// ```
+#[repr(transparent)]
+pub struct PodU8Enum<T> {
+    value: u8,
+    _marker: PhantomData<T>,
+}
+
+// SAFETY: transparent over a byte, every value is valid
+unsafe impl<T: PodEnum> Zeroable for PodU8Enum<T> {}
+unsafe impl<T: PodEnum> Pod for PodU8Enum<T> {}
+
+impl<T> PodU8Enum<T>
+where
+    T: PodEnum,
+{
+    #[inline(always)]
+    pub fn new(value: T) -> Self {
+        Self::from_raw(value.into())
+    }
+
+    /// Unchecked discriminant.
+    #[inline(always)]
+    pub const fn from_raw(value: u8) -> Self {
+        Self {
+            value,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Unchecked discriminant.
+    #[inline(always)]
+    pub const fn raw(&self) -> u8 {
+        self.value
+    }
+
+    /// Fails if the stored byte is not a variant of `T`.
+    #[inline(always)]
+    pub fn try_get(&self) -> Result<T> {
+        T::try_from(self.value)
+    }
+
+    #[inline(always)]
+    pub fn set(&mut self, value: T) {
+        self.value = value.into();
+    }
+}
+
+impl<T> Clone for PodU8Enum<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for PodU8Enum<T> {}
+
+impl<T> PartialEq for PodU8Enum<T> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> Eq for PodU8Enum<T> {}
+
+impl<T> From<T> for PodU8Enum<T>
+where
+    T: PodEnum,
+{
+    #[inline(always)]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> fmt::Debug for PodU8Enum<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PodU8Enum").field(&self.value).finish()
+    }
+}
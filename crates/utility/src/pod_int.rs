@@ -0,0 +1,189 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use bytemuck::{Pod, Zeroable};
+use core::cmp::Ordering;
+use core::fmt;
+use hayabusa_errors::Result;
+use solana_program_error::ProgramError;
+
+macro_rules! pod_int {
+    ($(#[$doc:meta])* $name:ident, $int:ty) => {
+        $(#[$doc])*
+        #[repr(transparent)]
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct $name([u8; core::mem::size_of::<$int>()]);
+
+        // SAFETY: transparent over a byte array
+        unsafe impl Zeroable for $name {}
+        unsafe impl Pod for $name {}
+
+        impl $name {
+            pub const ZERO: Self = Self([0; core::mem::size_of::<$int>()]);
+            pub const MAX: Self = Self::new(<$int>::MAX);
+
+            #[inline(always)]
+            pub const fn new(value: $int) -> Self {
+                Self(value.to_le_bytes())
+            }
+
+            #[inline(always)]
+            pub const fn get(&self) -> $int {
+                <$int>::from_le_bytes(self.0)
+            }
+
+            #[inline(always)]
+            pub fn set(&mut self, value: $int) {
+                self.0 = value.to_le_bytes();
+            }
+
+            #[inline(always)]
+            pub fn checked_add(self, rhs: $int) -> Option<Self> {
+                self.get().checked_add(rhs).map(Self::new)
+            }
+
+            #[inline(always)]
+            pub fn checked_sub(self, rhs: $int) -> Option<Self> {
+                self.get().checked_sub(rhs).map(Self::new)
+            }
+
+            #[inline(always)]
+            pub fn checked_mul(self, rhs: $int) -> Option<Self> {
+                self.get().checked_mul(rhs).map(Self::new)
+            }
+
+            #[inline(always)]
+            pub fn saturating_add(self, rhs: $int) -> Self {
+                Self::new(self.get().saturating_add(rhs))
+            }
+
+            #[inline(always)]
+            pub fn saturating_sub(self, rhs: $int) -> Self {
+                Self::new(self.get().saturating_sub(rhs))
+            }
+
+            /// Adds `rhs` in place, fails on overflow.
+            #[inline(always)]
+            pub fn try_add(&mut self, rhs: $int) -> Result<()> {
+                let Some(value) = self.checked_add(rhs) else {
+                    error_msg!("PodInt::try_add: overflow", ProgramError::ArithmeticOverflow);
+                };
+
+                *self = value;
+                Ok(())
+            }
+
+            /// Subtracts `rhs` in place, fails on overflow.
+            #[inline(always)]
+            pub fn try_sub(&mut self, rhs: $int) -> Result<()> {
+                let Some(value) = self.checked_sub(rhs) else {
+                    error_msg!("PodInt::try_sub: overflow", ProgramError::ArithmeticOverflow);
+                };
+
+                *self = value;
+                Ok(())
+            }
+
+            /// Multiplies in place, fails on overflow.
+            #[inline(always)]
+            pub fn try_mul(&mut self, rhs: $int) -> Result<()> {
+                let Some(value) = self.checked_mul(rhs) else {
+                    error_msg!("PodInt::try_mul: overflow", ProgramError::ArithmeticOverflow);
+                };
+
+                *self = value;
+                Ok(())
+            }
+        }
+
+        impl From<$int> for $name {
+            #[inline(always)]
+            fn from(value: $int) -> Self {
+                Self::new(value)
+            }
+        }
+
+        impl From<$name> for $int {
+            #[inline(always)]
+            fn from(value: $name) -> Self {
+                value.get()
+            }
+        }
+
+        /// Compares the values, not the little endian bytes
+        impl PartialOrd for $name {
+            #[inline(always)]
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            #[inline(always)]
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.get().cmp(&other.get())
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.get().fmt(f)
+            }
+        }
+    };
+}
+
+pod_int!(
+    /// `u16` stored as little endian bytes, with an alignment of 1.
+    PodU16,
+    u16
+);
+pod_int!(
+    /// `u32` stored as little endian bytes, with an alignment of 1.
+    PodU32,
+    u32
+);
+pod_int!(
+    /// `u64` stored as little endian bytes, with an alignment of 1.
+    ///
+    /// Lets `#[account]` structs mix integers with byte fields without implicit padding, and
+    /// reads stay valid at any offset, e.g. in a tail or a packed instruction.
+    PodU64,
+    u64
+);
+pod_int!(
+    /// `u128` stored as little endian bytes, with an alignment of 1.
+    PodU128,
+    u128
+);
+pod_int!(
+    /// `i64` stored as little endian bytes, with an alignment of 1.
+    PodI64,
+    i64
+);
+pod_int!(
+    /// `i128` stored as little endian bytes, with an alignment of 1.
+    PodI128,
+    i128
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pod_int() {
+        let mut balance = PodU64::new(5);
+        assert_eq!(core::mem::align_of::<PodU128>(), 1);
+
+        balance.try_add(10).unwrap();
+        assert_eq!(balance.get(), 15);
+        assert!(balance.try_sub(16).is_err());
+        assert_eq!(balance.get(), 15);
+        assert!(PodU64::MAX.checked_add(1).is_none());
+
+        // ordered by value, not by bytes
+        assert!(PodU64::new(256) > PodU64::new(1));
+        assert!(PodI64::new(-1) < PodI64::ZERO);
+    }
+}
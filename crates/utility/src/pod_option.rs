@@ -0,0 +1,217 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use bytemuck::{Pod, Zeroable};
+use core::mem::{align_of, size_of};
+use solana_address::Address;
+
+/// Optional value for use inside `#[account]` structs.
+///
+/// ```text
+/// flag: u8 | padding: [u8; PAD] | value: T
+/// ```
+///
+/// `flag` is 1 when the value is present, the padding and an absent value are always zero. `PAD`
+/// makes the alignment of `T` explicit: `1 + PAD` must be a multiple of it, which is checked at
+/// compile time the first time the option is used. Byte array based types like `Address` or
+/// [`PodU64`](crate::PodU64) need no padding, a `u64` needs 7 bytes.
+///
+/// ```ignore
+/// #[account]
+/// pub struct Vault {
+///     pub owner: Address,
+///     pub delegate: PodOption<Address>,
+///     pub limit: PodOption<u64, 7>,
+/// }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct PodOption<T, const PAD: usize = 0> {
+    flag: u8,
+    padding: [u8; PAD],
+    value: T,
+}
+
+// SAFETY: `T` is Pod and `NO_PADDING` rejects layouts with padding bytes
+unsafe impl<T: Zeroable, const PAD: usize> Zeroable for PodOption<T, PAD> {}
+unsafe impl<T: Pod, const PAD: usize> Pod for PodOption<T, PAD> {}
+
+impl<T, const PAD: usize> PodOption<T, PAD>
+where
+    T: Pod,
+{
+    const NO_PADDING: () = assert!(
+        size_of::<Self>() == 1 + PAD + size_of::<T>() && (1 + PAD) % align_of::<T>() == 0,
+        "PodOption: PAD does not align the value, 1 + PAD must be a multiple of its alignment"
+    );
+
+    #[inline(always)]
+    pub fn none() -> Self {
+        let () = Self::NO_PADDING;
+        Self::zeroed()
+    }
+
+    #[inline(always)]
+    pub fn some(value: T) -> Self {
+        let () = Self::NO_PADDING;
+        Self {
+            flag: 1,
+            padding: [0; PAD],
+            value,
+        }
+    }
+
+    /// Any non-zero flag counts as present.
+    #[inline(always)]
+    pub fn is_some(&self) -> bool {
+        let () = Self::NO_PADDING;
+        self.flag != 0
+    }
+
+    #[inline(always)]
+    pub fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+
+    #[inline(always)]
+    pub fn get(&self) -> Option<&T> {
+        self.is_some().then_some(&self.value)
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.is_some().then_some(&mut self.value)
+    }
+
+    /// Copy of the value as an `Option`.
+    #[inline(always)]
+    pub fn to_option(&self) -> Option<T> {
+        self.get().copied()
+    }
+
+    /// Stores `value`, returning the previous one.
+    #[inline(always)]
+    pub fn replace(&mut self, value: T) -> Option<T> {
+        core::mem::replace(self, Self::some(value)).to_option()
+    }
+
+    /// Clears the option, zeroing the value.
+    #[inline(always)]
+    pub fn take(&mut self) -> Option<T> {
+        core::mem::take(self).to_option()
+    }
+}
+
+impl<T, const PAD: usize> Default for PodOption<T, PAD>
+where
+    T: Pod,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl<T, const PAD: usize> From<Option<T>> for PodOption<T, PAD>
+where
+    T: Pod,
+{
+    #[inline(always)]
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Self::some(value),
+            None => Self::none(),
+        }
+    }
+}
+
+impl<T, const PAD: usize> From<PodOption<T, PAD>> for Option<T>
+where
+    T: Pod,
+{
+    #[inline(always)]
+    fn from(value: PodOption<T, PAD>) -> Self {
+        value.to_option()
+    }
+}
+
+/// Optional address without a flag, the default (all zero) address stands for `None`.
+///
+/// Saves the flag byte over [`PodOption<Address>`] but can't hold the default address, which is
+/// also the system program id: only use it for keys that can never be the system program.
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct PodOptionKey(Address);
+
+// SAFETY: transparent over `Address`, a 32 byte array
+unsafe impl Zeroable for PodOptionKey {}
+unsafe impl Pod for PodOptionKey {}
+
+impl PodOptionKey {
+    pub const NONE: Self = Self(Address::new_from_array([0; 32]));
+
+    /// `None` if `address` is the default address.
+    #[inline(always)]
+    pub fn new(address: Address) -> Self {
+        Self(address)
+    }
+
+    #[inline(always)]
+    pub fn is_some(&self) -> bool {
+        self.0 != Address::default()
+    }
+
+    #[inline(always)]
+    pub fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+
+    #[inline(always)]
+    pub fn get(&self) -> Option<&Address> {
+        self.is_some().then_some(&self.0)
+    }
+
+    #[inline(always)]
+    pub fn set(&mut self, address: Option<Address>) {
+        *self = address.into();
+    }
+}
+
+impl From<Option<Address>> for PodOptionKey {
+    #[inline(always)]
+    fn from(value: Option<Address>) -> Self {
+        Self(value.unwrap_or_default())
+    }
+}
+
+impl From<PodOptionKey> for Option<Address> {
+    #[inline(always)]
+    fn from(value: PodOptionKey) -> Self {
+        value.get().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pod_option() {
+        let mut limit = PodOption::<u64, 7>::none();
+        assert_eq!(size_of::<PodOption<u64, 7>>(), 16);
+        assert_eq!(size_of::<PodOption<Address>>(), 33);
+        assert_eq!(limit.get(), None);
+
+        assert_eq!(limit.replace(5), None);
+        assert_eq!(limit.replace(7), Some(5));
+        assert_eq!(limit.get(), Some(&7));
+
+        assert_eq!(limit.take(), Some(7));
+        // an absent value leaves no stale bytes
+        assert!(bytemuck::bytes_of(&limit).iter().all(|b| *b == 0));
+
+        let key = PodOptionKey::from(Some(Address::new_from_array([1; 32])));
+        assert!(key.is_some());
+        assert_eq!(Option::<Address>::from(PodOptionKey::NONE), None);
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use bytemuck::{Pod, Zeroable};
+use hayabusa_errors::Result;
+use solana_program_error::ProgramError;
+
+use crate::hint::unlikely;
+
+/// Fixed capacity UTF-8 string for use inside `#[account]` structs and events.
+///
+/// ```text
+/// len: u32 (le) | bytes: [u8; N]
+/// ```
+///
+/// Bytes past `len` are always zero. The content is only trusted to be UTF-8 once read back
+/// through [`PodStr::as_str`], account data can be written by anyone holding the account.
+///
+/// ```ignore
+/// #[account]
+/// pub struct Profile {
+///     pub owner: Address,
+///     pub name: PodStr<32>,
+/// }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PodStr<const N: usize> {
+    len: [u8; 4],
+    bytes: [u8; N],
+}
+
+// SAFETY: byte arrays only, no padding and every bit pattern is valid
+unsafe impl<const N: usize> Zeroable for PodStr<N> {}
+unsafe impl<const N: usize> Pod for PodStr<N> {}
+
+impl<const N: usize> PodStr<N> {
+    pub const EMPTY: Self = Self {
+        len: [0; 4],
+        bytes: [0; N],
+    };
+
+    /// Fails if `value` is longer than `N` bytes.
+    #[inline(always)]
+    pub fn try_new(value: &str) -> Result<Self> {
+        let mut pod_str = Self::EMPTY;
+        pod_str.set(value)?;
+
+        Ok(pod_str)
+    }
+
+    /// Length in bytes, not checked against the capacity.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        u32::from_le_bytes(self.len) as usize
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Fails if the stored length exceeds the capacity.
+    #[inline(always)]
+    pub fn as_bytes(&self) -> Result<&[u8]> {
+        let Some(bytes) = self.bytes.get(..self.len()) else {
+            error_msg!(
+                "PodStr::as_bytes: length exceeds capacity",
+                ProgramError::InvalidAccountData,
+            );
+        };
+
+        Ok(bytes)
+    }
+
+    /// Fails if the stored length exceeds the capacity or the bytes are not UTF-8.
+    #[inline(always)]
+    pub fn as_str(&self) -> Result<&str> {
+        let Ok(value) = core::str::from_utf8(self.as_bytes()?) else {
+            error_msg!(
+                "PodStr::as_str: invalid utf-8",
+                ProgramError::InvalidAccountData,
+            );
+        };
+
+        Ok(value)
+    }
+
+    /// Replaces the content, zeroing the unused bytes. Fails if `value` is longer than `N` bytes.
+    #[inline(always)]
+    pub fn set(&mut self, value: &str) -> Result<()> {
+        let len = value.len();
+
+        if unlikely(len > N) {
+            error_msg!(
+                "PodStr::set: string too long",
+                ProgramError::InvalidArgument,
+            );
+        }
+
+        self.bytes[..len].copy_from_slice(value.as_bytes());
+        self.bytes[len..].fill(0);
+        self.len = (len as u32).to_le_bytes();
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        *self = Self::EMPTY;
+    }
+}
+
+impl<const N: usize> Default for PodStr<N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_read() {
+        let mut value = PodStr::<8>::try_new("hayabusa").unwrap();
+        assert_eq!(value.as_str().unwrap(), "hayabusa");
+        assert!(value.set("too long!").is_err());
+
+        value.set("jutsu").unwrap();
+        assert_eq!(value.len(), 5);
+        assert_eq!(value.as_str().unwrap(), "jutsu");
+        // shorter content doesn't leave stale bytes behind
+        assert_eq!(value, PodStr::try_new("jutsu").unwrap());
+
+        let bytes: &mut [u8] = bytemuck::bytes_of_mut(&mut value);
+        bytes[4] = 0xff;
+        assert!(value.as_str().is_err());
+
+        bytemuck::bytes_of_mut(&mut value)[..4].copy_from_slice(&9u32.to_le_bytes());
+        assert!(value.as_bytes().is_err());
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+use bytemuck::{Pod, Zeroable};
+use core::mem::size_of;
+use hayabusa_errors::Result;
+use solana_program_error::ProgramError;
+
+use crate::hint::unlikely;
+
+/// Fixed capacity map for use inside `#[account]` structs.
+///
+/// Entries are kept sorted by key in two parallel arrays, so lookups are a binary search and the
+/// layout only depends on the entries, not on the order they were inserted in:
+///
+/// ```text
+/// len: u64 (le) | keys: [K; N] | values: [V; N]
+/// ```
+///
+/// Slots past `len` are always zero. `K` and `V` must not leave padding between the arrays, which
+/// is checked at compile time the first time the map is used.
+///
+/// ```ignore
+/// #[account]
+/// pub struct Whitelist {
+///     pub authority: Address,
+///     pub limits: ZcMap<Address, u64, 16>,
+/// }
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct ZcMap<K, V, const N: usize> {
+    len: [u8; 8],
+    keys: [K; N],
+    values: [V; N],
+}
+
+// SAFETY: `K` and `V` are Pod and `NO_PADDING` rejects layouts with padding bytes
+unsafe impl<K: Zeroable, V: Zeroable, const N: usize> Zeroable for ZcMap<K, V, N> {}
+unsafe impl<K: Pod, V: Pod, const N: usize> Pod for ZcMap<K, V, N> {}
+
+impl<K, V, const N: usize> ZcMap<K, V, N>
+where
+    K: Pod + Ord,
+    V: Pod,
+{
+    const NO_PADDING: () = assert!(
+        size_of::<Self>() == 8 + N * (size_of::<K>() + size_of::<V>()),
+        "ZcMap: key and value types leave padding in the layout"
+    );
+
+    #[inline(always)]
+    pub fn new() -> Self {
+        let () = Self::NO_PADDING;
+        Self::zeroed()
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        let () = Self::NO_PADDING;
+        u64::from_le_bytes(self.len) as usize
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline(always)]
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Keys in ascending order
+    #[inline(always)]
+    pub fn keys(&self) -> &[K] {
+        &self.keys[..self.len()]
+    }
+
+    /// Values in the order of their keys
+    #[inline(always)]
+    pub fn values(&self) -> &[V] {
+        &self.values[..self.len()]
+    }
+
+    #[inline(always)]
+    pub fn values_mut(&mut self) -> &mut [V] {
+        let len = self.len();
+        &mut self.values[..len]
+    }
+
+    #[inline(always)]
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.keys().iter().zip(self.values())
+    }
+
+    #[inline(always)]
+    fn search(&self, key: &K) -> core::result::Result<usize, usize> {
+        self.keys().binary_search(key)
+    }
+
+    #[inline(always)]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.search(key).is_ok()
+    }
+
+    #[inline(always)]
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let index = self.search(key).ok()?;
+        Some(&self.values[index])
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.search(key).ok()?;
+        Some(&mut self.values[index])
+    }
+
+    /// Inserts or replaces the value of `key`, returning the previous value. Fails if `key` is new
+    /// and the map is full.
+    #[inline]
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>> {
+        let len = self.len();
+
+        match self.search(&key) {
+            Ok(index) => Ok(Some(core::mem::replace(&mut self.values[index], value))),
+            Err(index) => {
+                if unlikely(len == N) {
+                    error_msg!(
+                        "ZcMap::try_insert: map is full",
+                        ProgramError::AccountDataTooSmall,
+                    );
+                }
+
+                self.keys.copy_within(index..len, index + 1);
+                self.values.copy_within(index..len, index + 1);
+                self.keys[index] = key;
+                self.values[index] = value;
+                self.set_len(len + 1);
+
+                Ok(None)
+            }
+        }
+    }
+
+    /// Removes `key`, returning its value. The freed slot is zeroed.
+    #[inline]
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.search(key).ok()?;
+        let len = self.len();
+        let value = self.values[index];
+
+        self.keys.copy_within(index + 1..len, index);
+        self.values.copy_within(index + 1..len, index);
+        self.keys[len - 1] = K::zeroed();
+        self.values[len - 1] = V::zeroed();
+        self.set_len(len - 1);
+
+        Some(value)
+    }
+
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    #[inline(always)]
+    fn set_len(&mut self, len: usize) {
+        self.len = (len as u64).to_le_bytes();
+    }
+}
+
+impl<K, V, const N: usize> Default for ZcMap<K, V, N>
+where
+    K: Pod + Ord,
+    V: Pod,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut map = ZcMap::<u64, u64, 3>::new();
+
+        assert_eq!(map.try_insert(20, 2).unwrap(), None);
+        assert_eq!(map.try_insert(10, 1).unwrap(), None);
+        assert_eq!(map.try_insert(30, 3).unwrap(), None);
+        assert!(map.try_insert(40, 4).is_err());
+        assert_eq!(map.try_insert(20, 22).unwrap(), Some(2));
+
+        assert_eq!(map.keys(), &[10, 20, 30]);
+        assert_eq!(map.values(), &[1, 22, 3]);
+        assert_eq!(map.get(&30), Some(&3));
+        assert_eq!(map.get(&40), None);
+
+        assert_eq!(map.remove(&10), Some(1));
+        assert_eq!(map.remove(&10), None);
+        assert_eq!(map.keys(), &[20, 30]);
+
+        // layout only depends on the entries
+        let mut other = ZcMap::<u64, u64, 3>::new();
+        other.try_insert(30, 3).unwrap();
+        other.try_insert(20, 22).unwrap();
+        assert_eq!(bytemuck::bytes_of(&map), bytemuck::bytes_of(&other));
+    }
+}
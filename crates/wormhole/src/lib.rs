@@ -0,0 +1,22 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_std]
+
+pub mod posted_vaa;
+
+pub use posted_vaa::*;
+
+hayabusa_common::declare_id!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+
+use hayabusa_accounts::ProgramIds;
+use hayabusa_common::Address;
+
+/// Wormhole core bridge on devnet.
+pub const DEVNET_ID: Address = hayabusa_common::address!("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5");
+
+pub struct CoreBridge;
+
+impl ProgramIds for CoreBridge {
+    const IDS: &'static [Address] = &[ID, DEVNET_ID];
+}
@@ -0,0 +1,245 @@
+// Copyright (c) 2026, Arcane Labs <dev@arcane.fi>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Posted VAA accounts of the Wormhole core bridge.
+//!
+//! The core bridge verifies the guardian signatures before posting, a posted VAA owned by the
+//! core bridge is therefore a verified message. Consumers still have to check who sent it,
+//! see [`PostedVaa::try_check_emitter`].
+
+use crate::CoreBridge;
+use hayabusa_accounts::ProgramIds;
+use hayabusa_common::{AccountView, Address, Ref};
+use hayabusa_errors::{ErrorCode, ProgramError, Result};
+use hayabusa_utility::{error_msg, hint::unlikely};
+
+/// `"vaa"` followed by the account version.
+pub const POSTED_VAA_DISCRIMINATOR: [u8; 4] = *b"vaa\x01";
+
+/// Chain and address of a message sender, addresses are left padded to 32 bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Emitter {
+    pub chain: u16,
+    pub address: [u8; 32],
+}
+
+impl Emitter {
+    pub const fn new(chain: u16, address: [u8; 32]) -> Self {
+        Self { chain, address }
+    }
+}
+
+/// Zero-copy view over a posted VAA account.
+///
+/// Layout after the discriminator: consistency level (u8), timestamp (u32), signature set
+/// (32 bytes), guardian set index (u32), nonce (u32), sequence (u64), emitter chain (u16),
+/// emitter address (32 bytes), payload length (u32) and the payload. Integers are little endian.
+pub struct PostedVaa<'a> {
+    data: Ref<'a, [u8]>,
+}
+
+impl<'a> PostedVaa<'a> {
+    const CONSISTENCY_LEVEL: usize = 4;
+    const TIMESTAMP: usize = 5;
+    const SIGNATURE_SET: usize = 9;
+    const GUARDIAN_SET_INDEX: usize = 41;
+    const NONCE: usize = 45;
+    const SEQUENCE: usize = 49;
+    const EMITTER_CHAIN: usize = 57;
+    const EMITTER_ADDRESS: usize = 59;
+    const PAYLOAD_LEN: usize = 91;
+
+    /// Length of the account data before the payload.
+    pub const HEADER_LEN: usize = 95;
+
+    /// Return the `PostedVaa` of the given account view.
+    ///
+    /// This method checks the owner, the discriminator and the payload length.
+    #[inline]
+    pub fn from_account_view(account_view: &'a AccountView) -> Result<Self> {
+        // SAFETY: the owner is only compared, no reference is kept
+        if unlikely(!CoreBridge::IDS.contains(unsafe { account_view.owner() })) {
+            error_msg!(
+                "PostedVaa::from_account_view: invalid owner",
+                ProgramError::InvalidAccountOwner,
+            );
+        }
+
+        let data = account_view.try_borrow()?;
+
+        if unlikely(data.len() < Self::HEADER_LEN) {
+            error_msg!(
+                "PostedVaa::from_account_view: account data too small",
+                ProgramError::AccountDataTooSmall,
+            );
+        }
+
+        if unlikely(data[..4] != POSTED_VAA_DISCRIMINATOR) {
+            error_msg!(
+                "PostedVaa::from_account_view: invalid discriminator",
+                ErrorCode::InvalidAccountDiscriminator,
+            );
+        }
+
+        let vaa = Self { data };
+
+        if unlikely(vaa.data.len() < Self::HEADER_LEN + vaa.payload_len()) {
+            error_msg!(
+                "PostedVaa::from_account_view: payload length mismatch",
+                ProgramError::InvalidAccountData,
+            );
+        }
+
+        Ok(vaa)
+    }
+
+    #[inline(always)]
+    fn bytes<const N: usize>(&self, offset: usize) -> [u8; N] {
+        self.data[offset..offset + N].try_into().unwrap()
+    }
+
+    #[inline(always)]
+    pub fn consistency_level(&self) -> u8 {
+        self.data[Self::CONSISTENCY_LEVEL]
+    }
+
+    /// Unix timestamp of the block the message was published in.
+    #[inline(always)]
+    pub fn timestamp(&self) -> u32 {
+        u32::from_le_bytes(self.bytes(Self::TIMESTAMP))
+    }
+
+    /// Signature set account the guardian signatures were verified into.
+    #[inline(always)]
+    pub fn signature_set(&self) -> &Address {
+        // SAFETY: `Address` is a 32 byte array with an alignment of 1, the range is in bounds
+        unsafe { &*(self.data[Self::SIGNATURE_SET..].as_ptr() as *const Address) }
+    }
+
+    #[inline(always)]
+    pub fn guardian_set_index(&self) -> u32 {
+        u32::from_le_bytes(self.bytes(Self::GUARDIAN_SET_INDEX))
+    }
+
+    #[inline(always)]
+    pub fn nonce(&self) -> u32 {
+        u32::from_le_bytes(self.bytes(Self::NONCE))
+    }
+
+    #[inline(always)]
+    pub fn sequence(&self) -> u64 {
+        u64::from_le_bytes(self.bytes(Self::SEQUENCE))
+    }
+
+    #[inline(always)]
+    pub fn emitter_chain(&self) -> u16 {
+        u16::from_le_bytes(self.bytes(Self::EMITTER_CHAIN))
+    }
+
+    #[inline(always)]
+    pub fn emitter_address(&self) -> &[u8; 32] {
+        self.data[Self::EMITTER_ADDRESS..Self::EMITTER_ADDRESS + 32]
+            .try_into()
+            .unwrap()
+    }
+
+    #[inline(always)]
+    pub fn emitter(&self) -> Emitter {
+        Emitter::new(self.emitter_chain(), *self.emitter_address())
+    }
+
+    #[inline(always)]
+    fn payload_len(&self) -> usize {
+        u32::from_le_bytes(self.bytes(Self::PAYLOAD_LEN)) as usize
+    }
+
+    /// The message payload, validated to be in bounds on construction.
+    #[inline(always)]
+    pub fn payload(&self) -> &[u8] {
+        &self.data[Self::HEADER_LEN..Self::HEADER_LEN + self.payload_len()]
+    }
+
+    /// Checks that the message was sent by one of `allowlist`.
+    #[inline]
+    pub fn try_check_emitter(&self, allowlist: &[Emitter]) -> Result<()> {
+        let emitter = self.emitter();
+
+        if unlikely(!allowlist.contains(&emitter)) {
+            error_msg!(
+                "PostedVaa::try_check_emitter: unknown emitter",
+                ErrorCode::UnknownEmitter,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use hayabusa_common::mock::MockAccount;
+    use std::vec::Vec;
+
+    const ADDRESS: Address = Address::new_from_array([3; 32]);
+
+    fn vaa_data(payload: &[u8]) -> Vec<u8> {
+        let mut data = POSTED_VAA_DISCRIMINATOR.to_vec();
+        data.push(1);
+        data.extend_from_slice(&1_700_000_000u32.to_le_bytes());
+        data.extend_from_slice(&[4; 32]);
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(&7u32.to_le_bytes());
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(&[5; 32]);
+        data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        data.extend_from_slice(payload);
+        data
+    }
+
+    #[test]
+    fn test_posted_vaa() {
+        let data = vaa_data(b"hello");
+
+        for owner in [crate::ID, crate::DEVNET_ID] {
+            let mut account = MockAccount::new(ADDRESS, owner, &data);
+            let account_view = account.view();
+            let vaa = PostedVaa::from_account_view(&account_view).unwrap();
+
+            assert_eq!(vaa.consistency_level(), 1);
+            assert_eq!(vaa.timestamp(), 1_700_000_000);
+            assert_eq!(vaa.signature_set(), &Address::new_from_array([4; 32]));
+            assert_eq!(vaa.guardian_set_index(), 3);
+            assert_eq!(vaa.nonce(), 7);
+            assert_eq!(vaa.sequence(), 42);
+            assert_eq!(vaa.emitter(), Emitter::new(2, [5; 32]));
+            assert_eq!(vaa.payload(), b"hello");
+
+            assert!(vaa.try_check_emitter(&[Emitter::new(2, [5; 32])]).is_ok());
+            // same address on another chain
+            assert!(vaa.try_check_emitter(&[Emitter::new(1, [5; 32])]).is_err());
+            assert!(vaa.try_check_emitter(&[]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_invalid_posted_vaa() {
+        let data = vaa_data(b"hello");
+
+        // not posted by the core bridge
+        let mut account = MockAccount::new(ADDRESS, ADDRESS, &data);
+        assert!(PostedVaa::from_account_view(&account.view()).is_err());
+
+        let mut other = data.clone();
+        other[3] = 2;
+        let mut account = MockAccount::new(ADDRESS, crate::ID, &other);
+        assert!(PostedVaa::from_account_view(&account.view()).is_err());
+
+        // payload past the end of the data
+        let mut account = MockAccount::new(ADDRESS, crate::ID, &data[..data.len() - 1]);
+        assert!(PostedVaa::from_account_view(&account.view()).is_err());
+    }
+}
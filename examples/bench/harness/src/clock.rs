@@ -0,0 +1,142 @@
+//! Clock warping and time guard boundaries.
+//!
+//! The warp helpers update LiteSVM's `Clock` sysvar and return it re-read through hayabusa's
+//! [`Clock`], the same layout programs see. [`assert_time_guard`] then checks that an instruction
+//! guarded by one of the `Clock::try_check_*` helpers flips exactly at its boundary.
+
+use hayabusa::prelude::Clock;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    clock::Clock as SdkClock, instruction::Instruction, signature::Keypair, signer::Signer, sysvar,
+    transaction::Transaction,
+};
+
+/// Current clock, read from the sysvar account through hayabusa's [`Clock`].
+pub fn clock(svm: &LiteSVM) -> Clock {
+    let account = svm
+        .get_account(&sysvar::clock::ID)
+        .expect("clock sysvar not found");
+
+    // copied into words, the account data has no alignment guarantee
+    let mut words = [0u64; Clock::LEN / 8];
+    for (word, bytes) in words.iter_mut().zip(account.data.chunks_exact(8)) {
+        *word = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+    // SAFETY: `words` is `Clock::LEN` bytes and aligned for `Clock`
+    let bytes = unsafe { std::slice::from_raw_parts(words.as_ptr() as *const u8, Clock::LEN) };
+
+    Clock::from_bytes(bytes).unwrap().clone()
+}
+
+/// Moves the clock to `slot`, leaving `unix_timestamp` as is.
+pub fn warp_to_slot(svm: &mut LiteSVM, slot: u64) -> Clock {
+    svm.warp_to_slot(slot);
+    clock(svm)
+}
+
+/// Advances `unix_timestamp` by `seconds`, leaving the slot as is.
+pub fn warp_seconds(svm: &mut LiteSVM, seconds: i64) -> Clock {
+    let mut clock = svm.get_sysvar::<SdkClock>();
+    clock.unix_timestamp += seconds;
+    svm.set_sysvar(&clock);
+
+    self::clock(svm)
+}
+
+/// Time guard of an instruction, named after the `Clock` helper enforcing it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeGuard {
+    /// `Clock::try_check_reached`
+    Reached(i64),
+    /// `Clock::try_check_deadline`
+    Deadline(i64),
+    /// `Clock::try_check_slot_reached`
+    SlotReached(u64),
+    /// `Clock::try_check_slot_deadline`
+    SlotDeadline(u64),
+}
+
+impl TimeGuard {
+    /// Whether hayabusa's helper lets `clock` through.
+    pub fn check(&self, clock: &Clock) -> bool {
+        match *self {
+            TimeGuard::Reached(start) => clock.try_check_reached(start),
+            TimeGuard::Deadline(deadline) => clock.try_check_deadline(deadline),
+            TimeGuard::SlotReached(start) => clock.try_check_slot_reached(start),
+            TimeGuard::SlotDeadline(deadline) => clock.try_check_slot_deadline(deadline),
+        }
+        .is_ok()
+    }
+
+    /// Expected outcome one unit before, at and one unit after the boundary.
+    fn expected(&self) -> [bool; 3] {
+        match self {
+            TimeGuard::Reached(_) | TimeGuard::SlotReached(_) => [false, true, true],
+            TimeGuard::Deadline(_) | TimeGuard::SlotDeadline(_) => [true, true, false],
+        }
+    }
+
+    fn set(&self, clock: &mut SdkClock, offset: i64) {
+        match *self {
+            TimeGuard::Reached(t) | TimeGuard::Deadline(t) => clock.unix_timestamp = t + offset,
+            TimeGuard::SlotReached(s) | TimeGuard::SlotDeadline(s) => {
+                clock.slot = s
+                    .checked_add_signed(offset)
+                    .expect("slot boundary out of range")
+            }
+        }
+    }
+}
+
+/// Simulates `instruction` one unit before, at and one unit after the boundary of `guard` and
+/// panics unless both hayabusa's helper and the program accept exactly the expected side.
+///
+/// The clock is restored afterwards and, as transactions are only simulated, no state changes.
+pub fn assert_time_guard(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    guard: TimeGuard,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) {
+    let original = svm.get_sysvar::<SdkClock>();
+
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+
+    for (offset, expected) in (-1..=1).zip(guard.expected()) {
+        let mut sdk_clock = original.clone();
+        guard.set(&mut sdk_clock, offset);
+        svm.set_sysvar(&sdk_clock);
+
+        let clock = clock(svm);
+        assert_eq!(
+            guard.check(&clock),
+            expected,
+            "{guard:?}: Clock helper disagrees at offset {offset}"
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            std::slice::from_ref(&instruction),
+            Some(&payer.pubkey()),
+            &all_signers,
+            svm.latest_blockhash(),
+        );
+
+        match svm.simulate_transaction(tx) {
+            Ok(_) if expected => {}
+            Err(_) if !expected => {}
+            Ok(info) => panic!(
+                "{guard:?}: succeeded at offset {offset}, expected a failure\n{}",
+                info.meta.logs.join("\n")
+            ),
+            Err(failed) => panic!(
+                "{guard:?}: failed at offset {offset}: {:?}\n{}",
+                failed.err,
+                failed.meta.logs.join("\n")
+            ),
+        }
+    }
+
+    svm.set_sysvar(&original);
+}
@@ -0,0 +1,467 @@
+//! Compute unit benchmark harness.
+//!
+//! Programs are loaded into LiteSVM, every case is sent as a single instruction transaction and
+//! the consumed compute units are recorded into a [`Report`]. Reports serialize to JSON, so a
+//! checked-in report acts as a regression baseline for [`Report::compare`].
+//!
+//! Programs built with hayabusa's `heap-watermark` feature log their heap usage after every
+//! instruction, the harness picks that line up and records it next to the compute units.
+//! Programs built with the `trace` feature log an execution trace, see [`parse_traces`].
+//!
+//! The measured compute units can also be written into a program's IDL, see
+//! [`update_idl_compute_units`], so clients can size the compute budget of each instruction.
+//!
+//! Lifecycle tests spanning several transactions can be written as a [`scenario::Scenario`], and
+//! [`clock`] warps the clock and checks instructions guarded by a deadline or start time.
+//! Tests of programs calling each other load them all with [`register_programs!`], and
+//! [`token`] sets up mints and token accounts for token integration tests.
+//!
+//! The harness does not know anything about the programs in this workspace and can be embedded
+//! in any repo that wants to track CU usage of its own instructions.
+
+pub mod clock;
+pub mod programs;
+pub mod scenario;
+pub mod token;
+
+use clock::TimeGuard;
+use hayabusa::prelude::Clock;
+use litesvm::LiteSVM;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use solana_sdk::{
+    account::Account, instruction::Instruction, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+use std::{collections::BTreeMap, fmt, fs, io, path::Path};
+
+pub struct Bench {
+    svm: LiteSVM,
+    payer: Keypair,
+    report: Report,
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bench {
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        let payer = Keypair::new();
+
+        svm.airdrop(&payer.pubkey(), 1_000_000_000_000).unwrap();
+
+        Self {
+            svm,
+            payer,
+            report: Report::default(),
+        }
+    }
+
+    pub fn add_program(&mut self, program_id: Pubkey, program_bytes: &[u8]) {
+        self.svm.add_program(program_id, program_bytes);
+    }
+
+    pub fn set_account(&mut self, address: Pubkey, account: Account) {
+        self.svm.set_account(address, account).unwrap();
+    }
+
+    pub fn svm(&mut self) -> &mut LiteSVM {
+        &mut self.svm
+    }
+
+    pub fn payer(&self) -> &Keypair {
+        &self.payer
+    }
+
+    /// Current clock, see [`clock::clock`].
+    pub fn clock(&self) -> Clock {
+        clock::clock(&self.svm)
+    }
+
+    pub fn warp_to_slot(&mut self, slot: u64) -> Clock {
+        clock::warp_to_slot(&mut self.svm, slot)
+    }
+
+    pub fn warp_seconds(&mut self, seconds: i64) -> Clock {
+        clock::warp_seconds(&mut self.svm, seconds)
+    }
+
+    /// See [`clock::assert_time_guard`].
+    pub fn assert_time_guard(
+        &mut self,
+        guard: TimeGuard,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) {
+        clock::assert_time_guard(&mut self.svm, &self.payer, guard, instruction, signers);
+    }
+
+    /// Sends `instruction` in its own transaction (paid for and signed by [`Bench::payer`]) and
+    /// records the consumed compute units under `case`/`group`.
+    ///
+    /// If the program logged its heap watermark (see `hayabusa::log_heap_usage!`), it is recorded
+    /// as well.
+    ///
+    /// Panics with the transaction logs if the transaction fails, a failing case would otherwise
+    /// silently report the CU spent up to the error.
+    pub fn measure(
+        &mut self,
+        group: &str,
+        case: &str,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> u64 {
+        let mut all_signers = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.payer.pubkey()),
+            &all_signers,
+            self.svm.latest_blockhash(),
+        );
+
+        let (compute_units, heap_usage) = match self.svm.send_transaction(tx) {
+            Ok(meta) => (meta.compute_units_consumed, parse_heap_usage(&meta.logs)),
+            Err(failed) => panic!(
+                "{group}/{case} failed: {:?}\n{}",
+                failed.err,
+                failed.meta.logs.join("\n")
+            ),
+        };
+
+        // identical transactions in a row would otherwise be rejected as already processed
+        self.svm.expire_blockhash();
+
+        self.report.record(group, case, compute_units);
+        if let Some(heap_usage) = heap_usage {
+            self.report.record_heap(group, case, heap_usage);
+        }
+
+        compute_units
+    }
+
+    pub fn report(&self) -> &Report {
+        &self.report
+    }
+
+    pub fn into_report(self) -> Report {
+        self.report
+    }
+}
+
+/// Last `heap usage: <n> bytes` line in `logs`, i.e. the watermark after the instruction.
+pub fn parse_heap_usage(logs: &[String]) -> Option<u64> {
+    logs.iter().rev().find_map(|line| {
+        line.strip_prefix("Program log: heap usage: ")?
+            .strip_suffix(" bytes")?
+            .parse()
+            .ok()
+    })
+}
+
+/// One instruction traced by a program built with hayabusa's `trace` feature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecutionTrace {
+    /// Instruction type name logged by `dispatch!`, `None` if the program failed before it.
+    pub instruction: Option<String>,
+    pub accounts: u64,
+    pub data_len: u64,
+    /// Value returned to the runtime, `0` on success.
+    pub result: u64,
+    /// Nesting depth, `0` for the top level instruction and `1+` for self CPIs.
+    pub depth: usize,
+}
+
+/// Every `trace: enter ...` / `trace: ix=...` / `trace: exit ...` block in `logs`, in the order
+/// the instructions completed.
+pub fn parse_traces(logs: &[String]) -> Vec<ExecutionTrace> {
+    let mut open: Vec<ExecutionTrace> = Vec::new();
+    let mut traces = Vec::new();
+
+    for line in logs {
+        let Some(trace) = line.strip_prefix("Program log: trace: ") else {
+            continue;
+        };
+
+        if let Some(rest) = trace.strip_prefix("enter ") {
+            let mut accounts = 0;
+            let mut data_len = 0;
+            for field in rest.split(' ') {
+                match field.split_once('=') {
+                    Some(("accounts", value)) => accounts = value.parse().unwrap_or_default(),
+                    Some(("data_len", value)) => data_len = value.parse().unwrap_or_default(),
+                    _ => {}
+                }
+            }
+
+            open.push(ExecutionTrace {
+                instruction: None,
+                accounts,
+                data_len,
+                result: 0,
+                depth: open.len(),
+            });
+        } else if let Some(name) = trace.strip_prefix("ix=") {
+            if let Some(current) = open.last_mut() {
+                current.instruction = Some(name.to_string());
+            }
+        } else if let Some(result) = trace.strip_prefix("exit result=") {
+            if let Some(mut current) = open.pop() {
+                current.result = result.parse().unwrap_or_default();
+                traces.push(current);
+            }
+        }
+    }
+
+    traces
+}
+
+/// Compute units per case, per group (framework, program, ...).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub cases: BTreeMap<String, BTreeMap<String, u64>>,
+    /// Peak heap bytes per case, per group. Only filled for programs that log their watermark.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub heap: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+impl Report {
+    pub fn record(&mut self, group: &str, case: &str, compute_units: u64) {
+        self.cases
+            .entry(case.to_string())
+            .or_default()
+            .insert(group.to_string(), compute_units);
+    }
+
+    pub fn record_heap(&mut self, group: &str, case: &str, bytes: u64) {
+        self.heap
+            .entry(case.to_string())
+            .or_default()
+            .insert(group.to_string(), bytes);
+    }
+
+    pub fn get(&self, group: &str, case: &str) -> Option<u64> {
+        self.cases.get(case)?.get(group).copied()
+    }
+
+    pub fn get_heap(&self, group: &str, case: &str) -> Option<u64> {
+        self.heap.get(case)?.get(group).copied()
+    }
+
+    /// Compute units of every case measured for `group`, by case name.
+    pub fn group_cases(&self, group: &str) -> BTreeMap<String, u64> {
+        self.cases
+            .iter()
+            .filter_map(|(case, groups)| Some((case.clone(), *groups.get(group)?)))
+            .collect()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        serde_json::from_slice(&bytes).map_err(io::Error::other)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json + "\n")
+    }
+
+    /// Returns every measurement that grew by more than `tolerance_percent` compared to
+    /// `baseline`. Cases missing from the baseline are ignored.
+    pub fn compare(&self, baseline: &Report, tolerance_percent: u64) -> Vec<Regression> {
+        let mut regressions = Vec::new();
+
+        for (case, groups) in &self.cases {
+            for (group, &current) in groups {
+                let Some(baseline) = baseline.get(group, case) else {
+                    continue;
+                };
+
+                if current * 100 > baseline * (100 + tolerance_percent) {
+                    regressions.push(Regression {
+                        group: group.clone(),
+                        case: case.clone(),
+                        baseline,
+                        current,
+                    });
+                }
+            }
+        }
+
+        regressions
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_table(f, "case (CU)", &self.cases)?;
+
+        if !self.heap.is_empty() {
+            writeln!(f)?;
+            write_table(f, "case (heap bytes)", &self.heap)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_table(
+    f: &mut fmt::Formatter<'_>,
+    title: &str,
+    cases: &BTreeMap<String, BTreeMap<String, u64>>,
+) -> fmt::Result {
+    let groups: Vec<&String> = {
+        let mut groups: Vec<&String> = cases.values().flat_map(|g| g.keys()).collect();
+        groups.sort();
+        groups.dedup();
+        groups
+    };
+
+    write!(f, "{title:<20}")?;
+    for group in &groups {
+        write!(f, "{group:>14}")?;
+    }
+    writeln!(f)?;
+
+    for (case, measured) in cases {
+        write!(f, "{case:<20}")?;
+        for group in &groups {
+            match measured.get(*group) {
+                Some(value) => write!(f, "{value:>14}")?,
+                None => write!(f, "{:>14}", "-")?,
+            }
+        }
+        writeln!(f)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Regression {
+    pub group: String,
+    pub case: String,
+    pub baseline: u64,
+    pub current: u64,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}: {} CU -> {} CU",
+            self.group, self.case, self.baseline, self.current
+        )
+    }
+}
+
+/// Top level IDL key of the compute units section, an object mapping instruction names to the
+/// compute units they consumed in the benchmark.
+///
+/// ```json
+/// "computeUnits": { "noop": 12, "transfer": 1305 }
+/// ```
+///
+/// Anchor and most IDL consumers ignore unknown keys, so the section can be uploaded with the rest
+/// of the IDL. The numbers are measured with fixed inputs, clients should add a margin for the
+/// data-dependent paths of an instruction.
+pub const IDL_COMPUTE_UNITS: &str = "computeUnits";
+
+/// Writes the compute units `group` measured into the [`IDL_COMPUTE_UNITS`] section of the IDL
+/// json at `path`, replacing the previous section and keeping the rest of the IDL as is.
+///
+/// Cases are matched to the `instructions` of the IDL by name, cases without a matching
+/// instruction are left out.
+pub fn update_idl_compute_units(
+    path: impl AsRef<Path>,
+    report: &Report,
+    group: &str,
+) -> io::Result<()> {
+    let path = path.as_ref();
+    let mut idl = load_idl(path)?;
+
+    let units: serde_json::Map<String, serde_json::Value> = idl_measured_units(&idl, report, group)
+        .into_iter()
+        .map(|(name, units)| (name, units.into()))
+        .collect();
+
+    let Some(idl_object) = idl.as_object_mut() else {
+        return Err(io::Error::other("IDL is not a json object"));
+    };
+    idl_object.insert(IDL_COMPUTE_UNITS.to_string(), units.into());
+
+    let json = serde_json::to_string_pretty(&idl).map_err(io::Error::other)?;
+    fs::write(path, json + "\n")
+}
+
+/// Instructions of the IDL at `path` whose [`IDL_COMPUTE_UNITS`] entry differs from what `group`
+/// measured, with the IDL value as the baseline. A measured instruction without an entry is
+/// stale too, reported with a baseline of 0.
+///
+/// Benchmarks run in a fresh LiteSVM are deterministic, so any difference means the IDL is stale
+/// and has to be regenerated with [`update_idl_compute_units`].
+pub fn check_idl_compute_units(
+    path: impl AsRef<Path>,
+    report: &Report,
+    group: &str,
+) -> io::Result<Vec<Regression>> {
+    let idl = load_idl(path.as_ref())?;
+    let recorded = idl.get(IDL_COMPUTE_UNITS);
+
+    Ok(idl_measured_units(&idl, report, group)
+        .into_iter()
+        .filter_map(|(name, current)| {
+            let baseline = recorded
+                .and_then(|units| units.get(&name)?.as_u64())
+                .unwrap_or(0);
+
+            (baseline != current).then(|| Regression {
+                group: group.to_string(),
+                case: name,
+                baseline,
+                current,
+            })
+        })
+        .collect())
+}
+
+fn load_idl(path: &Path) -> io::Result<serde_json::Value> {
+    let bytes = fs::read(path)?;
+    serde_json::from_slice(&bytes).map_err(io::Error::other)
+}
+
+/// Measured compute units of the cases named like an instruction of `idl`.
+fn idl_measured_units(
+    idl: &serde_json::Value,
+    report: &Report,
+    group: &str,
+) -> BTreeMap<String, u64> {
+    let cases = report.group_cases(group);
+
+    idl.get("instructions")
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|ix| ix.get("name")?.as_str())
+        .filter_map(|name| Some((name.to_string(), *cases.get(name)?)))
+        .collect()
+}
+
+/// Discriminator produced by `#[derive(Discriminator)]` for a type named `name`.
+pub fn discriminator(name: &str) -> [u8; 8] {
+    let hash = Sha256::digest(name.as_bytes());
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash[..8]);
+    discriminator
+}
+
+/// Anchor discriminator, e.g. `anchor_discriminator("global", "noop")` for instructions and
+/// `anchor_discriminator("account", "Foo")` for accounts.
+pub fn anchor_discriminator(namespace: &str, name: &str) -> [u8; 8] {
+    discriminator(&format!("{namespace}:{name}"))
+}
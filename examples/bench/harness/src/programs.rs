@@ -0,0 +1,48 @@
+//! Loading several programs into one LiteSVM.
+//!
+//! Protocols that CPI into each other are tested with every program deployed under its real ID:
+//! the program under test from `target/deploy`, its dependencies from checked-in fixtures (e.g.
+//! dumped with `solana program dump <id> fixtures/<name>.so`). [`register_programs!`] loads them
+//! all in one go, paths are relative to the manifest of the crate calling it.
+//!
+//! ```ignore
+//! register_programs!(svm, {
+//!     VAULT_ID => "../target/deploy/vault.so",
+//!     ORACLE_ID => "fixtures/oracle.so",
+//! });
+//! ```
+
+use litesvm::LiteSVM;
+use solana_sdk::pubkey::Pubkey;
+use std::path::Path;
+
+/// Loads the program at `path` under `program_id`, panicking with the path if it can't be read.
+pub fn register_program(svm: &mut LiteSVM, program_id: Pubkey, path: &Path) {
+    let bytes = std::fs::read(path).unwrap_or_else(|error| {
+        panic!(
+            "{} not found ({error}), run `cargo build-sbf` or add the fixture",
+            path.display()
+        )
+    });
+
+    svm.add_program(program_id, &bytes);
+}
+
+/// Loads every `program_id => "path"` pair into the LiteSVM `$svm`, see the
+/// [module docs](crate::programs).
+///
+/// Relative paths are resolved against `CARGO_MANIFEST_DIR` of the calling crate, so tests find
+/// their programs whatever directory `cargo test` runs from.
+#[macro_export]
+macro_rules! register_programs {
+    ($svm:expr, { $($program_id:expr => $path:literal),+ $(,)? }) => {{
+        let svm: &mut _ = $svm;
+        $(
+            $crate::programs::register_program(
+                &mut *svm,
+                $program_id,
+                ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join($path).as_path(),
+            );
+        )+
+    }};
+}
@@ -0,0 +1,303 @@
+//! Declarative multi-transaction tests.
+//!
+//! A [`Scenario`] is a list of steps run in order against a fresh LiteSVM: set up accounts, send
+//! instructions, warp the clock, edit accounts behind the program's back and assert on the
+//! resulting state and events. Steps are only recorded by the builder, [`Scenario::run`] executes
+//! them and panics with the name of the failing step and the logs of the last transaction.
+//!
+//! ```ignore
+//! Scenario::new()
+//!     .program(PROGRAM_ID, &bytes)
+//!     .account(vault, vault_account)
+//!     .send("deposit", deposit_ix, &[&user])
+//!     .send_err("early withdraw", withdraw_ix.clone(), &[&user])
+//!     .warp_slots(1_000)
+//!     .send("withdraw", withdraw_ix, &[&user])
+//!     .assert_events("withdraw event", |events| assert_eq!(events.len(), 1))
+//!     .assert_account("vault closed", vault, |account| assert!(account.is_none()))
+//!     .run();
+//! ```
+
+use crate::clock::{assert_time_guard, TimeGuard};
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account, clock::Clock, instruction::Instruction, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+
+type AccountFn = Box<dyn FnOnce(Option<&Account>)>;
+type MutateFn = Box<dyn FnOnce(&mut Account)>;
+type LogsFn = Box<dyn FnOnce(&[String])>;
+type ClockFn = Box<dyn FnOnce(&mut Clock)>;
+
+enum Step {
+    Program(Pubkey, Vec<u8>),
+    Account(Pubkey, Account),
+    Send {
+        name: String,
+        instructions: Vec<Instruction>,
+        signers: Vec<Keypair>,
+        expect_success: bool,
+    },
+    Clock(ClockFn),
+    TimeGuard(TimeGuard, Instruction, Vec<Keypair>),
+    Mutate(String, Pubkey, MutateFn),
+    AssertAccount(String, Pubkey, AccountFn),
+    AssertLogs(String, LogsFn),
+}
+
+/// Builder of a multi-transaction test, see the [module docs](self).
+pub struct Scenario {
+    svm: LiteSVM,
+    payer: Keypair,
+    steps: Vec<Step>,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scenario {
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        let payer = Keypair::new();
+
+        svm.airdrop(&payer.pubkey(), 1_000_000_000_000).unwrap();
+
+        Self {
+            svm,
+            payer,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Fee payer of every transaction, also available to build instructions before running.
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    pub fn program(mut self, program_id: Pubkey, program_bytes: &[u8]) -> Self {
+        self.steps
+            .push(Step::Program(program_id, program_bytes.to_vec()));
+        self
+    }
+
+    /// Creates or overwrites `address`.
+    pub fn account(mut self, address: Pubkey, account: Account) -> Self {
+        self.steps.push(Step::Account(address, account));
+        self
+    }
+
+    /// Sends `instruction` in its own transaction, paid for and signed by [`Scenario::payer`],
+    /// and fails the scenario if it doesn't succeed.
+    pub fn send(self, name: &str, instruction: Instruction, signers: &[&Keypair]) -> Self {
+        self.send_all(name, vec![instruction], signers)
+    }
+
+    /// Sends `instructions` in a single transaction.
+    pub fn send_all(
+        self,
+        name: &str,
+        instructions: Vec<Instruction>,
+        signers: &[&Keypair],
+    ) -> Self {
+        self.push_send(name, instructions, signers, true)
+    }
+
+    /// Sends `instruction` and fails the scenario if it succeeds.
+    pub fn send_err(self, name: &str, instruction: Instruction, signers: &[&Keypair]) -> Self {
+        self.push_send(name, vec![instruction], signers, false)
+    }
+
+    fn push_send(
+        mut self,
+        name: &str,
+        instructions: Vec<Instruction>,
+        signers: &[&Keypair],
+        expect_success: bool,
+    ) -> Self {
+        self.steps.push(Step::Send {
+            name: name.to_string(),
+            instructions,
+            signers: signers.iter().map(|k| k.insecure_clone()).collect(),
+            expect_success,
+        });
+        self
+    }
+
+    /// Edits the `Clock` sysvar, e.g. to move `unix_timestamp` past a deadline.
+    pub fn clock(mut self, f: impl FnOnce(&mut Clock) + 'static) -> Self {
+        self.steps.push(Step::Clock(Box::new(f)));
+        self
+    }
+
+    pub fn warp_to_slot(self, slot: u64) -> Self {
+        self.clock(move |clock| clock.slot = slot)
+    }
+
+    /// Advances the slot by `slots`.
+    pub fn warp_slots(self, slots: u64) -> Self {
+        self.clock(move |clock| clock.slot += slots)
+    }
+
+    /// Advances `unix_timestamp` by `seconds`.
+    pub fn warp_seconds(self, seconds: i64) -> Self {
+        self.clock(move |clock| clock.unix_timestamp += seconds)
+    }
+
+    /// Checks that `instruction` flips exactly at the boundary of `guard`, see
+    /// [`assert_time_guard`]. Only simulates, the state and clock are left as they were.
+    pub fn assert_time_guard(
+        mut self,
+        guard: TimeGuard,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> Self {
+        self.steps.push(Step::TimeGuard(
+            guard,
+            instruction,
+            signers.iter().map(|k| k.insecure_clone()).collect(),
+        ));
+        self
+    }
+
+    /// Edits `address` directly, without going through a program. Fails the scenario if the
+    /// account doesn't exist.
+    pub fn mutate_account(
+        mut self,
+        name: &str,
+        address: Pubkey,
+        f: impl FnOnce(&mut Account) + 'static,
+    ) -> Self {
+        self.steps
+            .push(Step::Mutate(name.to_string(), address, Box::new(f)));
+        self
+    }
+
+    /// Calls `f` with the current state of `address`, `None` if it doesn't exist.
+    pub fn assert_account(
+        mut self,
+        name: &str,
+        address: Pubkey,
+        f: impl FnOnce(Option<&Account>) + 'static,
+    ) -> Self {
+        self.steps
+            .push(Step::AssertAccount(name.to_string(), address, Box::new(f)));
+        self
+    }
+
+    /// Calls `f` with the logs of the last transaction.
+    pub fn assert_logs(mut self, name: &str, f: impl FnOnce(&[String]) + 'static) -> Self {
+        self.steps
+            .push(Step::AssertLogs(name.to_string(), Box::new(f)));
+        self
+    }
+
+    /// Calls `f` with the events emitted by the last transaction, see [`parse_events`].
+    pub fn assert_events(self, name: &str, f: impl FnOnce(&[Vec<u8>]) + 'static) -> Self {
+        self.assert_logs(name, move |logs| f(&parse_events(logs)))
+    }
+
+    /// Runs every step in order and returns the VM for further inspection.
+    pub fn run(self) -> LiteSVM {
+        let Self {
+            mut svm,
+            payer,
+            steps,
+        } = self;
+        let mut logs = Vec::new();
+
+        for step in steps {
+            match step {
+                Step::Program(program_id, bytes) => svm.add_program(program_id, &bytes),
+                Step::Account(address, account) => svm.set_account(address, account).unwrap(),
+                Step::Send {
+                    name,
+                    instructions,
+                    signers,
+                    expect_success,
+                } => {
+                    let mut all_signers = vec![&payer];
+                    all_signers.extend(&signers);
+
+                    let tx = Transaction::new_signed_with_payer(
+                        &instructions,
+                        Some(&payer.pubkey()),
+                        &all_signers,
+                        svm.latest_blockhash(),
+                    );
+
+                    let result = svm.send_transaction(tx);
+                    // identical transactions in a row would otherwise be rejected as already
+                    // processed
+                    svm.expire_blockhash();
+
+                    match (result, expect_success) {
+                        (Ok(meta), true) => logs = meta.logs,
+                        (Err(failed), false) => logs = failed.meta.logs,
+                        (Ok(meta), false) => {
+                            panic!(
+                                "{name}: succeeded, expected a failure\n{}",
+                                meta.logs.join("\n")
+                            )
+                        }
+                        (Err(failed), true) => panic!(
+                            "{name}: failed: {:?}\n{}",
+                            failed.err,
+                            failed.meta.logs.join("\n")
+                        ),
+                    }
+                }
+                Step::Clock(f) => {
+                    let mut clock = svm.get_sysvar::<Clock>();
+                    f(&mut clock);
+                    svm.set_sysvar(&clock);
+                }
+                Step::TimeGuard(guard, instruction, signers) => {
+                    let signers: Vec<_> = signers.iter().collect();
+                    assert_time_guard(&mut svm, &payer, guard, instruction, &signers);
+                }
+                Step::Mutate(name, address, f) => {
+                    let mut account = svm
+                        .get_account(&address)
+                        .unwrap_or_else(|| panic!("{name}: account {address} not found"));
+                    f(&mut account);
+                    svm.set_account(address, account).unwrap();
+                }
+                Step::AssertAccount(name, address, f) => {
+                    let account = svm.get_account(&address);
+                    run_step(&name, &logs, || f(account.as_ref()));
+                }
+                Step::AssertLogs(name, f) => run_step(&name, &logs, || f(&logs)),
+            }
+        }
+
+        svm
+    }
+}
+
+/// Runs an assertion, adding the step name and the last logs to its panic.
+fn run_step(name: &str, logs: &[String], f: impl FnOnce()) {
+    if let Err(panic) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)) {
+        eprintln!(
+            "step {name} failed, last transaction logs:\n{}",
+            logs.join("\n")
+        );
+        std::panic::resume_unwind(panic);
+    }
+}
+
+/// Bytes of every `Program log: EVENT: <hex>` line written by `emit!`, in log order.
+pub fn parse_events(logs: &[String]) -> Vec<Vec<u8>> {
+    logs.iter()
+        .filter_map(|line| line.strip_prefix("Program log: EVENT: "))
+        .filter_map(|hex| {
+            (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,184 @@
+//! SPL Token setup for token integration tests.
+//!
+//! `LiteSVM::new()` already deploys SPL Token, Token-2022 and the associated token program under
+//! their mainnet IDs, these helpers create the accounts a test needs on top of them. Mints are
+//! created under either token program, the ATA and minting helpers follow the owner of the mint.
+//! Every helper sends its own transaction paid by `payer` and panics with the logs on failure.
+//!
+//! ```ignore
+//! let mint = create_mint(&mut svm, &payer, &TOKEN_PROGRAM_ID, &authority.pubkey(), 6);
+//! let ata = create_ata(&mut svm, &payer, &user, &mint);
+//! mint_to(&mut svm, &payer, &mint, &ata, &authority, 1_000_000);
+//! assert_eq!(token_balance(&svm, &ata), 1_000_000);
+//! ```
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction, system_program,
+    transaction::Transaction,
+};
+
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Size of a mint without extensions
+pub const MINT_LEN: usize = 82;
+
+/// Token program instruction tags
+const INITIALIZE_MINT_2: u8 = 20;
+const MINT_TO: u8 = 7;
+/// Associated token program `CreateIdempotent`
+const CREATE_IDEMPOTENT: u8 = 1;
+
+/// Creates a mint owned by `token_program` without freeze authority, returning its address.
+pub fn create_mint(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    token_program: &Pubkey,
+    mint_authority: &Pubkey,
+    decimals: u8,
+) -> Pubkey {
+    let mint = Keypair::new();
+    let lamports = svm.minimum_balance_for_rent_exemption(MINT_LEN);
+
+    let mut data = vec![INITIALIZE_MINT_2, decimals];
+    data.extend_from_slice(mint_authority.as_ref());
+    // no freeze authority
+    data.push(0);
+
+    send(
+        svm,
+        payer,
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                lamports,
+                MINT_LEN as u64,
+                token_program,
+            ),
+            Instruction::new_with_bytes(
+                *token_program,
+                &data,
+                vec![AccountMeta::new(mint.pubkey(), false)],
+            ),
+        ],
+        &[&mint],
+    );
+
+    mint.pubkey()
+}
+
+/// Associated token account of `owner` for `mint` under `token_program`.
+pub fn ata_address(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Creates the associated token account of `owner` for `mint`, returning its address. Does
+/// nothing if it already exists.
+pub fn create_ata(svm: &mut LiteSVM, payer: &Keypair, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let token_program = token_program_of(svm, mint);
+    let ata = ata_address(owner, mint, &token_program);
+
+    send(
+        svm,
+        payer,
+        &[Instruction::new_with_bytes(
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+            &[CREATE_IDEMPOTENT],
+            vec![
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new(ata, false),
+                AccountMeta::new_readonly(*owner, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(token_program, false),
+            ],
+        )],
+        &[],
+    );
+
+    ata
+}
+
+/// Mints `amount` base units of `mint` to the token account `destination`.
+pub fn mint_to(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    mint_authority: &Keypair,
+    amount: u64,
+) {
+    let token_program = token_program_of(svm, mint);
+
+    let mut data = vec![MINT_TO];
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    send(
+        svm,
+        payer,
+        &[Instruction::new_with_bytes(
+            token_program,
+            &data,
+            vec![
+                AccountMeta::new(*mint, false),
+                AccountMeta::new(*destination, false),
+                AccountMeta::new_readonly(mint_authority.pubkey(), true),
+            ],
+        )],
+        &[mint_authority],
+    );
+}
+
+/// Balance of the token account `account`, panics if it doesn't exist.
+pub fn token_balance(svm: &LiteSVM, account: &Pubkey) -> u64 {
+    let account = svm
+        .get_account(account)
+        .unwrap_or_else(|| panic!("token account {account} not found"));
+
+    // mint (32), owner (32), amount (8)
+    u64::from_le_bytes(account.data[64..72].try_into().unwrap())
+}
+
+/// Owner of the mint account, the token program every instruction on it goes to.
+fn token_program_of(svm: &LiteSVM, mint: &Pubkey) -> Pubkey {
+    svm.get_account(mint)
+        .unwrap_or_else(|| panic!("mint {mint} not found"))
+        .owner
+}
+
+fn send(svm: &mut LiteSVM, payer: &Keypair, instructions: &[Instruction], signers: &[&Keypair]) {
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        svm.latest_blockhash(),
+    );
+
+    let result = svm.send_transaction(tx);
+    // the same setup may run twice, e.g. an idempotent ATA creation
+    svm.expire_blockhash();
+
+    if let Err(failed) = result {
+        panic!(
+            "token setup failed: {:?}\n{}",
+            failed.err,
+            failed.meta.logs.join("\n")
+        );
+    }
+}
@@ -0,0 +1,218 @@
+//! Head-to-head CU comparison of `bench-hayabusa`, `bench-pinocchio` and `bench-anchor`.
+//!
+//! Build the programs first with `cargo build-sbf`, then run `cargo test -- --nocapture`.
+//! Set `UPDATE_BASELINE=1` to overwrite `baseline.json` with the current numbers, and the compute
+//! units section of the `bench-hayabusa` IDL.
+
+use bench_harness::{
+    anchor_discriminator, check_idl_compute_units, discriminator, register_programs,
+    update_idl_compute_units, Bench, Report,
+};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+const HAYABUSA_ID: Pubkey = pubkey!("3ioimjgaQ7LZ48hXMjhygKHSqeCWEJcreBFkFJ8QToyb");
+const PINOCCHIO_ID: Pubkey = pubkey!("EjSZwcVdobuWJt1pMZsmNqSjkDkwvCPXiUsbeUt8Gcgt");
+const ANCHOR_ID: Pubkey = pubkey!("FRToYBY1fizAABUynpeoRoBe22AHDrwQCqsyh44PSQmW");
+
+const BASELINE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/baseline.json");
+const TOLERANCE_PERCENT: u64 = 2;
+const HAYABUSA_IDL: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../programs/bench-hayabusa/idl.json"
+);
+
+/// Per-framework instruction encoding, everything else is shared between the cases.
+struct Framework {
+    name: &'static str,
+    program_id: Pubkey,
+    instruction_tag: fn(&str) -> Vec<u8>,
+    account_tag: [u8; 8],
+}
+
+fn frameworks() -> [Framework; 3] {
+    [
+        Framework {
+            name: "hayabusa",
+            program_id: HAYABUSA_ID,
+            instruction_tag: |name| discriminator(&hayabusa_ix_name(name)).to_vec(),
+            account_tag: discriminator("BenchAccount"),
+        },
+        Framework {
+            name: "pinocchio",
+            program_id: PINOCCHIO_ID,
+            instruction_tag: |name| {
+                let tag = match name {
+                    "noop" => 0,
+                    "parse_accounts" => 1,
+                    "transfer" => 2,
+                    "emit_event" => 3,
+                    _ => unreachable!(),
+                };
+                vec![tag]
+            },
+            account_tag: *b"benchacc",
+        },
+        Framework {
+            name: "anchor",
+            program_id: ANCHOR_ID,
+            instruction_tag: |name| anchor_discriminator("global", name).to_vec(),
+            account_tag: anchor_discriminator("account", "BenchAccount"),
+        },
+    ]
+}
+
+/// `parse_accounts` -> `ParseAccountsIx`
+fn hayabusa_ix_name(name: &str) -> String {
+    let mut ix_name: String = name
+        .split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect();
+    ix_name.push_str("Ix");
+    ix_name
+}
+
+fn ix_data(framework: &Framework, name: &str, arg: Option<u64>) -> Vec<u8> {
+    let mut data = (framework.instruction_tag)(name);
+    if let Some(arg) = arg {
+        data.extend_from_slice(&arg.to_le_bytes());
+    }
+    data
+}
+
+#[test]
+fn bench() {
+    let mut bench = Bench::new();
+
+    register_programs!(bench.svm(), {
+        HAYABUSA_ID => "../target/deploy/bench_hayabusa.so",
+        PINOCCHIO_ID => "../target/deploy/bench_pinocchio.so",
+        ANCHOR_ID => "../target/deploy/bench_anchor.so",
+    });
+
+    let authority = Keypair::new();
+    let recipient = Pubkey::new_unique();
+
+    bench.svm().airdrop(&authority.pubkey(), 1_000_000_000).unwrap();
+    bench.svm().airdrop(&recipient, 1_000_000_000).unwrap();
+
+    for framework in frameworks() {
+        let program_id = framework.program_id;
+
+        // dispatch only
+        bench.measure(
+            framework.name,
+            "noop",
+            Instruction::new_with_bytes(program_id, &ix_data(&framework, "noop", None), vec![]),
+            &[],
+        );
+
+        // signer + writable zero-copy account, owner/discriminator/length checks
+        let state = Pubkey::new_unique();
+        let mut data = framework.account_tag.to_vec();
+        data.resize(8 + 32 + 8, 0);
+        let lamports = bench.svm().minimum_balance_for_rent_exemption(data.len());
+        bench.set_account(
+            state,
+            Account {
+                lamports,
+                data,
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        bench.measure(
+            framework.name,
+            "parse_accounts",
+            Instruction::new_with_bytes(
+                program_id,
+                &ix_data(&framework, "parse_accounts", Some(42)),
+                vec![
+                    AccountMeta::new_readonly(authority.pubkey(), true),
+                    AccountMeta::new(state, false),
+                ],
+            ),
+            &[&authority],
+        );
+
+        // system program transfer CPI
+        bench.measure(
+            framework.name,
+            "transfer",
+            Instruction::new_with_bytes(
+                program_id,
+                &ix_data(&framework, "transfer", Some(1_000)),
+                vec![
+                    AccountMeta::new(authority.pubkey(), true),
+                    AccountMeta::new(recipient, false),
+                    AccountMeta::new_readonly(system_program::ID, false),
+                ],
+            ),
+            &[&authority],
+        );
+
+        // event emission
+        bench.measure(
+            framework.name,
+            "emit_event",
+            Instruction::new_with_bytes(
+                program_id,
+                &ix_data(&framework, "emit_event", Some(42)),
+                vec![AccountMeta::new_readonly(authority.pubkey(), true)],
+            ),
+            &[&authority],
+        );
+    }
+
+    let report = bench.into_report();
+    println!("{report}");
+
+    if std::env::var("UPDATE_BASELINE").is_ok() {
+        report.save(BASELINE).unwrap();
+        update_idl_compute_units(HAYABUSA_IDL, &report, "hayabusa").unwrap();
+        return;
+    }
+
+    // exact, the numbers clients read from the IDL must match the program
+    let stale = check_idl_compute_units(HAYABUSA_IDL, &report, "hayabusa").unwrap();
+    assert!(
+        stale.is_empty(),
+        "stale compute units in {HAYABUSA_IDL}, run with UPDATE_BASELINE=1:\n{}",
+        stale
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+
+    let Ok(baseline) = Report::load(BASELINE) else {
+        println!("no baseline at {BASELINE}, run with UPDATE_BASELINE=1 to record one");
+        return;
+    };
+
+    let regressions = report.compare(&baseline, TOLERANCE_PERCENT);
+    assert!(
+        regressions.is_empty(),
+        "CU regressions above {TOLERANCE_PERCENT}%:\n{}",
+        regressions
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+}
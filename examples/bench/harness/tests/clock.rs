@@ -0,0 +1,41 @@
+use bench_harness::{
+    clock::{assert_time_guard, TimeGuard},
+    Bench,
+};
+use solana_sdk::{pubkey::Pubkey, signer::Signer, system_instruction};
+
+#[test]
+fn warp() {
+    let mut bench = Bench::new();
+    let start = bench.clock();
+
+    let clock = bench.warp_to_slot(start.slot + 100);
+    assert_eq!(clock.slot, start.slot + 100);
+    assert_eq!(clock.unix_timestamp, start.unix_timestamp);
+
+    let clock = bench.warp_seconds(60);
+    assert_eq!(clock.slot, start.slot + 100);
+    assert_eq!(clock.unix_timestamp, start.unix_timestamp + 60);
+
+    assert!(TimeGuard::Reached(clock.unix_timestamp).check(&clock));
+    assert!(!TimeGuard::Reached(clock.unix_timestamp + 1).check(&clock));
+    assert!(TimeGuard::SlotDeadline(clock.slot).check(&clock));
+    assert!(!TimeGuard::SlotDeadline(clock.slot - 1).check(&clock));
+}
+
+#[test]
+#[should_panic(expected = "succeeded at offset -1")]
+fn unguarded_instruction() {
+    let mut bench = Bench::new();
+    let payer = bench.payer().pubkey();
+    let transfer = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1_000_000);
+
+    let payer = bench.payer().insecure_clone();
+    assert_time_guard(
+        bench.svm(),
+        &payer,
+        TimeGuard::Reached(1_000),
+        transfer,
+        &[],
+    );
+}
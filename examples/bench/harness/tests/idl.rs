@@ -0,0 +1,37 @@
+use bench_harness::{check_idl_compute_units, update_idl_compute_units, Report};
+use std::fs;
+
+const IDL: &str = r#"{
+  "instructions": [{ "name": "noop" }, { "name": "transfer" }, { "name": "unmeasured" }]
+}"#;
+
+#[test]
+fn idl_compute_units() {
+    let path = std::env::temp_dir().join(format!("bench-idl-{}.json", std::process::id()));
+    fs::write(&path, IDL).unwrap();
+
+    let mut report = Report::default();
+    report.record("hayabusa", "noop", 12);
+    report.record("hayabusa", "transfer", 1305);
+    report.record("hayabusa", "not_an_instruction", 7);
+
+    // no section yet, every measured instruction is stale
+    let stale = check_idl_compute_units(&path, &report, "hayabusa").unwrap();
+    assert_eq!(
+        stale.iter().map(|r| (r.case.as_str(), r.baseline)).collect::<Vec<_>>(),
+        [("noop", 0), ("transfer", 0)]
+    );
+
+    update_idl_compute_units(&path, &report, "hayabusa").unwrap();
+    assert!(check_idl_compute_units(&path, &report, "hayabusa").unwrap().is_empty());
+
+    let idl: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    assert_eq!(idl["computeUnits"], serde_json::json!({ "noop": 12, "transfer": 1305 }));
+
+    report.record("hayabusa", "transfer", 1310);
+    let stale = check_idl_compute_units(&path, &report, "hayabusa").unwrap();
+    assert_eq!(stale.len(), 1);
+    assert_eq!((stale[0].baseline, stale[0].current), (1305, 1310));
+
+    fs::remove_file(&path).unwrap();
+}
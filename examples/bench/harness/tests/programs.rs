@@ -0,0 +1,13 @@
+use bench_harness::register_programs;
+use litesvm::LiteSVM;
+use solana_sdk::pubkey::Pubkey;
+
+#[test]
+#[should_panic(expected = "missing.so not found")]
+fn missing_program() {
+    let mut svm = LiteSVM::new();
+
+    register_programs!(&mut svm, {
+        Pubkey::new_unique() => "fixtures/missing.so",
+    });
+}
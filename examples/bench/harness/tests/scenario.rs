@@ -0,0 +1,58 @@
+use bench_harness::scenario::{parse_events, Scenario};
+use solana_sdk::{
+    account::Account, pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
+    system_program,
+};
+
+#[test]
+fn scenario() {
+    let sender = Keypair::new();
+    let recipient = Pubkey::new_unique();
+
+    let svm = Scenario::new()
+        .account(
+            sender.pubkey(),
+            Account::new(1_000_000_000, 0, &system_program::ID),
+        )
+        .send(
+            "transfer",
+            system_instruction::transfer(&sender.pubkey(), &recipient, 1_000),
+            &[&sender],
+        )
+        .assert_account("recipient funded", recipient, |account| {
+            assert_eq!(account.unwrap().lamports, 1_000)
+        })
+        .send_err(
+            "overdraft",
+            system_instruction::transfer(&sender.pubkey(), &recipient, 2_000_000_000),
+            &[&sender],
+        )
+        .assert_logs("overdraft logs", |logs| {
+            assert!(logs
+                .iter()
+                .any(|line| line.contains("insufficient lamports")))
+        })
+        .warp_slots(100)
+        .warp_seconds(60)
+        .mutate_account("drain recipient", recipient, |account| {
+            account.lamports = 500
+        })
+        .assert_account("recipient drained", recipient, |account| {
+            assert_eq!(account.unwrap().lamports, 500)
+        })
+        .run();
+
+    let clock = svm.get_sysvar::<solana_sdk::clock::Clock>();
+    assert_eq!(clock.slot, 100);
+}
+
+#[test]
+fn events() {
+    let logs = [
+        "Program log: EVENT: 0102ff".to_string(),
+        "Program log: hello".to_string(),
+        "Program log: EVENT: 0g".to_string(),
+    ];
+
+    assert_eq!(parse_events(&logs), vec![vec![1, 2, 255]]);
+}
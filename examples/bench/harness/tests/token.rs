@@ -0,0 +1,30 @@
+use bench_harness::token::{
+    ata_address, create_ata, create_mint, mint_to, token_balance, TOKEN_2022_PROGRAM_ID,
+    TOKEN_PROGRAM_ID,
+};
+use litesvm::LiteSVM;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+#[test]
+fn mint_to_ata() {
+    for token_program in [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+        let mut svm = LiteSVM::new();
+        let payer = Keypair::new();
+        let authority = Keypair::new();
+        let user = Pubkey::new_unique();
+
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+        let mint = create_mint(&mut svm, &payer, &token_program, &authority.pubkey(), 6);
+        let ata = create_ata(&mut svm, &payer, &user, &mint);
+        assert_eq!(ata, ata_address(&user, &mint, &token_program));
+        assert_eq!(svm.get_account(&ata).unwrap().owner, token_program);
+
+        mint_to(&mut svm, &payer, &mint, &ata, &authority, 1_000_000);
+        assert_eq!(token_balance(&svm, &ata), 1_000_000);
+
+        // idempotent
+        create_ata(&mut svm, &payer, &user, &mint);
+        assert_eq!(token_balance(&svm, &ata), 1_000_000);
+    }
+}
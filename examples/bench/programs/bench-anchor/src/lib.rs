@@ -0,0 +1,84 @@
+#![allow(unexpected_cfgs)]
+
+//! Anchor equivalent of `bench-hayabusa`, using zero-copy accounts so the comparison stays
+//! apples-to-apples on the account parsing path.
+
+use anchor_lang::{prelude::*, system_program};
+
+declare_id!("FRToYBY1fizAABUynpeoRoBe22AHDrwQCqsyh44PSQmW");
+
+#[program]
+pub mod bench_anchor {
+    use super::*;
+
+    pub fn noop(_ctx: Context<Noop>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn parse_accounts(ctx: Context<ParseAccounts>, value: u64) -> Result<()> {
+        let mut state = ctx.accounts.state.load_mut()?;
+
+        state.authority = ctx.accounts.authority.key();
+        state.value = value;
+
+        Ok(())
+    }
+
+    pub fn transfer(ctx: Context<TransferLamports>, amount: u64) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.from.to_account_info(),
+                    to: ctx.accounts.to.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+
+    pub fn emit_event(ctx: Context<EmitEvent>, value: u64) -> Result<()> {
+        emit!(BenchEvent {
+            authority: ctx.accounts.authority.key(),
+            value,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Noop {}
+
+#[derive(Accounts)]
+pub struct ParseAccounts<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub state: AccountLoader<'info, BenchAccount>,
+}
+
+#[derive(Accounts)]
+pub struct TransferLamports<'info> {
+    #[account(mut)]
+    pub from: Signer<'info>,
+    #[account(mut)]
+    pub to: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EmitEvent<'info> {
+    pub authority: Signer<'info>,
+}
+
+#[account(zero_copy)]
+pub struct BenchAccount {
+    pub authority: Pubkey,
+    pub value: u64,
+}
+
+#[event]
+pub struct BenchEvent {
+    pub authority: Pubkey,
+    pub value: u64,
+}
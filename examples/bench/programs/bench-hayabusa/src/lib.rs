@@ -0,0 +1,175 @@
+#![no_std]
+#![allow(dead_code, unexpected_cfgs)]
+
+use hayabusa::prelude::*;
+
+declare_id!("3ioimjgaQ7LZ48hXMjhygKHSqeCWEJcreBFkFJ8QToyb");
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint {
+    use super::*;
+
+    program_entrypoint!(program_entrypoint);
+    no_allocator!();
+    nostd_panic_handler!();
+
+    pub fn program_entrypoint(
+        program_id: &Address,
+        accounts: &[AccountView],
+        instruction_data: &[u8],
+    ) -> Result<()> {
+        dispatch!(
+            program_id,
+            instruction_data,
+            accounts,
+            NoopIx => noop(),
+            ParseAccountsIx => parse_accounts(value),
+            TransferIx => transfer_lamports(amount),
+            EmitEventIx => emit_event(value),
+        );
+    }
+}
+
+#[derive(Clone, Copy, Discriminator)]
+#[repr(C)]
+struct NoopIx {}
+
+impl<'ix> DecodeIx<'ix> for NoopIx {
+    #[inline(always)]
+    fn decode(_: &'ix [u8]) -> Result<Self> {
+        Ok(Self {})
+    }
+}
+
+fn noop<'ix>(_: Ctx<'ix, Noop>) -> Result<()> {
+    Ok(())
+}
+
+pub struct Noop;
+
+impl<'ix> FromAccountViews<'ix> for Noop {
+    type Bumps = NoBumps;
+
+    #[inline(always)]
+    fn try_from_account_views(_: &mut AccountIter<'ix>, _: &mut NoBumps) -> Result<Self> {
+        Ok(Noop)
+    }
+}
+
+#[derive(Clone, Copy, Discriminator)]
+#[repr(C)]
+struct ParseAccountsIx {
+    value: u64,
+}
+
+impl<'ix> DecodeIx<'ix> for ParseAccountsIx {
+    #[inline(always)]
+    fn decode(instruction_data: &'ix [u8]) -> Result<Self> {
+        Ok(Self {
+            value: decode_u64(instruction_data)?,
+        })
+    }
+}
+
+fn parse_accounts<'ix>(ctx: Ctx<'ix, ParseAccounts<'ix>>, value: u64) -> Result<()> {
+    let mut state = ctx.state.try_deserialize_mut()?;
+
+    state.authority = *ctx.authority.address();
+    state.value = value;
+
+    Ok(())
+}
+
+#[derive(FromAccountViews)]
+pub struct ParseAccounts<'ix> {
+    pub authority: Signer<'ix>,
+    pub state: Mut<ZcAccount<'ix, BenchAccount>>,
+}
+
+#[derive(Clone, Copy, Discriminator)]
+#[repr(C)]
+struct TransferIx {
+    amount: u64,
+}
+
+impl<'ix> DecodeIx<'ix> for TransferIx {
+    #[inline(always)]
+    fn decode(instruction_data: &'ix [u8]) -> Result<Self> {
+        Ok(Self {
+            amount: decode_u64(instruction_data)?,
+        })
+    }
+}
+
+fn transfer_lamports<'ix>(ctx: Ctx<'ix, TransferLamports<'ix>>, amount: u64) -> Result<()> {
+    let cpi_ctx = CpiCtx::try_new_without_signer(
+        ctx.system_program.to_account_view(),
+        system_program::instructions::Transfer {
+            from: ctx.from.to_account_view(),
+            to: ctx.to.to_account_view(),
+        },
+    )?;
+
+    system_program::instructions::transfer(cpi_ctx, Lamports::new(amount))
+}
+
+#[derive(FromAccountViews)]
+pub struct TransferLamports<'ix> {
+    pub from: Mut<Signer<'ix>>,
+    pub to: Mut<SystemAccount<'ix>>,
+    pub system_program: Program<'ix, System>,
+}
+
+#[derive(Clone, Copy, Discriminator)]
+#[repr(C)]
+struct EmitEventIx {
+    value: u64,
+}
+
+impl<'ix> DecodeIx<'ix> for EmitEventIx {
+    #[inline(always)]
+    fn decode(instruction_data: &'ix [u8]) -> Result<Self> {
+        Ok(Self {
+            value: decode_u64(instruction_data)?,
+        })
+    }
+}
+
+fn emit_event<'ix>(ctx: Ctx<'ix, EmitEvent<'ix>>, value: u64) -> Result<()> {
+    emit!(BenchEvent {
+        authority: *ctx.authority.address(),
+        value,
+    });
+
+    Ok(())
+}
+
+#[derive(FromAccountViews)]
+pub struct EmitEvent<'ix> {
+    pub authority: Signer<'ix>,
+}
+
+#[inline(always)]
+fn decode_u64(instruction_data: &[u8]) -> Result<u64> {
+    if unlikely(instruction_data.len() != 8) {
+        error_msg!(
+            "decode_u64: invalid instruction data length",
+            ProgramError::InvalidInstructionData,
+        );
+    }
+
+    Ok(unsafe { core::ptr::read_unaligned(instruction_data.as_ptr() as *const u64) })
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct BenchAccount {
+    pub authority: Address,
+    pub value: u64,
+}
+
+#[event]
+pub struct BenchEvent {
+    pub authority: Address,
+    pub value: u64,
+}
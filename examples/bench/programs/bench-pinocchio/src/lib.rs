@@ -0,0 +1,157 @@
+#![no_std]
+#![allow(dead_code, unexpected_cfgs)]
+
+//! Hand-written pinocchio equivalent of `bench-hayabusa`.
+//!
+//! Every check performed by the hayabusa account types is performed here by hand, so the
+//! measured difference is framework overhead rather than skipped validation.
+
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+pinocchio_pubkey::declare_id!("EjSZwcVdobuWJt1pMZsmNqSjkDkwvCPXiUsbeUt8Gcgt");
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint {
+    use super::*;
+
+    pinocchio::program_entrypoint!(process_instruction);
+    pinocchio::no_allocator!();
+    pinocchio::nostd_panic_handler!();
+}
+
+pub const NOOP: u8 = 0;
+pub const PARSE_ACCOUNTS: u8 = 1;
+pub const TRANSFER: u8 = 2;
+pub const EMIT_EVENT: u8 = 3;
+
+/// Fixed 8 byte account tag, keeps the account body aligned the same way hayabusa does.
+pub const BENCH_ACCOUNT_TAG: [u8; 8] = *b"benchacc";
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    if program_id != &ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let (tag, rest) = instruction_data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    match *tag {
+        NOOP => Ok(()),
+        PARSE_ACCOUNTS => parse_accounts(accounts, decode_u64(rest)?),
+        TRANSFER => transfer(accounts, decode_u64(rest)?),
+        EMIT_EVENT => emit_event(accounts, decode_u64(rest)?),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn parse_accounts(accounts: &[AccountInfo], value: u64) -> ProgramResult {
+    let [authority, state, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !state.is_writable() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !state.is_owned_by(&ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let mut data = state.try_borrow_mut_data()?;
+
+    if data.len() != 8 + core::mem::size_of::<BenchAccount>() || data[..8] != BENCH_ACCOUNT_TAG {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let bench_account: &mut BenchAccount = bytemuck::from_bytes_mut(&mut data[8..]);
+    bench_account.authority = *authority.key();
+    bench_account.value = value;
+
+    Ok(())
+}
+
+fn transfer(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let [from, to, system_program, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !from.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !from.is_writable() || !to.is_writable() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !to.is_owned_by(&pinocchio_system::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if system_program.key() != &pinocchio_system::ID || !system_program.executable() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    pinocchio_system::instructions::Transfer {
+        from,
+        to,
+        lamports: amount,
+    }
+    .invoke()
+}
+
+fn emit_event(accounts: &[AccountInfo], value: u64) -> ProgramResult {
+    let [authority, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Same wire format as hayabusa's `#[event]`: 8 byte tag + fields, hex encoded.
+    const LEN: usize = 8 + 32 + 8;
+    let mut buf = [0u8; LEN];
+    buf[8..40].copy_from_slice(authority.key());
+    buf[40..].copy_from_slice(&value.to_le_bytes());
+
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut log = [0u8; 7 + LEN * 2];
+    log[..7].copy_from_slice(b"EVENT: ");
+    for (i, b) in buf.iter().enumerate() {
+        log[7 + 2 * i] = HEX[(b >> 4) as usize];
+        log[7 + 2 * i + 1] = HEX[(b & 0x0f) as usize];
+    }
+
+    // SAFETY: hex output is always valid ASCII
+    pinocchio::log::sol_log(unsafe { core::str::from_utf8_unchecked(&log) });
+
+    Ok(())
+}
+
+#[inline(always)]
+fn decode_u64(data: &[u8]) -> Result<u64, ProgramError> {
+    let bytes: [u8; 8] = data
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct BenchAccount {
+    pub authority: Pubkey,
+    pub value: u64,
+}
@@ -1,6 +1,8 @@
 #![no_std]
 #![allow(dead_code, unexpected_cfgs)]
 
+extern crate alloc;
+
 use hayabusa::prelude::*;
 
 declare_id!("HPoDm7Kf63B6TpFKV7S8YSd7sGde6sVdztiDBEVkfuxz");
@@ -25,13 +27,27 @@ mod entrypoint {
             UpdateCounterIx => update_counter(amount),
             InitializeCounterIx => initialize_counter(),
             NoOpIx => noop(),
+            BuildInfoIx => build_info(),
+            ProgramManifestIx => program_manifest(offset),
         );
     }
 }
 
+build_info!();
+
+program_manifest!(
+    SetCounterInstruction,
+    AddCounterInstruction,
+    ClampCounterInstruction,
+    BumpLegacyCounterInstruction,
+    BumpTallyInstruction,
+    NothingInstruction,
+    RecordReceiptInstruction,
+);
+
 #[derive(Clone, Copy, Discriminator)]
 #[repr(C)]
-struct UpdateCounterIx {
+pub struct UpdateCounterIx {
     amount: u64, // field name must map identically to the instruction param name, and be in the same order.
 }
 
@@ -70,8 +86,10 @@ pub struct UpdateCounter<'ix> {
 
 // Intentionally kept manual, you get to see what the FromAccountViews proc macro is doing
 impl<'ix> FromAccountViews<'ix> for UpdateCounter<'ix> {
+    type Bumps = NoBumps;
+
     #[inline(always)]
-    fn try_from_account_views(account_views: &mut AccountIter<'ix>) -> Result<Self> {
+    fn try_from_account_views(account_views: &mut AccountIter<'ix>, _: &mut NoBumps) -> Result<Self> {
         let user = Signer::try_from_account_view(account_views.next()?, NoMeta)?;
         let counter = Mut::try_from_account_view(account_views.next()?, NoMeta)?;
 
@@ -84,7 +102,7 @@ impl<'ix> FromAccountViews<'ix> for UpdateCounter<'ix> {
 
 #[derive(Clone, Copy, Discriminator)]
 #[repr(C)]
-struct InitializeCounterIx {}
+pub struct InitializeCounterIx {}
 
 impl<'ix> DecodeIx<'ix> for InitializeCounterIx {
     fn decode(_: &'ix [u8]) -> Result<Self> {
@@ -115,7 +133,7 @@ pub struct InitializeCounter<'ix> {
 
 #[derive(Clone, Copy, Discriminator)]
 #[repr(C)]
-struct NoOpIx {}
+pub struct NoOpIx {}
 
 impl<'ix> DecodeIx<'ix> for NoOpIx {
     fn decode(_: &'ix [u8]) -> Result<Self> {
@@ -130,7 +148,9 @@ fn noop<'ix>(_: Ctx<'ix, NoOp>) -> Result<()> {
 pub struct NoOp;
 
 impl<'ix> FromAccountViews<'ix> for NoOp {
-    fn try_from_account_views(_: &mut AccountIter<'ix>) -> Result<Self> {
+    type Bumps = NoBumps;
+
+    fn try_from_account_views(_: &mut AccountIter<'ix>, _: &mut NoBumps) -> Result<Self> {
         Ok(NoOp)
     }
 }
@@ -187,4 +207,1050 @@ pub struct Test {
 #[event]
 pub struct TestEvent {
     pub value: u64,
-}
\ No newline at end of file
+}
+#[derive(FromAccountViews)]
+pub struct SeedsTest<'ix> {
+    pub user: Signer<'ix>,
+    #[meta(seeds = [b"counter", user.address()], bump)]
+    pub counter: ZcAccount<'ix, CounterAccount>,
+    #[meta(seeds = [b"vault", user.address(), counter.address()], bump = 254)]
+    pub vault: SystemAccount<'ix>,
+}
+
+pub const CONFIG: Address = Address::new_from_array([7; 32]);
+
+#[derive(FromAccountViews)]
+pub struct AddressTest<'ix> {
+    pub user: Signer<'ix>,
+    #[meta(address = crate::CONFIG)]
+    pub config: UncheckedAccount<'ix>,
+    #[meta(address = user.address())]
+    pub same_user: UncheckedAccount<'ix>,
+}
+
+#[error]
+pub enum CounterError {
+    InvalidCap,
+}
+
+#[derive(FromAccountViews)]
+pub struct ConstraintTest<'ix> {
+    #[meta(constraint = counter.try_deserialize()?.count > 0 @ CounterError::InvalidCap)]
+    pub user: Signer<'ix>,
+    #[meta(constraint = counter.lamports() > 0, constraint = user.address() != counter.address())]
+    pub counter: ZcAccount<'ix, CounterAccount>,
+}
+
+#[derive(FromAccountViews)]
+pub struct InitTest<'ix> {
+    #[meta(init, payer = user)]
+    pub counter: Mut<ZcAccount<'ix, CounterAccount>>,
+    #[meta(init, payer = user, space = 8 + core::mem::size_of::<CounterAccount>(), seeds = [b"counter", user.address()], bump)]
+    pub pda_counter: Mut<ZcAccount<'ix, CounterAccount>>,
+    pub user: Mut<Signer<'ix>>,
+    pub system_program: Program<'ix, System>,
+}
+
+#[derive(FromAccountViews)]
+pub struct CloseTest<'ix> {
+    pub user: Mut<Signer<'ix>>,
+    #[meta(close = user)]
+    pub counter: Mut<ZcAccount<'ix, CounterAccount>>,
+}
+
+#[derive(FromAccountViews)]
+pub struct ReallocTest<'ix> {
+    pub user: Mut<Signer<'ix>>,
+    #[meta(realloc = 8 + core::mem::size_of::<CounterAccount>() + 32, realloc::payer = user, realloc::zero = true)]
+    pub counter: Mut<ZcAccount<'ix, CounterAccount>>,
+    pub system_program: Program<'ix, System>,
+}
+
+#[derive(FromAccountViews)]
+pub struct TokenTest<'ix> {
+    pub authority: Signer<'ix>,
+    pub mint: UncheckedAccount<'ix>,
+    #[meta(token::mint = mint, token::authority = authority)]
+    pub vault: ZcAccount<'ix, hayabusa_token::state::TokenAccount>,
+    #[meta(token::mint = crate::CONFIG)]
+    pub other: Mut<ZcAccount<'ix, hayabusa_token::state::TokenAccount>>,
+}
+
+#[derive(FromAccountViews)]
+pub struct AtaTest<'ix> {
+    pub user: Mut<Signer<'ix>>,
+    pub mint: UncheckedAccount<'ix>,
+    #[meta(associated_token::mint = mint, associated_token::authority = user)]
+    pub user_ata: ZcAccount<'ix, hayabusa_token::state::TokenAccount>,
+    pub owner: UncheckedAccount<'ix>,
+    #[meta(init, payer = user, associated_token::mint = mint, associated_token::authority = owner)]
+    pub owner_ata: Mut<UncheckedAccount<'ix>>,
+    pub token_program: Program<'ix, hayabusa_token::Token>,
+    pub associated_token_program: Program<'ix, associated_token::AssociatedToken>,
+    pub system_program: Program<'ix, System>,
+}
+
+#[derive(FromAccountViews)]
+pub struct SysvarTest<'ix> {
+    pub clock: SysvarAccount<'ix, Clock>,
+    pub slot_hashes: SysvarAccount<'ix, SlotHashes<'ix>>,
+    #[meta(constraint = clock.try_get()?.slot > 0)]
+    pub rent: SysvarAccount<'ix, sysvars::rent::Rent>,
+}
+
+#[instruction(client, inline(never))]
+pub fn set_counter<'ix>(ctx: Ctx<'ix, UpdateCounter<'ix>>, value: u64, #[range(0..=1)] flag: u8) -> Result<()> {
+    ctx.counter.try_deserialize_mut()?.count = value + flag as u64;
+    Ok(())
+}
+
+#[instruction(varint, client, inline(never))]
+pub fn add_counter<'ix>(ctx: Ctx<'ix, UpdateCounter<'ix>>, amount: u64, delta: i32) -> Result<()> {
+    ctx.counter.try_deserialize_mut()?.count = amount.saturating_add_signed(delta as i64);
+    Ok(())
+}
+
+/// `Option` arguments don't fit the pod layout, borsh decodes them without allocating
+#[instruction(borsh, client, inline(never))]
+pub fn clamp_counter<'ix>(ctx: Ctx<'ix, UpdateCounter<'ix>>, min: Option<u64>, max: Option<u64>) -> Result<()> {
+    let mut counter = ctx.counter.try_deserialize_mut()?;
+
+    if let Some(min) = min {
+        counter.count = counter.count.max(min);
+    }
+    if let Some(max) = max {
+        counter.count = counter.count.min(max);
+    }
+
+    Ok(())
+}
+
+/// Keeps the discriminator the account had under the Anchor program it was migrated from
+#[account(discriminator = "account:Counter")]
+#[derive(OwnerProgram)]
+pub struct LegacyCounter {
+    pub count: u64,
+}
+
+#[derive(FromAccountViews)]
+#[accounts(cpi)]
+pub struct BumpLegacyCounter<'ix> {
+    pub counter: Mut<ZcAccount<'ix, LegacyCounter>>,
+}
+
+/// Keeps the one byte tag of the native program this instruction was ported from
+#[instruction(discriminator = [1], cpi, inline(never))]
+fn bump_legacy_counter<'ix>(ctx: Ctx<'ix, BumpLegacyCounter<'ix>>, amount: u64) -> Result<()> {
+    let mut counter = ctx.counter.try_deserialize_mut()?;
+    counter.count = counter.count.saturating_add(amount);
+    Ok(())
+}
+
+/// 4 byte tag, the struct only holds 4 byte aligned fields
+#[account(disc_len = 4)]
+#[derive(OwnerProgram)]
+pub struct Tally {
+    pub count: PodU32,
+}
+
+#[derive(FromAccountViews)]
+#[accounts(cpi, client)]
+pub struct BumpTally<'ix> {
+    pub tally: Mut<ZcAccount<'ix, Tally>>,
+}
+
+/// 1 byte tag, a 4 byte tally increment makes 5 bytes of instruction data
+#[instruction(disc_len = 1, cpi, client, inline(never))]
+pub fn bump_tally<'ix>(ctx: Ctx<'ix, BumpTally<'ix>>, #[non_zero] amount: u32) -> Result<()> {
+    let mut tally = ctx.tally.try_deserialize_mut()?;
+    tally.count = tally.count.get().saturating_add(amount).into();
+    Ok(())
+}
+
+/// A pod header followed by slices borrowed from the instruction data, one tally per proof
+/// entry, the memo is only logged
+#[instruction(zero_copy, client, inline(never))]
+pub fn tally_proofs<'ix>(
+    ctx: Ctx<'ix, BumpTally<'ix>>,
+    #[range(1..=8)] weight: u8,
+    memo: &'ix [u8],
+    proofs: &'ix [[u8; 32]],
+) -> Result<()> {
+    log!("memo: {} bytes", memo.len());
+
+    let mut tally = ctx.tally.try_deserialize_mut()?;
+    let added = (proofs.len() as u32).saturating_mul(weight as u32);
+    tally.count = tally.count.get().saturating_add(added).into();
+    Ok(())
+}
+
+#[instruction]
+fn nothing<'ix>(_ctx: Ctx<'ix, NoOp>) -> Result<()> {
+    Ok(())
+}
+
+pub fn test_dispatch(program_id: &Address, accounts: &[AccountView], instruction_data: &[u8]) -> Result<()> {
+    dispatch!(program_id, instruction_data, accounts, SetCounterInstruction, AddCounterInstruction, ClampCounterInstruction, BumpLegacyCounterInstruction, BumpTallyInstruction, TallyProofsInstruction, NothingInstruction, RecordReceiptInstruction);
+}
+
+/// Group of the one byte tagged instructions, routed by their tag after the group discriminator
+#[derive(Discriminator)]
+pub struct LegacyOps;
+
+pub fn test_dispatch_nested(program_id: &Address, accounts: &[AccountView], instruction_data: &[u8]) -> Result<()> {
+    dispatch_nested!(program_id, instruction_data, accounts,
+        LegacyOps => { BumpLegacyCounterInstruction, BumpTallyInstruction },
+    );
+}
+
+#[user_stats(seed = b"counter_stats")]
+#[account]
+#[derive(OwnerProgram)]
+pub struct CounterStats {
+    pub updates: u64,
+    pub total: u64,
+}
+
+#[derive(FromAccountViews)]
+pub struct RecordStats<'ix> {
+    pub user: Mut<Signer<'ix>>,
+    pub stats: Mut<UncheckedAccount<'ix>>,
+    pub system_program: Program<'ix, System>,
+}
+
+pub fn record_stats<'ix>(ctx: Ctx<'ix, RecordStats<'ix>>, amount: u64) -> Result<()> {
+    CounterStats::try_upsert(
+        ctx.stats.to_account_view(),
+        ctx.user.address(),
+        InitAccounts::new(&crate::ID, ctx.user.to_account_view(), ctx.system_program.to_account_view()),
+        |stats| {
+            stats.updates += 1;
+            stats.total += amount;
+        },
+    )
+}
+
+#[derive(FromAccountViews)]
+pub struct InterfaceTest<'ix> {
+    pub token_program: Interface<'ix, hayabusa_token_interface::TokenInterface>,
+}
+
+pub fn interface_test<'ix>(ctx: Ctx<'ix, InterfaceTest<'ix>>) -> Result<()> {
+    if ctx.token_program.is(&hayabusa_token::ID) {
+        log!("legacy token program");
+    }
+
+    Ok(())
+}
+
+#[derive(FromAccountViews)]
+pub struct InterfaceAccountTest<'ix> {
+    pub mint: hayabusa_token_interface::InterfaceAccount<'ix, hayabusa_token::state::Mint>,
+    #[meta(constraint = address::address_eq(token_account.try_deserialize()?.mint(), mint.address()))]
+    pub token_account: Mut<hayabusa_token_interface::InterfaceAccount<'ix, hayabusa_token::state::TokenAccount>>,
+    pub token_program: Interface<'ix, hayabusa_token_interface::TokenInterface>,
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct PartnerConfig {
+    pub oracle_program: ProgramRef,
+    pub fee_bps: u64,
+}
+
+#[derive(FromAccountViews)]
+pub struct PartnerCpi<'ix> {
+    pub config: ZcAccount<'ix, PartnerConfig>,
+    #[meta(constraint = config.try_deserialize()?.oracle_program.try_verify(oracle_program.to_account_view(), None).is_ok())]
+    pub oracle_program: UncheckedAccount<'ix>,
+}
+
+pub const PARTNER_ORACLE_ID: Address = address::address!("PartnerQuote1111111111111111111111111111111");
+
+/// Quote account of the partner oracle, read but never written by this program
+#[account(owner = PARTNER_ORACLE_ID, immutable)]
+pub struct PartnerQuote {
+    pub price: u64,
+    pub slot: u64,
+}
+
+/// Last `N` counter values, oldest overwritten first
+#[account]
+#[derive(OwnerProgram)]
+pub struct CounterHistory<const N: usize> {
+    pub next: u64,
+    pub values: [u64; N],
+}
+
+/// Lifecycle of a counter, stored as a `CounterPhaseAccount`
+#[account]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CounterPhase {
+    Open,
+    Frozen { authority: Address, slot: u64 },
+    Closed(u64),
+}
+
+#[event]
+pub struct CounterUpdated {
+    pub counter: Address,
+    pub count: u64,
+}
+
+/// Variable-length fields, built on the heap so emitting it needs an allocator
+#[event(dynamic)]
+pub struct CounterLabeled {
+    pub counter: Address,
+    pub label: alloc::string::String,
+    pub metadata: alloc::vec::Vec<u8>,
+}
+
+#[derive(FromAccountViews)]
+pub struct EmitCpiTest<'ix> {
+    pub counter: ZcAccount<'ix, CounterAccount>,
+    #[meta(seeds = [EVENT_AUTHORITY_SEED], bump)]
+    pub event_authority: UncheckedAccount<'ix>,
+    pub program: UncheckedAccount<'ix>,
+}
+
+pub fn emit_cpi_test<'ix>(ctx: Ctx<'ix, EmitCpiTest<'ix>>) -> Result<()> {
+    let event_cpi = EventCpi::new(&crate::ID, ctx.event_authority.to_account_view(), ctx.bumps.event_authority);
+
+    emit_cpi!(
+        event_cpi,
+        CounterUpdated {
+            counter: *ctx.counter.address(),
+            count: ctx.counter.try_deserialize()?.count,
+        }
+    )
+}
+
+#[derive(FromAccountViews)]
+pub struct NoDupTest<'ix> {
+    pub authority: Signer<'ix>,
+    #[meta(no_dup)]
+    pub source: Mut<ZcAccount<'ix, CounterAccount>>,
+    pub destination: Mut<ZcAccount<'ix, CounterAccount>>,
+}
+
+pub fn no_dup_test<'ix>(account_views: &'ix [AccountView]) -> Result<()> {
+    let _ctx = Ctx::<NoDupTest<'ix>>::construct_strict(account_views)?;
+
+    Ok(())
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct PaddedConfig {
+    pub authority: Address,
+    pub fee_bps: u64,
+    pub _reserved: Padding<64>,
+}
+
+assert_size!(PaddedConfig, 104);
+
+#[event]
+pub struct PaddedEvent {
+    pub fee_bps: u64,
+    pub _reserved: Padding<16>,
+}
+
+#[derive(FromAccountViews)]
+pub struct Withdraw<'ix> {
+    pub authority: Signer<'ix>,
+    pub vault: Mut<ZcAccount<'ix, CounterAccount>>,
+    pub destination: Mut<SystemAccount<'ix>>,
+}
+
+pub fn withdraw<'ix>(ctx: Ctx<'ix, Withdraw<'ix>>, amount: u64) -> Result<()> {
+    let amount = Lamports::new(amount);
+
+    ctx.vault.try_debit_lamports(&crate::ID, amount)?;
+    ctx.destination.try_credit_lamports(amount)?;
+
+    Ok(())
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct CounterAccountV2 {
+    pub count: u64,
+    pub last_updated_slot: u64,
+}
+
+impl Migrate for CounterAccountV2 {
+    type Previous = CounterAccount;
+
+    fn migrate(previous: &CounterAccount) -> Self {
+        Self {
+            count: previous.count,
+            last_updated_slot: 0,
+        }
+    }
+}
+
+#[derive(FromAccountViews)]
+pub struct MigrateCounter<'ix> {
+    pub payer: Mut<Signer<'ix>>,
+    pub counter: Mut<VersionedZcAccount<'ix, CounterAccountV2>>,
+    pub system_program: Program<'ix, System>,
+}
+
+pub fn migrate_counter<'ix>(ctx: Ctx<'ix, MigrateCounter<'ix>>) -> Result<()> {
+    let mut counter = ctx
+        .counter
+        .try_migrate(ctx.payer.to_account_view(), ctx.system_program.to_account_view())?;
+
+    counter.last_updated_slot = Clock::get()?.slot;
+
+    Ok(())
+}
+
+#[account]
+#[derive(OwnerProgram, InitSpace)]
+#[init_space(tail(RegistryEntry, 16))]
+pub struct RegistryHeader {
+    pub authority: Address,
+    pub len: u64,
+}
+
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+pub struct RegistryEntry {
+    pub key: Address,
+    pub value: u64,
+}
+
+#[derive(FromAccountViews)]
+pub struct CreateRegistry<'ix> {
+    pub authority: Mut<Signer<'ix>>,
+    #[meta(init, payer = authority, space = RegistryHeader::DISCRIMINATED_INIT_SPACE)]
+    pub registry: Mut<ZcAccountWithTail<'ix, RegistryHeader, RegistryEntry>>,
+    pub system_program: Program<'ix, System>,
+}
+
+#[derive(FromAccountViews)]
+pub struct Register<'ix> {
+    pub authority: Signer<'ix>,
+    pub registry: Mut<ZcAccountWithTail<'ix, RegistryHeader, RegistryEntry>>,
+}
+
+pub fn register<'ix>(ctx: Ctx<'ix, Register<'ix>>, key: Address, value: u64) -> Result<()> {
+    ctx.registry.try_split_mut(|header, entries| {
+        let len = header.len as usize;
+
+        if unlikely(len == entries.len()) {
+            error_msg!("register: registry full", ProgramError::AccountDataTooSmall);
+        }
+
+        entries[len] = RegistryEntry { key, value };
+        header.len += 1;
+
+        Ok(())
+    })?
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct Whitelist {
+    pub authority: Address,
+    pub limits: ZcMap<Address, u64, 16>,
+}
+
+#[derive(FromAccountViews)]
+pub struct SetLimit<'ix> {
+    pub authority: Signer<'ix>,
+    #[meta(constraint = address::address_eq(&whitelist.try_deserialize()?.authority, authority.address()))]
+    pub whitelist: Mut<ZcAccount<'ix, Whitelist>>,
+}
+
+pub fn set_limit<'ix>(ctx: Ctx<'ix, SetLimit<'ix>>, user: Address, limit: u64) -> Result<()> {
+    let mut whitelist = ctx.whitelist.try_deserialize_mut()?;
+
+    if limit == 0 {
+        whitelist.limits.remove(&user);
+    } else {
+        whitelist.limits.try_insert(user, limit)?;
+    }
+
+    Ok(())
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct ProtocolConfig {
+    /// Squads multisig whose default vault administers the protocol
+    pub admin_multisig: Address,
+    pub fee_bps: u64,
+}
+
+#[derive(FromAccountViews)]
+pub struct SetFee<'ix> {
+    pub admin: Signer<'ix>,
+    pub config: Mut<ZcAccount<'ix, ProtocolConfig>>,
+}
+
+pub fn set_fee<'ix>(ctx: Ctx<'ix, SetFee<'ix>>, fee_bps: u64) -> Result<()> {
+    let mut config = ctx.config.try_deserialize_mut()?;
+
+    hayabusa_squads::try_check_vault_signer(&ctx.admin, &config.admin_multisig, 0)?;
+    config.fee_bps = fee_bps;
+
+    Ok(())
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct Profile {
+    pub owner: Address,
+    pub name: PodStr<32>,
+}
+
+#[event]
+pub struct ProfileRenamed {
+    #[event_field(base58)]
+    pub owner: Address,
+    pub name: PodStr<32>,
+}
+
+#[derive(FromAccountViews)]
+pub struct RenameProfile<'ix> {
+    pub owner: Signer<'ix>,
+    #[meta(constraint = address::address_eq(&profile.try_deserialize()?.owner, owner.address()))]
+    pub profile: Mut<ZcAccount<'ix, Profile>>,
+}
+
+pub fn rename_profile<'ix>(ctx: Ctx<'ix, RenameProfile<'ix>>, name: &str) -> Result<()> {
+    let mut profile = ctx.profile.try_deserialize_mut()?;
+    profile.name.set(name)?;
+
+    emit!(ProfileRenamed {
+        owner: profile.owner,
+        name: profile.name,
+    });
+
+    Ok(())
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct DaoConfig {
+    /// SPL Governance governance whose proposals set the parameters
+    pub governance: Address,
+    /// Proposal that has to pass before `pending_max_amount` applies
+    pub pending_proposal: Address,
+    pub pending_max_amount: u64,
+    pub max_amount: u64,
+}
+
+#[derive(FromAccountViews)]
+pub struct ApplyMaxAmount<'ix> {
+    #[meta(constraint = address::address_eq(proposal.address(), &config.try_deserialize()?.pending_proposal))]
+    pub proposal: UncheckedAccount<'ix>,
+    pub config: Mut<ZcAccount<'ix, DaoConfig>>,
+}
+
+pub fn apply_max_amount<'ix>(ctx: Ctx<'ix, ApplyMaxAmount<'ix>>) -> Result<()> {
+    let mut config = ctx.config.try_deserialize_mut()?;
+
+    hayabusa_governance::Proposal::from_account_view(&ctx.proposal, &hayabusa_governance::ID)?
+        .try_check_passed(&config.governance)?;
+
+    config.max_amount = config.pending_max_amount;
+    config.pending_proposal = Address::default();
+
+    Ok(())
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct Allowance {
+    pub owner: Address,
+    pub delegate: PodOptionKey,
+    pub expires_at: PodOption<i64, 7>,
+}
+
+#[derive(FromAccountViews)]
+pub struct RevokeAllowance<'ix> {
+    /// Only has to be the signing owner while the allowance is live
+    pub authority: UncheckedAccount<'ix>,
+    pub allowance: Mut<ZcAccount<'ix, Allowance>>,
+}
+
+/// The owner can revoke at any time, anyone can clear an expired allowance.
+pub fn revoke_allowance<'ix>(ctx: Ctx<'ix, RevokeAllowance<'ix>>) -> Result<()> {
+    let mut allowance = ctx.allowance.try_deserialize_mut()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    if allowance.expires_at.get().is_none_or(|expires_at| *expires_at > now) {
+        let owner = ctx.authority.try_into_signer()?;
+
+        if unlikely(!address::address_eq(owner.address(), &allowance.owner)) {
+            error_msg!("revoke_allowance: not the owner", ProgramError::MissingRequiredSignature);
+        }
+    }
+
+    allowance.delegate = PodOptionKey::NONE;
+    allowance.expires_at.take();
+
+    Ok(())
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct Stake {
+    pub owner: Address,
+    pub bump: u8,
+    pub amount: PodU64,
+    pub rewards: PodU128,
+}
+
+#[derive(FromAccountViews)]
+pub struct Deposit<'ix> {
+    pub owner: Signer<'ix>,
+    #[meta(constraint = address::address_eq(&stake.try_deserialize()?.owner, owner.address()))]
+    pub stake: Mut<ZcAccount<'ix, Stake>>,
+}
+
+pub fn deposit<'ix>(ctx: Ctx<'ix, Deposit<'ix>>, amount: u64) -> Result<()> {
+    ctx.stake.try_deserialize_mut()?.amount.try_add(amount)
+}
+
+#[bitfield]
+pub enum MemberTier {
+    Basic,
+    Pro,
+    Partner,
+}
+
+#[bitfield]
+pub struct MemberFlags {
+    pub frozen: bool,
+    pub verified: bool,
+    pub tier: MemberTier,
+    /// Referrals left this epoch
+    #[bits(5)]
+    pub referrals: u8,
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct Member {
+    pub owner: Address,
+    pub flags: MemberFlags,
+}
+
+#[derive(FromAccountViews)]
+pub struct Upgrade<'ix> {
+    pub owner: Signer<'ix>,
+    #[meta(constraint = address::address_eq(&member.try_deserialize()?.owner, owner.address()))]
+    pub member: Mut<ZcAccount<'ix, Member>>,
+}
+
+pub fn upgrade<'ix>(ctx: Ctx<'ix, Upgrade<'ix>>) -> Result<()> {
+    let mut member = ctx.member.try_deserialize_mut()?;
+
+    if unlikely(member.flags.frozen() || member.flags.try_tier()? != MemberTier::Basic) {
+        error_msg!("upgrade: member can't be upgraded", ProgramError::InvalidAccountData);
+    }
+
+    member.flags.set_tier(MemberTier::Pro);
+    member.flags.try_set_referrals(10)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PodEnum)]
+#[repr(u8)]
+pub enum ListingStatus {
+    Open,
+    Sold,
+    Cancelled,
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct Listing {
+    pub seller: Address,
+    pub status: PodU8Enum<ListingStatus>,
+    pub featured: PodBool,
+}
+
+#[derive(FromAccountViews)]
+pub struct CancelListing<'ix> {
+    pub seller: Signer<'ix>,
+    #[meta(constraint = address::address_eq(&listing.try_deserialize()?.seller, seller.address()))]
+    pub listing: Mut<ZcAccount<'ix, Listing>>,
+}
+
+pub fn cancel_listing<'ix>(ctx: Ctx<'ix, CancelListing<'ix>>) -> Result<()> {
+    let mut listing = ctx.listing.try_deserialize_mut()?;
+
+    if unlikely(listing.status.try_get()? != ListingStatus::Open) {
+        error_msg!("cancel_listing: listing is not open", ProgramError::InvalidAccountData);
+    }
+
+    listing.status.set(ListingStatus::Cancelled);
+    listing.featured.set(false);
+
+    Ok(())
+}
+
+/// Stored as a compressed account, see `create_note`
+#[account]
+#[derive(OwnerProgram)]
+pub struct Note {
+    pub owner: Address,
+    pub value: u64,
+}
+
+#[derive(FromAccountViews)]
+pub struct CreateNote<'ix> {
+    pub owner: Mut<Signer<'ix>>,
+    #[meta(seeds = [hayabusa_light::CPI_AUTHORITY_SEED], bump)]
+    pub cpi_authority: UncheckedAccount<'ix>,
+    pub light_system_program: Program<'ix, hayabusa_light::LightSystem>,
+    pub registered_program_pda: UncheckedAccount<'ix>,
+    pub noop_program: UncheckedAccount<'ix>,
+    pub account_compression_authority: UncheckedAccount<'ix>,
+    pub account_compression_program: UncheckedAccount<'ix>,
+    pub program: UncheckedAccount<'ix>,
+    pub system_program: Program<'ix, System>,
+}
+
+/// Appends a rent-free `Note` to the state tree at `output_tree_index` of the remaining accounts.
+/// The note has no address, so no validity proof is needed.
+pub fn create_note<'ix>(
+    ctx: Ctx<'ix, CreateNote<'ix>>,
+    value: u64,
+    output_tree_index: u8,
+) -> Result<()> {
+    use hayabusa_light::instructions::{invoke_cpi, InvokeCpi, InvokeCpiArgs, OutputAccount};
+
+    let note = Note {
+        owner: *ctx.owner.address(),
+        value,
+    };
+    let outputs = [OutputAccount::from_pod(&crate::ID, &note, None, output_tree_index)];
+
+    let bump = [ctx.bumps.cpi_authority];
+    let seeds = [
+        instruction::Seed::from(hayabusa_light::CPI_AUTHORITY_SEED),
+        instruction::Seed::from(&bump),
+    ];
+    let signers = [instruction::PdaSigner::from(&seeds)];
+
+    let cpi_ctx = CpiCtx::try_new_with_signer(
+        ctx.light_system_program.to_account_view(),
+        InvokeCpi {
+            fee_payer: ctx.owner.to_account_view(),
+            authority: ctx.cpi_authority.to_account_view(),
+            registered_program_pda: ctx.registered_program_pda.to_account_view(),
+            noop_program: ctx.noop_program.to_account_view(),
+            account_compression_authority: ctx.account_compression_authority.to_account_view(),
+            account_compression_program: ctx.account_compression_program.to_account_view(),
+            invoking_program: ctx.program.to_account_view(),
+            sol_pool_pda: None,
+            decompression_recipient: None,
+            system_program: ctx.system_program.to_account_view(),
+        },
+        &signers,
+    )?;
+
+    invoke_cpi::<16, 256>(
+        cpi_ctx,
+        &InvokeCpiArgs {
+            proof: None,
+            new_addresses: &[],
+            inputs: &[],
+            outputs: &outputs,
+            compress_or_decompress_lamports: None,
+            is_compress: false,
+        },
+        ctx.remaining_accounts,
+    )
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct Pool {
+    pub authority: Address,
+    pub balance: PodU64,
+    /// Set while the pool is paying out, see `ZcAccount::try_guarded`
+    pub in_flight: PodBool,
+}
+
+#[derive(FromAccountViews)]
+pub struct PoolWithdraw<'ix> {
+    pub authority: Signer<'ix>,
+    /// Rejected unless it holds the rent exempt minimum for its size
+    #[meta(seeds = [b"pool", authority.address().as_ref()], bump, not_in_flight)]
+    pub pool: Mut<RentExempt<ZcAccount<'ix, Pool>>>,
+    pub pool_vault: Mut<UncheckedAccount<'ix>>,
+    pub destination: Mut<UncheckedAccount<'ix>>,
+    pub token_program: Program<'ix, hayabusa_token::Token>,
+}
+
+/// The pool stays flagged during the transfer, so a hook calling back into this program can't
+/// withdraw against the old balance.
+pub fn pool_withdraw<'ix>(ctx: Ctx<'ix, PoolWithdraw<'ix>>, amount: u64) -> Result<()> {
+    use hayabusa_token::instructions::{transfer, Transfer};
+
+    ctx.pool.try_deserialize_mut()?.balance.try_sub(amount)?;
+
+    let bump = [ctx.bumps.pool];
+    let seeds = [
+        instruction::Seed::from(b"pool"),
+        instruction::Seed::from(ctx.authority.address().as_ref()),
+        instruction::Seed::from(&bump),
+    ];
+    let signers = [instruction::PdaSigner::from(&seeds)];
+
+    ctx.pool.try_guarded(|| {
+        let cpi_ctx = CpiCtx::try_new_with_signer(
+            ctx.token_program.to_account_view(),
+            Transfer {
+                from: ctx.pool_vault.to_account_view(),
+                to: ctx.destination.to_account_view(),
+                authority: ctx.pool.to_account_view(),
+            },
+            &signers,
+        )?;
+
+        transfer(cpi_ctx, amount)
+    })
+}
+
+#[derive(FromAccountViews)]
+pub struct OpenTreasury<'ix> {
+    pub payer: Mut<Signer<'ix>>,
+    #[meta(seeds = [b"treasury", payer.address().as_ref()], bump)]
+    pub treasury: Mut<SystemAccount<'ix>>,
+    pub system_program: Program<'ix, System>,
+}
+
+/// Creates a lamport-only PDA wallet, it stays owned by the system program so it can pay for
+/// accounts created with its seeds later.
+pub fn open_treasury<'ix>(ctx: Ctx<'ix, OpenTreasury<'ix>>, lamports: u64) -> Result<()> {
+    let bump = [ctx.bumps.treasury];
+    let seeds = [
+        instruction::Seed::from(b"treasury"),
+        instruction::Seed::from(ctx.payer.address().as_ref()),
+        instruction::Seed::from(&bump),
+    ];
+    let signers = [instruction::PdaSigner::from(&seeds)];
+
+    ctx.treasury.try_create_from(
+        ctx.payer.to_account_view(),
+        ctx.system_program.to_account_view(),
+        Lamports::new(lamports),
+        Some(&signers),
+    )
+}
+
+/// Passes once `threshold` of the remaining accounts signed. The cosigners are passed in
+/// canonical order, so the same key can't be counted twice.
+pub fn cosign<'ix>(ctx: Ctx<'ix, NoAccounts>, threshold: u8) -> Result<()> {
+    try_check_canonical_order(ctx.remaining_accounts)?;
+
+    let signers = ctx.remaining_accounts.iter().filter(|a| a.is_signer()).count();
+    if unlikely(signers < threshold as usize) {
+        error_msg!("cosign: not enough signers", ProgramError::MissingRequiredSignature);
+    }
+
+    Ok(())
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct Receipt {
+    /// sha256 of the program id and the raw instruction data that wrote the receipt
+    pub payload_hash: [u8; 32],
+}
+
+#[derive(FromAccountViews)]
+pub struct RecordReceipt<'ix> {
+    pub authority: Signer<'ix>,
+    pub receipt: Mut<ZcAccount<'ix, Receipt>>,
+}
+
+/// Stores the hash an off-chain permit would be signed over, read straight from the raw payload.
+#[instruction]
+fn record_receipt<'ix>(ctx: Ctx<'ix, RecordReceipt<'ix>>, _nonce: u64) -> Result<()> {
+    let (Some(program_id), Some(instruction_data)) = (ctx.program_id(), ctx.instruction_data()) else {
+        error_msg!("record_receipt: not run by dispatch!", ProgramError::InvalidInstructionData);
+    };
+
+    ctx.receipt.try_deserialize_mut()?.payload_hash =
+        syscalls::sha256(&[program_id.as_ref(), instruction_data]);
+
+    Ok(())
+}
+
+#[account]
+#[derive(OwnerProgram)]
+pub struct TokenVault {
+    pub authority: Address,
+    pub mint: Address,
+    pub bump: u8,
+}
+
+impl PdaState for TokenVault {
+    fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    fn with_seeds<R>(&self, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[b"token_vault", self.authority.as_ref(), self.mint.as_ref()])
+    }
+}
+
+#[derive(FromAccountViews)]
+pub struct VaultPayout<'ix> {
+    pub authority: Signer<'ix>,
+    #[meta(constraint = address::address_eq(&vault.try_deserialize()?.authority, authority.address()))]
+    pub vault: PdaAccount<'ix, TokenVault>,
+    pub vault_tokens: Mut<UncheckedAccount<'ix>>,
+    pub destination: Mut<UncheckedAccount<'ix>>,
+    pub token_program: Program<'ix, hayabusa_token::Token>,
+}
+
+/// The vault signs with the seeds and bump stored in its state, nothing to re-derive.
+pub fn vault_payout<'ix>(ctx: Ctx<'ix, VaultPayout<'ix>>, amount: u64) -> Result<()> {
+    use hayabusa_token::instructions::{transfer, Transfer};
+
+    ctx.vault.try_with_signer(|signers| {
+        let cpi_ctx = CpiCtx::try_new_with_signer(
+            ctx.token_program.to_account_view(),
+            Transfer {
+                from: ctx.vault_tokens.to_account_view(),
+                to: ctx.destination.to_account_view(),
+                authority: ctx.vault.to_account_view(),
+            },
+            signers,
+        )?;
+
+        transfer(cpi_ctx, amount)
+    })
+}
+
+#[derive(FromAccountViews)]
+pub struct HandOverPool<'ix> {
+    pub authority: Signer<'ix>,
+    #[meta(seeds = [b"pool", authority.address().as_ref()], bump)]
+    pub pool: Mut<ZcAccount<'ix, Pool>>,
+    /// Must be able to sign on its own, a PDA would lock the pool behind another program
+    pub new_authority: Wallet<'ix>,
+}
+
+pub fn hand_over_pool<'ix>(ctx: Ctx<'ix, HandOverPool<'ix>>) -> Result<()> {
+    ctx.pool.try_deserialize_mut()?.authority = *ctx.new_authority.address();
+
+    Ok(())
+}
+
+#[derive(FromAccountViews)]
+pub struct MoveCount<'ix> {
+    pub authority: Signer<'ix>,
+    pub source: Mut<Loader<'ix, CounterAccount>>,
+    /// May be `source` itself, nothing is borrowed until the handler loads it
+    pub destination: Mut<Loader<'ix, CounterAccount>>,
+}
+
+pub fn move_count<'ix>(ctx: Ctx<'ix, MoveCount<'ix>>, amount: u64) -> Result<()> {
+    if same_account(&ctx.source, &ctx.destination) {
+        return Ok(());
+    }
+
+    let mut source = ctx.source.load_mut()?;
+    let mut destination = ctx.destination.load_mut()?;
+
+    source.count = source.count.checked_sub(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+    destination.count = destination.count.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+#[derive(FromAccountViews)]
+pub struct PricedTrade<'ix> {
+    pub trader: Signer<'ix>,
+    pub instructions: SysvarAccount<'ix, sysvars::instructions::Instructions<Ref<'ix, [u8]>>>,
+}
+
+/// `max_price` bounds slippage against the price the trader saw, which is only meaningful if
+/// the transaction was signed moments ago, not held back under a durable nonce.
+pub fn priced_trade<'ix>(ctx: Ctx<'ix, PricedTrade<'ix>>, max_price: u64, price: u64) -> Result<()> {
+    system_program::nonce::try_check_recent_blockhash(&ctx.instructions.try_get()?)?;
+
+    if price > max_price {
+        error_msg!("priced_trade: price above limit", ProgramError::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+pub struct Admin;
+
+impl KnownAddress for Admin {
+    const ADDRESS: Address = address::address!("Ad1111111111111111111111111111111111111111");
+}
+
+#[derive(FromAccountViews)]
+pub struct SetPartnerFee<'ix> {
+    pub admin: SignerAddress<'ix, Admin>,
+    pub config: Mut<ZcAccount<'ix, PartnerConfig>>,
+}
+
+pub fn set_partner_fee<'ix>(ctx: Ctx<'ix, SetPartnerFee<'ix>>, fee_bps: u64) -> Result<()> {
+    ctx.config.try_deserialize_mut()?.fee_bps = fee_bps;
+
+    Ok(())
+}
+
+#[derive(FromAccountViews)]
+pub struct NotifyHook<'ix> {
+    pub authority: Signer<'ix>,
+    pub hook_program: UncheckedAccount<'ix>,
+}
+
+/// Forwards the authority and every remaining account to a hook that looks accounts up by
+/// address, the client may pass the authority again or the same account twice.
+pub fn notify_hook<'ix>(ctx: Ctx<'ix, NotifyHook<'ix>>, data: &[u8]) -> Result<()> {
+    const MAX_ACCOUNTS: usize = 16;
+
+    if ctx.remaining_accounts.len() >= MAX_ACCOUNTS {
+        error_msg!("notify_hook: too many accounts", ProgramError::InvalidArgument);
+    }
+
+    let authority = ctx.authority.to_account_view();
+    let mut metas: [instruction::InstructionAccount; MAX_ACCOUNTS] =
+        core::array::from_fn(|_| instruction::InstructionAccount::readonly_signer(authority.address()));
+    let mut views: [&AccountView; MAX_ACCOUNTS] = [authority; MAX_ACCOUNTS];
+
+    for (i, account) in ctx.remaining_accounts.iter().enumerate() {
+        metas[i + 1] = instruction::InstructionAccount::new(
+            account.address(),
+            account.is_writable(),
+            account.is_signer(),
+        );
+        views[i + 1] = account;
+    }
+
+    let count = ctx.remaining_accounts.len() + 1;
+    let len = dedup_cpi_accounts(&mut metas[..count], &mut views[..count])?;
+
+    let ix = instruction::InstructionView {
+        program_id: ctx.hook_program.address(),
+        data,
+        accounts: &metas[..len],
+    };
+
+    instruction::invoke_signed_with_bounds::<MAX_ACCOUNTS>(&ix, &views[..len], &[])
+}
+
+/// Crank over any number of counters passed as remaining accounts, all of them are checked
+/// before the first one is written.
+pub fn tick_counters<'ix>(ctx: Ctx<'ix, NoAccounts>) -> Result<()> {
+    try_for_each_mut::<CounterAccount, _>(ctx.remaining_accounts, |_, counter| {
+        counter.count = counter.count.saturating_add(1);
+        Ok(())
+    })
+}
@@ -1,6 +1,13 @@
 #![allow(unused)]
 
-use hayabusa::prelude::Discriminator;
+use counter_program::{
+    add_counter_ix, bump_tally_ix, clamp_counter_ix, set_counter_ix, AddCounterInstruction,
+    BumpTallyClient, ClampCounterInstruction, InitializeCounterIx, NoOpIx, SetCounterInstruction,
+    UpdateCounterIx, BumpTallyInstruction, CounterLabeled, ID, PROGRAM_MANIFEST,
+    tally_proofs_ix, TallyProofsInstruction, ProfileRenamed, PartnerQuote, PARTNER_ORACLE_ID,
+    CounterHistory, CounterPhase, CounterPhaseAccount, CounterPhaseFrozen,
+};
+use hayabusa::prelude::{Address, DecodeIx, Discriminator, EventBuilder, Len, OwnerProgram, PodStr};
 use litesvm::LiteSVM;
 use solana_sdk::{
     account::Account, instruction::{AccountMeta, Instruction}, pubkey::Pubkey, signature::Keypair, signer::Signer, system_program, transaction::Transaction, pubkey,
@@ -35,8 +42,7 @@ fn integration() {
     svm.set_account(counter_account_pk, counter_account).unwrap();
 
     let ix_data = {
-        const UPDATE_COUNTER_DISCRIMINATOR: [u8; 8] = [18, 183, 6, 47, 227, 170, 61, 195];
-        let mut data = UPDATE_COUNTER_DISCRIMINATOR.to_vec();
+        let mut data = UpdateCounterIx::DISCRIMINATOR.to_vec();
         data.extend_from_slice(&1u64.to_le_bytes());
         data
     };
@@ -76,11 +82,7 @@ fn integration2() {
 
     svm.airdrop(&user, 1_000_000_000_000).unwrap();
 
-    let ix_data = {
-        const INITIALIZE_COUNTER_DISCRIMINATOR: [u8; 8] = [184, 155, 169, 181, 122, 145, 244, 45];
-        let data = INITIALIZE_COUNTER_DISCRIMINATOR.to_vec();
-        data
-    };
+    let ix_data = InitializeCounterIx::DISCRIMINATOR.to_vec();
 
     let ix = Instruction {
         program_id,
@@ -130,11 +132,7 @@ fn integration3() {
 
     svm.airdrop(&user, 1_000_000_000_000).unwrap();
 
-    let ix_data = {
-        const INITIALIZE_COUNTER_DISCRIMINATOR: [u8; 8] = [70, 103, 157, 50, 99, 187, 4, 24];
-        let data = INITIALIZE_COUNTER_DISCRIMINATOR.to_vec();
-        data
-    };
+    let ix_data = NoOpIx::DISCRIMINATOR.to_vec();
 
     let ix = Instruction {
         program_id,
@@ -148,4 +146,189 @@ fn integration3() {
 
     println!("Transaction result: {:#?}", res);
 
-}
\ No newline at end of file
+}
+#[test]
+fn instruction_builders() {
+    let authority = Pubkey::new_unique();
+    let counter = Pubkey::new_unique();
+    let metas = vec![
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new(counter, false),
+    ];
+
+    let ix = set_counter_ix(&ID, metas.clone(), 7, 1);
+    assert_eq!(ix.program_id.to_bytes(), ID.to_bytes());
+    assert_eq!(ix.accounts, metas);
+    let mut data = SetCounterInstruction::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&7u64.to_le_bytes());
+    data.push(1);
+    assert_eq!(ix.data, data);
+
+    // varint: 1000 takes 2 bytes, -1 zigzags to 1
+    let ix = add_counter_ix(&ID, metas.clone(), 1000, -1);
+    let mut data = AddCounterInstruction::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&[0xe8, 0x07, 0x01]);
+    assert_eq!(ix.data, data);
+
+    let ix = clamp_counter_ix(&ID, metas, Some(5), None);
+    let mut data = ClampCounterInstruction::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&[1, 5, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(ix.data, data);
+
+    // accounts flagged as the accounts struct declares them
+    let tally = Pubkey::new_unique();
+    let ix = bump_tally_ix(&ID, BumpTallyClient { tally }, 3);
+    assert_eq!(ix.accounts, vec![AccountMeta::new(tally, false)]);
+    assert_eq!(ix.data.len(), 5);
+    assert_eq!(&ix.data[1..], &3u32.to_le_bytes());
+}
+
+#[test]
+fn program_manifest() {
+    fn take<'a>(data: &mut &'a [u8], len: usize) -> &'a [u8] {
+        let (head, rest) = data.split_at(len);
+        *data = rest;
+        head
+    }
+    fn u32(data: &mut &[u8]) -> usize {
+        u32::from_le_bytes(take(data, 4).try_into().unwrap()) as usize
+    }
+    fn string<'a>(data: &mut &'a [u8]) -> &'a [u8] {
+        let len = u32(data);
+        take(data, len)
+    }
+
+    let mut data = &PROGRAM_MANIFEST[..];
+    assert_eq!(take(&mut data, 8), b"HYBSMNFT");
+    assert_eq!(u32(&mut data), data.len());
+    assert_eq!(string(&mut data), b"counter-program");
+    assert_eq!(string(&mut data), b"0.1.0");
+    assert_eq!(u32(&mut data), 7);
+
+    let mut tally = None;
+    for _ in 0..7 {
+        let discriminator = string(&mut data);
+        let name = string(&mut data);
+        let accounts: Vec<_> = (0..u32(&mut data))
+            .map(|_| (string(&mut data), take(&mut data, 2)))
+            .collect();
+        if name == b"bump_tally" {
+            assert_eq!(discriminator, BumpTallyInstruction::DISCRIMINATOR);
+            tally = Some(accounts);
+        }
+    }
+    assert!(data.is_empty());
+    assert_eq!(tally.unwrap(), vec![(&b"tally"[..], &[1, 0][..])]);
+}
+
+#[test]
+fn dynamic_event() {
+    let counter = Pubkey::new_unique();
+
+    let mut data = CounterLabeled::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(counter.as_ref());
+    data.extend_from_slice(&5u32.to_le_bytes());
+    data.extend_from_slice(b"hello");
+    data.extend_from_slice(&2u32.to_le_bytes());
+    data.extend_from_slice(&[7, 8]);
+
+    let event = CounterLabeled::decode_event(&data).unwrap();
+    assert_eq!(event.counter.to_bytes(), counter.to_bytes());
+    assert_eq!(event.label, "hello");
+    assert_eq!(event.metadata, vec![7, 8]);
+
+    // truncated, trailing bytes and invalid utf8
+    assert!(CounterLabeled::decode_event(&data[..data.len() - 1]).is_none());
+    assert!(CounterLabeled::decode_event(&[&data[..], &[0]].concat()).is_none());
+    let label = 8 + 32 + 4;
+    data[label] = 0xff;
+    assert!(CounterLabeled::decode_event(&data).is_none());
+}
+
+#[test]
+fn rendered_event_fields() {
+    let owner = Pubkey::new_unique();
+    let event = ProfileRenamed {
+        owner: Address::new_from_array(owner.to_bytes()),
+        name: PodStr::default(),
+    };
+
+    let mut line = [0u8; ProfileRenamed::RENDERED_LOG_LEN];
+    let len = event.write_rendered_fields(&mut line);
+    assert_eq!(line[..len], *format!("ProfileRenamed: owner={owner}").as_bytes());
+
+    // leading zero bytes are `1`s
+    let event = ProfileRenamed {
+        owner: Address::new_from_array([0; 32]),
+        name: PodStr::default(),
+    };
+    let len = event.write_rendered_fields(&mut line);
+    assert_eq!(line[..len], *format!("ProfileRenamed: owner={}", "1".repeat(32)).as_bytes());
+}
+
+#[test]
+fn account_owner() {
+    assert_eq!(PartnerQuote::OWNER, PARTNER_ORACLE_ID);
+    assert_ne!(PartnerQuote::OWNER, counter_program::ID);
+}
+
+#[test]
+fn generic_account() {
+    assert_eq!(CounterHistory::<4>::DISCRIMINATED_LEN, 8 + 8 + 4 * 8);
+    assert_eq!(CounterHistory::<16>::DISCRIMINATED_LEN, 8 + 8 + 16 * 8);
+    assert_eq!(CounterHistory::<4>::DISCRIMINATOR, CounterHistory::<16>::DISCRIMINATOR);
+}
+
+#[test]
+fn enum_account() {
+    let authority = Address::new_from_array([7; 32]);
+    assert_eq!(CounterPhaseAccount::PAYLOAD_LEN, size_of::<CounterPhaseFrozen>());
+    assert_eq!(CounterPhaseAccount::DISCRIMINATED_LEN, 8 + 1 + 32 + 8);
+
+    // a new account holds the first variant
+    let mut account: CounterPhaseAccount = bytemuck::Zeroable::zeroed();
+    assert_eq!(account.try_get().unwrap(), CounterPhase::Open);
+
+    account.set(CounterPhase::Frozen { authority, slot: 42 });
+    assert_eq!(account.tag(), 1);
+    assert_eq!(account.try_get().unwrap(), CounterPhase::Frozen { authority, slot: 42 });
+
+    // the rest of the larger variant is zeroed
+    account.set(CounterPhase::Closed(9));
+    let bytes = bytemuck::bytes_of(&account);
+    assert_eq!(bytes[0], 2);
+    assert_eq!(&bytes[1..9], &9u64.to_le_bytes());
+    assert!(bytes[9..].iter().all(|byte| *byte == 0));
+    assert_eq!(CounterPhaseAccount::from(CounterPhase::Closed(9)).try_get().unwrap(), CounterPhase::Closed(9));
+
+    let mut bytes = bytes.to_vec();
+    bytes[0] = 3;
+    assert!(bytemuck::pod_read_unaligned::<CounterPhaseAccount>(&bytes).try_get().is_err());
+}
+
+#[test]
+fn zero_copy_instruction() {
+    let tally = Pubkey::new_unique();
+    let proofs = [[1u8; 32], [2u8; 32]];
+
+    let ix = tally_proofs_ix(&ID, BumpTallyClient { tally }, 3, b"memo", &proofs);
+
+    let mut data = TallyProofsInstruction::DISCRIMINATOR.to_vec();
+    data.push(3);
+    data.extend_from_slice(&4u32.to_le_bytes());
+    data.extend_from_slice(b"memo");
+    data.extend_from_slice(&2u32.to_le_bytes());
+    data.extend_from_slice(&[1; 32]);
+    data.extend_from_slice(&[2; 32]);
+    assert_eq!(ix.data, data);
+
+    let disc_len = TallyProofsInstruction::DISCRIMINATOR.len();
+    let decoded = TallyProofsInstruction::decode(&ix.data[disc_len..]).unwrap();
+    assert_eq!(decoded.weight, 3);
+    assert_eq!(decoded.memo, b"memo");
+    assert_eq!(decoded.proofs, &proofs);
+
+    // a slice running past the data, and trailing bytes
+    assert!(TallyProofsInstruction::decode(&ix.data[disc_len..ix.data.len() - 1]).is_err());
+    assert!(TallyProofsInstruction::decode(&[&ix.data[disc_len..], &[0]].concat()).is_err());
+}